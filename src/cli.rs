@@ -0,0 +1,763 @@
+use std::io::Write;
+use std::path::PathBuf;
+use materialui::materials::MaterialThemes;
+use schrod::Schrod;
+use schrod::Schrod::{Pass, Fail};
+use crate::vault::bank::{Bank, Filters};
+use crate::vault::csv_import::parse_date;
+use crate::vault::export::{ExportFormats, export_rows, write_rows};
+use crate::vault::parse::{CashFlow, FlowTypes};
+use crate::vault::save_engine::{SaveData, VaultKey, export_to, get_active_vault, is_vault_encrypted, load, save};
+use crate::vault::transaction::{Date, Id, Months, Split, Tag, Transaction};
+
+/// Describes how to use the command-line interface.
+const USAGE: &str = "\
+Usage: ascent [--data-dir <path>] [--vault <name>] <command> [options]
+
+Running ascent without a command opens the window.
+
+Commands:
+  list [filter options]                      Lists the transactions, newest first.
+  add <transaction options>                  Adds a transaction. --value, --currency, --description, and at least one --tag are required.
+  edit <uid> <transaction options>           Edits a transaction. Only the given options are changed, and any --tag replaces all tags.
+  remove <uid>                               Removes a transaction.
+  cashflow [filter options] [--flow <type>]  Prints the cash flow. The type is collected, unified, or time.
+  export <path> [filter options] [--format <format>]
+                                             Writes the transactions to a file. The format is save, csv, or json.
+                                             Save files can be imported again, and paths ending in .csv default to csv.
+  help                                       Prints this message.
+
+Filter options:
+  --year <year>          Only transactions from this year.
+  --month <month>        Only transactions from this month, as a number or a name.
+  --tag <tag>            Only transactions with this tag. Can be given more than once.
+  --search <term>        Only transactions mentioning this term. Can be given more than once.
+  --any                  Matches transactions that pass any filter instead of all of them.
+
+Transaction options:
+  --value <value>        The value, negative for spending.
+  --currency <code>      The currency code, for example USD.
+  --date <YYYY-MM-DD>    The date. Added transactions default to today.
+  --description <text>   The description.
+  --tag <tag>            A tag. Can be given more than once.
+  --split <amount>,<tag>[,<tag>...]
+                         Splits part of the value off to its own tags. Can be given more than once, and any
+                         --split replaces all splits when editing.
+
+A uid can be shortened to any prefix that is unique, like the ones shown by list.
+Encrypted vaults read their passphrase from the ASCENT_PASSPHRASE environment variable, or ask for it.";
+
+/// The number of characters of a `Uid` shown in listings.
+const SHORT_UID_LENGTH: usize = 8;
+
+
+
+/// The options that narrow down which `Transaction`s a command works on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterOptions {
+    pub year: Option<u32>,
+    pub month: Option<Months>,
+    pub tags: Vec<Tag>,
+    pub search_terms: Vec<String>,
+    /// Whether a `Transaction` only has to match one filter (OR) instead of all of them (AND).
+    pub match_any: bool,
+}
+
+/// The parts of a `Transaction` given to the add and edit commands.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionOptions {
+    pub value: Option<String>,
+    pub currency: Option<String>,
+    pub date: Option<Date>,
+    pub description: Option<String>,
+    pub tags: Vec<Tag>,
+    pub splits: Vec<Split>,
+}
+
+/// An enumeration of the commands that run without opening a window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Commands {
+    List(FilterOptions),
+    Add(TransactionOptions),
+    Edit(String, TransactionOptions),
+    Remove(String),
+    CashFlow(FilterOptions, Option<FlowTypes>),
+    /// Exports to a save file when no `ExportFormat` is given.
+    Export(PathBuf, FilterOptions, Option<ExportFormats>),
+    Help,
+}
+
+/// A parsed command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    /// The vault to use instead of the active vault.
+    pub vault_name: Option<String>,
+    pub command: Commands,
+}
+
+
+
+//====================================================================================================//
+// RUNNING
+//====================================================================================================//
+/// Runs the command given on the command line, if there is one.
+/// Returns the exit code, or `None` if the window should be opened instead.
+#[must_use]
+pub fn run_from_arguments() -> Option<i32> {
+    let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+    let invocation_result = parse_arguments(&arguments)?;
+    attach_to_parent_console();
+    let Pass(invocation) = invocation_result else {
+        print_failures(&invocation_result);
+        eprintln!("Run `ascent help` to see how ascent can be used.");
+        return Some(2)
+    };
+
+    let output_result = execute(invocation);
+    match output_result {
+        Pass(lines) => {
+            let mut stdout = std::io::stdout().lock();
+            for line in lines { writeln!(stdout, "{line}").ok(); }
+            Some(0)
+        }
+        Fail(_) => {
+            print_failures(&output_result);
+            Some(1)
+        }
+    }
+}
+
+/// Connects the output to the console that ascent was started from.
+/// On Windows ascent is built as a windowed program, which has no console of its own, so commands would print nothing otherwise.
+#[cfg(windows)]
+fn attach_to_parent_console() {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    // when started without a console there is nowhere to print to, so a failure changes nothing
+    unsafe { AttachConsole(ATTACH_PARENT_PROCESS); }
+}
+
+/// Does nothing, since the output is already connected to the console outside of Windows.
+#[cfg(not(windows))]
+fn attach_to_parent_console() {}
+
+/// Prints the messages of a failed `Schrod`.
+fn print_failures<T>(failed: &Schrod<T>) {
+    for message in failed.results() { eprintln!("{message}"); }
+}
+
+/// Runs a command and returns the lines it prints.
+#[must_use]
+pub fn execute(invocation: Invocation) -> Schrod<Vec<String>> {
+    if invocation.command == Commands::Help { return Pass(USAGE.lines().map(str::to_string).collect()) }
+
+    // opening the vault
+    let vault_name = invocation.vault_name.unwrap_or_else(get_active_vault);
+    let vault_result = LoadedVault::open(vault_name);
+    if vault_result.is_fail() { return vault_result.convert("cli::execute()") }
+    let mut vault = vault_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()");
+
+    match invocation.command {
+        Commands::List(filter_options) => {
+            let transactions_result = vault.filtered(&filter_options);
+            if transactions_result.is_fail() { return transactions_result.convert("cli::execute()") }
+            let transactions = transactions_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()");
+            Pass(transactions.iter().map(listing_of).collect())
+        }
+
+        Commands::Add(transaction_options) => {
+            let add_result = vault.add(transaction_options);
+            if add_result.is_fail() { return add_result.convert("cli::execute()") }
+            let save_result = vault.save();
+            if save_result.is_fail() { return save_result.convert("cli::execute()") }
+            Pass(vec![format!("Added {}", add_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()"))])
+        }
+
+        Commands::Edit(uid_prefix, transaction_options) => {
+            let edit_result = vault.edit(&uid_prefix, transaction_options);
+            if edit_result.is_fail() { return edit_result.convert("cli::execute()") }
+            let save_result = vault.save();
+            if save_result.is_fail() { return save_result.convert("cli::execute()") }
+            Pass(vec![format!("Edited {}", edit_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()"))])
+        }
+
+        Commands::Remove(uid_prefix) => {
+            let remove_result = vault.remove(&uid_prefix);
+            if remove_result.is_fail() { return remove_result.convert("cli::execute()") }
+            let save_result = vault.save();
+            if save_result.is_fail() { return save_result.convert("cli::execute()") }
+            Pass(vec![format!("Removed {}", remove_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()"))])
+        }
+
+        Commands::CashFlow(filter_options, flow_type) => {
+            let ids_result = vault.filtered_ids(&filter_options);
+            if ids_result.is_fail() { return ids_result.convert("cli::execute()") }
+            let ids = ids_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()");
+            let cash_flow_result = CashFlow::new(&vault.bank, &ids);
+            if cash_flow_result.is_fail() { return cash_flow_result.convert("cli::execute()") }
+            let cash_flow = cash_flow_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()");
+
+            Pass(match flow_type {
+                Some(flow_type) => cash_flow.display(flow_type),
+                None => {
+                    let mut lines = cash_flow.display(FlowTypes::Collected);
+                    lines.push(format!("Unified: {}", cash_flow.display(FlowTypes::Unified).join(" ")));
+                    lines.push(format!("Time: {}", cash_flow.display(FlowTypes::Time).join(" ")));
+                    lines
+                }
+            })
+        }
+
+        Commands::Export(path, filter_options, Some(format)) => {
+            let ids_result = vault.filtered_ids(&filter_options);
+            if ids_result.is_fail() { return ids_result.convert("cli::execute()") }
+            let ids = ids_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()");
+            let rows = export_rows(&vault.bank, &ids);
+            let export_result = write_rows(&rows, &path, format);
+            if export_result.is_fail() { return export_result.convert("cli::execute()") }
+            Pass(vec![format!("Exported {} Transactions to {}", rows.len(), path.display())])
+        }
+
+        Commands::Export(path, filter_options, None) => {
+            let transactions_result = vault.filtered(&filter_options);
+            if transactions_result.is_fail() { return transactions_result.convert("cli::execute()") }
+            let transactions = transactions_result.wont_fail("This is past an is_fail() guard clause.", "cli::execute()");
+            let count = transactions.len();
+            let save_data = SaveData { transactions, ..vault.save_data() };
+            let export_result = export_to(save_data, &path);
+            if export_result.is_fail() { return export_result.convert("cli::execute()") }
+            Pass(vec![format!("Exported {count} Transactions to {}", path.display())])
+        }
+
+        Commands::Help => { Pass(Vec::new()) }
+    }
+}
+
+/// Returns a line describing a `Transaction`, followed by an indented line for each of its `Split`s.
+/// The `Tag`s of the first line include those of the `Split`s, so that every `Tag` the `Transaction` counts towards is listed.
+#[must_use]
+fn listing_of(transaction: &Transaction) -> String {
+    let uid = transaction.get_uid().to_string();
+    let tags = transaction.get_all_tags().iter().map(Tag::get_label).collect::<Vec<_>>().join(", ");
+    let mut lines = vec![format!(
+        "{}  {}  {:>12} {}  {}  [{tags}]",
+        &uid[..SHORT_UID_LENGTH],
+        transaction.date.display_iso(),
+        transaction.value.amount(),
+        transaction.value.currency(),
+        transaction.description,
+    )];
+    for split in transaction.get_splits() {
+        let split_tags = split.tags.iter().map(Tag::get_label).collect::<Vec<_>>().join(", ");
+        let note = split.note.as_ref().map(|note| format!("  {note}")).unwrap_or_default();
+        lines.push(format!("{:>33} {}  split{note}  [{split_tags}]", split.amount, transaction.value.currency()));
+    }
+    lines.join("\n")
+}
+
+
+
+//====================================================================================================//
+// VAULT
+//====================================================================================================//
+/// A vault loaded for a single command.
+struct LoadedVault {
+    vault_name: String,
+    vault_key: Option<VaultKey>,
+    theme: MaterialThemes,
+    bank: Bank,
+}
+impl LoadedVault {
+    // initializing
+    /// Loads a vault, asking for its passphrase if it is encrypted.
+    #[must_use]
+    fn open(vault_name: String) -> Schrod<LoadedVault> {
+        // unlocking the vault
+        let vault_key = if is_vault_encrypted(&vault_name) {
+            let passphrase_result = passphrase_for(&vault_name);
+            if passphrase_result.is_fail() { return passphrase_result.convert("LoadedVault::open()") }
+            let passphrase = passphrase_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::open()");
+            let vault_key_result = VaultKey::unlock(&vault_name, &passphrase);
+            if vault_key_result.is_fail() {
+                return vault_key_result.convert("LoadedVault::open()").fail("The passphrase is incorrect.", "LoadedVault::open()")
+            }
+            Some(vault_key_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::open()"))
+        }
+        else { None };
+
+        // loading the bank
+        let save_data_result = load(&vault_name, vault_key.as_ref());
+        if save_data_result.is_fail() { return save_data_result.convert("LoadedVault::open()") }
+        let save_data = save_data_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::open()");
+        if let Some(notice) = &save_data.recovery_notice { eprintln!("{notice}"); }
+        let mut bank = Bank::default();
+        let init_result = bank.init(save_data.transactions, save_data.currency_exchange, save_data.tag_registry, save_data.recurring_templates, save_data.budgets, save_data.tag_rules, save_data.accounts);
+        if init_result.is_fail() { return init_result.convert("LoadedVault::open()") }
+
+        Pass(LoadedVault { vault_name, vault_key, theme: save_data.theme, bank })
+    }
+
+
+
+    // management
+    /// Adds a new `Transaction` and returns its short `Uid`.
+    #[must_use]
+    fn add(&mut self, transaction_options: TransactionOptions) -> Schrod<String> {
+        let (Some(value), Some(currency), Some(description)) = (transaction_options.value, transaction_options.currency, transaction_options.description) else {
+            return Schrod::new_fail("Adding a Transaction needs --value, --currency, and --description.", "LoadedVault::add()")
+        };
+        let date = match transaction_options.date {
+            Some(date) => date,
+            None => {
+                let today_result = Date::today();
+                if today_result.is_fail() { return today_result.convert("LoadedVault::add()") }
+                today_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::add()")
+            }
+        };
+        if !Transaction::are_raw_parts_valid(&value, &currency, &description, &transaction_options.tags) {
+            return Schrod::new_fail("The Transaction is not valid. Please check the value, currency, description, and tags.", "LoadedVault::add()")
+        }
+
+        let known_uids = self.bank.get_ledger().iter().map(Transaction::get_uid).collect::<Vec<_>>();
+        let add_result = self.bank.add_transaction_from_raw_parts(&value, &currency, date, description, transaction_options.tags, transaction_options.splits, self.bank.get_default_account_uid());
+        if add_result.is_fail() { return add_result.convert("LoadedVault::add()") }
+
+        let new_uid = self.bank.get_ledger().iter().map(Transaction::get_uid).find(|uid| !known_uids.contains(uid));
+        Pass(new_uid.map(|uid| uid.to_string()[..SHORT_UID_LENGTH].to_string()).unwrap_or_default())
+    }
+
+    /// Edits a `Transaction`, changing only the given parts, and returns its short `Uid`.
+    #[must_use]
+    fn edit(&mut self, uid_prefix: &str, transaction_options: TransactionOptions) -> Schrod<String> {
+        let id_result = self.find(uid_prefix);
+        if id_result.is_fail() { return id_result.convert("LoadedVault::edit()") }
+        let id = id_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::edit()");
+        let transaction_result = self.bank.get(id);
+        if transaction_result.is_fail() { return transaction_result.convert("LoadedVault::edit()") }
+        let transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::edit()").clone();
+
+        let value = transaction_options.value.unwrap_or_else(|| transaction.value.amount().to_string());
+        let currency = transaction_options.currency.unwrap_or_else(|| transaction.value.currency().to_string());
+        let date = transaction_options.date.unwrap_or(transaction.date);
+        let description = transaction_options.description.unwrap_or_else(|| transaction.description.clone());
+        let tags = if transaction_options.tags.is_empty() { transaction.tags.clone() } else { transaction_options.tags };
+        let splits = if transaction_options.splits.is_empty() { transaction.get_splits().to_vec() } else { transaction_options.splits };
+
+        let edit_result = self.bank.edit_transaction_with_raw_parts(id, &value, &currency, date, description, tags, splits, transaction.get_account_uid());
+        if edit_result.is_fail() { return edit_result.convert("LoadedVault::edit()") }
+        Pass(transaction.get_uid().to_string()[..SHORT_UID_LENGTH].to_string())
+    }
+
+    /// Removes a `Transaction` and returns its short `Uid`.
+    #[must_use]
+    fn remove(&mut self, uid_prefix: &str) -> Schrod<String> {
+        let id_result = self.find(uid_prefix);
+        if id_result.is_fail() { return id_result.convert("LoadedVault::remove()") }
+        let id = id_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::remove()");
+        let uid = match self.bank.get(id) {
+            Pass(transaction) => transaction.get_uid().to_string()[..SHORT_UID_LENGTH].to_string(),
+            Fail(_) => String::new(),
+        };
+
+        let remove_result = self.bank.remove_transaction(id);
+        if remove_result.is_fail() { return remove_result.convert("LoadedVault::remove()") }
+        Pass(uid)
+    }
+
+    /// Saves the vault, keeping its encryption.
+    #[must_use]
+    fn save(&mut self) -> Schrod<()> {
+        self.bank.tag_registry = Bank::get_updated_tag_registry(self.bank.tag_registry.clone(), self.bank.get_tags());
+        save(self.save_data(), &self.vault_name, self.vault_key.as_ref())
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the `SaveData` of the vault.
+    #[must_use]
+    fn save_data(&self) -> SaveData {
+        SaveData::from_bank(self.theme, &self.bank)
+    }
+
+    /// Finds the `Id` of the only `Transaction` whose `Uid` starts with the given prefix.
+    #[must_use]
+    fn find(&self, uid_prefix: &str) -> Schrod<Id> {
+        let uid_prefix = uid_prefix.trim().to_lowercase();
+        if uid_prefix.is_empty() { return Schrod::new_fail("No uid was given.", "LoadedVault::find()") }
+
+        let matches = self.bank.get_ledger()
+            .iter()
+            .filter(|transaction| transaction.get_uid().to_string().starts_with(&uid_prefix))
+            .collect::<Vec<_>>();
+        match matches.as_slice() {
+            [] => Schrod::new_fail(&format!("No Transaction has a uid starting with \"{uid_prefix}\"."), "LoadedVault::find()"),
+            [transaction] => Schrod::from_option(transaction.get_id(), "The Transaction has no id.", "LoadedVault::find()"),
+            _ => Schrod::new_fail(&format!("{} Transactions have a uid starting with \"{uid_prefix}\". Please give more of the uid.", matches.len()), "LoadedVault::find()"),
+        }
+    }
+
+    /// Applies the filter options and returns the `Id`s of the matching `Transaction`s.
+    #[must_use]
+    fn filtered_ids(&mut self, filter_options: &FilterOptions) -> Schrod<Vec<Id>> {
+        let filter = Filters::Primary;
+        let mut results = vec![
+            self.bank.clear_filter_year(filter),
+            self.bank.clear_filter_month(filter),
+            self.bank.clear_filter_tags(filter),
+            self.bank.clear_filter_search_terms(filter),
+        ];
+        if let Some(year) = filter_options.year { results.push(self.bank.set_filter_year(year, filter)); }
+        if let Some(month) = filter_options.month { results.push(self.bank.set_filter_month(month, filter)); }
+        for tag in &filter_options.tags { results.push(self.bank.add_filter_tag(tag, filter)); }
+        for search_term in &filter_options.search_terms { results.push(self.bank.add_filter_search_term(search_term, filter)); }
+        if filter_options.match_any { results.push(self.bank.toggle_filter_mode(filter)); }
+        if Schrod::contains_fail(&results) {
+            return Schrod::collect_and_fail(&results, "LoadedVault::filtered_ids()").convert("LoadedVault::filtered_ids()").fail("Failed to filter the Transactions.", "LoadedVault::filtered_ids()")
+        }
+
+        Pass(self.bank.get_filtered_ids(filter))
+    }
+
+    /// Applies the filter options and returns the matching `Transaction`s, newest first.
+    #[must_use]
+    fn filtered(&mut self, filter_options: &FilterOptions) -> Schrod<Vec<Transaction>> {
+        let ids_result = self.filtered_ids(filter_options);
+        if ids_result.is_fail() { return ids_result.convert("LoadedVault::filtered()") }
+        let ids = ids_result.wont_fail("This is past an is_fail() guard clause.", "LoadedVault::filtered()");
+
+        let transactions = self.bank.get_ledger()
+            .iter()
+            .filter(|transaction| transaction.get_id().is_some_and(|id| ids.contains(&id)))
+            .cloned()
+            .collect::<Vec<_>>();
+        Pass(Bank::sorted_ledger(transactions))
+    }
+}
+
+/// Returns the passphrase of an encrypted vault from `ASCENT_PASSPHRASE`, or asks for it.
+#[must_use]
+fn passphrase_for(vault_name: &str) -> Schrod<String> {
+    if let Ok(passphrase) = std::env::var("ASCENT_PASSPHRASE") { return Pass(passphrase) }
+
+    eprint!("Passphrase for {vault_name}: ");
+    std::io::stderr().flush().ok();
+    let mut passphrase = String::new();
+    let read_result = Schrod::from_result(std::io::stdin().read_line(&mut passphrase), "Failed to read the passphrase.", "cli::passphrase_for()");
+    if read_result.is_fail() { return read_result.convert("cli::passphrase_for()") }
+    Pass(passphrase.trim_end_matches(['\n', '\r']).to_string())
+}
+
+
+
+//====================================================================================================//
+// PARSING
+//====================================================================================================//
+/// Parses the command-line arguments, not including the program name.
+/// Returns `None` if no command was given, in which case the window should be opened.
+#[must_use]
+pub fn parse_arguments(arguments: &[String]) -> Option<Schrod<Invocation>> {
+    let mut vault_name = None;
+    let mut remaining = Vec::new();
+
+    // taking out the options that apply to every command
+    let mut index = 0;
+    while index < arguments.len() {
+        let argument = &arguments[index];
+        if argument == "--data-dir" || argument == "--vault" {
+            let Some(value) = arguments.get(index + 1) else {
+                return Some(Schrod::new_fail(&format!("{argument} needs a value."), "cli::parse_arguments()"))
+            };
+            if argument == "--vault" { vault_name = Some(value.clone()); }
+            index += 2;
+            continue
+        }
+        if let Some(value) = argument.strip_prefix("--vault=") { vault_name = Some(value.to_string()); }
+        // the data directory is read by the save engine itself, and macOS may pass a process serial number
+        else if !argument.starts_with("--data-dir=") && !argument.starts_with("-psn_") { remaining.push(argument.clone()); }
+        index += 1;
+    }
+
+    let (command_name, options) = remaining.split_first()?;
+    let command_result = match command_name.as_str() {
+        "list" => as_command(parse_filter_options(options), Commands::List),
+        "add" => as_command(parse_transaction_options(options), Commands::Add),
+        "edit" => match options.split_first() {
+            Some((uid_prefix, options)) if !uid_prefix.starts_with("--") => as_command(parse_transaction_options(options), |transaction_options| Commands::Edit(uid_prefix.clone(), transaction_options)),
+            _ => Schrod::new_fail("edit needs the uid of a Transaction.", "cli::parse_arguments()"),
+        },
+        "remove" => match options {
+            [uid_prefix] => Pass(Commands::Remove(uid_prefix.clone())),
+            _ => Schrod::new_fail("remove needs the uid of a Transaction, and nothing else.", "cli::parse_arguments()"),
+        },
+        "cashflow" => parse_cash_flow_options(options),
+        "export" => match options.split_first() {
+            Some((path, options)) if !path.starts_with("--") => parse_export_options(PathBuf::from(path), options),
+            _ => Schrod::new_fail("export needs the path of the file to write.", "cli::parse_arguments()"),
+        },
+        "help" | "--help" | "-h" => Pass(Commands::Help),
+        unknown => Schrod::new_fail(&format!("\"{unknown}\" is not a command."), "cli::parse_arguments()"),
+    };
+
+    Some(match command_result {
+        Pass(command) => Pass(Invocation { vault_name, command }),
+        Fail(_) => command_result.convert("cli::parse_arguments()"),
+    })
+}
+
+/// Turns parsed options into a `Commands`, keeping any failure.
+#[must_use]
+fn as_command<T>(options_result: Schrod<T>, command: impl FnOnce(T) -> Commands) -> Schrod<Commands> {
+    match options_result {
+        Pass(options) => Pass(command(options)),
+        Fail(_) => options_result.convert("cli::as_command()"),
+    }
+}
+
+/// Parses the options of the cashflow command.
+#[must_use]
+fn parse_cash_flow_options(options: &[String]) -> Schrod<Commands> {
+    let mut flow_type = None;
+    let mut filter_arguments = Vec::new();
+    let mut index = 0;
+    while index < options.len() {
+        if options[index] == "--flow" {
+            flow_type = match options.get(index + 1).map(|value| value.to_lowercase()).as_deref() {
+                Some("collected") => Some(FlowTypes::Collected),
+                Some("unified") => Some(FlowTypes::Unified),
+                Some("time") => Some(FlowTypes::Time),
+                _ => return Schrod::new_fail("--flow needs to be collected, unified, or time.", "cli::parse_cash_flow_options()"),
+            };
+            index += 2;
+        }
+        else {
+            filter_arguments.push(options[index].clone());
+            index += 1;
+        }
+    }
+
+    as_command(parse_filter_options(&filter_arguments), |filter_options| Commands::CashFlow(filter_options, flow_type))
+}
+
+/// Parses the options of the export command.
+#[must_use]
+fn parse_export_options(path: PathBuf, options: &[String]) -> Schrod<Commands> {
+    let mut format = match ExportFormats::from_path(&path) {
+        Some(ExportFormats::Csv) => Some(ExportFormats::Csv),
+        _ => None,
+    };
+    let mut filter_arguments = Vec::new();
+    let mut index = 0;
+    while index < options.len() {
+        if options[index] == "--format" {
+            format = match options.get(index + 1).map(|value| value.to_lowercase()).as_deref() {
+                Some("save") => None,
+                Some("csv") => Some(ExportFormats::Csv),
+                Some("json") => Some(ExportFormats::Json),
+                _ => return Schrod::new_fail("--format needs to be save, csv, or json.", "cli::parse_export_options()"),
+            };
+            index += 2;
+        }
+        else {
+            filter_arguments.push(options[index].clone());
+            index += 1;
+        }
+    }
+
+    as_command(parse_filter_options(&filter_arguments), |filter_options| Commands::Export(path, filter_options, format))
+}
+
+/// Parses filter options.
+#[must_use]
+fn parse_filter_options(options: &[String]) -> Schrod<FilterOptions> {
+    let mut filter_options = FilterOptions::default();
+    let mut index = 0;
+    while index < options.len() {
+        let option = options[index].as_str();
+        if option == "--any" {
+            filter_options.match_any = true;
+            index += 1;
+            continue
+        }
+
+        let value_result = value_of(options, index);
+        if value_result.is_fail() { return value_result.convert("cli::parse_filter_options()") }
+        let value = value_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_filter_options()");
+        match option {
+            "--year" => {
+                let year_result = Schrod::from_result(value.parse::<u32>(), &format!("\"{value}\" is not a year."), "cli::parse_filter_options()");
+                if year_result.is_fail() { return year_result.convert("cli::parse_filter_options()") }
+                filter_options.year = Some(year_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_filter_options()"));
+            }
+            "--month" => {
+                let month_result = parse_month(value);
+                if month_result.is_fail() { return month_result.convert("cli::parse_filter_options()") }
+                filter_options.month = Some(month_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_filter_options()"));
+            }
+            "--tag" => {
+                let tag_result = Tag::new(value);
+                if tag_result.is_fail() { return tag_result.convert("cli::parse_filter_options()").fail(&format!("\"{value}\" is not a valid tag."), "cli::parse_filter_options()") }
+                filter_options.tags.push(tag_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_filter_options()"));
+            }
+            "--search" => { filter_options.search_terms.push(value.to_string()); }
+            _ => { return Schrod::new_fail(&format!("\"{option}\" is not a filter option."), "cli::parse_filter_options()") }
+        }
+        index += 2;
+    }
+
+    Pass(filter_options)
+}
+
+/// Parses the options describing a `Transaction`.
+#[must_use]
+fn parse_transaction_options(options: &[String]) -> Schrod<TransactionOptions> {
+    let mut transaction_options = TransactionOptions::default();
+    let mut index = 0;
+    while index < options.len() {
+        let option = options[index].as_str();
+        let value_result = value_of(options, index);
+        if value_result.is_fail() { return value_result.convert("cli::parse_transaction_options()") }
+        let value = value_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_transaction_options()");
+        match option {
+            "--value" => { transaction_options.value = Some(value.to_string()); }
+            "--currency" => { transaction_options.currency = Some(value.to_uppercase()); }
+            "--date" => {
+                let date_result = parse_date(value, "%Y-%m-%d");
+                if date_result.is_fail() { return date_result.convert("cli::parse_transaction_options()") }
+                transaction_options.date = Some(date_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_transaction_options()"));
+            }
+            "--description" => { transaction_options.description = Some(value.to_string()); }
+            "--tag" => {
+                let tag_result = Tag::new(value);
+                if tag_result.is_fail() { return tag_result.convert("cli::parse_transaction_options()").fail(&format!("\"{value}\" is not a valid tag."), "cli::parse_transaction_options()") }
+                transaction_options.tags.push(tag_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_transaction_options()"));
+            }
+            "--split" => {
+                let split_result = parse_split(value);
+                if split_result.is_fail() { return split_result.convert("cli::parse_transaction_options()") }
+                transaction_options.splits.push(split_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_transaction_options()"));
+            }
+            _ => { return Schrod::new_fail(&format!("\"{option}\" is not a transaction option."), "cli::parse_transaction_options()") }
+        }
+        index += 2;
+    }
+
+    Pass(transaction_options)
+}
+
+/// Parses a `Split` written as its amount followed by its comma-separated `Tag`s.
+#[must_use]
+fn parse_split(input: &str) -> Schrod<Split> {
+    let Some((amount, labels)) = input.split_once(',') else {
+        return Schrod::new_fail(&format!("\"{input}\" needs an amount and at least one tag, separated by commas."), "cli::parse_split()")
+    };
+    let tags_result = Tag::list_from(labels);
+    if tags_result.is_fail() { return tags_result.convert("cli::parse_split()") }
+    let split_result = Split::new_from_raw(amount, tags_result.wont_fail("This is past an is_fail() guard clause.", "cli::parse_split()"), "");
+    if split_result.is_fail() { return split_result.fail(&format!("\"{input}\" is not a valid split."), "cli::parse_split()") }
+    split_result
+}
+
+/// Returns the value following the option at the given index.
+#[must_use]
+fn value_of(options: &[String], index: usize) -> Schrod<&str> {
+    match options.get(index + 1) {
+        Some(value) => Pass(value.as_str()),
+        None => Schrod::new_fail(&format!("{} needs a value.", options[index]), "cli::value_of()"),
+    }
+}
+
+/// Parses a `Month` from its number or its name.
+#[must_use]
+fn parse_month(input: &str) -> Schrod<Months> {
+    if let Ok(value) = input.parse::<u32>() { return Months::from_value(value) }
+
+    let input = input.to_lowercase();
+    let month = (1..=12)
+        .filter_map(|value| match Months::from_value(value) { Pass(month) => Some(month), Fail(_) => None })
+        .find(|month| input.len() >= 3 && month.display().to_lowercase().starts_with(&input));
+    Schrod::from_option(month, &format!("\"{input}\" is not a month."), "cli::parse_month()")
+}
+
+
+
+//====================================================================================================//
+// TESTS
+//====================================================================================================//
+#[cfg(test)]
+mod tests {
+    use super::{Commands, FilterOptions, Invocation, listing_of, parse_arguments};
+    use crate::vault::export::ExportFormats;
+    use crate::vault::parse::FlowTypes;
+    use crate::vault::test_helpers::{date, tag, transaction};
+    use crate::vault::transaction::{Months, Split};
+
+    /// Parses a command line written as a single `String`.
+    fn parsed(command_line: &str) -> Option<Invocation> {
+        let arguments = command_line.split_whitespace().map(str::to_string).collect::<Vec<_>>();
+        parse_arguments(&arguments).map(|result| result.wont_fail("The command line should parse.", "tests::parsed()"))
+    }
+
+    #[test]
+    fn no_command_opens_the_window() {
+        assert!(parsed("").is_none());
+        assert!(parsed("--data-dir /tmp/ascent").is_none());
+    }
+
+    #[test]
+    fn filters_are_parsed() {
+        let invocation = parsed("--vault Business list --year 2026 --month mar --tag food --tag rent --search coffee --any");
+        let expected_filter_options = FilterOptions {
+            year: Some(2026),
+            month: Some(Months::March),
+            tags: vec![tag("food"), tag("rent")],
+            search_terms: vec!["coffee".to_string()],
+            match_any: true,
+        };
+        assert_eq!(invocation, Some(Invocation { vault_name: Some("Business".to_string()), command: Commands::List(expected_filter_options) }));
+    }
+
+    #[test]
+    fn cash_flow_type_is_separate_from_filters() {
+        let invocation = parsed("cashflow --flow time --month 4");
+        let expected_filter_options = FilterOptions { month: Some(Months::April), ..FilterOptions::default() };
+        assert_eq!(invocation.map(|invocation| invocation.command), Some(Commands::CashFlow(expected_filter_options, Some(FlowTypes::Time))));
+    }
+
+    #[test]
+    fn export_format_follows_the_path_unless_given() {
+        let format_of = |command_line: &str| match parsed(command_line).map(|invocation| invocation.command) {
+            Some(Commands::Export(_, _, format)) => format,
+            _ => panic!("{command_line} should be an export"),
+        };
+        assert_eq!(format_of("export out.json"), None);
+        assert_eq!(format_of("export out.CSV --year 2026"), Some(ExportFormats::Csv));
+        assert_eq!(format_of("export out.json --format json"), Some(ExportFormats::Json));
+        assert_eq!(format_of("export out.csv --format save"), None);
+    }
+
+    #[test]
+    fn bad_command_lines_are_rejected() {
+        for command_line in ["frobnicate", "list --year", "list --colour red", "remove", "edit --value 5", "add --date 2026-13-01"] {
+            let arguments = command_line.split_whitespace().map(str::to_string).collect::<Vec<_>>();
+            assert!(parse_arguments(&arguments).is_some_and(|result| result.is_fail()), "{command_line} should be rejected");
+        }
+    }
+
+    #[test]
+    fn listings_include_the_tags_of_splits() {
+        let mut receipt = transaction("-50", "USD", date(2026, Months::March, 7), "Market", vec![tag("groceries")]);
+        let household = Split::new_from_raw("-12.50", vec![tag("household")], "Cleaning supplies").wont_fail("The split is valid.", "tests");
+        assert!(receipt.set_splits(vec![household]).is_pass());
+
+        let listing = listing_of(&receipt);
+        let lines = listing.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("Market  [groceries, household]"));
+        assert!(lines[1].ends_with("-12.50 USD  split  Cleaning supplies  [household]"));
+    }
+}
@@ -1,5 +1,9 @@
+use std::path::PathBuf;
 use iced::keyboard::key::Named;
 use iced::widget::operation::{focus_next, focus_previous};
+use iced::advanced::widget::{self, Operation, operate};
+use iced::advanced::widget::operation::{Focusable, Outcome};
+use iced::Rectangle;
 use iced::{Element, Event, Subscription, Task, Theme, event, keyboard};
 use iced::widget::text_editor::Content;
 use materialui::materials::MaterialThemes;
@@ -8,37 +12,69 @@ use rust_decimal::prelude::FromPrimitive;
 use crate::container::signal::Signal;
 use crate::pages::confirm_import_page::confirm_import_page;
 use crate::pages::confirm_legacy_import_page::confirm_legacy_import_page;
+use crate::pages::confirm_csv_import_page::confirm_csv_import_page;
+use crate::pages::confirm_ofx_import_page::confirm_ofx_import_page;
+use crate::pages::confirm_rate_import_page::confirm_rate_import_page;
 use crate::pages::help_page::help_page;
 use crate::pages::settings_page::settings_page;
+use crate::pages::backups_page::backups_page;
+use crate::pages::accounts_page::accounts_page;
+use crate::pages::budgets_page::budgets_page;
+use crate::pages::bulk_edit_page::bulk_edit_page;
+use crate::pages::tag_rules_page::tag_rules_page;
+use crate::pages::recurring_page::recurring_page;
+use crate::pages::unlock_page::unlock_page;
 use crate::pages::transaction_management_pages::{add_transaction_page, edit_transaction_page};
 use crate::pages::transactions_page::transactions_page;
 use crate::pages::tag_registry_page::{TagRegistrationSlipStateManager, tag_registry_page};
 use crate::pages::application_errors_page::application_errors_page;
 use crate::pages::trends_page::trends_page;
 use materialui::components::{DatePickerModes, PageProvider, ThemeProvider, page_pointer};
+use crate::vault::account::{Account, AccountTypes};
 use crate::vault::bank::{Bank, CurrencyExchange, Filters, TagRegistry};
+use crate::vault::budget::Budget;
+use crate::vault::bulk_edit::{BulkActionTypes, BulkActions};
+use crate::vault::tag_rule::{TagRule, TagRuleChange};
+use crate::vault::tag_suggestion::TagSuggestions;
+use crate::vault::history::{BankCommand, BankState, History};
+use crate::vault::csv_import::{CsvImport, parse_date};
+use crate::vault::export::{ExportFormats, export_filtered};
+use crate::vault::import_merge::{ImportMerge, ImportModes};
+use crate::vault::ofx_import::OfxImport;
+use crate::vault::rate_import::RateImport;
 use crate::vault::parse::CashFlow;
+use crate::vault::recurring::{RecurrenceRules, RecurringTemplate};
 use crate::vault::ring_parse::{FlowDirections, RingParse, Segment};
 use crate::vault::save_engine::legacy::load_legacy_from;
-use crate::vault::transaction::{Date, Id, Months, Tag, Transaction/*, ValueDisplayFormats*/};
+use crate::vault::transaction::{Date, Id, Months, Split, Tag, Transaction, Uid/*, ValueDisplayFormats*/};
 use schrod::Schrod;
 use schrod::Schrod::{Pass, Fail};
 use crate::vault::trend_parse::{Intervals, TrendParse};
 use iced::futures::SinkExt;
 use iced::futures::channel::mpsc::Sender;
-use crate::vault::save_engine::{SaveData, backup, load, load_from, save};
+use crate::vault::save_engine::{BackupInfo, SAVES_PER_AUTOMATIC_BACKUP, SaveData, VaultKey, backup, change_encryption, create_vault, get_active_vault, is_vault_encrypted, list_backups, list_vaults, load, load_from, save, set_active_vault};
 
 /// The available pages in the `App`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Pages {
     Transactions,
+    Accounts,
     AddingTransaction,
     EditingTransaction,
     Trends,
+    Recurring,
+    Budgets,
+    TagRules,
     TagRegistry,
     Settings,
+    Backups,
     ConfirmImport,
     ConfirmLegacyImport,
+    ConfirmCsvImport,
+    ConfirmOfxImport,
+    ConfirmRateImport,
+    BulkEdit,
+    Unlock,
 }
 impl Pages {
     /// Returns the name for a given `Page`.
@@ -46,13 +82,23 @@ impl Pages {
     pub fn name(&self) -> &str {
         match self {
             Pages::Transactions => { "Transactions" }
+            Pages::Accounts => { "Accounts" }
             Pages::AddingTransaction => { "Adding Transaction" }
             Pages::EditingTransaction => { "Editing Transaction" }
             Pages::Trends => { "Trends" }
+            Pages::Recurring => { "Recurring" }
+            Pages::Budgets => { "Budgets" }
+            Pages::TagRules => { "Tag Rules" }
             Pages::TagRegistry => { "Tag Registry" }
             Pages::Settings => { "Settings" }
+            Pages::Backups => { "Backups" }
             Pages::ConfirmImport => { "Confirm Import" }
             Pages::ConfirmLegacyImport => { "Confirm Legacy Import" }
+            Pages::ConfirmCsvImport => { "Confirm CSV Import" }
+            Pages::ConfirmOfxImport => { "Confirm OFX Import" }
+            Pages::ConfirmRateImport => { "Confirm Exchange Rate Import" }
+            Pages::BulkEdit => { "Bulk Edit" }
+            Pages::Unlock => { "Unlock" }
         }
     }
     
@@ -61,12 +107,19 @@ impl Pages {
     pub fn icon_name(&self) -> &str {
         match self {
             Pages::Transactions => "money-bill",
+            Pages::Accounts => "building-columns",
             Pages::AddingTransaction => "plus",
             Pages::EditingTransaction => "pencil",
             Pages::Trends => "arrow-trend-up",
+            Pages::Recurring => "repeat",
+            Pages::Budgets => "wallet",
+            Pages::TagRules => "wand-magic-sparkles",
             Pages::TagRegistry => "tags",
             Pages::Settings => "gear",
-            Pages::ConfirmImport | Pages::ConfirmLegacyImport => "file-import",
+            Pages::Backups => "clock-rotate-left",
+            Pages::ConfirmImport | Pages::ConfirmLegacyImport | Pages::ConfirmCsvImport | Pages::ConfirmOfxImport | Pages::ConfirmRateImport => "file-import",
+            Pages::BulkEdit => "pen-to-square",
+            Pages::Unlock => "lock",
         }
     }
 
@@ -75,8 +128,13 @@ impl Pages {
     pub fn page_pointers<'a>(app: &'a App) -> Vec<Element<'a, Signal>> {
         let pages = vec![
             Pages::Transactions,
+            Pages::Accounts,
             Pages::Trends,
+            Pages::Recurring,
+            Pages::Budgets,
+            Pages::TagRules,
             Pages::TagRegistry,
+            Pages::Backups,
             Pages::Settings,
         ];
         
@@ -89,6 +147,28 @@ impl Pages {
 
 
 
+/// Finds out whether any text field is focused, so that keybinds can leave its text alone.
+#[derive(Default)]
+struct TextFieldFocus {
+    /// Whether a focused text field has been found.
+    is_focused: bool,
+}
+impl Operation<bool> for TextFieldFocus {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation<bool>)) {
+        operate(self);
+    }
+
+    fn focusable(&mut self, _id: Option<&widget::Id>, _bounds: Rectangle, state: &mut dyn Focusable) {
+        if state.is_focused() { self.is_focused = true; }
+    }
+
+    fn finish(&self) -> Outcome<bool> {
+        Outcome::Some(self.is_focused)
+    }
+}
+
+
+
 /// The central application container.
 /// This holds the `Bank` and all ui/ux state information.
 #[allow(clippy::struct_excessive_bools)] // This is more ergonomic than using enums for bool flags.
@@ -97,9 +177,21 @@ pub struct App {
     saved_successfully: bool,
     loaded_successfully: bool,
     pub import_data: Option<SaveData>,
+    pub import_mode: ImportModes,
+    pub import_merge: Option<ImportMerge>,
     pub legacy_import_data: Option<Vec<Transaction>>,
+    pub csv_import_data: Option<CsvImport>,
+    pub ofx_import_data: Option<OfxImport>,
+    pub rate_import_data: Option<RateImport>,
     //does_save_file_exist: bool, // todo: implement a notice
+    pub vault_name: String,
+    pub vault_names: Vec<String>,
+    vault_key: Option<VaultKey>,
+    pub locked_vault_name: Option<String>,
+    saves_since_backup: u32,
     pub bank: Bank,
+    /// The changes to the `Bank` that can be undone and redone.
+    pub history: History,
     
     // bank display state
     cash_flow_result: Schrod<CashFlow>, // todo: currently this is completele unused as it is duplicated in the transactions page
@@ -114,9 +206,16 @@ pub struct App {
     
     // transactions page state
     pub are_ring_charts_ready: bool,
+    pub are_ring_tags_rolled_up: bool,
     pub earning_ring_parse_result: Schrod<RingParse>,
     pub spending_ring_parse_result: Schrod<RingParse>,
     pub hovered_segment: Option<Segment>,
+    /// The `Uid`s of the `Transaction`s selected for a bulk edit.
+    pub selected_transaction_uids: Vec<Uid>,
+    
+    // bulk edit page
+    pub bulk_action_type: BulkActionTypes,
+    pub bulk_edit_input_string: String,
     
     // filtering
     pub primary_filter_current_search_term_string: String,
@@ -132,7 +231,14 @@ pub struct App {
     pub new_transaction_selected_date: Date,
     pub new_transaction_description_content: Content,
     pub new_transaction_current_tag_string: String,
+    pub new_transaction_tag_suggestions: TagSuggestions,
     pub new_transaction_tags: Vec<Tag>,
+    pub new_transaction_recurrence: Option<RecurrenceRules>,
+    pub new_transaction_account_uid: Uid,
+    pub new_transaction_splits: Vec<Split>,
+    pub new_split_amount_string: String,
+    pub new_split_tags_string: String,
+    pub new_split_note_string: String,
 
     // edit transaction state information
     pub edit_transaction_id: Id,
@@ -144,11 +250,19 @@ pub struct App {
     pub edit_transaction_selected_date: Date,
     pub edit_transaction_description_content: Content,
     pub edit_transaction_current_tag_string: String,
+    pub edit_transaction_tag_suggestions: TagSuggestions,
     pub edit_transaction_tags: Vec<Tag>,
+    pub edit_transaction_splits: Vec<Split>,
+    pub edit_transaction_account_uid: Uid,
+    pub edit_split_amount_string: String,
+    pub edit_split_tags_string: String,
+    pub edit_split_note_string: String,
     pub edit_transaction_is_delete_primed: bool,
     
     // tag registry page state information
     pub tag_registry_slip_state_manager: TagRegistrationSlipStateManager,
+    pub renaming_tag: Option<Tag>,
+    pub rename_tag_string: String,
 
     // trends page
     pub is_trend_chart_ready: bool,
@@ -162,6 +276,51 @@ pub struct App {
     // settings page
     pub new_main_currency_string: String,
     pub new_time_price_string: String,
+    pub is_time_price_history_open: bool,
+    pub historical_time_price_date_string: String,
+    pub historical_time_price_string: String,
+    pub historical_time_price_currency_string: String,
+    /// The currency pair of the `ExchangeRate` whose history is being shown.
+    pub rate_history_pair: Option<(String, String)>,
+    pub historical_rate_date_string: String,
+    pub historical_rate_string: String,
+    pub new_vault_name_string: String,
+    pub current_passphrase_string: String,
+    pub new_passphrase_string: String,
+    pub confirm_passphrase_string: String,
+    
+    // budgets page
+    pub new_budget_tags: Vec<Tag>,
+    pub new_budget_interval: Intervals,
+    pub new_budget_limit_string: String,
+    pub new_budget_currency_string: String,
+    
+    // tag rules page
+    pub new_tag_rule_description_string: String,
+    pub new_tag_rule_below_string: String,
+    pub new_tag_rule_above_string: String,
+    pub new_tag_rule_currency_string: String,
+    pub new_tag_rule_tags_string: String,
+    /// The changes that re-running the `TagRule`s would make, shown before they are applied.
+    pub tag_rule_changes: Option<Vec<TagRuleChange>>,
+    
+    // accounts page
+    pub new_account_name_string: String,
+    pub new_account_type: AccountTypes,
+    pub new_account_opening_balance_string: String,
+    pub new_account_currency_string: String,
+    pub transfer_from_account_uid: Uid,
+    pub transfer_to_account_uid: Uid,
+    pub transfer_amount_string: String,
+    pub transfer_date_string: String,
+    pub transfer_description_string: String,
+    
+    // backups page
+    pub backups: Vec<BackupInfo>,
+    
+    // unlock page
+    pub unlock_passphrase_string: String,
+    pub unlock_failure: Option<String>,
 }
 /*
 impl Default for App {
@@ -189,8 +348,10 @@ impl App {
         // general failure tracking
         let mut general_failures = Vec::new();
         
-        // getting the save data
-        let save_data_result = load();
+        // getting the save data, unless it has to be unlocked first
+        let vault_name = get_active_vault();
+        let is_locked = is_vault_encrypted(&vault_name);
+        let save_data_result = if is_locked { Pass(SaveData::empty()) } else { load(&vault_name, None) };
         if save_data_result.is_fail() {
             loaded_successfully = false;
             initializing_failures.extend(save_data_result.results());
@@ -220,15 +381,61 @@ impl App {
             Schrod::Fail(_) => TagRegistry::default(),
         };
         
+        // loading the recurring templates
+        let recurring_templates = match &save_data_result {
+            Schrod::Pass(save_data) => save_data.recurring_templates.clone(),
+            Schrod::Fail(_) => Vec::new(),
+        };
+        
+        // loading the budgets
+        let budgets = match &save_data_result {
+            Schrod::Pass(save_data) => save_data.budgets.clone(),
+            Schrod::Fail(_) => Vec::new(),
+        };
+        
+        // loading the tag rules
+        let tag_rules = match &save_data_result {
+            Schrod::Pass(save_data) => save_data.tag_rules.clone(),
+            Schrod::Fail(_) => Vec::new(),
+        };
+        
+        // loading the accounts
+        let accounts = match &save_data_result {
+            Schrod::Pass(save_data) => save_data.accounts.clone(),
+            Schrod::Fail(_) => Vec::new(),
+        };
+        
+        // checking if the save data was upgraded
+        let was_migrated = match &save_data_result {
+            Schrod::Pass(save_data) => save_data.was_migrated,
+            Schrod::Fail(_) => false,
+        };
+        
+        // checking if the save data was recovered from the previous save
+        if let Schrod::Pass(save_data) = &save_data_result && let Some(notice) = &save_data.recovery_notice {
+            general_failures.push(notice.clone());
+        }
+        
         // loading the bank
         let mut bank = Bank::default();
-        bank.init(transactions, currency_exchange, tag_registry);
+        bank.init(transactions, currency_exchange, tag_registry, recurring_templates, budgets, tag_rules, accounts);
         let tags = bank.get_tags();
         
         // bank display state
         let cash_flow_result = CashFlow::new(&bank, &bank.get_filtered_ids(Filters::Primary));
         if cash_flow_result.is_fail() { general_failures.extend(cash_flow_result.results()); }
         
+        // vaults
+        let vault_names_result = list_vaults();
+        if vault_names_result.is_fail() { general_failures.extend(vault_names_result.results()); }
+        let vault_names = match vault_names_result {
+            Schrod::Pass(vault_names) => vault_names,
+            Schrod::Fail(_) => vec![vault_name.clone()],
+        };
+        
+        // waiting for the vault to be unlocked
+        let locked_vault_name = if is_locked { Some(vault_name.clone()) } else { None };
+        
         // trend parse date
         let trend_parse_date = if bank.get_ledger().is_empty() { Date::default() } else { bank.get_ledger()[0].date };
         
@@ -237,21 +444,37 @@ impl App {
             saved_successfully: true,
             loaded_successfully,
             import_data: None,
+            import_mode: ImportModes::Merge,
+            import_merge: None,
             legacy_import_data: None,
+            csv_import_data: None,
+            ofx_import_data: None,
+            rate_import_data: None,
+            vault_name,
+            vault_names,
+            vault_key: None,
+            locked_vault_name,
+            saves_since_backup: 0,
             bank,
+            history: History::default(),
             
             cash_flow_result,
             
             theme_selection: theme,
             application_failures: Vec::new(),
             theme: theme.generate_iced_palette(),
-            page: Pages::Transactions,
+            page: if is_locked { Pages::Unlock } else { Pages::Transactions },
             helping: false,
             
             are_ring_charts_ready: false,
+            are_ring_tags_rolled_up: false,
             earning_ring_parse_result: Schrod::new_fail("No RingParse has been created.", "App::new()"),
             spending_ring_parse_result: Schrod::new_fail("No RingParse has been created.", "App::new()"),
             hovered_segment: None,
+            selected_transaction_uids: Vec::new(),
+            
+            bulk_action_type: BulkActionTypes::AddTag,
+            bulk_edit_input_string: String::new(),
             
             primary_filter_current_search_term_string: String::new(),
             deep_dive_1_filter_current_search_term_string: String::new(),
@@ -265,7 +488,14 @@ impl App {
             new_transaction_selected_date: Date::default(),
             new_transaction_description_content: Content::with_text(""),
             new_transaction_current_tag_string: String::new(),
+            new_transaction_tag_suggestions: TagSuggestions::default(),
             new_transaction_tags: Vec::new(),
+            new_transaction_recurrence: None,
+            new_transaction_account_uid: Uid::nil(),
+            new_transaction_splits: Vec::new(),
+            new_split_amount_string: String::new(),
+            new_split_tags_string: String::new(),
+            new_split_note_string: String::new(),
 
             edit_transaction_id: 0,
             edit_transaction_value_string: String::new(),
@@ -276,10 +506,18 @@ impl App {
             edit_transaction_selected_date: Date::default(),
             edit_transaction_description_content: Content::with_text(""),
             edit_transaction_current_tag_string: String::new(),
+            edit_transaction_tag_suggestions: TagSuggestions::default(),
             edit_transaction_tags: Vec::new(),
+            edit_transaction_splits: Vec::new(),
+            edit_transaction_account_uid: Uid::nil(),
+            edit_split_amount_string: String::new(),
+            edit_split_tags_string: String::new(),
+            edit_split_note_string: String::new(),
             edit_transaction_is_delete_primed: false,
             
             tag_registry_slip_state_manager: TagRegistrationSlipStateManager::new(tags),
+            renaming_tag: None,
+            rename_tag_string: String::new(),
 
             is_trend_chart_ready: false,
             trend_parse_result: Schrod::new_fail("No TrendParse has been created.", "App::new()"),
@@ -291,6 +529,44 @@ impl App {
 
             new_main_currency_string: String::new(),
             new_time_price_string: String::new(),
+            is_time_price_history_open: false,
+            historical_time_price_date_string: String::new(),
+            historical_time_price_string: String::new(),
+            historical_time_price_currency_string: String::new(),
+            rate_history_pair: None,
+            historical_rate_date_string: String::new(),
+            historical_rate_string: String::new(),
+            new_vault_name_string: String::new(),
+            current_passphrase_string: String::new(),
+            new_passphrase_string: String::new(),
+            confirm_passphrase_string: String::new(),
+            
+            new_budget_tags: Vec::new(),
+            new_budget_interval: Intervals::Monthly,
+            new_budget_limit_string: String::new(),
+            new_budget_currency_string: String::new(),
+            
+            new_tag_rule_description_string: String::new(),
+            new_tag_rule_below_string: String::new(),
+            new_tag_rule_above_string: String::new(),
+            new_tag_rule_currency_string: String::new(),
+            new_tag_rule_tags_string: String::new(),
+            tag_rule_changes: None,
+            
+            new_account_name_string: String::new(),
+            new_account_type: AccountTypes::Checking,
+            new_account_opening_balance_string: String::new(),
+            new_account_currency_string: String::new(),
+            transfer_from_account_uid: Uid::nil(),
+            transfer_to_account_uid: Uid::nil(),
+            transfer_amount_string: String::new(),
+            transfer_date_string: String::new(),
+            transfer_description_string: String::new(),
+            
+            backups: Vec::new(),
+            
+            unlock_passphrase_string: String::new(),
+            unlock_failure: None,
         };
         
         // checking for loading failures
@@ -299,8 +575,11 @@ impl App {
         // checking for other failures
         if !general_failures.is_empty() { app.application_failures.extend(general_failures); }
         
+        // saving upgraded save data so that it stays upgraded, or waiting for the vault to be unlocked
+        let launch_task = if is_locked { Task::none() } else if was_migrated { Task::batch(vec![Task::done(Signal::Launch), app.save_task()]) } else { Task::done(Signal::Launch) };
+        
         // returning the app
-        (app, Task::done(Signal::Launch))
+        (app, launch_task)
     }
 
     /// The tile of the `App`.
@@ -313,8 +592,52 @@ impl App {
 
     // running
     /// Updates the `App` based on a given `Signal`.
-    #[allow(clippy::too_many_lines)] // This is going to be large since it is the central signal handler.
+    /// Any changes that the `Signal` makes to the `Bank` are recorded so that they can be undone.
     pub fn update(&mut self, signal: Signal) -> Task<Signal> {
+        let Some(undo_name) = App::get_undo_name(&signal) else { return self.handle_signal(signal) };
+        let before = BankState::of(&self.bank);
+        let task = self.handle_signal(signal);
+        if let Some(command) = BankCommand::between(undo_name, &before, &self.bank) { self.history.record(command); }
+        task
+    }
+    
+    /// Returns the name of the change that a `Signal` makes to the `Bank`, or `None` if it is not a change that can be undone.
+    #[must_use]
+    fn get_undo_name(signal: &Signal) -> Option<&'static str> {
+        match signal {
+            Signal::AddTransaction => { Some("Add Transaction") }
+            Signal::EditTransaction => { Some("Edit Transaction") }
+            Signal::RemoveTransaction => { Some("Remove Transaction") }
+            Signal::ConfirmImport | Signal::ConfirmLegacyImport | Signal::ConfirmCsvImport | Signal::ConfirmOfxImport => { Some("Import") }
+            Signal::ConfirmRateImport => { Some("Import Exchange Rates") }
+            Signal::SetTagColor(..) => { Some("Change Tag Color") }
+            Signal::ResetTag(_) => { Some("Reset Tag") }
+            Signal::RenameTag => { Some("Rename Tag") }
+            Signal::SetMainCurrency => { Some("Set Main Currency") }
+            Signal::SetTimePrice | Signal::AddHistoricalTimePrice => { Some("Set Time Price") }
+            Signal::RemoveHistoricalTimePrice(_) => { Some("Remove Time Price") }
+            Signal::SetFlowType(_) => { Some("Set Flow Type") }
+            Signal::TrySetNewExchangeRate(..) | Signal::AddHistoricalRate => { Some("Set Exchange Rate") }
+            Signal::RemoveHistoricalRate(..) => { Some("Remove Exchange Rate") }
+            Signal::ConfirmRecurrence(_) | Signal::ConfirmAllRecurrences => { Some("Confirm Recurring Transaction") }
+            Signal::SkipRecurrence(_) => { Some("Skip Recurring Transaction") }
+            Signal::RemoveRecurringTemplate(_) => { Some("Remove Recurring Transaction") }
+            Signal::AddBudget => { Some("Add Budget") }
+            Signal::RemoveBudget(_) => { Some("Remove Budget") }
+            Signal::AddTagRule => { Some("Add Tag Rule") }
+            Signal::RemoveTagRule(_) => { Some("Remove Tag Rule") }
+            Signal::ApplyTagRuleRerun => { Some("Re-run Tag Rules") }
+            Signal::ApplyBulkEdit => { Some("Bulk Edit") }
+            Signal::AddAccount => { Some("Add Account") }
+            Signal::RemoveAccount(_) => { Some("Remove Account") }
+            Signal::AddTransfer => { Some("Add Transfer") }
+            _ => { None }
+        }
+    }
+    
+    /// Handles a given `Signal`.
+    #[allow(clippy::too_many_lines)] // This is going to be large since it is the central signal handler.
+    fn handle_signal(&mut self, signal: Signal) -> Task<Signal> {
         // does not allow any changes if the app did not save or load successfully
         if !self.saved_successfully || !self.loaded_successfully {
             return Task::none();
@@ -330,30 +653,63 @@ impl App {
             Signal::AddTransactionKeybind => {
                 match self.page {
                     Pages::Transactions => { Task::done(Signal::StartAddingTransaction) }
-                    Pages::AddingTransaction => {
-                        if Transaction::are_raw_parts_valid(
-                            &self.new_transaction_value_string,
-                            &self.new_transaction_currency_string,
-                            &self.new_transaction_description_content.text(),
-                            &self.new_transaction_tags) {
-                            Task::done(Signal::AddTransaction)
-                        }
-                        else { Task::none() }
+                    Pages::AddingTransaction if Transaction::are_raw_parts_valid(
+                        &self.new_transaction_value_string,
+                        &self.new_transaction_currency_string,
+                        &self.new_transaction_description_content.text(),
+                        &self.new_transaction_tags)
+                        && Transaction::are_splits_valid_for_raw(&self.new_transaction_value_string, &self.new_transaction_splits) => {
+                        Task::done(Signal::AddTransaction)
                     }
-                    Pages::EditingTransaction => {
-                        if Transaction::are_raw_parts_valid(
-                            &self.edit_transaction_value_string,
-                            &self.edit_transaction_currency_string,
-                            &self.edit_transaction_description_content.text(),
-                            &self.edit_transaction_tags) {
-                            Task::done(Signal::EditTransaction)
-                        }
-                        else { Task::none() }
+                    Pages::EditingTransaction if Transaction::are_raw_parts_valid(
+                        &self.edit_transaction_value_string,
+                        &self.edit_transaction_currency_string,
+                        &self.edit_transaction_description_content.text(),
+                        &self.edit_transaction_tags)
+                        && Transaction::are_splits_valid_for_raw(&self.edit_transaction_value_string, &self.edit_transaction_splits) => {
+                        Task::done(Signal::EditTransaction)
                     }
                     _ => { Task::none() }
                 }
             }
             
+            // text fields keep the undo and redo keybinds to themselves while they are focused
+            Signal::UndoKeybind => {
+                operate(TextFieldFocus::default()).then(|is_focused| if is_focused { Task::none() } else { Task::done(Signal::Undo) })
+            }
+            
+            Signal::RedoKeybind => {
+                operate(TextFieldFocus::default()).then(|is_focused| if is_focused { Task::none() } else { Task::done(Signal::Redo) })
+            }
+            
+            Signal::Undo => {
+                let undo_result = self.history.undo(&mut self.bank);
+                self.finish_history_change(undo_result)
+            }
+            
+            Signal::Redo => {
+                let redo_result = self.history.redo(&mut self.bank);
+                self.finish_history_change(redo_result)
+            }
+            
+            Signal::NextTagSuggestionKeybind => {
+                match self.page {
+                    Pages::AddingTransaction => { self.new_transaction_tag_suggestions.select_next(); }
+                    Pages::EditingTransaction => { self.edit_transaction_tag_suggestions.select_next(); }
+                    _ => {}
+                }
+                Task::none()
+            }
+            
+            Signal::PreviousTagSuggestionKeybind => {
+                match self.page {
+                    Pages::AddingTransaction => { self.new_transaction_tag_suggestions.select_previous(); }
+                    Pages::EditingTransaction => { self.edit_transaction_tag_suggestions.select_previous(); }
+                    _ => {}
+                }
+                Task::none()
+            }
+            
             Signal::AdvanceYearKeybind => {
                 match self.page {
                     Pages::Transactions => {
@@ -494,12 +850,16 @@ impl App {
         
             // general signals
             Signal::Launch => {
-                Task::batch(vec![
+                let mut tasks = vec![
                     self.refresh_currency_exchange_task(),
                     self.update_tag_registry_task(),
                     self.update_ring_parse_task(),
                     self.update_trend_parse_task(),
-                ])
+                ];
+                // automatic backup
+                if !self.bank.get_ledger().is_empty() { tasks.push(self.backup_task()); }
+                self.show_due_recurrences();
+                Task::batch(tasks)
             }
             
             Signal::FinishedUpdatingCurrencyExchange(updated_currency_exchange, refresh_result) => {
@@ -527,6 +887,9 @@ impl App {
             
             Signal::ChangePageTo(page) => {
                 self.page = page;
+                if page == Pages::Backups { self.refresh_backups(); }
+                if page == Pages::TagRules { self.tag_rule_changes = None; }
+                if page == Pages::Accounts { self.reset_transfer(); }
                 if page == Pages::Settings { self.refresh_currency_exchange_task() }
                 else { Task::none() }
             }
@@ -745,11 +1108,23 @@ impl App {
                 self.new_transaction_description_content = Content::with_text("");
                 self.new_transaction_current_tag_string = String::new();
                 self.new_transaction_tags = Vec::new();
+                self.new_transaction_recurrence = None;
+                self.new_transaction_account_uid = self.bank.get_default_account_uid();
+                self.new_transaction_splits = Vec::new();
+                self.new_split_amount_string = String::new();
+                self.new_split_tags_string = String::new();
+                self.new_split_note_string = String::new();
+                self.update_new_tag_suggestions();
                 self.page = Pages::AddingTransaction;
                 
                 Task::none()
             }
 
+            Signal::ToggleRingTagRollup => {
+                self.are_ring_tags_rolled_up = !self.are_ring_tags_rolled_up;
+                self.update_ring_parse_task()
+            }
+            
             Signal::StartEditingTransaction(id_result) => {
                 if id_result.is_fail() {
                     self.application_failures.extend(id_result.results());
@@ -770,7 +1145,13 @@ impl App {
                         self.edit_transaction_description_content = Content::with_text(&transaction.description);
                         self.edit_transaction_current_tag_string = String::new();
                         self.edit_transaction_tags = transaction.tags.clone();
+                        self.edit_transaction_splits = transaction.get_splits().to_vec();
+                        self.edit_transaction_account_uid = transaction.get_account_uid();
+                        self.edit_split_amount_string = String::new();
+                        self.edit_split_tags_string = String::new();
+                        self.edit_split_note_string = String::new();
                         self.edit_transaction_is_delete_primed = false;
+                        self.update_edit_tag_suggestions();
                         self.page = Pages::EditingTransaction;
                     }
                     Fail(_) => { self.application_failures.extend(transaction_result.results()); }
@@ -926,10 +1307,31 @@ impl App {
                     self.new_transaction_selected_date,
                     self.new_transaction_description_content.text(),
                     self.new_transaction_tags.clone(),
+                    self.new_transaction_splits.clone(),
+                    self.new_transaction_account_uid,
                 );
                 
                 match result {
                     Pass(()) => {
+                        // repeating the transaction, starting after this one
+                        if let Some(rule) = self.new_transaction_recurrence {
+                            let rule = rule.anchored_to(self.new_transaction_selected_date);
+                            let template_result = RecurringTemplate::new(
+                                &self.new_transaction_value_string,
+                                &self.new_transaction_currency_string,
+                                self.new_transaction_description_content.text(),
+                                self.new_transaction_tags.clone(),
+                                self.new_transaction_splits.clone(),
+                                self.new_transaction_account_uid,
+                                rule,
+                                rule.next_date_after(self.new_transaction_selected_date),
+                            );
+                            match template_result {
+                                Pass(template) => { self.bank.add_recurring_template(template); }
+                                Fail(_) => { self.application_failures.extend(template_result.results()); }
+                            }
+                        }
+                        
                         self.page = Pages::Transactions;
                         self.update_cash_flow_result();
                         Task::batch(vec![
@@ -947,6 +1349,52 @@ impl App {
                 }
             }
             
+            Signal::SetNewTransactionRecurrence(recurrence) => {
+                self.new_transaction_recurrence = recurrence;
+                Task::none()
+            }
+            
+            Signal::SetNewTransactionAccount(account_uid) => {
+                self.new_transaction_account_uid = account_uid;
+                Task::none()
+            }
+            
+            Signal::UpdateNewSplitAmountString(new_amount_string) => {
+                self.new_split_amount_string = new_amount_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewSplitTagsString(new_tags_string) => {
+                self.new_split_tags_string = new_tags_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewSplitNoteString(new_note_string) => {
+                self.new_split_note_string = new_note_string;
+                Task::none()
+            }
+            
+            Signal::AddNewTransactionSplit => {
+                let split_result = App::split_from_raw(&self.new_split_amount_string, &self.new_split_tags_string, &self.new_split_note_string);
+                
+                match split_result {
+                    Pass(split) => {
+                        self.new_transaction_splits.push(split);
+                        self.new_split_amount_string = String::new();
+                        self.new_split_tags_string = String::new();
+                        self.new_split_note_string = String::new();
+                    }
+                    Fail(_) => { self.application_failures.extend(split_result.results()); }
+                }
+                
+                Task::none()
+            }
+            
+            Signal::RemoveNewTransactionSplit(index) => {
+                if index < self.new_transaction_splits.len() { self.new_transaction_splits.remove(index); }
+                Task::none()
+            }
+            
             Signal::UpdateNewTransactionValueString(new_value_string) => {
                 self.new_transaction_value_string = new_value_string;
                 Task::none()
@@ -995,12 +1443,15 @@ impl App {
             }
             
             Signal::UpdateNewTransactionDescriptionContent(action) => {
+                let is_edit = action.is_edit();
                 self.new_transaction_description_content.perform(action);
+                if is_edit { self.update_new_tag_suggestions(); }
                 Task::none()
             }
 
             Signal::UpdateNewTransactionCurrentTagString(new_tag) => {
                 self.new_transaction_current_tag_string = new_tag;
+                self.update_new_tag_suggestions();
                 Task::none()
             }
 
@@ -1015,12 +1466,14 @@ impl App {
                     }
                     Fail(_) => { self.application_failures.extend(new_tag_result.results()); }
                 }
+                self.update_new_tag_suggestions();
                 
                 Task::none()
             }
 
             Signal::RemoveNewTransactionTag(tag) => {
                 self.new_transaction_tags.retain(|t| *t != tag);
+                self.update_new_tag_suggestions();
                 Task::none()
             }
 
@@ -1035,6 +1488,8 @@ impl App {
                     self.edit_transaction_selected_date,
                     self.edit_transaction_description_content.text(),
                     self.edit_transaction_tags.clone(),
+                    self.edit_transaction_splits.clone(),
+                    self.edit_transaction_account_uid,
                 );
                 
                 match result {
@@ -1099,6 +1554,11 @@ impl App {
                 Task::none()
             }
 
+            Signal::SetEditTransactionAccount(account_uid) => {
+                self.edit_transaction_account_uid = account_uid;
+                Task::none()
+            }
+
             Signal::UpdateEditTransactionDatePickerMode(new_mode) => {
                 self.edit_date_picker_mode = new_mode;
                 Task::none()
@@ -1137,12 +1597,15 @@ impl App {
             }
             
             Signal::UpdateEditTransactionDescriptionContent(action) => {
+                let is_edit = action.is_edit();
                 self.edit_transaction_description_content.perform(action);
+                if is_edit { self.update_edit_tag_suggestions(); }
                 Task::none()
             }
 
             Signal::UpdateEditTransactionCurrentTagString(new_tag) => {
                 self.edit_transaction_current_tag_string = new_tag;
+                self.update_edit_tag_suggestions();
                 Task::none()
             }
 
@@ -1157,12 +1620,50 @@ impl App {
                     }
                     Fail(_) => { self.application_failures.extend(new_tag_result.results()); }
                 }
+                self.update_edit_tag_suggestions();
                 
                 Task::none()
             }
             
             Signal::RemoveEditTransactionTag(tag) => {
                 self.edit_transaction_tags.retain(|t| *t != tag);
+                self.update_edit_tag_suggestions();
+                Task::none()
+            }
+            
+            Signal::UpdateEditSplitAmountString(new_amount_string) => {
+                self.edit_split_amount_string = new_amount_string;
+                Task::none()
+            }
+            
+            Signal::UpdateEditSplitTagsString(new_tags_string) => {
+                self.edit_split_tags_string = new_tags_string;
+                Task::none()
+            }
+            
+            Signal::UpdateEditSplitNoteString(new_note_string) => {
+                self.edit_split_note_string = new_note_string;
+                Task::none()
+            }
+            
+            Signal::AddEditTransactionSplit => {
+                let split_result = App::split_from_raw(&self.edit_split_amount_string, &self.edit_split_tags_string, &self.edit_split_note_string);
+                
+                match split_result {
+                    Pass(split) => {
+                        self.edit_transaction_splits.push(split);
+                        self.edit_split_amount_string = String::new();
+                        self.edit_split_tags_string = String::new();
+                        self.edit_split_note_string = String::new();
+                    }
+                    Fail(_) => { self.application_failures.extend(split_result.results()); }
+                }
+                
+                Task::none()
+            }
+            
+            Signal::RemoveEditTransactionSplit(index) => {
+                if index < self.edit_transaction_splits.len() { self.edit_transaction_splits.remove(index); }
                 Task::none()
             }
             
@@ -1198,6 +1699,62 @@ impl App {
                     self.update_trend_parse_task(),
                 ])
             }
+            
+            Signal::StartRenamingTag(tag) => {
+                self.rename_tag_string = tag.get_label();
+                self.renaming_tag = Some(tag);
+                Task::none()
+            }
+            
+            Signal::UpdateRenameTagString(new_label) => {
+                self.rename_tag_string = new_label;
+                Task::none()
+            }
+            
+            Signal::CancelRenamingTag => {
+                self.renaming_tag = None;
+                self.rename_tag_string = String::new();
+                Task::none()
+            }
+            
+            Signal::RenameTag => {
+                let old_tag_result = Schrod::from_option(self.renaming_tag.clone(), "No tag is being renamed!", "App::update() - RenameTag");
+                if old_tag_result.is_fail() {
+                    self.application_failures.extend(old_tag_result.results());
+                    return Task::none();
+                }
+                let old_tag = old_tag_result.wont_fail("This is past an is_fail() guard clause.", "App::update() - RenameTag");
+                let new_tag_result = Tag::new(&self.rename_tag_string);
+                if new_tag_result.is_fail() {
+                    self.application_failures.extend(new_tag_result.results());
+                    return Task::none();
+                }
+                let new_tag = new_tag_result.wont_fail("This is past an is_fail() guard clause.", "App::update() - RenameTag");
+                
+                // renaming into an existing tag merges the two
+                let result = if self.bank.get_tags().contains(&new_tag) { self.bank.merge_tags(&old_tag, &new_tag) }
+                else { self.bank.rename_tag(&old_tag, &new_tag) };
+                
+                match result {
+                    Pass(()) => {
+                        self.trending_tags = Tag::replaced_in(&self.trending_tags, &old_tag, &new_tag);
+                        self.new_budget_tags = Tag::replaced_in(&self.new_budget_tags, &old_tag, &new_tag);
+                        self.renaming_tag = None;
+                        self.rename_tag_string = String::new();
+                        self.update_cash_flow_result();
+                        Task::batch(vec![
+                            self.update_tag_registry_task(),
+                            self.save_task(),
+                            self.update_ring_parse_task(),
+                            self.update_trend_parse_task(),
+                        ])
+                    }
+                    Fail(_) => {
+                        self.application_failures.extend(result.results());
+                        Task::none()
+                    }
+                }
+            }
 
             Signal::SetTrendingInterval(interval) => {
                 self.trending_interval = interval;
@@ -1317,6 +1874,77 @@ impl App {
                 else { Task::none() }
             }
 
+            Signal::ToggleTimePriceHistory => {
+                self.is_time_price_history_open = !self.is_time_price_history_open;
+                self.historical_time_price_date_string = String::new();
+                self.historical_time_price_string = String::new();
+                self.historical_time_price_currency_string = String::new();
+                Task::none()
+            }
+
+            Signal::UpdateHistoricalTimePriceDateString(date_string) => {
+                self.historical_time_price_date_string = date_string;
+                Task::none()
+            }
+
+            Signal::UpdateHistoricalTimePriceString(time_price_string) => {
+                self.historical_time_price_string = time_price_string;
+                Task::none()
+            }
+
+            Signal::UpdateHistoricalTimePriceCurrencyString(currency_string) => {
+                self.historical_time_price_currency_string = currency_string;
+                Task::none()
+            }
+
+            Signal::AddHistoricalTimePrice => {
+                let date_result = parse_date(&self.historical_time_price_date_string, "%Y-%m-%d");
+                if date_result.is_fail() {
+                    self.application_failures.extend(date_result.results());
+                    return Task::none()
+                }
+                let price_result = Schrod::from_result(self.historical_time_price_string.trim().parse::<Decimal>(), "The time price is not a number.", "App::update() - AddHistoricalTimePrice");
+                if price_result.is_fail() {
+                    self.application_failures.extend(price_result.results());
+                    return Task::none()
+                }
+                let date = date_result.wont_fail("This is past an is_fail() guard clause.", "App::update() - AddHistoricalTimePrice");
+                let price = price_result.wont_fail("This is past an is_fail() guard clause.", "App::update() - AddHistoricalTimePrice");
+
+                // an empty currency follows the main currency
+                let currency_string = self.historical_time_price_currency_string.trim();
+                let currency_option = if currency_string.is_empty() { None } else { Some(currency_string) };
+                let set_result = self.bank.currency_exchange.set_time_price_on(price, currency_option, date);
+                if set_result.is_fail() {
+                    self.application_failures.extend(set_result.results());
+                    return Task::none()
+                }
+                self.historical_time_price_date_string = String::new();
+                self.historical_time_price_string = String::new();
+                self.historical_time_price_currency_string = String::new();
+
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
+
+            Signal::RemoveHistoricalTimePrice(date) => {
+                let remove_result = self.bank.currency_exchange.remove_time_price_on(date);
+                if remove_result.is_fail() { self.application_failures.extend(remove_result.results()); }
+
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
+
             Signal::SetFlowType(flow_type) => {
                 self.bank.currency_exchange.set_flow_type(flow_type);
                 
@@ -1358,6 +1986,67 @@ impl App {
                     self.update_trend_parse_task(),
                 ])
             }
+
+            Signal::ToggleRateHistory(from_string, to_string) => {
+                let pair = (from_string, to_string);
+                self.rate_history_pair = if self.rate_history_pair.as_ref() == Some(&pair) { None } else { Some(pair) };
+                self.historical_rate_date_string = String::new();
+                self.historical_rate_string = String::new();
+                Task::none()
+            }
+
+            Signal::UpdateHistoricalRateDateString(date_string) => {
+                self.historical_rate_date_string = date_string;
+                Task::none()
+            }
+
+            Signal::UpdateHistoricalRateString(rate_string) => {
+                self.historical_rate_string = rate_string;
+                Task::none()
+            }
+
+            Signal::AddHistoricalRate => {
+                let Some((from_string, to_string)) = self.rate_history_pair.clone() else { return Task::none() };
+                let date_result = parse_date(&self.historical_rate_date_string, "%Y-%m-%d");
+                let rate_result = Schrod::from_result(self.historical_rate_string.trim().parse::<Decimal>(), "The rate is not a number.", "App::update() - AddHistoricalRate");
+                if date_result.is_fail() {
+                    self.application_failures.extend(date_result.results());
+                    return Task::none()
+                }
+                if rate_result.is_fail() {
+                    self.application_failures.extend(rate_result.results());
+                    return Task::none()
+                }
+                let date = date_result.wont_fail("This is past an is_fail() guard clause.", "App::update() - AddHistoricalRate");
+                let rate = rate_result.wont_fail("This is past an is_fail() guard clause.", "App::update() - AddHistoricalRate");
+
+                let set_result = self.bank.currency_exchange.set_on(&from_string, &to_string, rate, date);
+                if set_result.is_fail() {
+                    self.application_failures.extend(set_result.results());
+                    return Task::none()
+                }
+                self.historical_rate_date_string = String::new();
+                self.historical_rate_string = String::new();
+
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
+
+            Signal::RemoveHistoricalRate(from_string, to_string, date) => {
+                let remove_result = self.bank.currency_exchange.remove_on(&from_string, &to_string, date);
+                if remove_result.is_fail() { self.application_failures.extend(remove_result.results()); }
+
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
             
             
             
@@ -1370,10 +2059,14 @@ impl App {
                     Fail(_) => {
                         self.saved_successfully = false;
                         self.application_failures.extend(save_result.results());
+                        return Task::none()
                     }
                 }
                 
-                Task::none()
+                // automatic backup
+                self.saves_since_backup += 1;
+                if self.saves_since_backup >= SAVES_PER_AUTOMATIC_BACKUP { self.backup_task() }
+                else { Task::none() }
             }
             
             Signal::OpenImportFilePicker => {
@@ -1395,50 +2088,66 @@ impl App {
             }
             
             Signal::ImportFileSelected(path) => {
-                let import_data_result = load_from(&path);
-                if let Pass(import_data) = import_data_result {
-                    self.import_data = Some(import_data);
-                    self.page = Pages::ConfirmImport;
-                    Task::none()
-                }
-                else {
-                    self.application_failures.extend(import_data_result.results());
-                    self.import_data = None;
-                    Task::none()
-                }
+                self.open_import(&path, ImportModes::Merge);
+                Task::none()
             }
             
             Signal::ConfirmImport => {
-                if let Some(import_data) = &self.import_data {
-                    let transactions = import_data.transactions.clone();
-                    let currency_exchange = import_data.currency_exchange.clone();
-                    let tag_registry = import_data.tag_registry.clone();
-                    let mut new_bank = Bank::default();
-                    new_bank.init(transactions, currency_exchange, tag_registry);
-                    self.bank = new_bank;
-                    self.import_data = None;
-                    self.page = Pages::Transactions;
+                match (&self.import_data, &self.import_merge, self.import_mode) {
+                    // merging the imported data into the current bank
+                    (Some(_), Some(import_merge), ImportModes::Merge) => {
+                        let merge_result = import_merge.apply(&mut self.bank);
+                        if merge_result.is_fail() { self.application_failures.extend(merge_result.results()); }
+                    }
                     
-                    self.update_cash_flow_result();
-                    Task::batch(vec![
-                        self.refresh_currency_exchange_task(),
-                        self.update_tag_registry_task(),
-                        self.save_task(),
-                        self.update_ring_parse_task(),
-                        self.update_trend_parse_task(),
-                    ])
+                    // replacing the current bank with the imported data
+                    (Some(import_data), _, _) => {
+                        let transactions = import_data.transactions.clone();
+                        let currency_exchange = import_data.currency_exchange.clone();
+                        let tag_registry = import_data.tag_registry.clone();
+                        let recurring_templates = import_data.recurring_templates.clone();
+                        let budgets = import_data.budgets.clone();
+                        let tag_rules = import_data.tag_rules.clone();
+                        let accounts = import_data.accounts.clone();
+                        let mut new_bank = Bank::default();
+                        new_bank.init(transactions, currency_exchange, tag_registry, recurring_templates, budgets, tag_rules, accounts);
+                        self.bank = new_bank;
+                    }
+                    
+                    (None, _, _) => { return Task::none() }
                 }
+                self.import_data = None;
+                self.import_merge = None;
+                self.page = Pages::Transactions;
                 
-                else { Task::none() }
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.update_tag_registry_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
             }
             
-            Signal::CancelImport => {
-                self.import_data = None;
-                self.page = Pages::Transactions;
+            Signal::SetImportMode(mode) => {
+                self.import_mode = mode;
                 Task::none()
             }
             
-            Signal::OpenLegacyImportFilePicker => {
+            Signal::SetImportConflictResolution(index, resolution) => {
+                if let Some(import_merge) = &mut self.import_merge { import_merge.set_resolution(index, resolution); }
+                Task::none()
+            }
+            
+            Signal::CancelImport => {
+                self.import_data = None;
+                self.import_merge = None;
+                self.page = Pages::Transactions;
+                Task::none()
+            }
+            
+            Signal::OpenLegacyImportFilePicker => {
                 Task::perform(
                     async {
                         rfd::AsyncFileDialog::new()
@@ -1474,6 +2183,9 @@ impl App {
                 if let Some(import_data) = &self.legacy_import_data {
                     let load_result = self.bank.load_transactions(import_data.clone());
                     if load_result.is_fail() { self.application_failures.extend(load_result.results()); }
+                    // the legacy data replaces the ledger, so the tag rules can run over all of it
+                    let rerun_result = self.bank.rerun_tag_rules();
+                    if rerun_result.is_fail() { self.application_failures.extend(rerun_result.results()); }
                     let init_filter_dates_result = self.bank.init_filter_dates();
                     if init_filter_dates_result.is_fail() { self.application_failures.extend(init_filter_dates_result.results()); }
                     self.legacy_import_data = None;
@@ -1498,14 +2210,678 @@ impl App {
                 Task::none()
             }
             
+            Signal::OpenCsvImportFilePicker => {
+                Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Import File")
+                            .add_filter("CSV", &["csv", "txt"])
+                            .add_filter("All Files", &["*"])
+                            .pick_file()
+                            .await
+                            .map(|f| f.path().to_path_buf())
+                    },
+                    |result| match result {
+                        Some(path) => Signal::CsvImportFileSelected(path),
+                        None => Signal::InvalidAction("No file selected".to_string()),
+                    },
+                )
+            }
+            
+            Signal::CsvImportFileSelected(path) => {
+                let csv_import_data_result = CsvImport::load_from(&path, &self.bank.currency_exchange.get_main_currency().to_string());
+                if let Pass(import_data) = csv_import_data_result {
+                    self.csv_import_data = Some(import_data);
+                    self.page = Pages::ConfirmCsvImport;
+                    Task::none()
+                }
+                else {
+                    self.application_failures.extend(csv_import_data_result.results());
+                    self.csv_import_data = None;
+                    Task::none()
+                }
+            }
+            
+            Signal::UpdateCsvImportField(field, input) => {
+                if let Some(import_data) = &mut self.csv_import_data { import_data.set_mapping_field(field, input); }
+                Task::none()
+            }
+            
+            Signal::ToggleCsvImportHeader => {
+                if let Some(import_data) = &mut self.csv_import_data { import_data.toggle_header(); }
+                Task::none()
+            }
+            
+            Signal::ConfirmCsvImport => {
+                if let Some(import_data) = &self.csv_import_data {
+                    let merge_result = self.bank.merge_transactions(import_data.get_transactions());
+                    if merge_result.is_fail() { self.application_failures.extend(merge_result.results()); }
+                    self.csv_import_data = None;
+                    self.page = Pages::Transactions;
+                    
+                    self.update_cash_flow_result();
+                    Task::batch(vec![
+                        self.refresh_currency_exchange_task(),
+                        self.update_tag_registry_task(),
+                        self.save_task(),
+                        self.update_ring_parse_task(),
+                        self.update_trend_parse_task(),
+                    ])
+                }
+                
+                else { Task::none() }
+            }
+            
+            Signal::CancelCsvImport => {
+                self.csv_import_data = None;
+                self.page = Pages::Transactions;
+                Task::none()
+            }
+            
+            Signal::OpenOfxImportFilePicker => {
+                Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Import File")
+                            .add_filter("OFX", &["ofx", "qfx"])
+                            .add_filter("All Files", &["*"])
+                            .pick_file()
+                            .await
+                            .map(|f| f.path().to_path_buf())
+                    },
+                    |result| match result {
+                        Some(path) => Signal::OfxImportFileSelected(path),
+                        None => Signal::InvalidAction("No file selected".to_string()),
+                    },
+                )
+            }
+            
+            Signal::OfxImportFileSelected(path) => {
+                let ofx_import_data_result = OfxImport::load_from(&path);
+                if let Pass(import_data) = ofx_import_data_result {
+                    self.ofx_import_data = Some(import_data);
+                    self.page = Pages::ConfirmOfxImport;
+                    Task::none()
+                }
+                else {
+                    self.application_failures.extend(ofx_import_data_result.results());
+                    self.ofx_import_data = None;
+                    Task::none()
+                }
+            }
+            
+            Signal::UpdateOfxImportTag(tag_string) => {
+                if let Some(import_data) = &mut self.ofx_import_data { import_data.set_import_tag(tag_string); }
+                Task::none()
+            }
+            
+            Signal::ConfirmOfxImport => {
+                if let Some(import_data) = &self.ofx_import_data {
+                    let merge_result = self.bank.merge_transactions(import_data.get_new_transactions(&self.bank));
+                    if merge_result.is_fail() { self.application_failures.extend(merge_result.results()); }
+                    self.ofx_import_data = None;
+                    self.page = Pages::Transactions;
+                    
+                    self.update_cash_flow_result();
+                    Task::batch(vec![
+                        self.refresh_currency_exchange_task(),
+                        self.update_tag_registry_task(),
+                        self.save_task(),
+                        self.update_ring_parse_task(),
+                        self.update_trend_parse_task(),
+                    ])
+                }
+                
+                else { Task::none() }
+            }
+            
+            Signal::CancelOfxImport => {
+                self.ofx_import_data = None;
+                self.page = Pages::Transactions;
+                Task::none()
+            }
+            
+            Signal::OpenRateImportFilePicker => {
+                Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Import Exchange Rates")
+                            .add_filter("Exchange Rates", &["csv", "xml"])
+                            .add_filter("All Files", &["*"])
+                            .pick_file()
+                            .await
+                            .map(|f| f.path().to_path_buf())
+                    },
+                    |result| match result {
+                        Some(path) => Signal::RateImportFileSelected(path),
+                        None => Signal::InvalidAction("No file selected".to_string()),
+                    },
+                )
+            }
+            
+            Signal::RateImportFileSelected(path) => {
+                let rate_import_data_result = RateImport::load_from(&path, &self.bank.currency_exchange);
+                if let Pass(import_data) = rate_import_data_result {
+                    self.rate_import_data = Some(import_data);
+                    self.page = Pages::ConfirmRateImport;
+                }
+                else {
+                    self.application_failures.extend(rate_import_data_result.results());
+                    self.rate_import_data = None;
+                }
+                Task::none()
+            }
+            
+            Signal::ConfirmRateImport => {
+                if let Some(import_data) = self.rate_import_data.take() {
+                    let apply_result = import_data.apply_to(&mut self.bank.currency_exchange);
+                    if apply_result.is_fail() { self.application_failures.extend(apply_result.results()); }
+                    self.page = Pages::Settings;
+                    
+                    self.update_cash_flow_result();
+                    Task::batch(vec![
+                        self.save_task(),
+                        self.update_ring_parse_task(),
+                        self.update_trend_parse_task(),
+                    ])
+                }
+                
+                else { Task::none() }
+            }
+            
+            Signal::CancelRateImport => {
+                self.rate_import_data = None;
+                self.page = Pages::Settings;
+                Task::none()
+            }
+            
             Signal::Backup => {
                 self.backup_task()
             }
             
             Signal::FinishedBackingup(backup_results) => {
                 if backup_results.is_fail() { self.application_failures.extend(backup_results.results()); }
+                if self.page == Pages::Backups { self.refresh_backups(); }
+                Task::none()
+            }
+            
+            Signal::RestoreBackup(path) => {
+                self.open_import(&path, ImportModes::Replace);
+                Task::none()
+            }
+            
+            Signal::OpenExportFilePicker(filter) => {
+                let file_name = format!("{} Transactions.csv", self.vault_name);
+                Task::perform(
+                    async move {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Export Transactions")
+                            .set_file_name(file_name)
+                            .add_filter(ExportFormats::Csv.name(), &[ExportFormats::Csv.extension()])
+                            .add_filter(ExportFormats::Json.name(), &[ExportFormats::Json.extension()])
+                            .save_file()
+                            .await
+                            .map(|f| f.path().to_path_buf())
+                    },
+                    move |result| match result {
+                        Some(path) => Signal::ExportFileSelected(filter, path),
+                        None => Signal::InvalidAction("No file selected".to_string()),
+                    },
+                )
+            }
+            
+            Signal::ExportFileSelected(filter, mut path) => {
+                // files without a known extension are exported as CSV
+                let format = match ExportFormats::from_path(&path) {
+                    Some(format) => format,
+                    None => {
+                        path.set_extension(ExportFormats::Csv.extension());
+                        ExportFormats::Csv
+                    }
+                };
+                let export_result = export_filtered(&self.bank, filter, &path, format);
+                if export_result.is_fail() { self.application_failures.extend(export_result.results()); }
+                Task::none()
+            }
+            
+            // vaults
+            Signal::UpdateNewVaultNameString(vault_name_string) => {
+                self.new_vault_name_string = vault_name_string;
+                Task::none()
+            }
+            
+            Signal::CreateVault => {
+                let vault_name = self.new_vault_name_string.trim().to_string();
+                let create_result = create_vault(&vault_name);
+                if create_result.is_fail() {
+                    self.application_failures.extend(create_result.results());
+                    return Task::none()
+                }
+                self.new_vault_name_string = String::new();
+                Task::done(Signal::OpenVault(vault_name))
+            }
+            
+            Signal::OpenVault(vault_name) => {
+                if vault_name == self.vault_name && !self.is_locked() { return Task::none() }
+                
+                // asking for the passphrase first
+                if is_vault_encrypted(&vault_name) {
+                    self.locked_vault_name = Some(vault_name);
+                    self.unlock_passphrase_string = String::new();
+                    self.unlock_failure = None;
+                    self.page = Pages::Unlock;
+                    return Task::none()
+                }
+                
+                let save_data_result = load(&vault_name, None);
+                if save_data_result.is_fail() {
+                    self.application_failures.extend(save_data_result.results());
+                    return Task::none()
+                }
+                let save_data = save_data_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                let was_locked = self.is_locked();
+                let open_task = self.open_vault(vault_name, save_data, None);
+                self.page = Pages::Transactions;
+                if was_locked && !self.bank.get_ledger().is_empty() { Task::batch(vec![open_task, self.backup_task()]) } else { open_task }
+            }
+            
+            // encryption
+            Signal::UpdateUnlockPassphraseString(passphrase_string) => {
+                self.unlock_passphrase_string = passphrase_string;
+                Task::none()
+            }
+            
+            Signal::Unlock => {
+                let Some(vault_name) = self.locked_vault_name.clone() else { return Task::none() };
+                
+                // checking the passphrase
+                let vault_key_result = VaultKey::unlock(&vault_name, &self.unlock_passphrase_string);
+                if vault_key_result.is_fail() {
+                    self.unlock_failure = Some("The vault could not be unlocked. Please check the passphrase.".to_string());
+                    return Task::none()
+                }
+                let vault_key = vault_key_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                
+                // loading the vault
+                let save_data_result = load(&vault_name, Some(&vault_key));
+                if save_data_result.is_fail() {
+                    self.unlock_failure = save_data_result.results().first().cloned();
+                    return Task::none()
+                }
+                let save_data = save_data_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                let was_locked = self.is_locked();
+                let open_task = self.open_vault(vault_name, save_data, Some(vault_key));
+                self.unlock_passphrase_string = String::new();
+                self.unlock_failure = None;
+                self.page = Pages::Transactions;
+                self.show_due_recurrences();
+                
+                // backing up on launch, which was put off until the vault was unlocked
+                if was_locked && !self.bank.get_ledger().is_empty() { Task::batch(vec![open_task, self.backup_task()]) } else { open_task }
+            }
+            
+            Signal::CancelUnlock => {
+                if self.is_locked() { return Task::none() }
+                self.locked_vault_name = None;
+                self.unlock_passphrase_string = String::new();
+                self.unlock_failure = None;
+                self.page = Pages::Settings;
+                Task::none()
+            }
+            
+            Signal::UpdateCurrentPassphraseString(passphrase_string) => {
+                self.current_passphrase_string = passphrase_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewPassphraseString(passphrase_string) => {
+                self.new_passphrase_string = passphrase_string;
+                Task::none()
+            }
+            
+            Signal::UpdateConfirmPassphraseString(passphrase_string) => {
+                self.confirm_passphrase_string = passphrase_string;
+                Task::none()
+            }
+            
+            Signal::SetPassphrase => {
+                if !self.can_change_passphrase() || self.new_passphrase_string.is_empty() || self.new_passphrase_string != self.confirm_passphrase_string { return Task::none() }
+                
+                let new_vault_key_result = VaultKey::new(&self.new_passphrase_string);
+                if new_vault_key_result.is_fail() {
+                    self.application_failures.extend(new_vault_key_result.results());
+                    return Task::none()
+                }
+                let new_vault_key = new_vault_key_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                self.change_encryption(Some(new_vault_key))
+            }
+            
+            Signal::RemovePassphrase => {
+                if !self.is_encrypted() || !self.can_change_passphrase() { return Task::none() }
+                self.change_encryption(None)
+            }
+            
+            // recurring
+            Signal::ConfirmRecurrence(uid) => {
+                let confirm_result = self.bank.confirm_recurrence(uid);
+                if confirm_result.is_fail() { self.application_failures.extend(confirm_result.results()); }
+                self.finish_recurrence_changes()
+            }
+            
+            Signal::SkipRecurrence(uid) => {
+                let skip_result = self.bank.skip_recurrence(uid);
+                if skip_result.is_fail() { self.application_failures.extend(skip_result.results()); }
+                self.save_task()
+            }
+            
+            Signal::ConfirmAllRecurrences => {
+                let today_result = Date::today();
+                if today_result.is_fail() {
+                    self.application_failures.extend(today_result.results());
+                    return Task::none()
+                }
+                let today = today_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                for due_recurrence in self.bank.get_due_recurrences(today) {
+                    let confirm_result = self.bank.confirm_recurrence(due_recurrence.template_uid);
+                    if confirm_result.is_fail() {
+                        self.application_failures.extend(confirm_result.results());
+                        break
+                    }
+                }
+                self.finish_recurrence_changes()
+            }
+            
+            Signal::RemoveRecurringTemplate(uid) => {
+                let remove_result = self.bank.remove_recurring_template(uid);
+                if remove_result.is_fail() { self.application_failures.extend(remove_result.results()); }
+                self.save_task()
+            }
+            
+            // budgets
+            Signal::ToggleNewBudgetTag(tag) => {
+                if self.new_budget_tags.contains(&tag) { self.new_budget_tags.retain(|t| *t != tag); }
+                else {
+                    self.new_budget_tags.push(tag);
+                    self.new_budget_tags = Tag::sorted(&self.new_budget_tags);
+                }
                 Task::none()
             }
+            
+            Signal::SetNewBudgetInterval(interval) => {
+                self.new_budget_interval = interval;
+                Task::none()
+            }
+            
+            Signal::UpdateNewBudgetLimitString(limit_string) => {
+                self.new_budget_limit_string = limit_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewBudgetCurrencyString(currency_string) => {
+                self.new_budget_currency_string = currency_string;
+                Task::none()
+            }
+            
+            Signal::AddBudget => {
+                let budget_result = Budget::new(self.new_budget_tags.clone(), self.new_budget_interval, &self.new_budget_limit_string, &self.new_budget_currency());
+                if budget_result.is_fail() {
+                    self.application_failures.extend(budget_result.results());
+                    return Task::none()
+                }
+                self.bank.add_budget(budget_result.wont_fail("This is past an is_fail() guard clause.", "App::update()"));
+                self.new_budget_tags = Vec::new();
+                self.new_budget_limit_string = String::new();
+                self.new_budget_currency_string = String::new();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.save_task(),
+                ])
+            }
+            
+            Signal::RemoveBudget(uid) => {
+                let remove_result = self.bank.remove_budget(uid);
+                if remove_result.is_fail() { self.application_failures.extend(remove_result.results()); }
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.save_task(),
+                ])
+            }
+            
+            // tag rules
+            Signal::UpdateNewTagRuleDescriptionString(description_string) => {
+                self.new_tag_rule_description_string = description_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewTagRuleBelowString(below_string) => {
+                self.new_tag_rule_below_string = below_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewTagRuleAboveString(above_string) => {
+                self.new_tag_rule_above_string = above_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewTagRuleCurrencyString(currency_string) => {
+                self.new_tag_rule_currency_string = currency_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewTagRuleTagsString(tags_string) => {
+                self.new_tag_rule_tags_string = tags_string;
+                Task::none()
+            }
+            
+            Signal::AddTagRule => {
+                let tag_rule_result = TagRule::new_from_raw(&self.new_tag_rule_description_string, &self.new_tag_rule_below_string, &self.new_tag_rule_above_string, &self.new_tag_rule_currency_string, &self.new_tag_rule_tags_string);
+                if tag_rule_result.is_fail() {
+                    self.application_failures.extend(tag_rule_result.results());
+                    return Task::none()
+                }
+                self.bank.add_tag_rule(tag_rule_result.wont_fail("This is past an is_fail() guard clause.", "App::update()"));
+                self.new_tag_rule_description_string = String::new();
+                self.new_tag_rule_below_string = String::new();
+                self.new_tag_rule_above_string = String::new();
+                self.new_tag_rule_currency_string = String::new();
+                self.new_tag_rule_tags_string = String::new();
+                self.tag_rule_changes = None;
+                self.save_task()
+            }
+            
+            Signal::RemoveTagRule(uid) => {
+                let remove_result = self.bank.remove_tag_rule(uid);
+                if remove_result.is_fail() { self.application_failures.extend(remove_result.results()); }
+                self.tag_rule_changes = None;
+                self.save_task()
+            }
+            
+            Signal::PreviewTagRuleRerun => {
+                self.tag_rule_changes = Some(self.bank.get_tag_rule_changes());
+                Task::none()
+            }
+            
+            Signal::CancelTagRuleRerun => {
+                self.tag_rule_changes = None;
+                Task::none()
+            }
+            
+            Signal::ApplyTagRuleRerun => {
+                let rerun_result = self.bank.rerun_tag_rules();
+                if rerun_result.is_fail() { self.application_failures.extend(rerun_result.results()); }
+                self.tag_rule_changes = None;
+                
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.update_tag_registry_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
+            
+            // bulk edit
+            Signal::ToggleTransactionSelection(uid) => {
+                if self.selected_transaction_uids.contains(&uid) { self.selected_transaction_uids.retain(|selected_uid| *selected_uid != uid); }
+                else { self.selected_transaction_uids.push(uid); }
+                Task::none()
+            }
+            
+            Signal::SelectAllFilteredTransactions => {
+                for id in self.bank.get_filtered_ids(Filters::Primary) {
+                    if let Pass(transaction) = self.bank.get(id) && !self.selected_transaction_uids.contains(&transaction.get_uid()) {
+                        self.selected_transaction_uids.push(transaction.get_uid());
+                    }
+                }
+                Task::none()
+            }
+            
+            Signal::ClearTransactionSelection => {
+                self.selected_transaction_uids = Vec::new();
+                Task::none()
+            }
+            
+            Signal::StartBulkEdit => {
+                // transactions removed since they were selected, such as by an undo, are dropped from the selection
+                self.selected_transaction_uids.retain(|uid| self.bank.contains_uid(*uid));
+                if self.selected_transaction_uids.is_empty() { return Task::none() }
+                self.bulk_edit_input_string = String::new();
+                self.page = Pages::BulkEdit;
+                Task::none()
+            }
+            
+            Signal::SetBulkActionType(action_type) => {
+                self.bulk_action_type = action_type;
+                self.bulk_edit_input_string = String::new();
+                Task::none()
+            }
+            
+            Signal::UpdateBulkEditInputString(input_string) => {
+                self.bulk_edit_input_string = input_string;
+                Task::none()
+            }
+            
+            Signal::CancelBulkEdit => {
+                self.page = Pages::Transactions;
+                Task::none()
+            }
+            
+            Signal::ApplyBulkEdit => {
+                let action_result = BulkActions::new_from_raw(self.bulk_action_type, &self.bulk_edit_input_string);
+                if action_result.is_fail() {
+                    self.application_failures.extend(action_result.results());
+                    return Task::none()
+                }
+                let action = action_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                
+                let edit_result = self.bank.bulk_edit(&self.selected_transaction_uids, &action);
+                if edit_result.is_fail() { self.application_failures.extend(edit_result.results()); }
+                self.selected_transaction_uids = Vec::new();
+                self.bulk_edit_input_string = String::new();
+                self.page = Pages::Transactions;
+                
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.update_tag_registry_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
+            
+            // accounts page
+            Signal::UpdateNewAccountNameString(name_string) => {
+                self.new_account_name_string = name_string;
+                Task::none()
+            }
+            
+            Signal::SetNewAccountType(account_type) => {
+                self.new_account_type = account_type;
+                Task::none()
+            }
+            
+            Signal::UpdateNewAccountOpeningBalanceString(opening_balance_string) => {
+                self.new_account_opening_balance_string = opening_balance_string;
+                Task::none()
+            }
+            
+            Signal::UpdateNewAccountCurrencyString(currency_string) => {
+                self.new_account_currency_string = currency_string;
+                Task::none()
+            }
+            
+            Signal::AddAccount => {
+                let account_result = Account::new_from_raw(&self.new_account_name_string, self.new_account_type, &self.new_account_opening_balance(), &self.new_account_currency());
+                if account_result.is_fail() {
+                    self.application_failures.extend(account_result.results());
+                    return Task::none()
+                }
+                self.bank.add_account(account_result.wont_fail("This is past an is_fail() guard clause.", "App::update()"));
+                self.new_account_name_string = String::new();
+                self.new_account_type = AccountTypes::Checking;
+                self.new_account_opening_balance_string = String::new();
+                self.new_account_currency_string = String::new();
+                self.reset_transfer();
+                self.save_task()
+            }
+            
+            Signal::RemoveAccount(uid) => {
+                let remove_result = self.bank.remove_account(uid);
+                if remove_result.is_fail() { self.application_failures.extend(remove_result.results()); }
+                self.reset_transfer();
+                self.save_task()
+            }
+            
+            Signal::SetTransferFromAccount(uid) => {
+                self.transfer_from_account_uid = uid;
+                Task::none()
+            }
+            
+            Signal::SetTransferToAccount(uid) => {
+                self.transfer_to_account_uid = uid;
+                Task::none()
+            }
+            
+            Signal::UpdateTransferAmountString(amount_string) => {
+                self.transfer_amount_string = amount_string;
+                Task::none()
+            }
+            
+            Signal::UpdateTransferDateString(date_string) => {
+                self.transfer_date_string = date_string;
+                Task::none()
+            }
+            
+            Signal::UpdateTransferDescriptionString(description_string) => {
+                self.transfer_description_string = description_string;
+                Task::none()
+            }
+            
+            Signal::AddTransfer => {
+                let date_result = parse_date(&self.transfer_date_string, "%Y-%m-%d");
+                if date_result.is_fail() {
+                    self.application_failures.extend(date_result.results());
+                    return Task::none()
+                }
+                let date = date_result.wont_fail("This is past an is_fail() guard clause.", "App::update()");
+                let transfer_result = self.bank.add_transfer(self.transfer_from_account_uid, self.transfer_to_account_uid, &self.transfer_amount_string, date, self.transfer_description_string.trim().to_string());
+                if transfer_result.is_fail() {
+                    self.application_failures.extend(transfer_result.results());
+                    return Task::none()
+                }
+                self.reset_transfer();
+                
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.update_tag_registry_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
         }
     }
     
@@ -1519,8 +2895,12 @@ impl App {
                     match key {
                         keyboard::Key::Named(Named::Tab) if modifiers.shift() => Some(Signal::FocusPrevious),
                         keyboard::Key::Named(Named::Tab) => Some(Signal::FocusNext),
+                        keyboard::Key::Named(Named::ArrowDown) => Some(Signal::NextTagSuggestionKeybind),
+                        keyboard::Key::Named(Named::ArrowUp) => Some(Signal::PreviousTagSuggestionKeybind),
                         
                         keyboard::Key::Character(c) => match c.as_str() {
+                            "z" | "Z" if modifiers.command() && modifiers.shift() => Some(Signal::RedoKeybind),
+                            "z" if modifiers.command() => Some(Signal::UndoKeybind),
                             "]" if modifiers.command() => Some(Signal::AdvanceDayKeybind),
                             "[" if modifiers.command() => Some(Signal::RecedeDayKeybind),
                             "'" if modifiers.command() => Some(Signal::AdvanceMonthKeybind),
@@ -1552,10 +2932,20 @@ impl App {
                     Pages::AddingTransaction => { add_transaction_page(self).into() }
                     Pages::EditingTransaction => { edit_transaction_page(self).into() }
                     Pages::Trends => { trends_page(self).into() }
+                    Pages::Recurring => { recurring_page(self).into() }
+                    Pages::Budgets => { budgets_page(self).into() }
+                    Pages::TagRules => { tag_rules_page(self).into() }
+                    Pages::Accounts => { accounts_page(self).into() }
                     Pages::TagRegistry => { tag_registry_page(self).into() }
                     Pages::Settings => { settings_page(self).into() }
+                    Pages::Backups => { backups_page(self).into() }
                     Pages::ConfirmImport => { confirm_import_page(self).into() }
                     Pages::ConfirmLegacyImport => { confirm_legacy_import_page(self).into() }
+                    Pages::ConfirmCsvImport => { confirm_csv_import_page(self).into() }
+                    Pages::ConfirmOfxImport => { confirm_ofx_import_page(self).into() }
+                    Pages::ConfirmRateImport => { confirm_rate_import_page(self).into() }
+                    Pages::BulkEdit => { bulk_edit_page(self).into() }
+                    Pages::Unlock => { unlock_page(self).into() }
                 }
             }
         }
@@ -1574,6 +2964,215 @@ impl App {
         self.theme = self.theme_selection.generate_iced_palette();
     }
     
+    /// Loads save data to be imported and moves to the confirm import page.
+    fn open_import(&mut self, path: &PathBuf, import_mode: ImportModes) {
+        let import_data_result = load_from(path, self.vault_key.as_ref());
+        if let Pass(import_data) = import_data_result {
+            self.import_merge = Some(ImportMerge::new(&self.bank, &import_data.transactions, &import_data.tag_registry));
+            self.import_mode = import_mode;
+            self.import_data = Some(import_data);
+            self.page = Pages::ConfirmImport;
+        }
+        else {
+            self.application_failures.extend(import_data_result.results());
+            self.import_data = None;
+            self.import_merge = None;
+        }
+    }
+    
+    /// Refreshes the list of backups of the current vault.
+    fn refresh_backups(&mut self) {
+        let backups_result = list_backups(&self.vault_name, self.vault_key.as_ref());
+        match backups_result {
+            Pass(backups) => { self.backups = backups; }
+            Fail(_) => { self.application_failures.extend(backups_result.results()); }
+        }
+    }
+    
+    /// Checks if the open vault is still waiting to be unlocked.
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.locked_vault_name.as_ref() == Some(&self.vault_name)
+    }
+    
+    /// Checks if the open vault is encrypted.
+    #[must_use]
+    pub fn is_encrypted(&self) -> bool {
+        self.vault_key.is_some()
+    }
+    
+    /// Moves to the recurring page if any recurring `Transaction`s are waiting to be confirmed.
+    fn show_due_recurrences(&mut self) {
+        if let Pass(today) = Date::today() && !self.bank.get_due_recurrences(today).is_empty() {
+            self.page = Pages::Recurring;
+        }
+    }
+    
+    /// Returns the `Task`s that follow confirming recurring `Transaction`s.
+    fn finish_recurrence_changes(&mut self) -> Task<Signal> {
+        self.update_cash_flow_result();
+        Task::batch(vec![
+            self.refresh_currency_exchange_task(),
+            self.update_tag_registry_task(),
+            self.save_task(),
+            self.update_ring_parse_task(),
+            self.update_trend_parse_task(),
+        ])
+    }
+    
+    /// Returns the `Task`s that follow undoing or redoing a change to the `Bank`.
+    fn finish_history_change(&mut self, result: Schrod<Option<String>>) -> Task<Signal> {
+        match result {
+            Pass(Some(_)) => {
+                // the transaction being edited may have been changed or removed
+                if self.page == Pages::EditingTransaction { self.page = Pages::Transactions; }
+                self.tag_rule_changes = None;
+                self.update_cash_flow_result();
+                Task::batch(vec![
+                    self.refresh_currency_exchange_task(),
+                    self.update_tag_registry_task(),
+                    self.save_task(),
+                    self.update_ring_parse_task(),
+                    self.update_trend_parse_task(),
+                ])
+            }
+            Pass(None) => { Task::none() }
+            Fail(_) => {
+                self.application_failures.extend(result.results());
+                Task::none()
+            }
+        }
+    }
+    
+    /// Ranks the `Tag` suggestions for the `Transaction` being added.
+    fn update_new_tag_suggestions(&mut self) {
+        self.new_transaction_tag_suggestions = TagSuggestions::new(&self.bank, &self.new_transaction_current_tag_string, &self.new_transaction_tags, &self.new_transaction_description_content.text());
+    }
+    
+    /// Ranks the `Tag` suggestions for the `Transaction` being edited.
+    fn update_edit_tag_suggestions(&mut self) {
+        self.edit_transaction_tag_suggestions = TagSuggestions::new(&self.bank, &self.edit_transaction_current_tag_string, &self.edit_transaction_tags, &self.edit_transaction_description_content.text());
+    }
+    
+    /// Returns the currency code of the new `Budget`, which is the main currency unless another one has been entered.
+    #[must_use]
+    pub fn new_budget_currency(&self) -> String {
+        if self.new_budget_currency_string.trim().is_empty() { self.bank.currency_exchange.get_main_currency().to_string() }
+        else { self.new_budget_currency_string.clone() }
+    }
+    
+    /// Returns the opening balance of the new `Account`, which is zero unless another one has been entered.
+    #[must_use]
+    pub fn new_account_opening_balance(&self) -> String {
+        if self.new_account_opening_balance_string.trim().is_empty() { "0".to_string() }
+        else { self.new_account_opening_balance_string.clone() }
+    }
+    
+    /// Returns the currency code of the new `Account`, which is the main currency unless another one has been entered.
+    #[must_use]
+    pub fn new_account_currency(&self) -> String {
+        if self.new_account_currency_string.trim().is_empty() { self.bank.currency_exchange.get_main_currency().to_string() }
+        else { self.new_account_currency_string.clone() }
+    }
+    
+    /// Clears the transfer being entered, which starts out moving money from the first `Account` to the second today.
+    fn reset_transfer(&mut self) {
+        let account_uids = self.bank.accounts.iter().map(Account::get_uid).collect::<Vec<Uid>>();
+        self.transfer_from_account_uid = account_uids.first().copied().unwrap_or_default();
+        self.transfer_to_account_uid = account_uids.get(1).copied().unwrap_or_default();
+        self.transfer_amount_string = String::new();
+        self.transfer_date_string = match Date::today() {
+            Pass(today) => { today.display_iso() }
+            Fail(_) => { String::new() }
+        };
+        self.transfer_description_string = String::new();
+    }
+    
+    /// Creates a `Split` from the amount, comma-separated `Tag` labels, and note entered on a transaction management page.
+    fn split_from_raw(amount_string: &str, tags_string: &str, note_string: &str) -> Schrod<Split> {
+        let tags_result = Tag::list_from(tags_string);
+        if tags_result.is_fail() {
+            return tags_result
+                .convert("App::split_from_raw()")
+                .fail("Failed to add Split.", "App::split_from_raw()")
+        }
+        let tags = tags_result.wont_fail("This is past an is_fail() guard clause.", "App::split_from_raw()");
+        
+        Split::new_from_raw(amount_string, tags, note_string)
+    }
+    
+    /// Replaces the `Bank` with the loaded contents of another vault and makes it the active vault.
+    fn open_vault(&mut self, vault_name: String, save_data: SaveData, vault_key: Option<VaultKey>) -> Task<Signal> {
+        // switching to the vault
+        if let Some(notice) = &save_data.recovery_notice { self.application_failures.push(notice.clone()); }
+        let active_vault_result = set_active_vault(&vault_name);
+        if active_vault_result.is_fail() { self.application_failures.extend(active_vault_result.results()); }
+        
+        // replacing the bank
+        let mut new_bank = Bank::default();
+        let init_result = new_bank.init(save_data.transactions, save_data.currency_exchange, save_data.tag_registry, save_data.recurring_templates, save_data.budgets, save_data.tag_rules, save_data.accounts);
+        if init_result.is_fail() { self.application_failures.extend(init_result.results()); }
+        self.bank = new_bank;
+        self.history.clear();
+        self.vault_name = vault_name;
+        self.vault_key = vault_key;
+        self.locked_vault_name = None;
+        self.update_theme(save_data.theme);
+        
+        // resetting state tied to the previous vault
+        self.primary_filter_current_search_term_string = String::new();
+        self.deep_dive_1_filter_current_search_term_string = String::new();
+        self.deep_dive_2_filter_current_search_term_string = String::new();
+        self.trending_tags = Vec::new();
+        self.last_trending_date = self.bank.get_latest_date();
+        self.saves_since_backup = 0;
+        self.backups = Vec::new();
+        self.selected_transaction_uids = Vec::new();
+        self.current_passphrase_string = String::new();
+        self.new_passphrase_string = String::new();
+        self.confirm_passphrase_string = String::new();
+        self.tag_registry_slip_state_manager = TagRegistrationSlipStateManager::new(self.bank.get_tags());
+        let vault_names_result = list_vaults();
+        match vault_names_result {
+            Schrod::Pass(vault_names) => { self.vault_names = vault_names; }
+            Schrod::Fail(_) => { self.application_failures.extend(vault_names_result.results()); }
+        }
+        
+        self.update_cash_flow_result();
+        let mut tasks = vec![
+            self.refresh_currency_exchange_task(),
+            self.update_tag_registry_task(),
+            self.update_ring_parse_task(),
+            self.update_trend_parse_task(),
+        ];
+        if save_data.was_migrated { tasks.push(self.save_task()); }
+        Task::batch(tasks)
+    }
+    
+    /// Checks if the current passphrase has been entered correctly, which is needed to change or remove it.
+    #[must_use]
+    pub fn can_change_passphrase(&self) -> bool {
+        match &self.vault_key {
+            Some(vault_key) => vault_key.has_passphrase(&self.current_passphrase_string),
+            None => true,
+        }
+    }
+    
+    /// Encrypts the open vault and its backups with a new `VaultKey`, or removes the encryption if none is given.
+    fn change_encryption(&mut self, new_vault_key: Option<VaultKey>) -> Task<Signal> {
+        let change_result = change_encryption(&self.vault_name, self.vault_key.as_ref(), new_vault_key.as_ref());
+        if change_result.is_fail() {
+            self.application_failures.extend(change_result.results());
+            return Task::none()
+        }
+        
+        self.vault_key = new_vault_key;
+        self.current_passphrase_string = String::new();
+        self.new_passphrase_string = String::new();
+        self.confirm_passphrase_string = String::new();
+        self.save_task()
+    }
+    
     /// Updates the `cash_flow_result` for the `App`.
     fn update_cash_flow_result(&mut self) {
         let new_cash_flow_result = CashFlow::new(&self.bank, &self.bank.get_filtered_ids(Filters::Primary));
@@ -1662,9 +3261,10 @@ impl App {
     fn refresh_currency_exchange_task(&mut self) -> Task<Signal> {
         let mut currency_exchange = self.bank.currency_exchange.clone();
         let ledger_copy = self.bank.get_ledger_copy();
+        let budget_currencies = self.bank.get_budget_currencies();
         
         Task::stream(iced::stream::channel(16, move |mut sender: Sender<Signal>| async move {
-            let refresh_result = currency_exchange.refresh(ledger_copy).await;
+            let refresh_result = currency_exchange.refresh(ledger_copy, budget_currencies).await;
             sender.send(Signal::FinishedUpdatingCurrencyExchange(currency_exchange, refresh_result)).await.ok();
         }))
     }
@@ -1683,16 +3283,15 @@ impl App {
     }
     
     /// Returns a `Task` that saves persistent data to the disk.
+    /// Nothing is saved while the vault is locked, since the `Bank` has not been loaded.
     fn save_task(&mut self) -> Task<Signal> {
-        let save_data = SaveData {
-            theme: self.theme_selection,
-            transactions: self.bank.get_ledger_copy(),
-            currency_exchange: self.bank.currency_exchange.clone(),
-            tag_registry: self.bank.tag_registry.clone(),
-        };
+        if self.is_locked() { return Task::none() }
+        let save_data = SaveData::from_bank(self.theme_selection, &self.bank);
+        let vault_name = self.vault_name.clone();
+        let vault_key = self.vault_key.clone();
         
         Task::stream(iced::stream::channel(16, move |mut sender: Sender<Signal>| async move {
-            let save_result = save(save_data).await;
+            let save_result = save(save_data, &vault_name, vault_key.as_ref()).await;
             
             sender.send(Signal::FinishedSaving(save_result)).await.ok();
         }))
@@ -1700,15 +3299,14 @@ impl App {
     
     /// Returns a `Task` that backs up persistent data to the disk.
     fn backup_task(&mut self) -> Task<Signal> {
-        let save_data = SaveData {
-            theme: self.theme_selection,
-            transactions: self.bank.get_ledger_copy(),
-            currency_exchange: self.bank.currency_exchange.clone(),
-            tag_registry: self.bank.tag_registry.clone(),
-        };
+        if self.is_locked() { return Task::none() }
+        self.saves_since_backup = 0;
+        let save_data = SaveData::from_bank(self.theme_selection, &self.bank);
+        let vault_name = self.vault_name.clone();
+        let vault_key = self.vault_key.clone();
         
         Task::stream(iced::stream::channel(16, move |mut sender: Sender<Signal>| async move {
-            let backup_result = backup(save_data).await;
+            let backup_result = backup(save_data, &vault_name, vault_key.as_ref()).await;
             
             sender.send(Signal::FinishedBackingup(backup_result)).await.ok();
         }))
@@ -5,11 +5,16 @@ use crate::vault::bank::{CurrencyExchange, TagRegistry};
 use crate::vault::parse::FlowTypes;
 use crate::container::app::Pages;
 use crate::vault::bank::Filters;
+use crate::vault::csv_import::CsvFields;
+use crate::vault::import_merge::{ConflictResolutions, ImportModes};
 use materialui::components::DatePickerModes;
 use materialui::materials::{MaterialThemes, MaterialColors};
 use crate::vault::ring_parse::RingParse;
 use schrod::Schrod;
-use crate::vault::transaction::{Date, Id, Months, Tag};
+use crate::vault::recurring::RecurrenceRules;
+use crate::vault::account::AccountTypes;
+use crate::vault::bulk_edit::BulkActionTypes;
+use crate::vault::transaction::{Date, Id, Months, Tag, Uid};
 use crate::vault::trend_parse::{Intervals, TrendParse};
 use iced::{Point, Size};
 
@@ -29,6 +34,30 @@ pub enum Signal {
     /// Data passed: nothing
     AddTransactionKeybind,
     
+    /// Tells the `App` to undo the latest change to the `Bank`, unless a text field is focused.
+    /// Data passed: nothing
+    UndoKeybind,
+    
+    /// Tells the `App` to redo the latest undone change to the `Bank`, unless a text field is focused.
+    /// Data passed: nothing
+    RedoKeybind,
+    
+    /// Tells the `App` to undo the latest change to the `Bank`.
+    /// Data passed: nothing
+    Undo,
+    
+    /// Tells the `App` to redo the latest undone change to the `Bank`.
+    /// Data passed: nothing
+    Redo,
+    
+    /// Tells the `App` to select the next `Tag` suggestion as the context requires.
+    /// Data passed: nothing
+    NextTagSuggestionKeybind,
+    
+    /// Tells the `App` to select the previous `Tag` suggestion as the context requires.
+    /// Data passed: nothing
+    PreviousTagSuggestionKeybind,
+    
     /// Tells the `App` to advance the year as the context requires.
     /// Data passed: nothing
     AdvanceYearKeybind,
@@ -170,6 +199,10 @@ pub enum Signal {
     /// Data passed: transaction id
     StartEditingTransaction(Schrod<Id>),
     
+    /// Tells the `App` to toggle rolling child `Tag`s up into their parents in the `RingChart`s.
+    /// Data passed: nothing
+    ToggleRingTagRollup,
+    
     /// Tells the `App` that the mouse has moved in the earning `RingChart`.
     /// Data passed: new mouse position, layout size
     MouseMovedInEarningRingChart(Point, Size),
@@ -242,6 +275,34 @@ pub enum Signal {
     /// Data passed: `Tag` to remove
     RemoveNewTransactionTag(Tag),
 
+    /// Sets how often the new `Transaction` repeats.
+    /// Data passed: the new `RecurrenceRule`, or `None` if it happens once
+    SetNewTransactionRecurrence(Option<RecurrenceRules>),
+
+    /// Sets the `Account` that the new `Transaction` belongs to.
+    /// Data passed: `Uid` of the `Account`
+    SetNewTransactionAccount(Uid),
+
+    /// Updates the amount of the next `Split` for `Transaction` adding.
+    /// Data passed: new amount `String`
+    UpdateNewSplitAmountString(String),
+
+    /// Updates the `Tag`s of the next `Split` for `Transaction` adding.
+    /// Data passed: new comma-separated `Tag` `String`
+    UpdateNewSplitTagsString(String),
+
+    /// Updates the note of the next `Split` for `Transaction` adding.
+    /// Data passed: new note `String`
+    UpdateNewSplitNoteString(String),
+
+    /// Adds the next `Split` for `Transaction` adding.
+    /// Data passed: nothing
+    AddNewTransactionSplit,
+
+    /// Removes a `Split` for `Transaction` adding.
+    /// Data passed: index of the `Split` to remove
+    RemoveNewTransactionSplit(usize),
+
     
 
     // editing transaction page signals
@@ -269,6 +330,10 @@ pub enum Signal {
     /// Data passed: new `Currency` `String`
     UpdateEditTransactionCurrencyString(String),
 
+    /// Sets the `Account` that the `Transaction` being edited belongs to.
+    /// Data passed: `Uid` of the `Account`
+    SetEditTransactionAccount(Uid),
+
     /// Updates the date picker mode in `Transaction` editing.
     /// Data passed: new date picker mode
     UpdateEditTransactionDatePickerMode(DatePickerModes),
@@ -305,6 +370,26 @@ pub enum Signal {
     /// Data passed: `Tag` to remove
     RemoveEditTransactionTag(Tag),
     
+    /// Updates the amount of the next `Split` for `Transaction` editing.
+    /// Data passed: new amount `String`
+    UpdateEditSplitAmountString(String),
+    
+    /// Updates the `Tag`s of the next `Split` for `Transaction` editing.
+    /// Data passed: new comma-separated `Tag` `String`
+    UpdateEditSplitTagsString(String),
+    
+    /// Updates the note of the next `Split` for `Transaction` editing.
+    /// Data passed: new note `String`
+    UpdateEditSplitNoteString(String),
+    
+    /// Adds the next `Split` for `Transaction` editing.
+    /// Data passed: nothing
+    AddEditTransactionSplit,
+    
+    /// Removes a `Split` for `Transaction` editing.
+    /// Data passed: index of the `Split` to remove
+    RemoveEditTransactionSplit(usize),
+    
     
     
     // tag registry page signals
@@ -323,6 +408,22 @@ pub enum Signal {
     /// Tells the `App` to reset the color of a `Tag` in the `TagRegistry`.
     /// Data passed: `Tag`
     ResetTag(Tag),
+    
+    /// Starts renaming a `Tag` in the tag registry page.
+    /// Data passed: `Tag` to rename
+    StartRenamingTag(Tag),
+    
+    /// Updates the new label of the `Tag` being renamed.
+    /// Data passed: new label `String`
+    UpdateRenameTagString(String),
+    
+    /// Stops renaming a `Tag` without changing anything.
+    /// Data passed: nothing
+    CancelRenamingTag,
+    
+    /// Tells the `App` to rename the `Tag` being renamed on every `Transaction`, merging it if the new label is already a `Tag`.
+    /// Data passed: nothing
+    RenameTag,
 
 
 
@@ -387,6 +488,30 @@ pub enum Signal {
     /// Data passed: nothing
     SetTimePrice,
 
+    /// Tells the `App` to show or hide the history of time prices.
+    /// Data passed: nothing
+    ToggleTimePriceHistory,
+
+    /// Tells the `App` to update the date of the past time price being added.
+    /// Data passed: `String` of the date, formatted as YYYY-MM-DD
+    UpdateHistoricalTimePriceDateString(String),
+
+    /// Tells the `App` to update the past time price being added.
+    /// Data passed: `String` of the time price
+    UpdateHistoricalTimePriceString(String),
+
+    /// Tells the `App` to update the `Currency` of the past time price being added.
+    /// Data passed: `String` of the `Currency`, or an empty `String` for the main `Currency`
+    UpdateHistoricalTimePriceCurrencyString(String),
+
+    /// Tells the `App` to add the past time price to the history.
+    /// Data passed: nothing
+    AddHistoricalTimePrice,
+
+    /// Tells the `App` to remove a time price from the history.
+    /// Data passed: `Date` the time price was set on
+    RemoveHistoricalTimePrice(Date),
+
     /// Tells the `App` to set the flow type in the `CurrencyExchange`.
     /// Data passed: new flow type
     SetFlowType(FlowTypes),
@@ -398,6 +523,26 @@ pub enum Signal {
     /// Tells the `App` to try setting a new `ExchangeRate`.
     /// Data passed: `from_string`, `to_string`, `new_rate_string`
     TrySetNewExchangeRate(String, String, String),
+
+    /// Tells the `App` to show or hide the history of an `ExchangeRate`.
+    /// Data passed: `from_string`, `to_string`
+    ToggleRateHistory(String, String),
+
+    /// Tells the `App` to update the date of the past rate being added to the open history.
+    /// Data passed: `String` of the date, formatted as YYYY-MM-DD
+    UpdateHistoricalRateDateString(String),
+
+    /// Tells the `App` to update the past rate being added to the open history.
+    /// Data passed: `String` of the rate
+    UpdateHistoricalRateString(String),
+
+    /// Tells the `App` to add the past rate to the open history.
+    /// Data passed: nothing
+    AddHistoricalRate,
+
+    /// Tells the `App` to remove a rate from the history of an `ExchangeRate`.
+    /// Data passed: `from_string`, `to_string`, `Date` the rate was set on
+    RemoveHistoricalRate(String, String, Date),
     
     
     
@@ -418,6 +563,14 @@ pub enum Signal {
     /// Data passed: 
     ConfirmImport,
     
+    /// Tells the `App` whether an import should be merged into or replace the current data.
+    /// Data passed: the new `ImportMode`
+    SetImportMode(ImportModes),
+    
+    /// Tells the `App` how to resolve a conflict between a local and an imported `Transaction`.
+    /// Data passed: index of the conflict, the new `ConflictResolution`
+    SetImportConflictResolution(usize, ConflictResolutions),
+    
     /// Tells the `App` to cancel an import.
     /// Data passed: nothing
     CancelImport,
@@ -438,6 +591,66 @@ pub enum Signal {
     /// Data passed: nothing
     CancelLegacyImport,
     
+    /// Tells the `App` to open the CSV import file picker.
+    /// Data passed: nothing
+    OpenCsvImportFilePicker,
+    
+    /// Tells the `App` that a CSV import file has been selected.
+    /// Data passed: `PathBuf` of the selected file
+    CsvImportFileSelected(PathBuf),
+    
+    /// Tells the `App` to update a field of the CSV column mapping.
+    /// Data passed: the field and its new input
+    UpdateCsvImportField(CsvFields, String),
+    
+    /// Tells the `App` to toggle whether the first CSV row is a header.
+    /// Data passed: nothing
+    ToggleCsvImportHeader,
+    
+    /// Tells the `App` to confirm a CSV import.
+    /// Data passed: nothing
+    ConfirmCsvImport,
+    
+    /// Tells the `App` to cancel a CSV import.
+    /// Data passed: nothing
+    CancelCsvImport,
+    
+    /// Tells the `App` to open the OFX import file picker.
+    /// Data passed: nothing
+    OpenOfxImportFilePicker,
+    
+    /// Tells the `App` that an OFX import file has been selected.
+    /// Data passed: `PathBuf` of the selected file
+    OfxImportFileSelected(PathBuf),
+    
+    /// Tells the `App` to update the `Tag` given to imported OFX `Transaction`s.
+    /// Data passed: the new tag string
+    UpdateOfxImportTag(String),
+    
+    /// Tells the `App` to confirm an OFX import.
+    /// Data passed: nothing
+    ConfirmOfxImport,
+    
+    /// Tells the `App` to cancel an OFX import.
+    /// Data passed: nothing
+    CancelOfxImport,
+    
+    /// Tells the `App` to open the exchange rate import file picker.
+    /// Data passed: nothing
+    OpenRateImportFilePicker,
+    
+    /// Tells the `App` that an exchange rate file has been selected.
+    /// Data passed: `PathBuf` of the selected file
+    RateImportFileSelected(PathBuf),
+    
+    /// Tells the `App` to confirm an exchange rate import.
+    /// Data passed: nothing
+    ConfirmRateImport,
+    
+    /// Tells the `App` to cancel an exchange rate import.
+    /// Data passed: nothing
+    CancelRateImport,
+    
     /// Tells the `App` to create a backup.
     /// Data passed: nothing
     Backup,
@@ -445,4 +658,241 @@ pub enum Signal {
     /// Tells the `App` that a backup has finished.
     /// Data passed: backup result
     FinishedBackingup(Schrod<()>),
-}
\ No newline at end of file
+    
+    /// Tells the `App` to restore a backup through the confirm import page.
+    /// Data passed: `PathBuf` of the backup
+    RestoreBackup(PathBuf),
+    
+    /// Tells the `App` to open the export file picker for the `Transaction`s selected by a `Filter`.
+    /// Data passed: the `Filter` to export
+    OpenExportFilePicker(Filters),
+    
+    /// Tells the `App` that an export file has been chosen.
+    /// Data passed: the `Filter` to export, `PathBuf` of the chosen file
+    ExportFileSelected(Filters, PathBuf),
+    
+    
+    
+    // vault signals
+    /// Tells the `App` to update the name of the vault to create.
+    /// Data passed: new vault name string
+    UpdateNewVaultNameString(String),
+    
+    /// Tells the `App` to create a new vault and open it.
+    /// Data passed: nothing
+    CreateVault,
+    
+    /// Tells the `App` to open another vault.
+    /// Data passed: name of the vault
+    OpenVault(String),
+    
+    
+    
+    // encryption signals
+    /// Tells the `App` to update the passphrase entered on the unlock page.
+    /// Data passed: new passphrase string
+    UpdateUnlockPassphraseString(String),
+    
+    /// Tells the `App` to unlock the encrypted vault with the entered passphrase.
+    /// Data passed: nothing
+    Unlock,
+    
+    /// Tells the `App` to stay in the current vault instead of unlocking another one.
+    /// Data passed: nothing
+    CancelUnlock,
+    
+    /// Tells the `App` to update the entered current passphrase.
+    /// Data passed: new current passphrase string
+    UpdateCurrentPassphraseString(String),
+    
+    /// Tells the `App` to update the entered new passphrase.
+    /// Data passed: new passphrase string
+    UpdateNewPassphraseString(String),
+    
+    /// Tells the `App` to update the repeated new passphrase.
+    /// Data passed: new confirmation passphrase string
+    UpdateConfirmPassphraseString(String),
+    
+    /// Tells the `App` to encrypt the vault with the new passphrase.
+    /// Data passed: nothing
+    SetPassphrase,
+    
+    /// Tells the `App` to remove the encryption from the vault.
+    /// Data passed: nothing
+    RemovePassphrase,
+    
+    
+    
+    // recurring page signals
+    /// Tells the `App` to add the earliest due `Transaction` of a `RecurringTemplate`.
+    /// Data passed: `Uid` of the `RecurringTemplate`
+    ConfirmRecurrence(Uid),
+    
+    /// Tells the `App` to skip the earliest due `Transaction` of a `RecurringTemplate`.
+    /// Data passed: `Uid` of the `RecurringTemplate`
+    SkipRecurrence(Uid),
+    
+    /// Tells the `App` to add every due `Transaction` of every `RecurringTemplate`.
+    /// Data passed: nothing
+    ConfirmAllRecurrences,
+    
+    /// Tells the `App` to remove a `RecurringTemplate`.
+    /// Data passed: `Uid` of the `RecurringTemplate`
+    RemoveRecurringTemplate(Uid),
+    
+    
+    
+    // budgets page signals
+    /// Tells the `App` to add or remove a `Tag` from the new `Budget`.
+    /// Data passed: `Tag` to toggle
+    ToggleNewBudgetTag(Tag),
+    
+    /// Tells the `App` which interval the new `Budget` covers.
+    /// Data passed: `Intervals` of the new `Budget`
+    SetNewBudgetInterval(Intervals),
+    
+    /// Tells the `App` to update the limit of the new `Budget`.
+    /// Data passed: `String` of the limit
+    UpdateNewBudgetLimitString(String),
+    
+    /// Tells the `App` to update the currency of the new `Budget`.
+    /// Data passed: `String` of the currency code
+    UpdateNewBudgetCurrencyString(String),
+    
+    /// Tells the `App` to add the new `Budget`.
+    /// Data passed: nothing
+    AddBudget,
+    
+    /// Tells the `App` to remove a `Budget`.
+    /// Data passed: `Uid` of the `Budget`
+    RemoveBudget(Uid),
+    
+    
+    
+    // tag rules page signals
+    /// Tells the `App` to update the description text of the new `TagRule`.
+    /// Data passed: `String` of the description text
+    UpdateNewTagRuleDescriptionString(String),
+    
+    /// Tells the `App` to update the amount that values must be below for the new `TagRule`.
+    /// Data passed: `String` of the amount
+    UpdateNewTagRuleBelowString(String),
+    
+    /// Tells the `App` to update the amount that values must be above for the new `TagRule`.
+    /// Data passed: `String` of the amount
+    UpdateNewTagRuleAboveString(String),
+    
+    /// Tells the `App` to update the currency of the new `TagRule`.
+    /// Data passed: `String` of the currency code
+    UpdateNewTagRuleCurrencyString(String),
+    
+    /// Tells the `App` to update the `Tag`s added by the new `TagRule`.
+    /// Data passed: `String` of comma-separated `Tag` labels
+    UpdateNewTagRuleTagsString(String),
+    
+    /// Tells the `App` to add the new `TagRule`.
+    /// Data passed: nothing
+    AddTagRule,
+    
+    /// Tells the `App` to remove a `TagRule`.
+    /// Data passed: `Uid` of the `TagRule`
+    RemoveTagRule(Uid),
+    
+    /// Tells the `App` to show the changes that re-running the `TagRule`s over the ledger would make.
+    /// Data passed: nothing
+    PreviewTagRuleRerun,
+    
+    /// Tells the `App` to re-run the `TagRule`s over the ledger.
+    /// Data passed: nothing
+    ApplyTagRuleRerun,
+    
+    /// Tells the `App` to discard the preview of re-running the `TagRule`s.
+    /// Data passed: nothing
+    CancelTagRuleRerun,
+    
+    
+    
+    // bulk edit signals
+    /// Tells the `App` to select or deselect a `Transaction` for a bulk edit.
+    /// Data passed: `Uid` of the `Transaction`
+    ToggleTransactionSelection(Uid),
+    
+    /// Tells the `App` to select every `Transaction` that matches the primary `Filter`.
+    /// Data passed: nothing
+    SelectAllFilteredTransactions,
+    
+    /// Tells the `App` to deselect every `Transaction`.
+    /// Data passed: nothing
+    ClearTransactionSelection,
+    
+    /// Tells the `App` to open the bulk edit page for the selected `Transaction`s.
+    /// Data passed: nothing
+    StartBulkEdit,
+    
+    /// Tells the `App` which kind of change the bulk edit makes.
+    /// Data passed: `BulkActionTypes` to use
+    SetBulkActionType(BulkActionTypes),
+    
+    /// Tells the `App` to update the input of the bulk edit.
+    /// Data passed: `String` of the input
+    UpdateBulkEditInputString(String),
+    
+    /// Tells the `App` to make the bulk edit to every selected `Transaction`.
+    /// Data passed: nothing
+    ApplyBulkEdit,
+    
+    /// Tells the `App` to leave the bulk edit page without making any changes.
+    /// Data passed: nothing
+    CancelBulkEdit,
+    
+    
+    
+    // accounts page signals
+    /// Tells the `App` to update the name of the new `Account`.
+    /// Data passed: `String` of the name
+    UpdateNewAccountNameString(String),
+    
+    /// Tells the `App` which kind of `Account` the new one is.
+    /// Data passed: `AccountTypes` to use
+    SetNewAccountType(AccountTypes),
+    
+    /// Tells the `App` to update the opening balance of the new `Account`.
+    /// Data passed: `String` of the amount
+    UpdateNewAccountOpeningBalanceString(String),
+    
+    /// Tells the `App` to update the currency of the new `Account`.
+    /// Data passed: `String` of the currency code
+    UpdateNewAccountCurrencyString(String),
+    
+    /// Tells the `App` to add the new `Account`.
+    /// Data passed: nothing
+    AddAccount,
+    
+    /// Tells the `App` to remove an `Account`.
+    /// Data passed: `Uid` of the `Account`
+    RemoveAccount(Uid),
+    
+    /// Tells the `App` which `Account` the transfer moves money out of.
+    /// Data passed: `Uid` of the `Account`
+    SetTransferFromAccount(Uid),
+    
+    /// Tells the `App` which `Account` the transfer moves money into.
+    /// Data passed: `Uid` of the `Account`
+    SetTransferToAccount(Uid),
+    
+    /// Tells the `App` to update the amount of the transfer.
+    /// Data passed: `String` of the amount
+    UpdateTransferAmountString(String),
+    
+    /// Tells the `App` to update the date of the transfer.
+    /// Data passed: `String` of the date, formatted as YYYY-MM-DD
+    UpdateTransferDateString(String),
+    
+    /// Tells the `App` to update the description of the transfer.
+    /// Data passed: `String` of the description
+    UpdateTransferDescriptionString(String),
+    
+    /// Tells the `App` to add the transfer between the two `Account`s.
+    /// Data passed: nothing
+    AddTransfer,
+}
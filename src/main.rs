@@ -7,8 +7,12 @@ use crate::container::app::App;
 pub mod vault;
 pub mod container;
 pub mod pages;
+pub mod cli;
 
 fn main() -> iced::Result {
+    // commands given on the command line run without opening a window
+    if let Some(exit_code) = cli::run_from_arguments() { std::process::exit(exit_code) }
+
     // there have been some rendering issues on Fedora, and this fixed it
     #[cfg(target_os = "linux")]
     unsafe { std::env::set_var("WGPU_BACKEND", "gl"); }
@@ -4,7 +4,17 @@ pub mod application_errors_page;
 pub mod tag_registry_page;
 pub mod filter_ui;
 pub mod settings_page;
+pub mod backups_page;
+pub mod recurring_page;
+pub mod budgets_page;
+pub mod tag_rules_page;
+pub mod bulk_edit_page;
+pub mod accounts_page;
+pub mod unlock_page;
 pub mod confirm_import_page;
 pub mod confirm_legacy_import_page;
+pub mod confirm_csv_import_page;
+pub mod confirm_ofx_import_page;
+pub mod confirm_rate_import_page;
 pub mod help_page;
 pub mod trends_page;
\ No newline at end of file
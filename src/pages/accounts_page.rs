@@ -0,0 +1,317 @@
+use iced_font_awesome::fa_icon_solid as icon;
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, scrollable, stack};
+use iced::widget::column;
+use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::container::app::{App, Pages};
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, ThemeProvider, Widths, header, navigation_panel, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::account::{Account, AccountTypes};
+use crate::vault::csv_import::parse_date;
+use crate::vault::transaction::{Transaction, Uid, Value};
+use rust_decimal::Decimal;
+use schrod::Schrod::{Fail, Pass};
+
+/// The page used to manage `Account`s and move money between them.
+#[must_use]
+pub fn accounts_page<'a>(
+    app: &'a App
+) -> Stack<'a, Signal> {
+    stack![
+        row![
+            navigation_panel(app, Pages::page_pointers(app)),
+            container(accounts_list(app)).center_x(Fill),
+        ],
+        header(app, Vec::new()),
+    ]
+}
+
+/// The list of `Account`s, followed by the panels for adding a transfer and a new `Account`.
+#[must_use]
+fn accounts_list<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let account_slips = app.bank.accounts.iter().map(|account| account_slip(app, account)).collect::<Vec<_>>();
+
+    scrollable(
+        column![
+            spacer(Orientations::Vertical, Spacing::HeaderSpace),
+            ui_string(app, "Accounts", TextSizes::LargeHeading, MaterialColors::StrongText),
+            column(account_slips).spacing(Spacing::Small.size()),
+            spacer(Orientations::Vertical, Spacing::Medium),
+            ui_string(app, "Transfer", TextSizes::LargeHeading, MaterialColors::StrongText),
+            if app.bank.accounts.len() < 2 {
+                ui_string(app, "Add a second account below to move money between accounts.", TextSizes::Body, MaterialColors::WeakText)
+            }
+            else { transfer_panel(app) },
+            spacer(Orientations::Vertical, Spacing::Medium),
+            ui_string(app, "New Account", TextSizes::LargeHeading, MaterialColors::StrongText),
+            new_account_panel(app),
+        ]
+        .spacing(Spacing::Medium.size())
+    )
+    .direction(Direction::Vertical(Scrollbar::hidden()))
+    .width(Widths::LargeCard.size())
+    .height(Fill)
+    .into()
+}
+
+/// Displays the kind, name, and current balance of an `Account`, and allows it to be removed once it has no `Transaction`s.
+#[must_use]
+fn account_slip<'a>(
+    app: &'a App,
+    account: &'a Account,
+) -> Element<'a, Signal> {
+    let balance = account.get_balance(app.bank.get_ledger(), &app.bank.currency_exchange);
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            row![
+                icon(account.account_type.icon_name()),
+                column![
+                    ui_string(app, account.name.clone(), TextSizes::SmallHeading, MaterialColors::StrongText),
+                    ui_string(app, account.account_type.name(), TextSizes::Body, MaterialColors::WeakText),
+                ]
+                .spacing(Spacing::Micro.size()),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                ui_string(app, display_balance(account, balance), TextSizes::SmallHeading, MaterialColors::MediumText),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::danger(),
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    icon("trash"),
+                    Signal::RemoveAccount(account.get_uid()),
+                    app.bank.can_remove_account(account.get_uid()),
+                ),
+            ]
+            .spacing(Spacing::Small.size())
+            .align_y(Center)
+            .into()
+        }
+    )
+}
+
+/// Formats a balance in the `Currency` of its `Account`.
+#[must_use]
+pub fn display_balance(account: &Account, balance: Decimal) -> String {
+    match account.get_currency() {
+        Pass(currency) => { Value::from_decimal(balance, currency).to_string() }
+        Fail(_) => { format!("{balance} {}", account.get_currency_string()) }
+    }
+}
+
+/// The panel used to move an amount from one `Account` to another.
+#[must_use]
+fn transfer_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let is_amount_error = !app.transfer_amount_string.is_empty() && !Transaction::is_value_string_valid(&app.transfer_amount_string);
+    let is_date_error = parse_date(&app.transfer_date_string, "%Y-%m-%d").is_fail();
+    let is_valid = !app.transfer_amount_string.is_empty() && !is_amount_error && !is_date_error
+        && app.transfer_from_account_uid != app.transfer_to_account_uid;
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column![
+                ui_string(app, "From", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row(app.bank.accounts.iter().map(|account| transfer_account_button(app, account, app.transfer_from_account_uid, Signal::SetTransferFromAccount(account.get_uid()))))
+                    .spacing(Spacing::Micro.size()),
+                ui_string(app, "To", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row(app.bank.accounts.iter().map(|account| transfer_account_button(app, account, app.transfer_to_account_uid, Signal::SetTransferToAccount(account.get_uid()))))
+                    .spacing(Spacing::Micro.size()),
+                ui_string(app, "Amount, Date, and Description", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row![
+                    transfer_input(app, is_amount_error, Widths::MicroField, "Amount", &app.transfer_amount_string, Signal::UpdateTransferAmountString, is_valid),
+                    transfer_input(app, is_date_error, Widths::SmallField, "YYYY-MM-DD", &app.transfer_date_string, Signal::UpdateTransferDateString, is_valid),
+                    transfer_input(app, false, Widths::MediumField, "Description", &app.transfer_description_string, Signal::UpdateTransferDescriptionString, is_valid),
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::success(),
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        ui_string(app, "Add Transfer", TextSizes::Interactable, MaterialColors::StrongText),
+                        Signal::AddTransfer,
+                        is_valid,
+                    ),
+                ]
+                .spacing(Spacing::Micro.size())
+                .align_y(Center),
+            ]
+            .spacing(Spacing::Small.size())
+            .into()
+        }
+    )
+}
+
+/// Chooses one side of the transfer.
+#[must_use]
+fn transfer_account_button<'a>(
+    app: &'a App,
+    account: &Account,
+    selected_uid: Uid,
+    signal: Signal,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if account.get_uid() == selected_uid { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        row![icon(account.account_type.icon_name()), ui_string(app, account.name.clone(), TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(Spacing::Micro.size()),
+        signal,
+        true,
+    )
+}
+
+/// A text input for one of the parts of the transfer, which adds the transfer when submitted.
+#[must_use]
+fn transfer_input<'a>(
+    app: &'a App,
+    is_error: bool,
+    width: Widths,
+    placeholder: &str,
+    value: &str,
+    on_change: impl Fn(String) -> Signal + 'a,
+    can_submit: bool,
+) -> Element<'a, Signal> {
+    panel_text_input(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if is_error { MaterialColors::danger() } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        width,
+        placeholder,
+        value,
+        on_change,
+        Some(Signal::AddTransfer),
+        can_submit,
+    )
+}
+
+/// The panel used to enter the name, kind, opening balance, and `Currency` of a new `Account`.
+#[must_use]
+fn new_account_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let is_valid = Account::are_raw_parts_valid(&app.new_account_name_string, &app.new_account_opening_balance(), &app.new_account_currency());
+    let is_opening_balance_error = !Account::are_raw_parts_valid("Account", &app.new_account_opening_balance(), "USD");
+    let is_currency_error = !Transaction::is_currency_string_valid(app.new_account_currency().trim());
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column![
+                ui_string(app, "Name", TextSizes::SmallHeading, MaterialColors::StrongText),
+                new_account_input(app, false, Widths::LargeField, "Rainy Day Fund", &app.new_account_name_string, Signal::UpdateNewAccountNameString, is_valid),
+                ui_string(app, "Kind", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row(AccountTypes::all().into_iter().map(|account_type| new_account_type_button(app, account_type)))
+                    .spacing(Spacing::Micro.size())
+                    .align_y(Center),
+                ui_string(app, "Opening Balance and Currency", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row![
+                    new_account_input(app, is_opening_balance_error, Widths::MicroField, "0", &app.new_account_opening_balance_string, Signal::UpdateNewAccountOpeningBalanceString, is_valid),
+                    new_account_input(app, is_currency_error, Widths::MicroField, app.bank.currency_exchange.get_main_currency().iso_alpha_code, &app.new_account_currency_string, Signal::UpdateNewAccountCurrencyString, is_valid),
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::success(),
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        ui_string(app, "Add Account", TextSizes::Interactable, MaterialColors::StrongText),
+                        Signal::AddAccount,
+                        is_valid,
+                    ),
+                ]
+                .spacing(Spacing::Micro.size())
+                .align_y(Center),
+            ]
+            .spacing(Spacing::Small.size())
+            .into()
+        }
+    )
+}
+
+/// Chooses the kind of the new `Account`.
+#[must_use]
+fn new_account_type_button<'a>(
+    app: &'a App,
+    account_type: AccountTypes,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if app.new_account_type == account_type { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        row![icon(account_type.icon_name()), ui_string(app, account_type.name().to_string(), TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(Spacing::Micro.size()),
+        Signal::SetNewAccountType(account_type),
+        true,
+    )
+}
+
+/// A text input for one of the parts of the new `Account`, which adds the `Account` when submitted.
+#[must_use]
+fn new_account_input<'a>(
+    app: &'a App,
+    is_error: bool,
+    width: Widths,
+    placeholder: &str,
+    value: &str,
+    on_change: impl Fn(String) -> Signal + 'a,
+    can_submit: bool,
+) -> Element<'a, Signal> {
+    panel_text_input(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if is_error { MaterialColors::danger() } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        width,
+        placeholder,
+        value,
+        on_change,
+        Some(Signal::AddAccount),
+        can_submit,
+    )
+}
@@ -0,0 +1,118 @@
+use iced_font_awesome::fa_icon_solid as icon;
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, scrollable, stack};
+use iced::widget::column;
+use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::container::app::{App, Pages};
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, panel, panel_button, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::save_engine::BackupInfo;
+
+/// The page used to list and restore the backups of the open vault.
+#[must_use]
+pub fn backups_page<'a>(
+    app: &'a App
+) -> Stack<'a, Signal> {
+    stack![
+        row![
+            navigation_panel(app, Pages::page_pointers(app)),
+            container(backup_list(app)).center_x(Fill),
+        ],
+        header(app, Vec::new()),
+    ]
+}
+
+/// The list of backups.
+#[must_use]
+fn backup_list<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let mut backup_slips = app.backups.iter().map(|backup| backup_slip(app, backup)).collect::<Vec<_>>();
+    if backup_slips.is_empty() {
+        backup_slips.push(ui_string(app, "There are no backups of this vault yet.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    scrollable(
+        column![
+            spacer(Orientations::Vertical, Spacing::HeaderSpace),
+            row![
+                ui_string(app, format!("Backups of {}", app.vault_name), TextSizes::LargeHeading, MaterialColors::StrongText),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                create_backup_button(app),
+            ]
+            .align_y(Center),
+            column(backup_slips).spacing(Spacing::Small.size()),
+        ]
+        .spacing(Spacing::Medium.size())
+    )
+    .direction(Direction::Vertical(Scrollbar::hidden()))
+    .width(Widths::LargeCard.size())
+    .height(Fill)
+    .into()
+}
+
+/// Displays a single backup and allows it to be restored.
+#[must_use]
+fn backup_slip<'a>(
+    app: &'a App,
+    backup: &'a BackupInfo,
+) -> Element<'a, Signal> {
+    let transaction_count = match backup.transaction_count {
+        Some(count) => format!("{count} Transactions"),
+        None => "Unreadable".to_string(),
+    };
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            row![
+                ui_string(app, backup.time.format("%B %-d, %Y  %H:%M").to_string(), TextSizes::SmallHeading, MaterialColors::StrongText),
+                spacer(Orientations::Horizontal, Spacing::Medium),
+                ui_string(app, transaction_count, TextSizes::Body, MaterialColors::WeakText),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::CardContent,
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    ui_string(app, "Restore", TextSizes::Interactable, MaterialColors::StrongText),
+                    Signal::RestoreBackup(backup.path.clone()),
+                    backup.transaction_count.is_some(),
+                ),
+            ]
+            .align_y(Center)
+            .into()
+        }
+    )
+}
+
+/// Creates a backup right away.
+#[must_use]
+fn create_backup_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        icon("floppy-disk"),
+        Signal::Backup,
+        true,
+    )
+}
@@ -0,0 +1,321 @@
+use iced_font_awesome::fa_icon_solid as icon;
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, scrollable, stack};
+use iced::widget::column;
+use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::container::app::{App, Pages};
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, ThemeProvider, Widths, header, navigation_panel, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::budget::{Budget, BudgetProgress};
+use crate::vault::transaction::{Date, Tag, Transaction, Value};
+use crate::vault::trend_parse::Intervals;
+use schrod::Schrod::{Fail, Pass};
+
+/// The height of the bar showing how much of a `Budget` has been spent.
+const PROGRESS_BAR_HEIGHT: f32 = 16.0;
+
+/// The page used to manage `Budget`s and track spending against them.
+#[must_use]
+pub fn budgets_page<'a>(
+    app: &'a App
+) -> Stack<'a, Signal> {
+    stack![
+        row![
+            navigation_panel(app, Pages::page_pointers(app)),
+            container(budgets_list(app)).center_x(Fill),
+        ],
+        header(app, Vec::new()),
+    ]
+}
+
+/// The list of `Budget`s, followed by the panel for adding a new one.
+#[must_use]
+fn budgets_list<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let today_option = match Date::today() {
+        Pass(today) => Some(today),
+        Fail(_) => None,
+    };
+
+    let mut budget_slips = app.bank.budgets.iter().map(|budget| budget_slip(app, budget, today_option)).collect::<Vec<_>>();
+    if budget_slips.is_empty() {
+        budget_slips.push(ui_string(app, "There are no budgets yet. Choose some tags below to start one.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    scrollable(
+        column![
+            spacer(Orientations::Vertical, Spacing::HeaderSpace),
+            ui_string(app, "Budgets", TextSizes::LargeHeading, MaterialColors::StrongText),
+            column(budget_slips).spacing(Spacing::Small.size()),
+            spacer(Orientations::Vertical, Spacing::Medium),
+            ui_string(app, "New Budget", TextSizes::LargeHeading, MaterialColors::StrongText),
+            new_budget_panel(app),
+        ]
+        .spacing(Spacing::Medium.size())
+    )
+    .direction(Direction::Vertical(Scrollbar::hidden()))
+    .width(Widths::LargeCard.size())
+    .height(Fill)
+    .into()
+}
+
+/// Displays a `Budget` with its spending for the current period, and allows it to be removed.
+#[must_use]
+fn budget_slip<'a>(
+    app: &'a App,
+    budget: &'a Budget,
+    today_option: Option<Date>,
+) -> Element<'a, Signal> {
+    let progress_option = today_option.and_then(|today| match budget.get_progress(&app.bank, today) {
+        Pass(progress) => Some(progress),
+        Fail(_) => None,
+    });
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column![
+                row![
+                    column![
+                        ui_string(app, budget.display_tags(), TextSizes::SmallHeading, MaterialColors::StrongText),
+                        ui_string(app, budget.interval.name(), TextSizes::Body, MaterialColors::WeakText),
+                    ]
+                    .spacing(Spacing::Micro.size()),
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::danger(),
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        icon("trash"),
+                        Signal::RemoveBudget(budget.get_uid()),
+                        true,
+                    ),
+                ]
+                .align_y(Center),
+                match &progress_option {
+                    Some(progress) => progress_display(app, budget, progress),
+                    None => ui_string(app, format!("Add an exchange rate to {} in the settings to track this budget.", budget.get_currency_string()), TextSizes::Body, MaterialColors::WeakText),
+                },
+            ]
+            .spacing(Spacing::Small.size())
+            .into()
+        }
+    )
+}
+
+/// Displays how much of a `Budget` has been spent, in the color of its first `Tag` if it has been overspent.
+#[must_use]
+fn progress_display<'a>(
+    app: &'a App,
+    budget: &Budget,
+    progress: &BudgetProgress,
+) -> Element<'a, Signal> {
+    let is_overspent = progress.is_overspent();
+    let bar_color = match budget.tags.first() {
+        Some(tag) if is_overspent => app.bank.tag_registry.get(tag),
+        _ => MaterialColors::accent(app.material_theme()),
+    };
+    let status = if is_overspent { format!("{} over", Value::from_decimal(-*progress.remaining().amount(), progress.limit.currency())) }
+    else { format!("{} left", progress.remaining()) };
+
+    column![
+        progress_bar(app, progress.fraction(), bar_color),
+        row![
+            ui_string(app, format!("{} of {} spent", progress.spent, progress.limit), TextSizes::Body, MaterialColors::MediumText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            if is_overspent {
+                panel(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: bar_color,
+                        depth: Depths::Proud,
+                    },
+                    PanelSize { width: Widths::Shrink, height: Heights::Shrink },
+                    PaddingSizes::Micro,
+                    ui_string(app, status, TextSizes::Body, MaterialColors::StrongText),
+                )
+            }
+            else { ui_string(app, status, TextSizes::Body, MaterialColors::WeakText) },
+        ]
+        .align_y(Center),
+    ]
+    .spacing(Spacing::Micro.size())
+    .into()
+}
+
+/// A bar filled up to the given fraction.
+#[must_use]
+fn progress_bar<'a>(
+    app: &'a App,
+    fraction: f32,
+    color: MaterialColors,
+) -> Element<'a, Signal> {
+    let bar_width = Widths::LargeField.size();
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardHollow,
+            depth: Depths::Recessed,
+        },
+        PanelSize { width: Widths::Other(bar_width), height: Heights::Other(PROGRESS_BAR_HEIGHT) },
+        PaddingSizes::None, {
+            if fraction > 0.0 {
+                panel(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color,
+                        depth: Depths::Proud,
+                    },
+                    PanelSize { width: Widths::Other((bar_width - (PaddingSizes::Micro.size() * 2.0)) * fraction), height: Heights::Fill },
+                    PaddingSizes::None,
+                    spacer(Orientations::Vertical, Spacing::None),
+                )
+            }
+            else { spacer(Orientations::Vertical, Spacing::None) }
+        }
+    )
+}
+
+/// The panel used to choose the `Tag`s, interval, and limit of a new `Budget`.
+#[must_use]
+fn new_budget_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let is_valid = Budget::are_raw_parts_valid(&app.new_budget_tags, &app.new_budget_limit_string, &app.new_budget_currency());
+    let is_limit_error = !app.new_budget_limit_string.is_empty() && !Budget::is_limit_string_valid(&app.new_budget_limit_string);
+    let is_currency_error = !Transaction::is_currency_string_valid(app.new_budget_currency().trim());
+
+    let mut tag_buttons = app.bank.get_tags().into_iter().map(|tag| new_budget_tag_button(app, tag)).collect::<Vec<_>>();
+    if tag_buttons.is_empty() {
+        tag_buttons.push(ui_string(app, "Tag some transactions to budget for them.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column![
+                ui_string(app, "Tags", TextSizes::SmallHeading, MaterialColors::StrongText),
+                scrollable(row(tag_buttons))
+                    .direction(Direction::Horizontal(Scrollbar::hidden())),
+                ui_string(app, "Interval", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row(Intervals::all().into_iter().map(|interval| new_budget_interval_button(app, interval)))
+                    .spacing(Spacing::Micro.size())
+                    .align_y(Center),
+                ui_string(app, "Limit", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row![
+                    panel_text_input(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: if is_limit_error { MaterialColors::danger() } else { MaterialColors::CardContent },
+                            depth: Depths::Proud,
+                        },
+                        Widths::MicroField,
+                        "Limit",
+                        &app.new_budget_limit_string,
+                        Signal::UpdateNewBudgetLimitString,
+                        Some(Signal::AddBudget),
+                        is_valid,
+                    ),
+                    panel_text_input(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: if is_currency_error { MaterialColors::danger() } else { MaterialColors::CardContent },
+                            depth: Depths::Proud,
+                        },
+                        Widths::MicroField,
+                        app.bank.currency_exchange.get_main_currency().iso_alpha_code,
+                        &app.new_budget_currency_string,
+                        Signal::UpdateNewBudgetCurrencyString,
+                        Some(Signal::AddBudget),
+                        is_valid,
+                    ),
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::success(),
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        ui_string(app, "Add Budget", TextSizes::Interactable, MaterialColors::StrongText),
+                        Signal::AddBudget,
+                        is_valid,
+                    ),
+                ]
+                .align_y(Center),
+            ]
+            .spacing(Spacing::Small.size())
+            .into()
+        }
+    )
+}
+
+/// Toggles if a `Tag` is part of the new `Budget`.
+#[must_use]
+fn new_budget_tag_button<'a>(
+    app: &'a App,
+    tag: Tag,
+) -> Element<'a, Signal> {
+    let color = if app.new_budget_tags.contains(&tag) { app.bank.tag_registry.get(&tag) }
+    else { MaterialColors::CardContent };
+
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color,
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Minimal,
+        ui_string(app, tag.get_label(), TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ToggleNewBudgetTag(tag),
+        true,
+    )
+}
+
+/// Selects the interval of the new `Budget`.
+#[must_use]
+fn new_budget_interval_button<'a>(
+    app: &'a App,
+    interval: Intervals,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if app.new_budget_interval == interval { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Minimal,
+        ui_string(app, interval.name(), TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::SetNewBudgetInterval(interval),
+        true,
+    )
+}
@@ -0,0 +1,179 @@
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, stack};
+use iced::widget::column;
+use iced::widget::row;
+use crate::container::app::App;
+use crate::container::signal::Signal;
+use crate::pages::confirm_csv_import_page::preview_list;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, ThemeProvider, Widths, header, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::bulk_edit::{BulkActionTypes, BulkActions};
+use crate::vault::transaction::Transaction;
+use schrod::Schrod;
+
+/// The page used to choose and confirm one change to every selected `Transaction`.
+#[must_use]
+pub fn bulk_edit_page<'a>(
+    app: &'a App,
+) -> Stack<'a, Signal> {
+    stack![
+        container(bulk_edit_panel(app)).center(Fill),
+        header(app, Vec::new()),
+    ]
+}
+
+/// The panel that allows a user to choose a bulk edit, preview it, and confirm or cancel it.
+#[must_use]
+fn bulk_edit_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let selected_transactions: Vec<&Transaction> = app.bank.get_ledger().iter()
+        .filter(|transaction| app.selected_transaction_uids.contains(&transaction.get_uid()))
+        .collect();
+    let action_result = BulkActions::new_from_raw(app.bulk_action_type, &app.bulk_edit_input_string);
+
+    // splitting the selection into the transactions that will and will not change
+    let (summary, changed_transactions, skipped_count) = match &action_result {
+        Schrod::Pass(action) => {
+            let changed_transactions: Vec<&Transaction> = selected_transactions.iter().copied().filter(|transaction| action.changes(transaction)).collect();
+            let skipped_count = selected_transactions.len() - changed_transactions.len();
+            let summary = match changed_transactions.len() {
+                1 => format!("This will {} 1 Transaction.", action.display()),
+                count => format!("This will {} {count} Transactions.", action.display()),
+            };
+            (summary, changed_transactions, skipped_count)
+        }
+        Schrod::Fail(_) => { ("Enter what to change to see which Transactions it affects.".to_string(), Vec::new(), 0) }
+    };
+    let is_deleting = app.bulk_action_type == BulkActionTypes::Delete;
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::LargeCard, height: Heights::Shrink },
+        PaddingSizes::Medium, {
+            column![
+                ui_string(app, format!("Bulk edit {} selected Transactions", selected_transactions.len()), TextSizes::LargeHeading, MaterialColors::StrongText),
+
+                // action
+                spacer(Orientations::Vertical, Spacing::Medium),
+                row(BulkActionTypes::all().into_iter().map(|action_type| bulk_action_type_button(app, action_type)))
+                    .spacing(Spacing::Micro.size()),
+                spacer(Orientations::Vertical, Spacing::Small),
+                match app.bulk_action_type.input_placeholder() {
+                    Some(placeholder) => bulk_edit_input(app, placeholder, action_result.is_pass()),
+                    None => spacer(Orientations::Vertical, Spacing::None),
+                },
+
+                // preview
+                spacer(Orientations::Vertical, Spacing::Medium),
+                ui_string(app, summary, TextSizes::SmallHeading, MaterialColors::StrongText),
+                if skipped_count > 0 {
+                    ui_string(app, format!("{skipped_count} selected Transactions would not change and will be skipped."), TextSizes::Body, MaterialColors::WeakText)
+                }
+                else { spacer(Orientations::Vertical, Spacing::None) },
+                spacer(Orientations::Vertical, Spacing::Small),
+                preview_list(app, &changed_transactions),
+
+                spacer(Orientations::Vertical, Spacing::Ginormous),
+                row![
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    apply_bulk_edit_button(app, is_deleting, !changed_transactions.is_empty()),
+                    spacer(Orientations::Horizontal, Spacing::Medium),
+                    cancel_bulk_edit_button(app),
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                ]
+            ]
+            .align_x(Center)
+            .into()
+        }
+    )
+}
+
+/// Chooses which kind of change the bulk edit makes.
+#[must_use]
+fn bulk_action_type_button<'a>(
+    app: &'a App,
+    action_type: BulkActionTypes,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if app.bulk_action_type == action_type { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        ui_string(app, action_type.name().to_string(), TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::SetBulkActionType(action_type),
+        true,
+    )
+}
+
+/// Edits the input of the bulk edit, such as the `Tag` to add.
+#[must_use]
+fn bulk_edit_input<'a>(
+    app: &'a App,
+    placeholder: &str,
+    is_valid: bool,
+) -> Element<'a, Signal> {
+    panel_text_input(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if is_valid || app.bulk_edit_input_string.is_empty() { MaterialColors::CardContent } else { MaterialColors::danger() },
+            depth: Depths::Proud,
+        },
+        Widths::MediumField,
+        placeholder,
+        &app.bulk_edit_input_string,
+        Signal::UpdateBulkEditInputString,
+        None,
+        false,
+    )
+}
+
+/// Applies the bulk edit to every selected `Transaction`.
+#[must_use]
+fn apply_bulk_edit_button<'a>(
+    app: &'a App,
+    is_deleting: bool,
+    can_apply: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if is_deleting { MaterialColors::danger() } else { MaterialColors::success() },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        ui_string(app, if is_deleting { "Delete" } else { "Apply" }, TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ApplyBulkEdit,
+        can_apply,
+    )
+}
+
+/// Leaves the bulk edit without changing anything.
+#[must_use]
+fn cancel_bulk_edit_button<'a>(
+    app: &'a App
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardContent,
+            depth: Depths::Proud
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Cancel", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::CancelBulkEdit,
+        true,
+    )
+}
@@ -0,0 +1,308 @@
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, scrollable, stack};
+use iced::widget::column;
+use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::container::app::App;
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::csv_import::{CsvFields, CsvImport};
+use crate::vault::transaction::Transaction;
+use schrod::Schrod::{Pass, Fail};
+
+/// The page used to map the columns of a CSV bank statement and confirm merging its `Transaction`s into the `Bank`.
+#[must_use]
+pub fn confirm_csv_import_page<'a>(
+    app: &'a App,
+) -> Stack<'a, Signal> {
+    stack![
+        container(confirm_csv_import_panel(app)).center(Fill),
+        header(app, Vec::new()),
+    ]
+}
+
+/// The panel that allows a user to map columns and confirm or cancel a CSV import.
+#[must_use]
+fn confirm_csv_import_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    match &app.csv_import_data {
+        // assuming the app contains csv import data
+        Some(import_data) => {
+            let transaction_results = import_data.parse();
+            let transactions: Vec<&Transaction> = transaction_results.iter().filter_map(|result| match result { Pass(transaction) => Some(transaction), Fail(_) => None }).collect();
+            let failed_rows = transaction_results.len() - transactions.len();
+            let first_failure = transaction_results.iter().find(|result| result.is_fail()).and_then(|result| result.results().last().cloned());
+
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                PanelSize { width: Widths::LargeCard, height: Heights::Shrink },
+                PaddingSizes::Medium, {
+                    column![
+                        ui_string(app, "Would you like to import a bank statement?", TextSizes::LargeHeading, MaterialColors::StrongText),
+
+                        // columns
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        column_list(app, import_data),
+
+                        // mapping
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        row![
+                            mapping_field(app, import_data, CsvFields::DateColumn),
+                            mapping_field(app, import_data, CsvFields::ValueColumn),
+                            mapping_field(app, import_data, CsvFields::DescriptionColumn),
+                            mapping_field(app, import_data, CsvFields::CurrencyColumn),
+                        ]
+                        .spacing(Spacing::Small.size()),
+                        spacer(Orientations::Vertical, Spacing::Small),
+                        row![
+                            mapping_field(app, import_data, CsvFields::DateFormat),
+                            mapping_field(app, import_data, CsvFields::FallbackCurrency),
+                            mapping_field(app, import_data, CsvFields::ImportTag),
+                            mapping_field(app, import_data, CsvFields::Delimiter),
+                        ]
+                        .spacing(Spacing::Small.size()),
+                        spacer(Orientations::Vertical, Spacing::Small),
+                        header_toggle(app, import_data),
+
+                        // preview
+                        spacer(Orientations::Vertical, Spacing::Large),
+                        ui_string(app, format!("{} Transactions found.", transactions.len()), TextSizes::SmallHeading, MaterialColors::StrongText),
+                        if failed_rows > 0 {
+                            ui_string(app, format!("{failed_rows} rows could not be read. {}", first_failure.unwrap_or_default()), TextSizes::Body, MaterialColors::WeakText)
+                        }
+                        else { spacer(Orientations::Vertical, Spacing::None) },
+                        spacer(Orientations::Vertical, Spacing::Small),
+                        preview_list(app, &transactions),
+
+                        spacer(Orientations::Vertical, Spacing::Large),
+                        ui_string(app, "These Transactions will be added alongside your current Transactions.", TextSizes::SmallHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Ginormous),
+
+                        row![
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                            confirm_csv_import_button(app, !transactions.is_empty()),
+                            spacer(Orientations::Horizontal, Spacing::Medium),
+                            cancel_csv_import_button(app),
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                        ]
+                    ]
+                    .align_x(Center)
+                    .into()
+                }
+            )
+        }
+
+        // fallback if it does not
+        None => {
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                PanelSize { width: Widths::SmallCard, height: Heights::Shrink },
+                PaddingSizes::Medium, {
+                    column![
+                        ui_string(app, "Woops! No data has been loaded.", TextSizes::LargeHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        cancel_csv_import_button(app)
+                    ]
+                    .align_x(Center)
+                    .into()
+                }
+            )
+        }
+    }
+}
+
+/// Displays the columns found in the CSV file.
+#[must_use]
+fn column_list<'a>(
+    app: &'a App,
+    import_data: &CsvImport,
+) -> Element<'a, Signal> {
+    let mut column_panels = import_data.get_headers().into_iter().map(|header| {
+        panel(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::CardHollowContent,
+                depth: Depths::Proud,
+            },
+            PanelSize { width: Widths::Shrink, height: Heights::Shrink },
+            PaddingSizes::Small, {
+                ui_string(app, header, TextSizes::Body, MaterialColors::StrongText)
+            }
+        )
+    }).collect::<Vec<_>>();
+    column_panels.insert(0, spacer(Orientations::Horizontal, Spacing::Small));
+    column_panels.push(spacer(Orientations::Horizontal, Spacing::Small));
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardHollow,
+            depth: Depths::Recessed,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::None, {
+            column![
+                spacer(Orientations::Vertical, Spacing::Micro),
+                scrollable(
+                    row(column_panels)
+                        .spacing(Spacing::Micro.size())
+                )
+                .direction(Direction::Horizontal(Scrollbar::hidden())),
+                spacer(Orientations::Vertical, Spacing::Micro),
+            ]
+            .into()
+        }
+    )
+}
+
+/// A labeled field used to edit part of the `CsvColumnMapping`.
+#[must_use]
+fn mapping_field<'a>(
+    app: &'a App,
+    import_data: &CsvImport,
+    field: CsvFields,
+) -> Element<'a, Signal> {
+    let is_valid = import_data.is_field_valid(field);
+
+    column![
+        ui_string(app, field.name().to_string(), TextSizes::Body, MaterialColors::WeakText),
+        panel_text_input(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if is_valid { MaterialColors::CardContent } else { MaterialColors::danger() },
+                depth: Depths::Proud,
+            },
+            Widths::MicroField,
+            field.name(),
+            import_data.get_mapping().get(field),
+            move |input| Signal::UpdateCsvImportField(field, input),
+            None,
+            false,
+        ),
+    ]
+    .spacing(Spacing::Nano.size())
+    .into()
+}
+
+/// Toggles whether the first row of the CSV file holds the column names.
+#[must_use]
+fn header_toggle<'a>(
+    app: &'a App,
+    import_data: &CsvImport,
+) -> Element<'a, Signal> {
+    let has_header = import_data.get_mapping().has_header;
+
+    row![
+        ui_string(app, "First Row Is Header", TextSizes::Body, MaterialColors::StrongText),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if has_header { MaterialColors::success() } else { MaterialColors::CardContent },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::LowProfile,
+            ui_string(app, if has_header { "Yes" } else { "No" }, TextSizes::Interactable, MaterialColors::StrongText),
+            Signal::ToggleCsvImportHeader,
+            true,
+        ),
+        spacer(Orientations::Horizontal, Spacing::Fill),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
+/// Shows the first few `Transaction`s that will be imported.
+#[must_use]
+pub fn preview_list<'a>(
+    app: &'a App,
+    transactions: &[&Transaction],
+) -> Element<'a, Signal> {
+    let preview_length = 5;
+    let mut preview_rows = transactions.iter().take(preview_length).map(|transaction| {
+        row![
+            ui_string(app, transaction.date.display(), TextSizes::Body, MaterialColors::WeakText),
+            spacer(Orientations::Horizontal, Spacing::Medium),
+            ui_string(app, transaction.value.to_string(), TextSizes::Body, MaterialColors::StrongText),
+            spacer(Orientations::Horizontal, Spacing::Medium),
+            ui_string(app, transaction.description.clone(), TextSizes::Body, MaterialColors::StrongText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+        ]
+        .align_y(Center)
+        .into()
+    }).collect::<Vec<Element<'a, Signal>>>();
+    if transactions.len() > preview_length {
+        preview_rows.push(ui_string(app, format!("and {} more...", transactions.len() - preview_length), TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardHollow,
+            depth: Depths::Recessed,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column(preview_rows)
+                .spacing(Spacing::Micro.size())
+                .into()
+        }
+    )
+}
+
+/// Confirms a CSV import.
+#[must_use]
+fn confirm_csv_import_button<'a>(
+    app: &'a App,
+    can_import: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::success(),
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Confirm", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ConfirmCsvImport,
+        can_import,
+    )
+}
+
+/// Cancels a CSV import.
+#[must_use]
+fn cancel_csv_import_button<'a>(
+    app: &'a App
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::danger(),
+            depth: Depths::Proud
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Cancel", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::CancelCsvImport,
+        true,
+    )
+}
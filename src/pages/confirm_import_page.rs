@@ -1,14 +1,18 @@
 use iced::{Center, Fill};
 use iced::Element;
-use iced::widget::{Stack, container, stack};
+use iced::widget::{Stack, container, scrollable, stack};
 use iced::widget::column;
 use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
 use crate::container::app::App;
 use crate::container::signal::Signal;
+use crate::pages::confirm_csv_import_page::preview_list;
 use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, panel, panel_button, spacer, ui_string};
 use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::import_merge::{ConflictResolutions, ImportConflict, ImportMerge, ImportModes};
+use crate::vault::transaction::{Tag, Transaction};
 
-/// The page used to confirm if the user wants to replace the current `Transaction`s with those from an external save file.
+/// The page used to confirm if the user wants to merge or replace the current `Transaction`s with those from an external save file.
 #[must_use]
 pub fn confirm_import_page<'a>(
     app: &'a App,
@@ -34,14 +38,34 @@ fn confirm_import_panel<'a>(
                     color: MaterialColors::Card,
                     depth: Depths::Proud,
                 },
-                PanelSize { width: Widths::MediumCard, height: Heights::Shrink },
+                PanelSize { width: Widths::LargeCard, height: Heights::Shrink },
                 PaddingSizes::Medium, {
                     column![
                         ui_string(app, "Would you like to load Transactions?", TextSizes::LargeHeading, MaterialColors::StrongText),
                         spacer(Orientations::Vertical, Spacing::Small),
                         ui_string(app, format!("{} Transactions found.", import_data.transactions.len()), TextSizes::SmallHeading, MaterialColors::StrongText),
+                        
+                        // mode
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        row![
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                            import_mode_button(app, ImportModes::Merge),
+                            spacer(Orientations::Horizontal, Spacing::Small),
+                            import_mode_button(app, ImportModes::Replace),
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                        ],
+                        
+                        // differences
+                        match (&app.import_merge, app.import_mode) {
+                            (Some(import_merge), ImportModes::Merge) => { merge_summary(app, import_merge) }
+                            _ => { spacer(Orientations::Vertical, Spacing::None) }
+                        },
+                        
                         spacer(Orientations::Vertical, Spacing::Large),
-                        ui_string(app, "Please note that importing these Transactions will erase all previous save data.", TextSizes::SmallHeading, MaterialColors::StrongText),
+                        match app.import_mode {
+                            ImportModes::Merge => { ui_string(app, "New Transactions will be added alongside your current Transactions.", TextSizes::SmallHeading, MaterialColors::StrongText) }
+                            ImportModes::Replace => { ui_string(app, "Please note that importing these Transactions will erase all previous save data.", TextSizes::SmallHeading, MaterialColors::StrongText) }
+                        },
                         spacer(Orientations::Vertical, Spacing::Ginormous),
                         
                         row![
@@ -82,6 +106,142 @@ fn confirm_import_panel<'a>(
     }
 }
 
+/// Shows what merging the imported data would change and lets the user resolve conflicts.
+#[must_use]
+fn merge_summary<'a>(
+    app: &'a App,
+    import_merge: &'a ImportMerge,
+) -> Element<'a, Signal> {
+    let new_transactions: Vec<&Transaction> = import_merge.get_new_transactions().iter().collect();
+    let conflicts = import_merge.get_conflicts();
+    
+    let mut conflict_rows = conflicts.iter().enumerate().map(|(index, conflict)| conflict_row(app, index, conflict)).collect::<Vec<_>>();
+    conflict_rows.insert(0, spacer(Orientations::Vertical, Spacing::Nano));
+    conflict_rows.push(spacer(Orientations::Vertical, Spacing::Nano));
+    
+    column![
+        spacer(Orientations::Vertical, Spacing::Medium),
+        ui_string(app, format!("{} new, {} identical, {} conflicting.", new_transactions.len(), import_merge.get_identical_count(), conflicts.len()), TextSizes::Body, MaterialColors::WeakText),
+        
+        // new transactions
+        if new_transactions.is_empty() { spacer(Orientations::Vertical, Spacing::None) }
+        else {
+            column![
+                spacer(Orientations::Vertical, Spacing::Small),
+                preview_list(app, &new_transactions),
+            ]
+            .into()
+        },
+        
+        // conflicts
+        if conflicts.is_empty() { spacer(Orientations::Vertical, Spacing::None) }
+        else {
+            column![
+                spacer(Orientations::Vertical, Spacing::Small),
+                panel(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::CardHollow,
+                        depth: Depths::Recessed,
+                    },
+                    PanelSize { width: Widths::Fill, height: Heights::MediumCard },
+                    PaddingSizes::None, {
+                        row![
+                            spacer(Orientations::Horizontal, Spacing::Small),
+                            
+                            scrollable(column(conflict_rows).spacing(Spacing::Small.size()))
+                                .direction(Direction::Vertical(Scrollbar::hidden()))
+                                .width(Fill),
+                            
+                            spacer(Orientations::Horizontal, Spacing::Small),
+                        ]
+                        .into()
+                    }
+                ),
+            ]
+            .into()
+        },
+    ]
+    .into()
+}
+
+/// Shows a local and an imported `Transaction` side by side with the ways to resolve the conflict.
+#[must_use]
+fn conflict_row<'a>(
+    app: &'a App,
+    index: usize,
+    conflict: &'a ImportConflict,
+) -> Element<'a, Signal> {
+    let resolution_buttons = ConflictResolutions::all().into_iter().map(|resolution| {
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if conflict.resolution == resolution { MaterialColors::accent(app.theme_selection) } else { MaterialColors::CardContent },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::LowProfile,
+            ui_string(app, resolution.name(), TextSizes::Interactable, MaterialColors::StrongText),
+            Signal::SetImportConflictResolution(index, resolution),
+            true,
+        )
+    }).collect::<Vec<_>>();
+    
+    column![
+        conflict_transaction_line(app, "Local", conflict.get_local()),
+        conflict_transaction_line(app, "Imported", conflict.get_imported()),
+        row(resolution_buttons).spacing(Spacing::Small.size()),
+    ]
+    .spacing(Spacing::Micro.size())
+    .into()
+}
+
+/// Shows a single `Transaction` within a conflict.
+#[must_use]
+fn conflict_transaction_line<'a>(
+    app: &'a App,
+    source: &'a str,
+    transaction: &'a Transaction,
+) -> Element<'a, Signal> {
+    let tags = Tag::sorted(&transaction.tags).iter().map(Tag::get_label).collect::<Vec<_>>().join(", ");
+    
+    row![
+        ui_string(app, source, TextSizes::Body, MaterialColors::WeakText),
+        spacer(Orientations::Horizontal, Spacing::Medium),
+        ui_string(app, transaction.date.display(), TextSizes::Body, MaterialColors::WeakText),
+        spacer(Orientations::Horizontal, Spacing::Medium),
+        ui_string(app, transaction.value.to_string(), TextSizes::Body, MaterialColors::StrongText),
+        spacer(Orientations::Horizontal, Spacing::Medium),
+        ui_string(app, transaction.description.clone(), TextSizes::Body, MaterialColors::StrongText),
+        spacer(Orientations::Horizontal, Spacing::Medium),
+        ui_string(app, tags, TextSizes::Body, MaterialColors::WeakText),
+        spacer(Orientations::Horizontal, Spacing::Fill),
+    ]
+    .align_y(Center)
+    .into()
+}
+
+/// Selects whether the imported data is merged into or replaces the current data.
+#[must_use]
+fn import_mode_button<'a>(
+    app: &'a App,
+    mode: ImportModes,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if app.import_mode == mode { MaterialColors::accent(app.theme_selection) } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        ui_string(app, mode.name(), TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::SetImportMode(mode),
+        true,
+    )
+}
+
 /// Confirms a data import.
 #[must_use]
 fn confirm_import_button<'a>(
@@ -0,0 +1,177 @@
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, stack};
+use iced::widget::column;
+use iced::widget::row;
+use crate::container::app::App;
+use crate::container::signal::Signal;
+use crate::pages::confirm_csv_import_page::preview_list;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::transaction::{Tag, Transaction};
+
+/// The page used to confirm merging the `Transaction`s from an OFX or QFX statement into the `Bank`.
+#[must_use]
+pub fn confirm_ofx_import_page<'a>(
+    app: &'a App,
+) -> Stack<'a, Signal> {
+    stack![
+        container(confirm_ofx_import_panel(app)).center(Fill),
+        header(app, Vec::new()),
+    ]
+}
+
+/// The panel that allows a user to confirm or cancel an OFX import.
+#[must_use]
+fn confirm_ofx_import_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    match &app.ofx_import_data {
+        // assuming the app contains ofx import data
+        Some(import_data) => {
+            let new_transactions = import_data.get_new_transactions(&app.bank);
+            let failed_entries = import_data.get_failure_count();
+            let duplicate_entries = import_data.get_entry_count().saturating_sub(new_transactions.len() + failed_entries);
+            let preview_transactions: Vec<&Transaction> = new_transactions.iter().collect();
+
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                PanelSize { width: Widths::LargeCard, height: Heights::Shrink },
+                PaddingSizes::Medium, {
+                    column![
+                        ui_string(app, "Would you like to import a bank statement?", TextSizes::LargeHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Small),
+                        ui_string(app, format!("{} new Transactions found.", new_transactions.len()), TextSizes::SmallHeading, MaterialColors::StrongText),
+                        if duplicate_entries > 0 {
+                            ui_string(app, format!("{duplicate_entries} Transactions were already imported and will be skipped."), TextSizes::Body, MaterialColors::WeakText)
+                        }
+                        else { spacer(Orientations::Vertical, Spacing::None) },
+                        if failed_entries > 0 {
+                            ui_string(app, format!("{failed_entries} entries could not be read. {}", import_data.get_first_failure().unwrap_or_default()), TextSizes::Body, MaterialColors::WeakText)
+                        }
+                        else { spacer(Orientations::Vertical, Spacing::None) },
+
+                        // import tag
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        import_tag_field(app, import_data.get_import_tag()),
+
+                        // preview
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        preview_list(app, &preview_transactions),
+
+                        spacer(Orientations::Vertical, Spacing::Large),
+                        ui_string(app, "These Transactions will be added alongside your current Transactions.", TextSizes::SmallHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Ginormous),
+
+                        row![
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                            confirm_ofx_import_button(app, !new_transactions.is_empty()),
+                            spacer(Orientations::Horizontal, Spacing::Medium),
+                            cancel_ofx_import_button(app),
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                        ]
+                    ]
+                    .align_x(Center)
+                    .into()
+                }
+            )
+        }
+
+        // fallback if it does not
+        None => {
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                PanelSize { width: Widths::SmallCard, height: Heights::Shrink },
+                PaddingSizes::Medium, {
+                    column![
+                        ui_string(app, "Woops! No data has been loaded.", TextSizes::LargeHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        cancel_ofx_import_button(app)
+                    ]
+                    .align_x(Center)
+                    .into()
+                }
+            )
+        }
+    }
+}
+
+/// Edits the `Tag` given to every imported `Transaction`.
+#[must_use]
+fn import_tag_field<'a>(
+    app: &'a App,
+    import_tag: &str,
+) -> Element<'a, Signal> {
+    let is_valid = Tag::is_allowed(import_tag);
+
+    row![
+        ui_string(app, "Import Tag", TextSizes::Body, MaterialColors::StrongText),
+        panel_text_input(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if is_valid { MaterialColors::CardContent } else { MaterialColors::danger() },
+                depth: Depths::Proud,
+            },
+            Widths::SmallField,
+            "Import Tag",
+            import_tag,
+            Signal::UpdateOfxImportTag,
+            None,
+            false,
+        ),
+        spacer(Orientations::Horizontal, Spacing::Fill),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
+/// Confirms an OFX import.
+#[must_use]
+fn confirm_ofx_import_button<'a>(
+    app: &'a App,
+    can_import: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::success(),
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Confirm", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ConfirmOfxImport,
+        can_import,
+    )
+}
+
+/// Cancels an OFX import.
+#[must_use]
+fn cancel_ofx_import_button<'a>(
+    app: &'a App
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::danger(),
+            depth: Depths::Proud
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Cancel", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::CancelOfxImport,
+        true,
+    )
+}
@@ -0,0 +1,177 @@
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, stack};
+use iced::widget::column;
+use iced::widget::row;
+use crate::container::app::App;
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, panel, panel_button, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::rate_import::RateImport;
+
+/// The page used to confirm setting the rates from an exchange rate file on the `Bank`'s `CurrencyExchange`.
+#[must_use]
+pub fn confirm_rate_import_page<'a>(
+    app: &'a App,
+) -> Stack<'a, Signal> {
+    stack![
+        container(confirm_rate_import_panel(app)).center(Fill),
+        header(app, Vec::new()),
+    ]
+}
+
+/// The panel that allows a user to confirm or cancel an exchange rate import.
+#[must_use]
+fn confirm_rate_import_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    match &app.rate_import_data {
+        // assuming the app contains rate import data
+        Some(import_data) => {
+            let date_range = match import_data.get_date_range() {
+                Some((oldest, newest)) => format!("from {} to {}", oldest.display_iso(), newest.display_iso()),
+                None => String::new(),
+            };
+
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                PanelSize { width: Widths::LargeCard, height: Heights::Shrink },
+                PaddingSizes::Medium, {
+                    column![
+                        ui_string(app, "Would you like to import exchange rates?", TextSizes::LargeHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Small),
+                        ui_string(app, format!("{} rates found in {}, {date_range}.", import_data.get_quote_count(), import_data.get_format().name()), TextSizes::SmallHeading, MaterialColors::StrongText),
+                        if import_data.get_skipped_count() > 0 {
+                            ui_string(app, format!("{} rates could not be read and will be skipped.", import_data.get_skipped_count()), TextSizes::Body, MaterialColors::WeakText)
+                        }
+                        else { spacer(Orientations::Vertical, Spacing::None) },
+
+                        // preview
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        pair_list(app, import_data),
+
+                        spacer(Orientations::Vertical, Spacing::Large),
+                        ui_string(app, "Rates already set on the same dates will be replaced.", TextSizes::SmallHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Ginormous),
+
+                        row![
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                            confirm_rate_import_button(app, import_data.get_rate_count() > 0),
+                            spacer(Orientations::Horizontal, Spacing::Medium),
+                            cancel_rate_import_button(app),
+                            spacer(Orientations::Horizontal, Spacing::Fill),
+                        ]
+                    ]
+                    .align_x(Center)
+                    .into()
+                }
+            )
+        }
+
+        // fallback if it does not
+        None => {
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                PanelSize { width: Widths::SmallCard, height: Heights::Shrink },
+                PaddingSizes::Medium, {
+                    column![
+                        ui_string(app, "Woops! No data has been loaded.", TextSizes::LargeHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Vertical, Spacing::Medium),
+                        cancel_rate_import_button(app)
+                    ]
+                    .align_x(Center)
+                    .into()
+                }
+            )
+        }
+    }
+}
+
+/// Lists how many rates were found for each currency pair used by the `Bank`.
+#[must_use]
+fn pair_list<'a>(
+    app: &'a App,
+    import_data: &'a RateImport,
+) -> Element<'a, Signal> {
+    let mut pair_rows = import_data.get_pair_rates().iter().map(|(from_string, to_string, dated_rates)| {
+        row![
+            ui_string(app, format!("{from_string} → {to_string}"), TextSizes::Body, MaterialColors::StrongText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            if dated_rates.is_empty() {
+                ui_string(app, "No rates found", TextSizes::Body, MaterialColors::WeakText)
+            }
+            else {
+                ui_string(app, format!("{} rates", dated_rates.len()), TextSizes::Body, MaterialColors::StrongText)
+            },
+        ]
+        .align_y(Center)
+        .into()
+    }).collect::<Vec<Element<'a, Signal>>>();
+    if pair_rows.is_empty() {
+        pair_rows.push(ui_string(app, "Only one currency is in use, so no exchange rates are needed.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardHollow,
+            depth: Depths::Recessed,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column(pair_rows)
+                .spacing(Spacing::Micro.size())
+                .into()
+        }
+    )
+}
+
+/// Confirms an exchange rate import.
+#[must_use]
+fn confirm_rate_import_button<'a>(
+    app: &'a App,
+    can_import: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::success(),
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Confirm", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ConfirmRateImport,
+        can_import,
+    )
+}
+
+/// Cancels an exchange rate import.
+#[must_use]
+fn cancel_rate_import_button<'a>(
+    app: &'a App
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::danger(),
+            depth: Depths::Proud
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Cancel", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::CancelRateImport,
+        true,
+    )
+}
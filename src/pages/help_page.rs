@@ -59,21 +59,38 @@ fn help_panel<'a>(
 #[must_use]
 fn get_page_info(app: &App) -> String {
     match app.page {
-        Pages::Transactions => "This page lists all your transactions.\n\nThese transactions can be filtered by date, tag, and search term, either requiring a full filter match or a partial match.\nThe ring chart and cash flow display then show how money is spent and earned.".to_string(),
+        Pages::Transactions => "This page lists all your transactions.\n\nThese transactions can be filtered by date, tag, and search term, either requiring a full filter match or a partial match.\nFiltering on a parent tag, such as food, also matches its children, such as food/groceries.\nThe ring chart and cash flow display then show how money is spent and earned, and the ring chart can roll child tags up into their parents.\n\nThe export button writes the filtered transactions to a CSV or JSON file, including their\nvalue in the main currency, their time price, and their splits.\n\nTransactions can be selected with the box next to their edit button, or all at once with the select all button, which selects every transaction matching the current filter.\nThe bulk edit button then makes one change to every selected transaction.\n\nEach transaction shows the balance of its account just after it, once the vault has more than one account.\nTransfers between accounts are tagged transfer and left out of the ring chart, cash flow, trends, and budgets.\n\nAdding, editing, removing, importing, and bulk editing transactions can be undone, as can changes to tags, exchange rates, recurring transactions, budgets, tag rules, and accounts.\nThe history lasts until Ascent is closed or another vault is opened.".to_string(),
         
-        Pages::AddingTransaction => "This page allows you to add a new transaction.".to_string(),
+        Pages::AddingTransaction => "This page allows you to add a new transaction.\n\nPart of a transaction can be split off to other tags, such as the household items on a grocery receipt.\nEach split counts only towards its own tags, and whatever is left over counts towards the transaction's tags.\nSplits must have the same sign as the value and cannot add up to more than it.\n\nWhile typing a tag, existing tags are suggested below the tag field.\nTags used often, used alongside the transaction's other tags, or used on transactions with a similar description are suggested first.\nThe arrow keys select a suggestion and return in the tag field adds it, or a suggestion can be clicked.".to_string(),
         
-        Pages::EditingTransaction => "This page allows you to edit an existing transaction.".to_string(),
+        Pages::EditingTransaction => "This page allows you to edit an existing transaction.\n\nPart of a transaction can be split off to other tags, such as the household items on a grocery receipt.\nEach split counts only towards its own tags, and whatever is left over counts towards the transaction's tags.\nSplits must have the same sign as the value and cannot add up to more than it.\n\nWhile typing a tag, existing tags are suggested below the tag field.\nTags used often, used alongside the transaction's other tags, or used on transactions with a similar description are suggested first.\nThe arrow keys select a suggestion and return in the tag field adds it, or a suggestion can be clicked.".to_string(),
 
         Pages::Trends => "This page displays your spending and earning trends over time.".to_string(),
         
-        Pages::TagRegistry => "This page allows you to select the color for each tag.".to_string(),
+        Pages::Recurring => "This page lists the recurring transactions, such as rent, salary, and subscriptions.\n\nA transaction is made recurring by choosing how often it repeats while adding it.\nWhenever one comes due, it waits here until it is confirmed, which adds it to your transactions with the same account and splits, or skipped.\nDue transactions of the same recurring transaction are handled oldest first.".to_string(),
         
-        Pages::Settings => "This page allows you to configure your application settings.".to_string(),
+        Pages::Budgets => "This page tracks spending against budgets.\n\nA budget limits spending on one or more tags over each week, two weeks, month, quarter, or year.\nA transaction counts towards it if it has any of those tags, and refunds are taken off of the spending.\nOnly the current period is shown, converted to the currency of the budget with the exchange rates from the settings.\nOverspent budgets are shown in the color of their first tag.".to_string(),
+        Pages::TagRules => "This page manages rules that tag transactions automatically.\n\nA rule adds its tags to every transaction that meets all of its conditions: a piece of text in the description, a value below or above an amount, and a currency.\nConditions left empty are ignored, and the value is compared in the transaction's own currency.\nRules run on every transaction that is added or imported, but not on transactions that are already in the ledger.\nTo tag those as well, preview a re-run to see which transactions would gain which tags, and then apply it.\nRemoving a rule keeps the tags it has already added.".to_string(),
         
-        Pages::ConfirmImport => "This page is used to import data from a backup file.\n\nPlease note that importing from a backup file will overwrite any existing data\nincluding transactions, tag coloring, and any other data.".to_string(),
+        Pages::Accounts => "This page lists the places your money is kept, such as a checking account, a savings account, a credit card, or a wallet.\n\nEvery transaction belongs to one account, which can be chosen while adding or editing it once there is more than one.\nImported transactions go into the first account, and recurring transactions go into the account of the transaction they repeat.\nAn account's balance is its opening balance plus all of its transactions, converted to its currency with the exchange rates from the settings.\n\nA transfer moves money from one account to another as a pair of linked transactions, converting the amount if the accounts use different currencies.\nTransfers do not count as earning or spending, and removing either side removes both.\nAn account can only be removed once it has no transactions, and there must always be at least one.".to_string(),
+        
+        Pages::TagRegistry => "This page allows you to select the color for each tag.\n\nTags can be nested with a slash, so food/groceries and food/restaurants are children of food.\nA child tag uses the color of its parent unless it is given its own, and resetting it goes back to the parent's color.\n\nThe pencil button renames a tag on every transaction, recurring transaction, budget, and filter, along with its children.\nRenaming a tag to the name of another tag merges the two, keeping the color of the tag it is merged into.\nThe number of transactions that will change is shown before anything is renamed.".to_string(),
+        
+        Pages::Settings => "This page allows you to configure your application settings.\n\nVaults are separate save files, for example one for a household and one for a business.\nThey are stored in the data directory, which can be changed with the ASCENT_DATA_DIR\nenvironment variable or the --data-dir argument.\n\nA vault can be encrypted with a passphrase, which is then asked for whenever the vault is opened.\nIts backups are encrypted with the same passphrase. A forgotten passphrase cannot be recovered.\n\nEach exchange rate keeps a history of the rates it has been set to. Transactions are converted\nat the rate that applied on their date, so changing a rate today does not change past months.\nOpen the history of a rate to add rates for past dates or remove ones that were set by mistake.\nWhole histories can be imported from a downloaded ECB reference rate file or a date,from,to,rate CSV.\n\nNot every rate has to be set. Rates that are missing are derived from the others, by inverting\nthe opposite rate or chaining rates through other currencies, such as CAD to USD to EUR.\nOnly enough rates to connect every currency are needed.\n\nThe time price is how much you earn per hour, and can be earned in a currency other than the main one.\nIt also keeps a history, so a raise does not change what older spending cost in hours.\nOpen the history of the time price to add wages for past dates.".to_string(),
+        
+        Pages::Backups => "This page lists the backups of the open vault.\n\nA backup is made on launch and after every few saves, and the Create Backup setting makes one right away.\nThe newest backup of each of the last 7 days, 4 weeks, and 12 months is kept, and older ones are removed.\nRestoring a backup opens the confirm import page, where it can replace or be merged into your current data.".to_string(),
+        
+        Pages::ConfirmImport => "This page is used to import data from a backup file.\n\nMerge adds the new transactions alongside your existing ones. Transactions that look\nlike an existing one but differ are listed as conflicts, each of which can keep the local\nversion, use the imported version, or keep both.\n\nReplace will overwrite any existing data including transactions, tag coloring, and any other data.".to_string(),
         
         Pages::ConfirmLegacyImport => "This page is used to import legacy data from a legacy backup file.\n\nPlease note that importing from a legacy backup file will overwrite all existing transactions.".to_string(),
+        
+        Pages::ConfirmCsvImport => "This page is used to import transactions from a bank statement CSV file.\n\nColumns can be chosen by their header name or by their position starting at 1.\nDates are read with the date format (for example %Y-%m-%d or %m/%d/%Y).\nImported transactions are added alongside your existing transactions.".to_string(),
+        
+        Pages::ConfirmOfxImport => "This page is used to import transactions from an OFX or QFX bank statement.\n\nTransactions that were already imported from an earlier statement are skipped.\nImported transactions are added alongside your existing transactions.".to_string(),
+        Pages::ConfirmRateImport => "This page is used to import exchange rates from a file, without connecting to the internet.\n\nThe European Central Bank's reference rates (eurofxref-hist, as CSV or XML) and CSV files\nwith date,from,to,rate columns are supported. Pairs that are not in the file are derived\nby crossing through the euro. Rates already set on the same dates are replaced.".to_string(),
+        Pages::BulkEdit => "This page makes one change to every selected transaction at once.\n\nChoose to add or remove a tag, set the currency, shift the date by a number of days, ignore, or delete the transactions.\nThe preview lists the transactions that will change, and transactions that would stay the same are skipped.\nA tag is never removed from a transaction when it is the only tag left, and setting the currency keeps the amount as it is.\nThe whole edit is saved at once, and it can be undone in one step from the transactions page.".to_string(),
+        
+        Pages::Unlock => "This vault is encrypted, so its passphrase is needed before it can be opened.".to_string(),
     }
 }
 
@@ -88,6 +105,8 @@ fn get_page_keybinds<'a>(app: &'a App) -> Vec<Element<'a, Signal>> {
             Keybind::new("Recede Filter Year", KeybindKeys::StandardKey(','), vec![KeybindModifiers::Command]).widget(app),
             Keybind::new("Advance Filter Month", KeybindKeys::StandardKey('\''), vec![KeybindModifiers::Command]).widget(app),
             Keybind::new("Recede Filter Month", KeybindKeys::StandardKey(';'), vec![KeybindModifiers::Command]).widget(app),
+            Keybind::new("Undo", KeybindKeys::StandardKey('z'), vec![KeybindModifiers::Command]).widget(app),
+            Keybind::new("Redo", KeybindKeys::StandardKey('z'), vec![KeybindModifiers::Command, KeybindModifiers::Shift]).widget(app),
         ],
         
         Pages::AddingTransaction => vec![
@@ -98,6 +117,9 @@ fn get_page_keybinds<'a>(app: &'a App) -> Vec<Element<'a, Signal>> {
             Keybind::new("Recede Month", KeybindKeys::StandardKey(';'), vec![KeybindModifiers::Command]).widget(app),
             Keybind::new("Advance Day", KeybindKeys::StandardKey(']'), vec![KeybindModifiers::Command]).widget(app),
             Keybind::new("Recede Day", KeybindKeys::StandardKey('['), vec![KeybindModifiers::Command]).widget(app),
+            Keybind::new("Next Tag Suggestion", KeybindKeys::ArrowKey(ArrowKeys::Down), vec![]).widget(app),
+            Keybind::new("Previous Tag Suggestion", KeybindKeys::ArrowKey(ArrowKeys::Up), vec![]).widget(app),
+            Keybind::new("Add Tag Suggestion", KeybindKeys::Return, vec![]).widget(app),
         ],
         
         Pages::EditingTransaction => vec![
@@ -108,17 +130,45 @@ fn get_page_keybinds<'a>(app: &'a App) -> Vec<Element<'a, Signal>> {
             Keybind::new("Recede Month", KeybindKeys::StandardKey(';'), vec![KeybindModifiers::Command]).widget(app),
             Keybind::new("Advance Day", KeybindKeys::StandardKey(']'), vec![KeybindModifiers::Command]).widget(app),
             Keybind::new("Recede Day", KeybindKeys::StandardKey('['), vec![KeybindModifiers::Command]).widget(app),
+            Keybind::new("Next Tag Suggestion", KeybindKeys::ArrowKey(ArrowKeys::Down), vec![]).widget(app),
+            Keybind::new("Previous Tag Suggestion", KeybindKeys::ArrowKey(ArrowKeys::Up), vec![]).widget(app),
+            Keybind::new("Add Tag Suggestion", KeybindKeys::Return, vec![]).widget(app),
         ],
 
         Pages::Trends => vec![],
         
-        Pages::TagRegistry => vec![],
+        Pages::Recurring => vec![],
+        
+        Pages::Budgets => vec![],
+        
+        Pages::TagRules => vec![],
         
-        Pages::Settings => vec![],
+        Pages::Accounts => vec![],
+        
+        Pages::TagRegistry => vec![
+            Keybind::new("Undo", KeybindKeys::StandardKey('z'), vec![KeybindModifiers::Command]).widget(app),
+            Keybind::new("Redo", KeybindKeys::StandardKey('z'), vec![KeybindModifiers::Command, KeybindModifiers::Shift]).widget(app),
+        ],
+        
+        Pages::Settings => vec![
+            Keybind::new("Undo", KeybindKeys::StandardKey('z'), vec![KeybindModifiers::Command]).widget(app),
+            Keybind::new("Redo", KeybindKeys::StandardKey('z'), vec![KeybindModifiers::Command, KeybindModifiers::Shift]).widget(app),
+        ],
+        
+        Pages::Backups => vec![],
         
         Pages::ConfirmImport => vec![],
         
         Pages::ConfirmLegacyImport => vec![],
+        
+        Pages::ConfirmCsvImport => vec![],
+        
+        Pages::ConfirmOfxImport => vec![],
+        Pages::ConfirmRateImport => vec![],
+        
+        Pages::BulkEdit => vec![],
+        
+        Pages::Unlock => vec![],
     }
 }
 
@@ -0,0 +1,199 @@
+use iced_font_awesome::fa_icon_solid as icon;
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, scrollable, stack};
+use iced::widget::column;
+use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::container::app::{App, Pages};
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, panel, panel_button, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::recurring::{DueRecurrence, RecurringTemplate};
+use crate::vault::transaction::Date;
+use schrod::Schrod::Pass;
+
+/// The page used to confirm due recurring `Transaction`s and manage `RecurringTemplate`s.
+#[must_use]
+pub fn recurring_page<'a>(
+    app: &'a App
+) -> Stack<'a, Signal> {
+    stack![
+        row![
+            navigation_panel(app, Pages::page_pointers(app)),
+            container(recurring_list(app)).center_x(Fill),
+        ],
+        header(app, Vec::new()),
+    ]
+}
+
+/// The lists of due recurring `Transaction`s and `RecurringTemplate`s.
+#[must_use]
+fn recurring_list<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let due_recurrences = match Date::today() {
+        Pass(today) => app.bank.get_due_recurrences(today),
+        _ => Vec::new(),
+    };
+
+    // due transactions
+    let mut due_slips = due_recurrences.iter().filter_map(|due_recurrence| due_recurrence_slip(app, due_recurrence)).collect::<Vec<_>>();
+    if due_slips.is_empty() {
+        due_slips.push(ui_string(app, "Nothing is due right now.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    // templates
+    let mut template_slips = app.bank.recurring_templates.iter().map(|template| template_slip(app, template)).collect::<Vec<_>>();
+    if template_slips.is_empty() {
+        template_slips.push(ui_string(app, "There are no recurring transactions yet. Choose how often a transaction repeats while adding it.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    scrollable(
+        column![
+            spacer(Orientations::Vertical, Spacing::HeaderSpace),
+            row![
+                ui_string(app, "Due", TextSizes::LargeHeading, MaterialColors::StrongText),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                confirm_all_button(app, !due_recurrences.is_empty()),
+            ]
+            .align_y(Center),
+            column(due_slips).spacing(Spacing::Small.size()),
+            spacer(Orientations::Vertical, Spacing::Medium),
+            ui_string(app, "Recurring Transactions", TextSizes::LargeHeading, MaterialColors::StrongText),
+            column(template_slips).spacing(Spacing::Small.size()),
+        ]
+        .spacing(Spacing::Medium.size())
+    )
+    .direction(Direction::Vertical(Scrollbar::hidden()))
+    .width(Widths::LargeCard.size())
+    .height(Fill)
+    .into()
+}
+
+/// Displays a due recurring `Transaction` and allows it to be confirmed or skipped.
+/// Only the earliest due `Transaction` of each `RecurringTemplate` can be handled, so that they are handled in order.
+#[must_use]
+fn due_recurrence_slip<'a>(
+    app: &'a App,
+    due_recurrence: &DueRecurrence,
+) -> Option<Element<'a, Signal>> {
+    let template = match app.bank.get_recurring_template(due_recurrence.template_uid) {
+        Pass(template) => template,
+        _ => return None,
+    };
+    let uid = template.get_uid();
+
+    Some(panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            row![
+                ui_string(app, due_recurrence.date.display(), TextSizes::SmallHeading, MaterialColors::StrongText),
+                spacer(Orientations::Horizontal, Spacing::Medium),
+                ui_string(app, template.description.clone(), TextSizes::Body, MaterialColors::MediumText),
+                spacer(Orientations::Horizontal, Spacing::Small),
+                ui_string(app, format!("{} {}", template.get_value_string(), template.get_currency_string()), TextSizes::Body, MaterialColors::WeakText),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::success(),
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    icon("check"),
+                    Signal::ConfirmRecurrence(uid),
+                    due_recurrence.is_next,
+                ),
+                spacer(Orientations::Horizontal, Spacing::Micro),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::CardContent,
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    icon("forward"),
+                    Signal::SkipRecurrence(uid),
+                    due_recurrence.is_next,
+                ),
+            ]
+            .align_y(Center)
+            .into()
+        }
+    ))
+}
+
+/// Displays a `RecurringTemplate` and allows it to be removed.
+#[must_use]
+fn template_slip<'a>(
+    app: &'a App,
+    template: &'a RecurringTemplate,
+) -> Element<'a, Signal> {
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            row![
+                column![
+                    row![
+                        ui_string(app, template.description.clone(), TextSizes::SmallHeading, MaterialColors::StrongText),
+                        spacer(Orientations::Horizontal, Spacing::Small),
+                        ui_string(app, format!("{} {}", template.get_value_string(), template.get_currency_string()), TextSizes::Body, MaterialColors::WeakText),
+                    ]
+                    .align_y(Center),
+                    ui_string(app, format!("{}, next on {}", template.rule.display(), template.get_next_date().display()), TextSizes::Body, MaterialColors::WeakText),
+                ]
+                .spacing(Spacing::Micro.size()),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::danger(),
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    icon("trash"),
+                    Signal::RemoveRecurringTemplate(template.get_uid()),
+                    true,
+                ),
+            ]
+            .align_y(Center)
+            .into()
+        }
+    )
+}
+
+/// Adds every due recurring `Transaction`.
+#[must_use]
+fn confirm_all_button<'a>(
+    app: &'a App,
+    enabled: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::success(),
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        ui_string(app, "Confirm All", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ConfirmAllRecurrences,
+        enabled,
+    )
+}
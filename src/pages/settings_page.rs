@@ -7,11 +7,14 @@ use iced::widget::row;
 use iced::widget::scrollable::{Direction, Scrollbar};
 use crate::container::app::{App, Pages};
 use crate::container::signal::Signal;
-use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, ThemeProvider, Widths, header, navigation_panel, panel, panel_button, panel_text_input, spacer, ui_string};
 use materialui::materials::{MaterialThemes, Depths, MaterialColors, MaterialStyle, Materials};
 use crate::vault::bank::{CurrencyExchange, ExchangeRate, ExchangeRateStatus};
 use crate::vault::parse::FlowTypes;
+use crate::vault::save_engine::is_vault_name_valid;
+use crate::vault::csv_import::parse_date;
 use crate::vault::transaction::Transaction;
+use crate::pages::unlock_page::passphrase_input;
 
 /// The page used to display settings for the `App`.
 #[must_use]
@@ -40,12 +43,27 @@ fn settings_list<'a>(
             setting_heading(app, "Appearance".to_string()),
             theme_setting(app),
             
+            // vaults
+            spacer(Orientations::Vertical, Spacing::Large),
+            setting_heading(app, "Vaults".to_string()),
+            vault_switcher(app),
+            new_vault_input(app),
+            
+            // encryption
+            spacer(Orientations::Vertical, Spacing::Large),
+            setting_heading(app, "Encryption".to_string()),
+            encryption_status(app),
+            passphrase_inputs(app),
+            passphrase_buttons(app),
+            
             // save data
             spacer(Orientations::Vertical, Spacing::Large),
             setting_heading(app, "Save Data".to_string()),
             backup_button(app),
             save_data_import_button(app),
             legacy_save_data_import_button(app),
+            csv_import_button(app),
+            ofx_import_button(app),
 
             // currency exchange
             spacer(Orientations::Vertical, Spacing::Large),
@@ -54,6 +72,7 @@ fn settings_list<'a>(
             time_price_overlay(app),
             flow_type_setting(app),
             exchange_rate_panel_overlay(app),
+            rate_import_button(app),
         ]
         .spacing(Spacing::Medium.size())
     )
@@ -156,6 +175,152 @@ fn theme_setting<'a>(
     .into()
 }
 
+/// Lists the vaults and opens the one that is selected.
+#[must_use]
+fn vault_switcher<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let vault_buttons = app.vault_names.iter().map(|vault_name| {
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if *vault_name == app.vault_name { MaterialColors::accent(app.theme_selection) } else { MaterialColors::Card },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            ui_string(app, vault_name.clone(), TextSizes::Interactable, MaterialColors::StrongText),
+            Signal::OpenVault(vault_name.clone()),
+            true,
+        )
+    }).collect::<Vec<_>>();
+    
+    row![
+        ui_string(app, "Open Vault", TextSizes::SmallHeading, MaterialColors::StrongText),
+        row(vault_buttons).spacing(Spacing::Small.size()).wrap(),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
+/// Allows a new vault to be created.
+#[must_use]
+fn new_vault_input<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let name = app.new_vault_name_string.trim();
+    let error = !name.is_empty() && (!is_vault_name_valid(name) || app.vault_names.iter().any(|n| n.eq_ignore_ascii_case(name)));
+    
+    row![
+        ui_string(app, "New Vault", TextSizes::SmallHeading, MaterialColors::StrongText),
+        panel_text_input(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if error { MaterialColors::danger() } else { MaterialColors::Card },
+                depth: Depths::Proud,
+            },
+            Widths::SmallField,
+            "Vault Name",
+            &app.new_vault_name_string,
+            Signal::UpdateNewVaultNameString,
+            if error || name.is_empty() { None } else { Some(Signal::CreateVault) },
+            true,
+        ),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::Card,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            icon("plus"),
+            Signal::CreateVault,
+            !error && !name.is_empty(),
+        ),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
+/// Shows whether the open vault is encrypted.
+#[must_use]
+fn encryption_status<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let status = if app.is_encrypted() {
+        format!("{} and its backups are encrypted with a passphrase.", app.vault_name)
+    }
+    else {
+        format!("{} is not encrypted. Anyone with access to its files can read them.", app.vault_name)
+    };
+    
+    ui_string(app, status, TextSizes::Body, MaterialColors::MediumText)
+}
+
+/// Allows the current and new passphrases to be entered.
+#[must_use]
+fn passphrase_inputs<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let current_error = app.is_encrypted() && !app.current_passphrase_string.is_empty() && !app.can_change_passphrase();
+    let confirm_error = !app.confirm_passphrase_string.is_empty() && app.confirm_passphrase_string != app.new_passphrase_string;
+    
+    let mut inputs = row![].spacing(Spacing::Small.size()).align_y(Center);
+    if app.is_encrypted() {
+        inputs = inputs.push(passphrase_input(app, "Current Passphrase", &app.current_passphrase_string, Signal::UpdateCurrentPassphraseString, None, current_error));
+    }
+    inputs
+        .push(passphrase_input(app, "New Passphrase", &app.new_passphrase_string, Signal::UpdateNewPassphraseString, None, false))
+        .push(passphrase_input(app, "Repeat New Passphrase", &app.confirm_passphrase_string, Signal::UpdateConfirmPassphraseString, None, confirm_error))
+        .into()
+}
+
+/// Sets, changes, or removes the passphrase.
+#[must_use]
+fn passphrase_buttons<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let can_set = app.can_change_passphrase() && !app.new_passphrase_string.is_empty() && app.new_passphrase_string == app.confirm_passphrase_string;
+    
+    let mut buttons = row![
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::Card,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Wide,
+            ui_string(app, if app.is_encrypted() { "Change Passphrase" } else { "Encrypt Vault" }, TextSizes::Interactable, MaterialColors::StrongText),
+            Signal::SetPassphrase,
+            can_set,
+        ),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center);
+    
+    if app.is_encrypted() {
+        buttons = buttons.push(panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::danger(),
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Wide,
+            ui_string(app, "Remove Encryption", TextSizes::Interactable, MaterialColors::StrongText),
+            Signal::RemovePassphrase,
+            app.can_change_passphrase(),
+        ));
+    }
+    
+    buttons.into()
+}
+
 /// The save data backup button.
 #[must_use]
 fn backup_button<'a>(
@@ -231,6 +396,81 @@ fn legacy_save_data_import_button<'a>(
     .into()
 }
 
+/// The CSV bank statement import button.
+#[must_use]
+fn csv_import_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    row![
+        ui_string(app, "Import Bank Statement (CSV)", TextSizes::SmallHeading, MaterialColors::StrongText),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::Card,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            icon("file-csv"),
+            Signal::OpenCsvImportFilePicker,
+            true,
+        ),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
+/// The OFX/QFX bank statement import button.
+#[must_use]
+fn ofx_import_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    row![
+        ui_string(app, "Import Bank Statement (OFX/QFX)", TextSizes::SmallHeading, MaterialColors::StrongText),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::Card,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            icon("building-columns"),
+            Signal::OpenOfxImportFilePicker,
+            true,
+        ),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
+/// The exchange rate file import button.
+#[must_use]
+fn rate_import_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    row![
+        ui_string(app, "Import Exchange Rates (ECB/CSV)", TextSizes::SmallHeading, MaterialColors::StrongText),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::Card,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            icon("money-bill-transfer"),
+            Signal::OpenRateImportFilePicker,
+            true,
+        ),
+    ]
+    .spacing(Spacing::Small.size())
+    .align_y(Center)
+    .into()
+}
+
 /// Position the main `Currency` panel and input.
 #[must_use]
 fn main_currency_overlay<'a>(
@@ -295,18 +535,148 @@ fn main_currency_input<'a>(
     )
 }
 
-/// Position the main `Currency` panel and input.
+/// Position the time price panel and input, along with the history of time prices when it is open.
 #[must_use]
 fn time_price_overlay<'a>(
     app: &'a App,
 ) -> Element<'a, Signal> {
-    row![
+    let overlay = row![
         time_price_panel(app),
         time_price_input(app),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if app.is_time_price_history_open { MaterialColors::accent(app.material_theme()) } else { MaterialColors::Card },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            icon("clock-rotate-left"),
+            Signal::ToggleTimePriceHistory,
+            true,
+        ),
     ]
     .spacing(Spacing::Medium.size())
-    .align_y(Center)
-    .into()
+    .align_y(Center);
+
+    if app.is_time_price_history_open {
+        column![overlay, time_price_history(app)].spacing(Spacing::Small.size()).into()
+    }
+    else { overlay.into() }
+}
+
+/// Lists the past time prices, newest first, and allows time prices to be added for past dates.
+#[must_use]
+fn time_price_history<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let currency_exchange = &app.bank.currency_exchange;
+    let mut history_slips: Vec<Element<'a, Signal>> = currency_exchange.get_time_prices().iter().rev().map(|time_price| {
+        let currency = currency_exchange.get_currency_of(Some(time_price));
+        row![
+            ui_string(app, time_price.date.display_iso(), TextSizes::Interactable, MaterialColors::MediumText),
+            spacer(Orientations::Horizontal, Spacing::Large),
+            ui_string(app, format!("{}{} {} per hour", currency.symbol, time_price.price, currency), TextSizes::Interactable, MaterialColors::StrongText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            panel_button(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::danger(),
+                    depth: Depths::Proud,
+                },
+                ButtonShapes::Standard,
+                icon("trash"),
+                Signal::RemoveHistoricalTimePrice(time_price.date),
+                true,
+            ),
+        ]
+        .align_y(Center)
+        .spacing(0)
+        .into()
+    }).collect();
+    if history_slips.is_empty() {
+        history_slips.push(ui_string(app, "No time prices have been set yet, so every hour is worth 1 of the main currency.".to_string(), TextSizes::Interactable, MaterialColors::MediumText));
+    }
+
+    let date_error = !app.historical_time_price_date_string.trim().is_empty() && parse_date(&app.historical_time_price_date_string, "%Y-%m-%d").is_fail();
+    let price_error = !app.historical_time_price_string.trim().is_empty() && !CurrencyExchange::is_time_price_string_valid(app.historical_time_price_string.trim());
+    let currency_error = !app.historical_time_price_currency_string.trim().is_empty() && !Transaction::is_currency_string_valid(app.historical_time_price_currency_string.trim());
+    let can_add = !app.historical_time_price_date_string.trim().is_empty() && !app.historical_time_price_string.trim().is_empty() && !date_error && !price_error && !currency_error;
+    history_slips.push(
+        row![
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: if date_error { MaterialColors::danger() } else { MaterialColors::CardHollowContent },
+                    depth: Depths::Proud,
+                },
+                Widths::SmallField,
+                "Date (YYYY-MM-DD)",
+                &app.historical_time_price_date_string,
+                Signal::UpdateHistoricalTimePriceDateString,
+                None,
+                true,
+            ),
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: if price_error { MaterialColors::danger() } else { MaterialColors::CardHollowContent },
+                    depth: Depths::Proud,
+                },
+                Widths::MicroField,
+                "Per Hour",
+                &app.historical_time_price_string,
+                Signal::UpdateHistoricalTimePriceString,
+                if can_add { Some(Signal::AddHistoricalTimePrice) } else { None },
+                true,
+            ),
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: if currency_error { MaterialColors::danger() } else { MaterialColors::CardHollowContent },
+                    depth: Depths::Proud,
+                },
+                Widths::MicroField,
+                currency_exchange.get_main_currency().iso_alpha_code,
+                &app.historical_time_price_currency_string,
+                Signal::UpdateHistoricalTimePriceCurrencyString,
+                if can_add { Some(Signal::AddHistoricalTimePrice) } else { None },
+                true,
+            ),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            panel_button(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                ButtonShapes::Standard,
+                row![icon("plus"), ui_string(app, "Add".to_string(), TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(Spacing::Micro.size()),
+                Signal::AddHistoricalTimePrice,
+                can_add,
+            ),
+        ]
+        .align_y(Center)
+        .spacing(Spacing::Small.size())
+        .into()
+    );
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Acrylic,
+            color: MaterialColors::CardHollow,
+            depth: Depths::Recessed,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Medium,
+        column(history_slips).spacing(Spacing::Small.size()).into(),
+    )
 }
 
 /// Displays the current time price.
@@ -326,8 +696,8 @@ fn time_price_panel<'a>(
             },
             PanelSize { width: Widths::Shrink, height: Heights::Shrink },
             PaddingSizes::Small, {
-                let main_currency = app.bank.currency_exchange.get_main_currency();
-                ui_string(app, format!("{}{} {}", main_currency.symbol, app.bank.currency_exchange.get_time_price(), main_currency), TextSizes::Interactable, MaterialColors::StrongText)
+                let time_price_currency = app.bank.currency_exchange.get_time_price_currency();
+                ui_string(app, format!("{}{} {}", time_price_currency.symbol, app.bank.currency_exchange.get_time_price(), time_price_currency), TextSizes::Interactable, MaterialColors::StrongText)
             }
         )
     ]
@@ -433,6 +803,11 @@ fn exchange_rate_panel<'a>(
         PaddingSizes::Small, {
             column![
                 ui_string(app, "Exchange Rates", TextSizes::Interactable, MaterialColors::StrongText),
+                ui_string(app, match app.bank.currency_exchange.count_missing_rates() {
+                    0 => "Every currency is connected. Rates that are not set are derived from the others.".to_string(),
+                    1 => "1 more rate is needed to connect every currency.".to_string(),
+                    missing_rates => format!("{missing_rates} more rates are needed to connect every currency."),
+                }, TextSizes::Body, MaterialColors::WeakText),
                 
                 spacer(Orientations::Vertical, Spacing::Medium),
                 panel(
@@ -477,18 +852,159 @@ fn exchange_rate_slip<'a>(
     app: &'a App,
     rate: &'a ExchangeRate,
 ) -> Element<'a, Signal> {
-    row![
+    let is_history_open = app.rate_history_pair.as_ref().is_some_and(|(from, to)| from == rate.get_from() && to == rate.get_to());
+    let slip = row![
         exchange_rate_status_panel(app, rate),
         spacer(Orientations::Horizontal, Spacing::Large),
-        ui_string(app, format!("1 {} → {} {}", rate.get_from(), rate.get_rate(), rate.get_to()), TextSizes::Interactable, MaterialColors::StrongText),
+        exchange_rate_label(app, rate),
         spacer(Orientations::Horizontal, Spacing::Fill),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if is_history_open { MaterialColors::accent(app.material_theme()) } else { MaterialColors::Card },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            icon("clock-rotate-left"),
+            Signal::ToggleRateHistory(rate.get_from().to_string(), rate.get_to().to_string()),
+            true,
+        ),
+        spacer(Orientations::Horizontal, Spacing::Small),
         new_rate_field(app, rate),
     ]
     .align_y(Center)
+    .spacing(0);
+
+    if is_history_open {
+        column![slip, rate_history(app, rate)].spacing(Spacing::Small.size()).into()
+    }
+    else { slip.into() }
+}
+
+/// Displays the current rate of an `ExchangeRate`.
+/// Derived rates also show the `Currency`s they were derived through.
+#[must_use]
+fn exchange_rate_label<'a>(
+    app: &'a App,
+    rate: &'a ExchangeRate,
+) -> Element<'a, Signal> {
+    let currency_exchange = &app.bank.currency_exchange;
+    if rate.get_status() != ExchangeRateStatus::Derived {
+        return ui_string(app, format!("1 {} → {} {}", rate.get_from(), rate.get_rate(), rate.get_to()), TextSizes::Interactable, MaterialColors::StrongText)
+    }
+
+    let derived_rate = currency_exchange.find_rate(rate.get_from(), rate.get_to()).unwrap_or_default().round_dp(6).normalize();
+    let path = currency_exchange.find_path(rate.get_from(), rate.get_to()).unwrap_or_default();
+    let intermediate_currencies = path.get(1..path.len().saturating_sub(1)).unwrap_or_default().join(", ");
+    row![
+        ui_string(app, format!("1 {} → {derived_rate} {}", rate.get_from(), rate.get_to()), TextSizes::Interactable, MaterialColors::MediumText),
+        spacer(Orientations::Horizontal, Spacing::Small),
+        ui_string(app, if intermediate_currencies.is_empty() { "(inverse)".to_string() } else { format!("(via {intermediate_currencies})") }, TextSizes::Body, MaterialColors::WeakText),
+    ]
+    .align_y(Center)
     .spacing(0)
     .into()
 }
 
+/// Lists the past rates of an `ExchangeRate`, newest first, and allows rates to be added for past dates.
+#[must_use]
+fn rate_history<'a>(
+    app: &'a App,
+    rate: &'a ExchangeRate,
+) -> Element<'a, Signal> {
+    let mut history_slips: Vec<Element<'a, Signal>> = rate.get_history().iter().rev().map(|dated_rate| {
+        row![
+            ui_string(app, dated_rate.date.display_iso(), TextSizes::Interactable, MaterialColors::MediumText),
+            spacer(Orientations::Horizontal, Spacing::Large),
+            ui_string(app, format!("1 {} → {} {}", rate.get_from(), dated_rate.rate, rate.get_to()), TextSizes::Interactable, MaterialColors::StrongText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            panel_button(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::danger(),
+                    depth: Depths::Proud,
+                },
+                ButtonShapes::Standard,
+                icon("trash"),
+                Signal::RemoveHistoricalRate(rate.get_from().to_string(), rate.get_to().to_string(), dated_rate.date),
+                true,
+            ),
+        ]
+        .align_y(Center)
+        .spacing(0)
+        .into()
+    }).collect();
+    if history_slips.is_empty() {
+        history_slips.push(ui_string(app, "No rates have been set yet.".to_string(), TextSizes::Interactable, MaterialColors::MediumText));
+    }
+
+    let date_error = !app.historical_rate_date_string.trim().is_empty() && parse_date(&app.historical_rate_date_string, "%Y-%m-%d").is_fail();
+    let rate_error = !app.historical_rate_string.trim().is_empty() && !ExchangeRate::is_rate_string_valid(&app.historical_rate_string);
+    let can_add = !app.historical_rate_date_string.trim().is_empty() && !app.historical_rate_string.trim().is_empty() && !date_error && !rate_error;
+    history_slips.push(
+        row![
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: if date_error { MaterialColors::danger() } else { MaterialColors::CardHollowContent },
+                    depth: Depths::Proud,
+                },
+                Widths::SmallField,
+                "Date (YYYY-MM-DD)",
+                &app.historical_rate_date_string,
+                Signal::UpdateHistoricalRateDateString,
+                None,
+                true,
+            ),
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: if rate_error { MaterialColors::danger() } else { MaterialColors::CardHollowContent },
+                    depth: Depths::Proud,
+                },
+                Widths::MicroField,
+                "Rate",
+                &app.historical_rate_string,
+                Signal::UpdateHistoricalRateString,
+                if can_add { Some(Signal::AddHistoricalRate) } else { None },
+                true,
+            ),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            panel_button(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::Card,
+                    depth: Depths::Proud,
+                },
+                ButtonShapes::Standard,
+                row![icon("plus"), ui_string(app, "Add".to_string(), TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(Spacing::Micro.size()),
+                Signal::AddHistoricalRate,
+                can_add,
+            ),
+        ]
+        .align_y(Center)
+        .spacing(Spacing::Small.size())
+        .into()
+    );
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Acrylic,
+            color: MaterialColors::CardHollow,
+            depth: Depths::Recessed,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Medium,
+        column(history_slips).spacing(Spacing::Small.size()).into(),
+    )
+}
+
 /// Displays the status of an `ExchangeRate`.
 #[must_use]
 fn exchange_rate_status_panel<'a>(
@@ -522,6 +1038,19 @@ fn exchange_rate_status_panel<'a>(
                 row![icon("triangle-exclamation"), ui_string(app, format!("{} days old", rate.get_age()), TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(0).into(),
             )
         }
+        ExchangeRateStatus::Derived => {
+            panel(
+                app,
+                MaterialStyle {
+                    material: Materials::Acrylic,
+                    color: MaterialColors::accent(app.material_theme()),
+                    depth: Depths::Flat,
+                },
+                PanelSize { width: Widths::Shrink, height: Heights::Shrink },
+                PaddingSizes::Small,
+                row![icon("link"), ui_string(app, "Derived", TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(0).into(),
+            )
+        }
         ExchangeRateStatus::Valid => {
             panel(
                 app,
@@ -8,9 +8,10 @@ use crate::container::app::{App, Pages};
 use iced_font_awesome::fa_icon_solid as icon;
 use crate::container::signal::Signal;
 use crate::pages::transactions_page::tag_panel;
-use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, panel, panel_button, spacer, ui_string};
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, panel, panel_button, panel_text_input, spacer, ui_string};
 use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
 use crate::vault::transaction::Tag;
+use schrod::Schrod::Pass;
 
 /// The page used for managing the persistent coloring of `Tag`s.
 #[must_use]
@@ -94,16 +95,24 @@ fn tag_registration_slip<'a>(
     app: &'a App,
     state: &'a TagRegistrationSlipState,
 ) -> Element<'a, Signal> {
+    // child tags are indented under their parents
+    #[allow(clippy::cast_precision_loss)] // hierarchies are never deep enough to lose precision
+    let indent = Spacing::Medium.size() * state.get_tag().get_depth() as f32;
     
     row![
+        spacer(Orientations::Horizontal, Spacing::Other(indent)),
         tag_panel(app, state.get_tag()),
         
         spacer(Orientations::Horizontal, Spacing::None),
         reset_registration_button(app, state),
+        rename_tag_button(app, state),
         
         spacer(Orientations::Horizontal, Spacing::Medium),
         {
-            if state.is_expanded {
+            if app.renaming_tag.as_ref() == Some(state.get_tag()) {
+                rename_tag_panel(app, state.get_tag())
+            }
+            else if state.is_expanded {
                 panel(
                     app,
                     MaterialStyle {
@@ -168,7 +177,7 @@ fn tag_registration_slip<'a>(
     .into()
 }
 
-/// Resets the color of a `Tag` in the `TagRegistry`.
+/// Resets the color of a `Tag` in the `TagRegistry`, so that it inherits the color of its parent if it has one.
 #[must_use]
 fn reset_registration_button<'a>(
     app: &'a App,
@@ -189,6 +198,95 @@ fn reset_registration_button<'a>(
 }
 
 
+/// Starts renaming a `Tag`.
+#[must_use]
+fn rename_tag_button<'a>(
+    app: &'a App,
+    state: &'a TagRegistrationSlipState,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardHollowContent,
+            depth: Depths::Proud
+        },
+        ButtonShapes::Minimal,
+        icon("pencil"),
+        Signal::StartRenamingTag(state.get_tag().clone()),
+        true
+    )
+}
+
+/// Renames a `Tag` on every `Transaction`, or merges it into another `Tag` if the new label is already in use.
+/// Shows how many `Transaction`s will be changed before anything is done.
+#[must_use]
+fn rename_tag_panel<'a>(
+    app: &'a App,
+    tag: &Tag,
+) -> Element<'a, Signal> {
+    let new_tag_option = match Tag::new(&app.rename_tag_string) {
+        Pass(new_tag) if !tag.contains(&new_tag) => Some(new_tag),
+        _ => None,
+    };
+    let affected_count = app.bank.count_transactions_with_tag(tag);
+    let affected = if affected_count == 1 { "1 transaction".to_string() } else { format!("{affected_count} transactions") };
+    let preview = match &new_tag_option {
+        Some(new_tag) if app.bank.get_tags().contains(new_tag) => format!("Merges into {}, changing {affected}", new_tag.get_label()),
+        Some(_) => format!("Renames {affected}"),
+        None => "Enter a new name".to_string(),
+    };
+    let is_valid = new_tag_option.is_some();
+    
+    row![
+        panel_text_input(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if is_valid { MaterialColors::CardContent } else { MaterialColors::danger() },
+                depth: Depths::Proud,
+            },
+            Widths::SmallField,
+            "New Name",
+            &app.rename_tag_string,
+            Signal::UpdateRenameTagString,
+            Some(Signal::RenameTag),
+            is_valid,
+        ),
+        spacer(Orientations::Horizontal, Spacing::Small),
+        ui_string(app, preview, TextSizes::Body, MaterialColors::WeakText),
+        spacer(Orientations::Horizontal, Spacing::Fill),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::success(),
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Minimal,
+            icon("check"),
+            Signal::RenameTag,
+            is_valid,
+        ),
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: MaterialColors::CardHollowContent,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Minimal,
+            icon("xmark"),
+            Signal::CancelRenamingTag,
+            true,
+        ),
+    ]
+    .spacing(Spacing::None.size())
+    .align_y(Center)
+    .into()
+}
+
+
 
 /// Manages the states of every `TagRegistrationSlip` in the `App`.
 pub struct TagRegistrationSlipStateManager {
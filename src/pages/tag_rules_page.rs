@@ -0,0 +1,291 @@
+use iced_font_awesome::fa_icon_solid as icon;
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, scrollable, stack};
+use iced::widget::column;
+use iced::widget::row;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::container::app::{App, Pages};
+use crate::container::signal::Signal;
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, panel, panel_button, panel_text_input, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::tag_rule::{TagRule, TagRuleChange};
+use crate::vault::transaction::Tag;
+
+/// The page used to manage `TagRule`s and re-run them over the ledger.
+#[must_use]
+pub fn tag_rules_page<'a>(
+    app: &'a App
+) -> Stack<'a, Signal> {
+    stack![
+        row![
+            navigation_panel(app, Pages::page_pointers(app)),
+            container(tag_rules_list(app)).center_x(Fill),
+        ],
+        header(app, Vec::new()),
+    ]
+}
+
+/// The list of `TagRule`s, followed by the re-run preview and the panel for adding a new one.
+#[must_use]
+fn tag_rules_list<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let mut tag_rule_slips = app.bank.tag_rules.iter().map(|tag_rule| tag_rule_slip(app, tag_rule)).collect::<Vec<_>>();
+    if tag_rule_slips.is_empty() {
+        tag_rule_slips.push(ui_string(app, "There are no tag rules yet. Add one below to tag new transactions automatically.", TextSizes::Body, MaterialColors::WeakText));
+    }
+
+    scrollable(
+        column![
+            spacer(Orientations::Vertical, Spacing::HeaderSpace),
+            row![
+                ui_string(app, "Tag Rules", TextSizes::LargeHeading, MaterialColors::StrongText),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::CardContent,
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    ui_string(app, "Preview Re-run", TextSizes::Interactable, MaterialColors::StrongText),
+                    Signal::PreviewTagRuleRerun,
+                    !app.bank.tag_rules.is_empty(),
+                ),
+            ]
+            .align_y(Center),
+            column(tag_rule_slips).spacing(Spacing::Small.size()),
+            match &app.tag_rule_changes {
+                Some(changes) => rerun_preview_panel(app, changes),
+                None => spacer(Orientations::Vertical, Spacing::None),
+            },
+            spacer(Orientations::Vertical, Spacing::Medium),
+            ui_string(app, "New Tag Rule", TextSizes::LargeHeading, MaterialColors::StrongText),
+            new_tag_rule_panel(app),
+        ]
+        .spacing(Spacing::Medium.size())
+    )
+    .direction(Direction::Vertical(Scrollbar::hidden()))
+    .width(Widths::LargeCard.size())
+    .height(Fill)
+    .into()
+}
+
+/// Displays the conditions and `Tag`s of a `TagRule`, and allows it to be removed.
+#[must_use]
+fn tag_rule_slip<'a>(
+    app: &'a App,
+    tag_rule: &'a TagRule,
+) -> Element<'a, Signal> {
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            row![
+                column![
+                    ui_string(app, format!("If {}", tag_rule.display_conditions()), TextSizes::Body, MaterialColors::MediumText),
+                    row(tag_rule.tags.iter().map(|tag| tag_chip(app, tag)))
+                        .spacing(Spacing::Micro.size()),
+                ]
+                .spacing(Spacing::Micro.size()),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::danger(),
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::Standard,
+                    icon("trash"),
+                    Signal::RemoveTagRule(tag_rule.get_uid()),
+                    true,
+                ),
+            ]
+            .align_y(Center)
+            .into()
+        }
+    )
+}
+
+/// Displays a `Tag` in its registered color.
+#[must_use]
+fn tag_chip<'a>(
+    app: &'a App,
+    tag: &Tag,
+) -> Element<'a, Signal> {
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: app.bank.tag_registry.get(tag),
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Shrink, height: Heights::Shrink },
+        PaddingSizes::Micro,
+        ui_string(app, tag.get_label(), TextSizes::Body, MaterialColors::StrongText),
+    )
+}
+
+/// Lists the `Tag`s that re-running the `TagRule`s would add to each `Transaction`, and allows the re-run to be applied.
+#[must_use]
+fn rerun_preview_panel<'a>(
+    app: &'a App,
+    changes: &'a [TagRuleChange],
+) -> Element<'a, Signal> {
+    let summary = match changes.len() {
+        0 => "Re-running the rules would not change any transactions.".to_string(),
+        1 => "Re-running the rules would change 1 transaction:".to_string(),
+        count => format!("Re-running the rules would change {count} transactions:"),
+    };
+
+    let change_rows = changes.iter().map(|change| {
+        row![
+            ui_string(app, change.description.clone(), TextSizes::Body, MaterialColors::MediumText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            ui_string(app, "+", TextSizes::Body, MaterialColors::WeakText),
+            row(change.added_tags.iter().map(|tag| tag_chip(app, tag)))
+                .spacing(Spacing::Micro.size()),
+        ]
+        .spacing(Spacing::Small.size())
+        .align_y(Center)
+        .into()
+    });
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column![
+                ui_string(app, summary, TextSizes::SmallHeading, MaterialColors::StrongText),
+                column(change_rows).spacing(Spacing::Micro.size()),
+                row![
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::CardContent,
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        ui_string(app, "Cancel", TextSizes::Interactable, MaterialColors::StrongText),
+                        Signal::CancelTagRuleRerun,
+                        true,
+                    ),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::success(),
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        ui_string(app, "Apply", TextSizes::Interactable, MaterialColors::StrongText),
+                        Signal::ApplyTagRuleRerun,
+                        !changes.is_empty(),
+                    ),
+                ]
+                .spacing(Spacing::Small.size())
+                .align_y(Center),
+            ]
+            .spacing(Spacing::Small.size())
+            .into()
+        }
+    )
+}
+
+/// The panel used to enter the conditions and `Tag`s of a new `TagRule`.
+#[must_use]
+fn new_tag_rule_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let is_valid = TagRule::are_raw_parts_valid(&app.new_tag_rule_description_string, &app.new_tag_rule_below_string, &app.new_tag_rule_above_string, &app.new_tag_rule_currency_string, &app.new_tag_rule_tags_string);
+    let is_below_error = !TagRule::are_raw_conditions_valid(&app.new_tag_rule_below_string, "", "");
+    let is_above_error = !TagRule::are_raw_conditions_valid("", &app.new_tag_rule_above_string, "");
+    let is_currency_error = !TagRule::are_raw_conditions_valid("", "", &app.new_tag_rule_currency_string);
+    let is_tags_error = Tag::list_from(&app.new_tag_rule_tags_string).is_fail();
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::Fill, height: Heights::Shrink },
+        PaddingSizes::Small, {
+            column![
+                ui_string(app, "Description Contains", TextSizes::SmallHeading, MaterialColors::StrongText),
+                new_tag_rule_input(app, false, Widths::LargeField, "SHELL", &app.new_tag_rule_description_string, Signal::UpdateNewTagRuleDescriptionString, is_valid),
+                ui_string(app, "Value and Currency", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row![
+                    new_tag_rule_input(app, is_below_error, Widths::MicroField, "Below", &app.new_tag_rule_below_string, Signal::UpdateNewTagRuleBelowString, is_valid),
+                    new_tag_rule_input(app, is_above_error, Widths::MicroField, "Above", &app.new_tag_rule_above_string, Signal::UpdateNewTagRuleAboveString, is_valid),
+                    new_tag_rule_input(app, is_currency_error, Widths::MicroField, "Any", &app.new_tag_rule_currency_string, Signal::UpdateNewTagRuleCurrencyString, is_valid),
+                ]
+                .spacing(Spacing::Micro.size())
+                .align_y(Center),
+                ui_string(app, "Tags to Add", TextSizes::SmallHeading, MaterialColors::StrongText),
+                row![
+                    new_tag_rule_input(app, is_tags_error, Widths::LargeField, "gas, car", &app.new_tag_rule_tags_string, Signal::UpdateNewTagRuleTagsString, is_valid),
+                    spacer(Orientations::Horizontal, Spacing::Fill),
+                    panel_button(
+                        app,
+                        MaterialStyle {
+                            material: Materials::Plastic,
+                            color: MaterialColors::success(),
+                            depth: Depths::Proud,
+                        },
+                        ButtonShapes::Standard,
+                        ui_string(app, "Add Rule", TextSizes::Interactable, MaterialColors::StrongText),
+                        Signal::AddTagRule,
+                        is_valid,
+                    ),
+                ]
+                .align_y(Center),
+            ]
+            .spacing(Spacing::Small.size())
+            .into()
+        }
+    )
+}
+
+/// A text input for one of the parts of the new `TagRule`, which adds the `TagRule` when submitted.
+#[must_use]
+fn new_tag_rule_input<'a>(
+    app: &'a App,
+    is_error: bool,
+    width: Widths,
+    placeholder: &str,
+    value: &str,
+    on_change: impl Fn(String) -> Signal + 'a,
+    can_submit: bool,
+) -> Element<'a, Signal> {
+    panel_text_input(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if is_error { MaterialColors::danger() } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        width,
+        placeholder,
+        value,
+        on_change,
+        Some(Signal::AddTagRule),
+        can_submit,
+    )
+}
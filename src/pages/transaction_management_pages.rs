@@ -6,11 +6,14 @@ use iced::widget::{row, column};
 use iced::widget::scrollable::{Direction, Scrollbar};
 use iced::widget::text::Alignment;
 use iced_font_awesome::fa_icon_solid as icon;
+use rust_decimal::Decimal;
 use crate::container::app::App;
 use crate::container::signal::Signal;
-use materialui::components::{ButtonShapes, DatePickerModes, Directions, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, TransactionManagementTypes, Widths, header, panel, panel_button, panel_text_editor, panel_text_input, spacer, ui_string};
+use materialui::components::{ButtonShapes, DatePickerModes, Directions, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, TransactionManagementTypes, Widths, ThemeProvider, header, panel, panel_button, panel_text_editor, panel_text_input, spacer, ui_string};
 use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
-use crate::vault::transaction::{Date, Months, Tag, TagStyles, Transaction};
+use crate::vault::recurring::RecurrenceRules;
+use crate::vault::transaction::{Date, Months, Split, Tag, TagStyles, Transaction};
+use schrod::Schrod::{Fail, Pass};
 
 /// The page used for adding `Transaction`s.
 #[must_use]
@@ -83,6 +86,11 @@ fn transaction_management_panel<'a>(
                 ]
                 .align_y(Center)
                 .spacing(Spacing::None.size()),
+
+
+
+                // account
+                account_selector(app, transaction_management),
     
     
     
@@ -119,11 +127,25 @@ fn transaction_management_panel<'a>(
                 .align_y(Center)
                 .spacing(Spacing::None.size()),
     
+                tag_suggestion_row(app, transaction_management),
                 spacer(Orientations::Vertical, Spacing::Micro),
                 editor_tag_list(app, transaction_management),
     
     
     
+                // splits
+                split_editor(app, transaction_management),
+    
+    
+    
+                // repeating
+                match transaction_management {
+                    TransactionManagementTypes::Adding => { recurrence_selector(app) }
+                    TransactionManagementTypes::Editing => { spacer(Orientations::Vertical, Spacing::None) }
+                },
+    
+    
+    
                 // buttons
                 spacer(Orientations::Vertical, Spacing::Large),
                 match transaction_management {
@@ -592,14 +614,20 @@ fn add_current_tag_button<'a>(
 }
 
 /// Returns the appropriate `Signal` to add the current `Tag` and a flag indicating if the `Tag` is valid.
+/// A suggestion selected by keybind is added instead of the typed text.
 #[must_use]
 fn add_current_tag_signal(
     app: &App,
     transaction_management: TransactionManagementTypes,
 ) -> (Signal, bool) {
-    let tag_string = match transaction_management {
-        TransactionManagementTypes::Adding => { &app.new_transaction_current_tag_string }
-        TransactionManagementTypes::Editing => { &app.edit_transaction_current_tag_string }
+    let suggestions = match transaction_management {
+        TransactionManagementTypes::Adding => { &app.new_transaction_tag_suggestions }
+        TransactionManagementTypes::Editing => { &app.edit_transaction_tag_suggestions }
+    };
+    let tag_string = match (suggestions.get_selected(), transaction_management) {
+        (Some(tag), _) => { &tag.get_label() }
+        (None, TransactionManagementTypes::Adding) => { &app.new_transaction_current_tag_string }
+        (None, TransactionManagementTypes::Editing) => { &app.edit_transaction_current_tag_string }
     };
     let signal = match transaction_management {
         TransactionManagementTypes::Adding => { Signal::AddNewTransactionTag(tag_string.clone()) }
@@ -610,6 +638,54 @@ fn add_current_tag_signal(
     (signal, is_valid)
 }
 
+/// Displays the suggested `Tag`s, which are added when clicked or when selected by keybind.
+#[must_use]
+fn tag_suggestion_row<'a>(
+    app: &'a App,
+    transaction_management: TransactionManagementTypes,
+) -> Element<'a, Signal> {
+    let suggestions = match transaction_management {
+        TransactionManagementTypes::Adding => { &app.new_transaction_tag_suggestions }
+        TransactionManagementTypes::Editing => { &app.edit_transaction_tag_suggestions }
+    };
+    if suggestions.get_all().is_empty() { return spacer(Orientations::Vertical, Spacing::None) }
+
+    let suggestion_buttons = suggestions.get_all().iter().map(|tag| {
+        let signal = match transaction_management {
+            TransactionManagementTypes::Adding => { Signal::AddNewTransactionTag(tag.get_label()) }
+            TransactionManagementTypes::Editing => { Signal::AddEditTransactionTag(tag.get_label()) }
+        };
+        let color = if suggestions.get_selected() == Some(tag) { MaterialColors::accent(app.material_theme()) }
+        else { MaterialColors::CardContent };
+
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color,
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Minimal,
+            ui_string(app, tag.display(TagStyles::Lowercase), TextSizes::Interactable, MaterialColors::StrongText),
+            signal,
+            true,
+        )
+    });
+
+    column![
+        spacer(Orientations::Vertical, Spacing::Micro),
+        row![
+            spacer(Orientations::Horizontal, Spacing::Small),
+            ui_string(app, "Suggested", TextSizes::Body, MaterialColors::WeakText),
+            spacer(Orientations::Horizontal, Spacing::Small),
+            scrollable(row(suggestion_buttons).spacing(Spacing::Micro.size()))
+                .direction(Direction::Horizontal(Scrollbar::hidden())),
+        ]
+        .align_y(Center),
+    ]
+    .into()
+}
+
 /// Displays the `Tag`s in a `Transaction` for editing.
 #[must_use]
 fn editor_tag_list<'a>(
@@ -721,6 +797,279 @@ fn editor_tag_panel<'a>(
     )
 }
 
+/// Edits the `Split`s of a `Transaction`, which carve parts of its value out for other `Tag`s.
+#[must_use]
+fn split_editor<'a>(
+    app: &'a App,
+    transaction_management: TransactionManagementTypes,
+) -> Element<'a, Signal> {
+    let (value_string, splits) = match transaction_management {
+        TransactionManagementTypes::Adding => { (&app.new_transaction_value_string, &app.new_transaction_splits) }
+        TransactionManagementTypes::Editing => { (&app.edit_transaction_value_string, &app.edit_transaction_splits) }
+    };
+    let (amount_string, tags_string, note_string) = match transaction_management {
+        TransactionManagementTypes::Adding => { (&app.new_split_amount_string, &app.new_split_tags_string, &app.new_split_note_string) }
+        TransactionManagementTypes::Editing => { (&app.edit_split_amount_string, &app.edit_split_tags_string, &app.edit_split_note_string) }
+    };
+    let amount_signal = match transaction_management {
+        TransactionManagementTypes::Adding => { Signal::UpdateNewSplitAmountString }
+        TransactionManagementTypes::Editing => { Signal::UpdateEditSplitAmountString }
+    };
+    let tags_signal = match transaction_management {
+        TransactionManagementTypes::Adding => { Signal::UpdateNewSplitTagsString }
+        TransactionManagementTypes::Editing => { Signal::UpdateEditSplitTagsString }
+    };
+    let note_signal = match transaction_management {
+        TransactionManagementTypes::Adding => { Signal::UpdateNewSplitNoteString }
+        TransactionManagementTypes::Editing => { Signal::UpdateEditSplitNoteString }
+    };
+    let add_signal = match transaction_management {
+        TransactionManagementTypes::Adding => { Signal::AddNewTransactionSplit }
+        TransactionManagementTypes::Editing => { Signal::AddEditTransactionSplit }
+    };
+    let is_valid = match Tag::list_from(tags_string) {
+        Pass(tags) => Split::are_raw_parts_valid(amount_string, &tags, note_string),
+        Fail(_) => false,
+    };
+    let are_splits_valid = Transaction::are_splits_valid_for_raw(value_string, splits);
+    
+    // what is left for the transaction's own tags
+    let remaining = match value_string.parse::<Decimal>() {
+        Ok(value_amount) if !splits.is_empty() => {
+            let remaining_amount = value_amount - splits.iter().map(|split| split.amount).sum::<Decimal>();
+            ui_string(app, format!("{remaining_amount} left for the tags above"), TextSizes::Body, if are_splits_valid { MaterialColors::WeakText } else { MaterialColors::danger() })
+        }
+        _ => spacer(Orientations::Horizontal, Spacing::None),
+    };
+
+    column![
+        spacer(Orientations::Vertical, Spacing::Medium),
+        row![
+            spacer(Orientations::Horizontal, Spacing::Small),
+            ui_string(app, "Splits", TextSizes::Body, MaterialColors::WeakText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            remaining,
+            spacer(Orientations::Horizontal, Spacing::Small),
+        ]
+        .align_y(Center)
+        .spacing(Spacing::None.size()),
+        
+        row![
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::CardContent,
+                    depth: Depths::Proud,
+                },
+                Widths::MicroField,
+                "Amount",
+                amount_string,
+                amount_signal,
+                Some(add_signal.clone()),
+                is_valid,
+            ),
+            spacer(Orientations::Horizontal, Spacing::Micro),
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::CardContent,
+                    depth: Depths::Proud,
+                },
+                Widths::SmallField,
+                "Tags, separated by commas",
+                tags_string,
+                tags_signal,
+                Some(add_signal.clone()),
+                is_valid,
+            ),
+            spacer(Orientations::Horizontal, Spacing::Micro),
+            panel_text_input(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::CardContent,
+                    depth: Depths::Proud,
+                },
+                Widths::SmallField,
+                "Note",
+                note_string,
+                note_signal,
+                Some(add_signal.clone()),
+                is_valid,
+            ),
+            spacer(Orientations::Horizontal, Spacing::Micro),
+            panel_button(
+                app,
+                MaterialStyle {
+                    material: Materials::Plastic,
+                    color: MaterialColors::success(),
+                    depth: Depths::Proud,
+                },
+                ButtonShapes::Bloated,
+                icon("plus"),
+                add_signal,
+                is_valid,
+            ),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+        ]
+        .align_y(Center)
+        .spacing(Spacing::None.size()),
+        
+        spacer(Orientations::Vertical, Spacing::Micro),
+        column(splits.iter().enumerate().map(|(index, split)| editor_split_panel(app, transaction_management, index, split)))
+            .spacing(PaddingSizes::Nano.size()),
+    ]
+    .spacing(Spacing::None.size())
+    .into()
+}
+
+/// Displays a `Split` for editing.
+#[must_use]
+fn editor_split_panel<'a>(
+    app: &'a App,
+    transaction_management: TransactionManagementTypes,
+    index: usize,
+    split: &Split,
+) -> Element<'a, Signal> {
+    let signal = match transaction_management {
+        TransactionManagementTypes::Adding => { Signal::RemoveNewTransactionSplit(index) }
+        TransactionManagementTypes::Editing => { Signal::RemoveEditTransactionSplit(index) }
+    };
+    let tags = split.tags.iter().map(|tag| tag.display(TagStyles::Lowercase)).collect::<Vec<_>>().join(", ");
+    let label = match &split.note {
+        Some(note) => format!("{}  {tags}  ({note})", split.amount),
+        None => format!("{}  {tags}", split.amount),
+    };
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardHollowContent,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::LargeField, height: Heights::Shrink },
+        PaddingSizes::None, {
+            row![
+                ui_string(app, label, TextSizes::Interactable, MaterialColors::StrongText),
+                spacer(Orientations::Horizontal, Spacing::Fill),
+                panel_button(
+                    app,
+                    MaterialStyle {
+                        material: Materials::Plastic,
+                        color: MaterialColors::danger(),
+                        depth: Depths::Proud,
+                    },
+                    ButtonShapes::LowProfile,
+                    icon("trash"),
+                    signal,
+                    true,
+                )
+            ]
+                .spacing(Spacing::None.size())
+                .align_y(Center)
+                .padding([PaddingSizes::Nano.size(), PaddingSizes::Small.size()])
+                .into()
+        }
+    )
+}
+
+/// A row of buttons used to choose how often a new `Transaction` repeats.
+#[must_use]
+fn recurrence_selector<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let date = app.new_transaction_selected_date;
+    let selected = app.new_transaction_recurrence.map(|rule| rule.anchored_to(date));
+    let options = iter::once(None).chain(RecurrenceRules::all_anchored_to(date).into_iter().map(Some));
+
+    let buttons = options.map(|option| {
+        let name = match option {
+            Some(rule) => rule.name().to_string(),
+            None => "Once".to_string(),
+        };
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if option == selected { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            ui_string(app, name, TextSizes::Interactable, MaterialColors::StrongText),
+            Signal::SetNewTransactionRecurrence(option),
+            true,
+        )
+    });
+
+    column![
+        spacer(Orientations::Vertical, Spacing::Medium),
+        row![
+            spacer(Orientations::Horizontal, Spacing::Small),
+            ui_string(app, "Repeat", TextSizes::Body, MaterialColors::WeakText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+            match selected {
+                Some(rule) => ui_string(app, rule.display(), TextSizes::Body, MaterialColors::WeakText),
+                None => spacer(Orientations::Horizontal, Spacing::None),
+            },
+            spacer(Orientations::Horizontal, Spacing::Small),
+        ]
+        .align_y(Center)
+        .spacing(Spacing::None.size()),
+        row(buttons).spacing(Spacing::Micro.size()),
+    ]
+    .spacing(Spacing::None.size())
+    .into()
+}
+
+/// A row of buttons used to choose which `Account` a `Transaction` belongs to.
+/// Hidden while the vault only has one `Account`, since there is nothing to choose.
+#[must_use]
+fn account_selector<'a>(
+    app: &'a App,
+    transaction_management: TransactionManagementTypes,
+) -> Element<'a, Signal> {
+    if app.bank.accounts.len() < 2 { return spacer(Orientations::Vertical, Spacing::None) }
+    let selected = match transaction_management {
+        TransactionManagementTypes::Adding => { app.new_transaction_account_uid }
+        TransactionManagementTypes::Editing => { app.edit_transaction_account_uid }
+    };
+
+    let buttons = app.bank.accounts.iter().map(|account| {
+        panel_button(
+            app,
+            MaterialStyle {
+                material: Materials::Plastic,
+                color: if account.get_uid() == selected { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+                depth: Depths::Proud,
+            },
+            ButtonShapes::Standard,
+            row![icon(account.account_type.icon_name()), ui_string(app, account.name.clone(), TextSizes::Interactable, MaterialColors::StrongText)].align_y(Center).spacing(Spacing::Micro.size()),
+            match transaction_management {
+                TransactionManagementTypes::Adding => { Signal::SetNewTransactionAccount(account.get_uid()) }
+                TransactionManagementTypes::Editing => { Signal::SetEditTransactionAccount(account.get_uid()) }
+            },
+            true,
+        )
+    });
+
+    column![
+        spacer(Orientations::Vertical, Spacing::Medium),
+        row![
+            spacer(Orientations::Horizontal, Spacing::Small),
+            ui_string(app, "Account", TextSizes::Body, MaterialColors::WeakText),
+            spacer(Orientations::Horizontal, Spacing::Fill),
+        ]
+        .align_y(Center)
+        .spacing(Spacing::None.size()),
+        row(buttons).spacing(Spacing::Micro.size()),
+    ]
+    .spacing(Spacing::None.size())
+    .into()
+}
+
 /// Saves the `Transaction`.
 #[must_use]
 fn save_button<'a>(
@@ -747,7 +1096,12 @@ fn save_button<'a>(
         TransactionManagementTypes::Adding => { &app.new_transaction_tags }
         TransactionManagementTypes::Editing => { &app.edit_transaction_tags }
     };
-    let is_valid = Transaction::are_raw_parts_valid(value_string, currency_string, description, tags);
+    let splits = match transaction_management {
+        TransactionManagementTypes::Adding => { &app.new_transaction_splits }
+        TransactionManagementTypes::Editing => { &app.edit_transaction_splits }
+    };
+    let is_valid = Transaction::are_raw_parts_valid(value_string, currency_string, description, tags)
+        && Transaction::are_splits_valid_for_raw(value_string, splits);
 
     panel_button(
         app,
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use iced::Length::FillPortion;
 use iced::{Center, Fill};
 use iced::Element;
@@ -8,13 +9,16 @@ use iced::widget::scrollable::{Direction, Scrollbar};
 use iced_font_awesome::fa_icon_solid as icon;
 use crate::container::app::{App, Pages};
 use crate::container::signal::Signal;
+use crate::pages::accounts_page::display_balance;
 use crate::pages::filter_ui::{advance_filter_month_panel, advance_filter_year_panel, filter_mode_toggle_button, filter_tags, recede_filter_month_panel, recede_filter_year_panel, search_bar, search_terms, toggle_filter_month_panel, toggle_filter_year_panel};
-use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, Widths, header, navigation_panel, pad, panel, panel_button, spacer, ui_string};
+use materialui::components::{ButtonShapes, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, ThemeProvider, Widths, header, navigation_panel, pad, panel, panel_button, spacer, ui_string};
 use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+use crate::vault::account::Account;
 use crate::vault::bank::{CurrencyExchange, Filters};
 use crate::vault::parse::CashFlow;
 use crate::vault::ring_parse::RingParse;
-use crate::vault::transaction::{Tag, TagStyles, Transaction};
+use crate::vault::transaction::{Tag, TagStyles, Transaction, Uid};
+use rust_decimal::Decimal;
 use schrod::Schrod::{self, Fail, Pass};
 
 /// The page used to display `Transaction`s.
@@ -28,12 +32,14 @@ pub fn transactions_page<'a>(
         .filter(|id| { bank.get(**id).is_pass() })
         .map(|id| { bank.get(*id).wont_fail("These ids are guaranteed to have transactions attached.", "transactions_page::transactions_page()")})
         .collect();
+    // the running balances are only worth showing once there is more than one account
+    let running_balances = if bank.accounts.len() > 1 { Account::get_running_balances(&bank.accounts, bank.get_ledger(), &bank.currency_exchange) } else { HashMap::new() };
     
     stack![
         row![
             navigation_panel(app, Pages::page_pointers(app)),
             stack![
-                container(transaction_list(app, &transactions)).center_x(Fill),
+                container(transaction_list(app, &transactions, &running_balances)).center_x(Fill),
                 management_panel_overlay(app),
             ].width(FillPortion(3)),
             parse_panel(app)
@@ -51,6 +57,7 @@ pub fn transactions_page<'a>(
 fn transaction_list<'a>(
     app: &'a App,
     transactions: &[&Transaction],
+    running_balances: &HashMap<Uid, Decimal>,
     //value_display_format: ValueDisplayFormats,
 )  -> Element<'a, Signal> {
     let mut first_half = Vec::new();
@@ -64,10 +71,10 @@ fn transaction_list<'a>(
             spacer(Orientations::Vertical, Spacing::HeaderSpace),
 
             row![
-                column(first_half.into_iter().map(|transaction| { transaction_panel(app, transaction) }))
+                column(first_half.into_iter().map(|transaction| { transaction_panel(app, transaction, running_balances) }))
                 .spacing(Spacing::Micro.size()),
 
-                column(second_half.into_iter().map(|transaction| { transaction_panel(app, transaction) }))
+                column(second_half.into_iter().map(|transaction| { transaction_panel(app, transaction, running_balances) }))
                 .spacing(Spacing::Micro.size()),
             ]
             .spacing(Spacing::Small.size()),
@@ -82,15 +89,19 @@ fn transaction_list<'a>(
     .into()
 }
 
-/// A panel that displays an individual `Transaction`.
+/// A panel that displays an individual `Transaction`, along with the balance of its `Account` just after it.
 #[must_use]
 fn transaction_panel<'a>(
     app: &'a App,
     transaction: &Transaction,
+    running_balances: &HashMap<Uid, Decimal>,
 ) -> Element<'a, Signal> {
     let value_string = transaction.value.to_string();
     let symbol_string = transaction.value.currency().to_string();
     let time_string = CurrencyExchange::as_time_price_string(transaction.get_time_price(&app.bank.currency_exchange));
+    let balance_string = app.bank.get_account(transaction.get_account_uid())
+        .zip(running_balances.get(&transaction.get_uid()))
+        .map(|(account, balance)| format!("{}: {}", account.name, display_balance(account, *balance)));
     
     panel(
         app,
@@ -123,10 +134,12 @@ fn transaction_panel<'a>(
                 ]
                 .spacing(0),
 
-                // date and edit button
+                // date, select button, and edit button
                 spacer(Orientations::Horizontal, Spacing::Medium),
                 ui_string(app, transaction.date.display(), TextSizes::Interactable, MaterialColors::MediumText),
                 spacer(Orientations::Horizontal, Spacing::Fill),
+                select_transaction_button(app, transaction),
+                spacer(Orientations::Horizontal, Spacing::Micro),
                 edit_transaction_button(app, transaction),
 
                 spacer(Orientations::Horizontal, Spacing::Medium),
@@ -146,11 +159,24 @@ fn transaction_panel<'a>(
             .spacing(0)
             .align_y(Center),
 
+            // account balance
+            match balance_string {
+                Some(balance_string) => {
+                    row![
+                        spacer(Orientations::Horizontal, Spacing::Medium),
+                        ui_string(app, balance_string, TextSizes::Body, MaterialColors::WeakText),
+                    ]
+                    .spacing(0)
+                    .into()
+                }
+                None => { spacer(Orientations::Vertical, Spacing::None) }
+            },
+
             // tags
             spacer(Orientations::Vertical, Spacing::Small),
             scrollable(
                 row({
-                    let mut tags: Vec<_> = transaction.tags.iter().filter(|t| **t != Transaction::ignore_tag()).map(|tag| {
+                    let mut tags: Vec<_> = transaction.get_all_tags().iter().filter(|t| **t != Transaction::ignore_tag()).map(|tag| {
                         tag_panel(app, tag)
                     }).collect();
                     tags.insert(0, spacer(Orientations::Horizontal, Spacing::Small));
@@ -188,6 +214,27 @@ fn edit_transaction_button<'a>(
     )
 }
 
+/// A button that selects or deselects a `Transaction` for a bulk edit.
+#[must_use]
+fn select_transaction_button<'a>(
+    app: &'a App,
+    transaction: &Transaction,
+) -> Element<'a, Signal> {
+    let is_selected = app.selected_transaction_uids.contains(&transaction.get_uid());
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: if is_selected { MaterialColors::accent(app.material_theme()) } else { MaterialColors::CardContent },
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Bloated,
+        icon(if is_selected { "square-check" } else { "square" }),
+        Signal::ToggleTransactionSelection(transaction.get_uid()),
+        true,
+    )
+}
+
 /// A panel that displays a `Tag`.
 #[must_use]
 pub fn tag_panel<'a>(
@@ -246,6 +293,7 @@ fn management_panel<'a>(
                     column![
                         add_transaction_button(app),
                         filter_mode_toggle_button(app, Filters::Primary),
+                        export_transactions_button(app),
                     ]
                     .align_x(Center),
                     
@@ -282,6 +330,17 @@ fn management_panel<'a>(
                     ]
                     .align_x(Center),
                     
+                    // selection
+                    column![
+                        ui_string(app, format!("{} Selected", app.selected_transaction_uids.len()), TextSizes::Body, MaterialColors::WeakText),
+                        row![
+                            selection_button(app, "check-double", Signal::SelectAllFilteredTransactions, !app.bank.get_filtered_ids(Filters::Primary).is_empty()),
+                            selection_button(app, "xmark", Signal::ClearTransactionSelection, !app.selected_transaction_uids.is_empty()),
+                        ],
+                        selection_button(app, "pen-to-square", Signal::StartBulkEdit, !app.selected_transaction_uids.is_empty()),
+                    ]
+                    .align_x(Center),
+                    
                     spacer(Orientations::Vertical, Spacing::Fill),
                 ]
                 .align_y(Center)
@@ -311,6 +370,47 @@ fn add_transaction_button<'a>(
     )
 }
 
+/// Allows a user to export the filtered `Transaction`s to a CSV or JSON file.
+#[must_use]
+fn export_transactions_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardContent,
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        icon("file-export"),
+        Signal::OpenExportFilePicker(Filters::Primary),
+        !app.bank.get_filtered_ids(Filters::Primary).is_empty(),
+    )
+}
+
+/// A button that manages the `Transaction`s selected for a bulk edit.
+#[must_use]
+fn selection_button<'a>(
+    app: &'a App,
+    icon_name: &str,
+    signal: Signal,
+    is_enabled: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::CardContent,
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Standard,
+        icon(icon_name),
+        signal,
+        is_enabled,
+    )
+}
+
 /// A panel that visualizes information about the `Transaction`s on the screen.
 #[must_use]
 fn parse_panel<'a>(
@@ -412,6 +512,8 @@ fn ring_charts<'a>(
 ) -> Element<'a, Signal> {
     if app.are_ring_charts_ready {
         column![
+            ring_tag_rollup_button(app),
+            spacer(Orientations::Vertical, Spacing::Small),
             ui_string(app, "Earning", TextSizes::SmallHeading, MaterialColors::StrongText),
             spacer(Orientations::Vertical, Spacing::Micro),
             match &app.earning_ring_parse_result {
@@ -457,6 +559,28 @@ fn ring_charts<'a>(
     }
 }
 
+/// Toggles rolling child `Tag`s up into their parents in the `RingChart`s.
+#[must_use]
+fn ring_tag_rollup_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let color = if app.are_ring_tags_rolled_up { MaterialColors::accent(app.theme_selection) }
+    else { MaterialColors::CardContent };
+    
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color,
+            depth: Depths::Proud
+        },
+        ButtonShapes::Minimal,
+        ui_string(app, "Roll Up Child Tags", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::ToggleRingTagRollup,
+        true,
+    )
+}
+
 /// A popup for displaying the `Tag` and percentage when a `RingChart` `Segment` is hovered over.
 #[must_use]
 fn segment_popup<'a>(
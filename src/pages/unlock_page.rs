@@ -0,0 +1,157 @@
+use iced::{Center, Fill};
+use iced::Element;
+use iced::widget::{Stack, container, stack, text_input};
+use iced::widget::column;
+use iced::widget::row;
+use crate::container::app::App;
+use crate::container::signal::Signal;
+use materialui::components::{BorderThickness, ButtonShapes, CornerRadii, Heights, Orientations, PaddingSizes, PanelSize, Spacing, TextSizes, ThemeProvider, Widths, panel, panel_button, spacer, ui_string};
+use materialui::materials::{Depths, MaterialColors, MaterialStyle, Materials};
+
+/// The page used to enter the passphrase of an encrypted vault before it is loaded.
+#[must_use]
+pub fn unlock_page<'a>(
+    app: &'a App,
+) -> Stack<'a, Signal> {
+    stack![
+        container(unlock_panel(app)).center(Fill),
+    ]
+}
+
+/// The panel that asks for the passphrase.
+#[must_use]
+fn unlock_panel<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    let vault_name = app.locked_vault_name.clone().unwrap_or_default();
+    let can_unlock = !app.unlock_passphrase_string.is_empty();
+
+    let mut buttons = row![
+        spacer(Orientations::Horizontal, Spacing::Fill),
+        unlock_button(app, can_unlock),
+    ];
+    if !app.is_locked() {
+        buttons = buttons.push(spacer(Orientations::Horizontal, Spacing::Medium)).push(cancel_unlock_button(app));
+    }
+    buttons = buttons.push(spacer(Orientations::Horizontal, Spacing::Fill));
+
+    panel(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::Card,
+            depth: Depths::Proud,
+        },
+        PanelSize { width: Widths::SmallCard, height: Heights::Shrink },
+        PaddingSizes::Medium, {
+            column![
+                ui_string(app, format!("{vault_name} is locked"), TextSizes::LargeHeading, MaterialColors::StrongText),
+                spacer(Orientations::Vertical, Spacing::Small),
+                ui_string(app, "Enter the passphrase to open this vault.", TextSizes::SmallHeading, MaterialColors::MediumText),
+                spacer(Orientations::Vertical, Spacing::Large),
+                passphrase_input(
+                    app,
+                    "Passphrase",
+                    &app.unlock_passphrase_string,
+                    Signal::UpdateUnlockPassphraseString,
+                    if can_unlock { Some(Signal::Unlock) } else { None },
+                    app.unlock_failure.is_some(),
+                ),
+                match &app.unlock_failure {
+                    Some(failure) => ui_string(app, failure.clone(), TextSizes::Body, MaterialColors::danger()),
+                    None => spacer(Orientations::Vertical, Spacing::None),
+                },
+                spacer(Orientations::Vertical, Spacing::Large),
+                buttons,
+            ]
+            .align_x(Center)
+            .into()
+        }
+    )
+}
+
+/// Unlocks the vault.
+#[must_use]
+fn unlock_button<'a>(
+    app: &'a App,
+    enabled: bool,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::success(),
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Unlock", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::Unlock,
+        enabled,
+    )
+}
+
+/// Returns to the vault that is already open.
+#[must_use]
+fn cancel_unlock_button<'a>(
+    app: &'a App,
+) -> Element<'a, Signal> {
+    panel_button(
+        app,
+        MaterialStyle {
+            material: Materials::Plastic,
+            color: MaterialColors::danger(),
+            depth: Depths::Proud,
+        },
+        ButtonShapes::Wide,
+        ui_string(app, "Cancel", TextSizes::Interactable, MaterialColors::StrongText),
+        Signal::CancelUnlock,
+        true,
+    )
+}
+
+/// A text input that hides what is typed into it.
+/// This matches the look of `panel_text_input()`, which has no way to hide its contents.
+#[must_use]
+pub fn passphrase_input<'a>(
+    app: &'a App,
+    placeholder: &str,
+    value: &str,
+    on_change: fn(String) -> Signal,
+    on_submit_option: Option<Signal>,
+    error: bool,
+) -> Element<'a, Signal> {
+    let material_style = MaterialStyle {
+        material: Materials::Plastic,
+        color: if error { MaterialColors::danger() } else { MaterialColors::Card },
+        depth: Depths::Proud,
+    };
+    let theme = app.material_theme();
+
+    let mut input = text_input(placeholder, value)
+        .secure(true)
+        .on_input(on_change)
+        .style(move |_, status| text_input::Style {
+            background: material_style.color.materialized(material_style.material, material_style.depth, false, theme).into(),
+            border: iced::Border::default()
+                .rounded(CornerRadii::Medium.size())
+                .width(BorderThickness::Thin.size())
+                .color(material_style.color.materialized(material_style.material, material_style.depth, !matches!(status, text_input::Status::Active), theme)),
+            icon: MaterialColors::CardContent.materialized(material_style.material, material_style.depth, false, theme),
+            placeholder: MaterialColors::text().materialized(Materials::Plastic, Depths::Flat, true, theme),
+            value: MaterialColors::text().materialized(Materials::Plastic, Depths::Flat, false, theme),
+            selection: MaterialColors::accent(theme).materialized(Materials::Plastic, Depths::Flat, false, theme),
+        });
+    if let Some(on_submit) = on_submit_option { input = input.on_submit(on_submit); }
+
+    container(
+        panel(
+            app,
+            material_style,
+            PanelSize { width: Widths::SmallField, height: Heights::Shrink },
+            PaddingSizes::None,
+            input.into(),
+        )
+    )
+    .padding(PaddingSizes::Micro.size())
+    .into()
+}
@@ -4,4 +4,18 @@ pub mod filter;
 pub mod parse;
 pub mod save_engine;
 pub mod trend_parse;
-pub mod ring_parse;
\ No newline at end of file
+pub mod ring_parse;
+pub mod csv_import;
+pub mod ofx_import;
+pub mod import_merge;
+pub mod export;
+pub mod recurring;
+pub mod budget;
+pub mod tag_rule;
+pub mod tag_suggestion;
+pub mod history;
+pub mod bulk_edit;
+pub mod account;
+pub mod rate_import;
+#[cfg(test)]
+pub mod test_helpers;
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use rusty_money::iso;
+use rusty_money::iso::Currency;
+use serde::{Deserialize, Serialize};
+use crate::vault::bank::CurrencyExchange;
+use crate::vault::transaction::{Tag, Transaction, Uid};
+use uuid::Uuid;
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// The kinds of places that money can be kept in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AccountTypes {
+    Checking,
+    Savings,
+    CreditCard,
+    Cash,
+}
+impl AccountTypes {
+    /// Returns every `AccountTypes` variant.
+    #[must_use]
+    pub fn all() -> Vec<AccountTypes> {
+        vec![AccountTypes::Checking, AccountTypes::Savings, AccountTypes::CreditCard, AccountTypes::Cash]
+    }
+
+    /// Returns the name of the kind of `Account`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            AccountTypes::Checking => { "Checking" }
+            AccountTypes::Savings => { "Savings" }
+            AccountTypes::CreditCard => { "Credit Card" }
+            AccountTypes::Cash => { "Cash" }
+        }
+    }
+
+    /// Returns the icon name for the kind of `Account`.
+    #[must_use]
+    pub fn icon_name(&self) -> &str {
+        match self {
+            AccountTypes::Checking => "building-columns",
+            AccountTypes::Savings => "piggy-bank",
+            AccountTypes::CreditCard => "credit-card",
+            AccountTypes::Cash => "money-bill",
+        }
+    }
+}
+
+
+
+/// A place that money is kept in, such as a checking account or a wallet.
+/// Every `Transaction` belongs to exactly one `Account`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    uid: Uid,
+    pub name: String,
+    pub account_type: AccountTypes,
+    /// The balance before any of the `Account`'s `Transaction`s.
+    pub opening_balance: Decimal,
+    /// The `Currency` that the balance is kept in.
+    currency_string: String,
+}
+impl Account {
+    // initializing
+    /// Creates a new `Account` from raw data parts.
+    #[must_use]
+    pub fn new_from_raw(name: &str, account_type: AccountTypes, opening_balance_string: &str, currency_string: &str) -> Schrod<Account> {
+        if !Account::are_raw_parts_valid(name, opening_balance_string, currency_string) {
+            return Schrod::new_fail("Accounts need a name, an opening balance, and a valid currency.", "Account::new_from_raw()")
+                .fail("Failed to create Account.", "Account::new_from_raw()")
+        }
+        let opening_balance = Decimal::from_str(opening_balance_string.trim()).unwrap_or_default();
+        Pass(Account { uid: Uuid::new_v4(), name: name.trim().to_string(), account_type, opening_balance, currency_string: currency_string.trim().to_uppercase() })
+    }
+
+    /// Creates the checking `Account` that holds every `Transaction` of a vault that has no `Account`s yet.
+    #[must_use]
+    pub fn default_for(currency: &Currency) -> Account {
+        Account { uid: Uuid::new_v4(), name: "Main".to_string(), account_type: AccountTypes::Checking, opening_balance: Decimal::ZERO, currency_string: currency.to_string() }
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the persistent `Uid`.
+    #[must_use]
+    pub fn get_uid(&self) -> Uid {
+        self.uid
+    }
+
+    /// Returns the code of the `Currency` that the balance is kept in.
+    #[must_use]
+    pub fn get_currency_string(&self) -> &str {
+        &self.currency_string
+    }
+
+    /// Returns the `Currency` that the balance is kept in.
+    #[must_use]
+    pub fn get_currency(&self) -> Schrod<&'static Currency> {
+        Schrod::from_option(iso::find(&self.currency_string), &format!("\"{}\" is not a valid currency.", self.currency_string), "Account::get_currency()")
+    }
+
+    /// Returns the value of a `Transaction` in the `Account`'s `Currency`, converted at the rate that applied on its `Date`.
+    /// Values that cannot be converted count as zero, like they do everywhere else in Ascent.
+    #[must_use]
+    pub fn get_amount_of(&self, transaction: &Transaction, currency_exchange: &CurrencyExchange) -> Decimal {
+        let Pass(currency) = self.get_currency() else { return Decimal::ZERO };
+        match currency_exchange.convert(transaction.value.amount(), transaction.value.currency(), currency, transaction.date) {
+            Pass(amount) => { amount }
+            _ => { Decimal::ZERO }
+        }
+    }
+
+    /// Returns the balance of every `Account` after each of its `Transaction`s, keyed by the `Uid` of the `Transaction`.
+    /// The ledger must be sorted from newest to oldest, like the `Bank`'s.
+    #[must_use]
+    pub fn get_running_balances(accounts: &[Account], ledger: &[Transaction], currency_exchange: &CurrencyExchange) -> HashMap<Uid, Decimal> {
+        let mut balances = accounts.iter().map(|account| (account.uid, account.opening_balance)).collect::<HashMap<Uid, Decimal>>();
+        let mut running_balances = HashMap::new();
+        for transaction in ledger.iter().rev() {
+            let Some(account) = accounts.iter().find(|account| account.uid == transaction.get_account_uid()) else { continue };
+            let balance = balances.entry(account.uid).or_default();
+            *balance += account.get_amount_of(transaction, currency_exchange);
+            running_balances.insert(transaction.get_uid(), *balance);
+        }
+        running_balances
+    }
+
+    /// Returns the current balance of the `Account`.
+    #[must_use]
+    pub fn get_balance(&self, ledger: &[Transaction], currency_exchange: &CurrencyExchange) -> Decimal {
+        self.opening_balance + ledger.iter()
+            .filter(|transaction| transaction.get_account_uid() == self.uid)
+            .map(|transaction| self.get_amount_of(transaction, currency_exchange))
+            .sum::<Decimal>()
+    }
+
+
+
+    // validating
+    /// Checks if an `Account` can be created from the given raw parts.
+    #[must_use]
+    pub fn are_raw_parts_valid(name: &str, opening_balance_string: &str, currency_string: &str) -> bool {
+        Tag::is_allowed(name.trim())
+            && Decimal::from_str(opening_balance_string.trim()).is_ok()
+            && Transaction::is_currency_string_valid(currency_string.trim())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Account, AccountTypes};
+    use crate::vault::bank::{Bank, CurrencyExchange, TagRegistry};
+    use crate::vault::test_helpers::{date, tag, transaction};
+    use crate::vault::transaction::{Date, Months, Transaction};
+
+    fn june(day: u32) -> Date {
+        date(2026, Months::June, day)
+    }
+
+    fn bank() -> Bank {
+        let transactions = vec![
+            transaction("2000", "USD", june(1), "Salary", vec![tag("salary")]),
+            transaction("-80", "USD", june(3), "Groceries", vec![tag("food")]),
+        ];
+        let checking = Account::new_from_raw("Checking", AccountTypes::Checking, "100", "usd").wont_fail("The test account is valid.", "tests::bank()");
+        let savings = Account::new_from_raw("Savings", AccountTypes::Savings, "1000", "USD").wont_fail("The test account is valid.", "tests::bank()");
+        let mut bank = Bank::default();
+        assert!(bank.init(transactions, CurrencyExchange::default(), TagRegistry::default(), Vec::new(), Vec::new(), Vec::new(), vec![checking, savings]).is_pass());
+        bank
+    }
+
+    #[test]
+    fn raw_parts_are_validated() {
+        assert!(Account::are_raw_parts_valid("Wallet", "-20.50", "eur"));
+        assert!(!Account::are_raw_parts_valid("", "0", "USD"));
+        assert!(!Account::are_raw_parts_valid("Wallet", "lots", "USD"));
+        assert!(!Account::are_raw_parts_valid("Wallet", "0", "euro"));
+    }
+
+    #[test]
+    fn transfers_move_balances_without_counting_as_cash_flow() {
+        let mut bank = bank();
+        let checking = bank.accounts[0].get_uid();
+        let savings = bank.accounts[1].get_uid();
+
+        // transactions without an account land in the first one
+        assert!(bank.get_ledger().iter().all(|transaction| transaction.get_account_uid() == checking));
+        assert_eq!(bank.accounts[0].get_balance(bank.get_ledger(), &bank.currency_exchange), "2020".parse().unwrap_or_default());
+
+        assert!(bank.add_transfer(checking, savings, "500", june(5), "Saving up".to_string()).is_pass());
+        assert_eq!(bank.accounts[0].get_balance(bank.get_ledger(), &bank.currency_exchange), "1520".parse().unwrap_or_default());
+        assert_eq!(bank.accounts[1].get_balance(bank.get_ledger(), &bank.currency_exchange), "1500".parse().unwrap_or_default());
+        let running_balances = Account::get_running_balances(&bank.accounts, bank.get_ledger(), &bank.currency_exchange);
+        let salary_uid = bank.get_ledger().iter().find(|transaction| transaction.description == "Salary").map(Transaction::get_uid).unwrap_or_default();
+        assert_eq!(running_balances.get(&salary_uid), Some(&"2100".parse().unwrap_or_default()));
+
+        // both sides of the transfer point at each other and are removed together
+        let transfer_sides = bank.get_ledger().iter().filter(|transaction| transaction.is_transfer()).cloned().collect::<Vec<_>>();
+        assert_eq!(transfer_sides.len(), 2);
+        assert_eq!(transfer_sides[0].get_transfer_uid(), Some(transfer_sides[1].get_uid()));
+        let id = transfer_sides[0].get_id().unwrap_or_default();
+        assert!(bank.remove_transaction(id).is_pass());
+        assert!(!bank.get_ledger().iter().any(Transaction::is_transfer));
+
+        // accounts with transactions cannot be removed
+        assert!(bank.remove_account(checking).is_fail());
+        assert!(bank.remove_account(savings).is_pass());
+    }
+}
@@ -3,13 +3,21 @@ use rust_decimal::prelude::FromPrimitive;
 use rusty_money::iso;
 use rusty_money::iso::Currency;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::iter;
 
 use materialui::materials::MaterialColors;
+use crate::vault::account::Account;
+use crate::vault::budget::Budget;
+use crate::vault::bulk_edit::BulkActions;
 use crate::vault::filter::Filter;
 use crate::vault::parse::FlowTypes;
-use crate::vault::transaction::{Date, Id, Months, Tag, Transaction, Value};
+use crate::vault::recurring::{DueRecurrence, RecurringTemplate};
+use crate::vault::tag_rule::{TagRule, TagRuleChange};
+use crate::vault::transaction::{Date, Id, Months, Split, Tag, Transaction, Uid, Value};
+use uuid::Uuid;
 use schrod::Schrod;
-use schrod::Schrod::{Pass, Fail};
+use schrod::Schrod::Pass;
 
 /// An enumeration of the available `Filter`s.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -29,6 +37,15 @@ pub struct Bank {
     pub currency_exchange: CurrencyExchange,
     /// The `TagRegistry`.
     pub tag_registry: TagRegistry,
+    /// The `RecurringTemplate`s that create `Transaction`s on a schedule.
+    pub recurring_templates: Vec<RecurringTemplate>,
+    /// The `Budget`s that limit spending on `Tag`s.
+    pub budgets: Vec<Budget>,
+    /// The `TagRule`s that tag new `Transaction`s automatically.
+    pub tag_rules: Vec<TagRule>,
+    /// The `Account`s that the `Transaction`s belong to.
+    /// There is always at least one, and the first one is the default for new `Transaction`s.
+    pub accounts: Vec<Account>,
     /// The central `Id` tracker for new `Transaction`s.
     id_tracker: Id,
     /// The primary `Filter`.
@@ -53,6 +70,10 @@ impl Bank {
             ledger: Vec::new(),
             currency_exchange: CurrencyExchange::default(),
             tag_registry: TagRegistry::new(),
+            recurring_templates: Vec::new(),
+            budgets: Vec::new(),
+            tag_rules: Vec::new(),
+            accounts: vec![Account::default_for(CurrencyExchange::default().get_main_currency())],
             id_tracker: 0,
             primary_filter: Filter::default(),
             deep_dive_1_filter: Filter::default(),
@@ -61,13 +82,20 @@ impl Bank {
     }
 
     /// Initializes the `Bank`.
-    pub fn init(&mut self, transactions: Vec<Transaction>, currency_exchange: CurrencyExchange, tag_registry: TagRegistry) -> Schrod<()> {
+    /// Without any `Account`s, a default one is made in the main currency to hold every `Transaction`.
+    #[allow(clippy::too_many_arguments)] // one for each saved part of a bank
+    pub fn init(&mut self, transactions: Vec<Transaction>, currency_exchange: CurrencyExchange, tag_registry: TagRegistry, recurring_templates: Vec<RecurringTemplate>, budgets: Vec<Budget>, tag_rules: Vec<TagRule>, accounts: Vec<Account>) -> Schrod<()> {
+        self.accounts = if accounts.is_empty() { vec![Account::default_for(currency_exchange.get_main_currency())] } else { accounts };
         let load_result = self.load_transactions(transactions);
         if load_result.is_fail() { return load_result.fail("Failed to initialize the Bank!", "Bank::init()"); }
         let init_filter_dates_result = self.init_filter_dates();
         if init_filter_dates_result.is_fail() { return init_filter_dates_result.fail("Failed to initialize the Bank!", "Bank::init()"); }
         self.currency_exchange = currency_exchange;
         self.tag_registry = tag_registry;
+        self.recurring_templates = recurring_templates;
+        self.budgets = budgets;
+        self.tag_rules = tag_rules;
+        self.place_in_accounts();
         Pass(())
     }
     
@@ -75,10 +103,12 @@ impl Bank {
     /// This is used when loading from `SaveData`.
     #[must_use]
     pub fn load_transactions(&mut self, transactions: Vec<Transaction>) -> Schrod<()> {
-        let mut new_ledger = Vec::new();
+        let mut new_ledger: Vec<Transaction> = Vec::new();
         for mut transaction in transactions {
             let set_result = transaction.set_id(self.get_next_id()); // uses set_id() instead of override_id() to ensure proper data flow
             if set_result.is_fail() { return set_result.fail("Could not load transactions into ledger!", "Bank::load_transactions()"); }
+            // a damaged save file could repeat a uid
+            if new_ledger.iter().any(|t| t.get_uid() == transaction.get_uid()) { transaction.set_uid(Uuid::new_v4()); }
             new_ledger.push(transaction);
         }
         self.ledger = new_ledger;
@@ -87,6 +117,24 @@ impl Bank {
         Pass(())
     }
 
+    /// Merges `Transaction`s into the `Bank` alongside the existing `ledger`.
+    /// This is used when importing external data that should not replace the current `Transaction`s.
+    /// The `TagRule`s are applied to every merged `Transaction`.
+    #[must_use]
+    pub fn merge_transactions(&mut self, transactions: Vec<Transaction>) -> Schrod<()> {
+        for mut transaction in transactions {
+            TagRule::apply_all(&self.tag_rules, &mut transaction);
+            let set_result = transaction.set_id(self.get_next_id());
+            if set_result.is_fail() { return set_result.fail("Could not merge transactions into ledger!", "Bank::merge_transactions()"); }
+            // an imported copy of a local transaction is kept as a separate transaction
+            if self.contains_uid(transaction.get_uid()) { transaction.set_uid(Uuid::new_v4()); }
+            self.ledger.push(transaction);
+        }
+        let filter_result = self.refilter();
+        if filter_result.is_fail() { return filter_result.fail("Could not filter the merged ledger.", "Bank::merge_transactions()"); }
+        Pass(())
+    }
+
 
 
     // management
@@ -118,14 +166,25 @@ impl Bank {
     #[must_use]
     pub fn sorted_ledger(ledger: Vec<Transaction>) -> Vec<Transaction> {
         let mut ledger = ledger;
-        ledger.sort_by(|a, b| b.date.as_value().cmp(&a.date.as_value()));
+        ledger.sort_by_key(|t| std::cmp::Reverse(t.date.as_value()));
         ledger
     }
 
+    /// Places every `Transaction` and `RecurringTemplate` that does not belong to a known `Account` in the default `Account`.
+    fn place_in_accounts(&mut self) {
+        let default_account_uid = self.get_default_account_uid();
+        for transaction in &mut self.ledger {
+            if !self.accounts.iter().any(|account| account.get_uid() == transaction.get_account_uid()) { transaction.set_account_uid(default_account_uid); }
+        }
+        for template in &mut self.recurring_templates {
+            if !self.accounts.iter().any(|account| account.get_uid() == template.get_account_uid()) { template.set_account_uid(default_account_uid); }
+        }
+    }
+
     /// Sorts the `ledger` by `Date`.
     fn sort_ledger(&mut self) {
         // I could duplicate sorted_ledger() here, but this is faster
-        self.ledger.sort_by(|a, b| b.date.as_value().cmp(&a.date.as_value()));
+        self.ledger.sort_by_key(|t| std::cmp::Reverse(t.date.as_value()));
     }
 
     /// Adds a new `Transaction` from concrete values.
@@ -149,14 +208,19 @@ impl Bank {
         }
     }
 
-    /// Creates a new `Transaction` from raw data parts.
+    /// Creates a new `Transaction` in an `Account` from raw data parts and applies the `TagRule`s to it.
     /// This is intended to be used when a new `Transaction` is created from within the `App`.
     #[must_use]
-    pub fn add_transaction_from_raw_parts(&mut self, value_string: &str, currency_string: &str, date: Date, description: String, tags: Vec<Tag>) -> Schrod<()> {
+    #[allow(clippy::too_many_arguments)] // one for each part of a new transaction
+    pub fn add_transaction_from_raw_parts(&mut self, value_string: &str, currency_string: &str, date: Date, description: String, tags: Vec<Tag>, splits: Vec<Split>, account_uid: Uid) -> Schrod<()> {
         let id = self.get_next_id();
         let transaction_result = Transaction::new_from_raw(id, value_string, currency_string, date, description, tags);
         
-        if let Pass(transaction) = transaction_result {
+        if let Pass(mut transaction) = transaction_result {
+            let split_result = transaction.set_splits(splits);
+            if split_result.is_fail() { return split_result.fail("Failed to add a new transaction from raw parts.", "Bank::add_transaction_from_raw_parts()") }
+            transaction.set_account_uid(account_uid);
+            TagRule::apply_all(&self.tag_rules, &mut transaction);
             self.ledger.push(transaction);
             let filter_result = self.refilter();
             if filter_result.is_fail() { return filter_result; }
@@ -171,16 +235,21 @@ impl Bank {
     }
 
     /// Edits a `Transaction` with raw parts.
+    /// Nothing is changed unless both the parts and the `Split`s are valid.
     #[must_use]
-    pub fn edit_transaction_with_raw_parts(&mut self, id: Id, value_string: &str, currency_string: &str, date: Date, description: String, tags: Vec<Tag>) -> Schrod<()> {
+    #[allow(clippy::too_many_arguments)] // one for each editable part of a transaction
+    pub fn edit_transaction_with_raw_parts(&mut self, id: Id, value_string: &str, currency_string: &str, date: Date, description: String, tags: Vec<Tag>, splits: Vec<Split>, account_uid: Uid) -> Schrod<()> {
         let transaction_result = self.get_mut(id);
         
         if let Pass(transaction) = transaction_result {
-            let edit_result = transaction.edit_with_raw_parts(value_string, currency_string, date, description, tags);
-            match edit_result {
-                Pass(()) => { self.refilter() }
-                Fail(_) => { edit_result }
-            }
+            let mut edited_transaction = transaction.clone();
+            let edit_result = edited_transaction.edit_with_raw_parts(value_string, currency_string, date, description, tags);
+            if edit_result.is_fail() { return edit_result }
+            let split_result = edited_transaction.set_splits(splits);
+            if split_result.is_fail() { return split_result.fail("Failed to edit a transaction with raw parts.", "Bank::edit_transaction_with_raw_parts()") }
+            edited_transaction.set_account_uid(account_uid);
+            *transaction = edited_transaction;
+            self.refilter()
         }
         
         else {
@@ -191,12 +260,14 @@ impl Bank {
     }
 
     /// Removes a `Transaction` from the `ledger`.
+    /// Removing one side of a transfer removes the other side as well.
     #[must_use]
     pub fn remove_transaction(&mut self, id: Id) -> Schrod<()> {
         for i in 0..self.ledger.len() {
             let transaction = &mut self.ledger[i];
             if let Some(transaction_id) = transaction.get_id() && transaction_id == id {
-                self.ledger.remove(i);
+                let removed_transaction = self.ledger.remove(i);
+                if let Some(transfer_uid) = removed_transaction.get_transfer_uid() { self.ledger.retain(|t| t.get_uid() != transfer_uid); }
                 let filter_result = self.refilter();
                 if filter_result.is_fail() { return filter_result; }
                 return Pass(());
@@ -206,6 +277,287 @@ impl Bank {
         Schrod::new_fail("Transaction could not be found!", "Bank::remove_transaction()")
     }
     
+    /// Removes, adds back, and replaces `Transaction`s by their `Uid`s.
+    /// Unlike adding new `Transaction`s, this keeps their `Uid`s so that changes to the `ledger` can be undone and redone.
+    #[must_use]
+    pub fn apply_ledger_changes(&mut self, removed_uids: &[Uid], added_transactions: Vec<Transaction>, replaced_transactions: Vec<Transaction>) -> Schrod<()> {
+        self.ledger.retain(|transaction| !removed_uids.contains(&transaction.get_uid()));
+        for mut transaction in added_transactions {
+            transaction.override_id(self.get_next_id());
+            self.ledger.push(transaction);
+        }
+        for mut transaction in replaced_transactions {
+            let Some(existing) = self.ledger.iter_mut().find(|t| t.get_uid() == transaction.get_uid()) else {
+                return Schrod::new_fail("The transaction to replace could not be found!", "Bank::apply_ledger_changes()")
+            };
+            if let Some(id) = existing.get_id() { transaction.override_id(id); }
+            *existing = transaction;
+        }
+        // the changes can bring back or take away tags that the filters use
+        self.verify_filtered_tags()
+    }
+    
+    /// Adds a `RecurringTemplate`.
+    pub fn add_recurring_template(&mut self, recurring_template: RecurringTemplate) {
+        self.recurring_templates.push(recurring_template);
+    }
+
+    /// Removes the `RecurringTemplate` with the given `Uid`.
+    #[must_use]
+    pub fn remove_recurring_template(&mut self, uid: Uid) -> Schrod<()> {
+        let count = self.recurring_templates.len();
+        self.recurring_templates.retain(|template| template.get_uid() != uid);
+        if self.recurring_templates.len() == count { return Schrod::new_fail("RecurringTemplate could not be found!", "Bank::remove_recurring_template()") }
+        Pass(())
+    }
+
+    /// Adds a `Transaction` for the earliest due `Date` of a `RecurringTemplate` and moves the template on to its next `Date`.
+    #[must_use]
+    pub fn confirm_recurrence(&mut self, uid: Uid) -> Schrod<()> {
+        let template_result = self.get_recurring_template(uid);
+        if template_result.is_fail() {
+            return template_result
+                .convert("Bank::confirm_recurrence()")
+                .fail("Failed to confirm a recurring Transaction.", "Bank::confirm_recurrence()")
+        }
+        let template = template_result.wont_fail("This is past an is_fail() guard clause.", "Bank::confirm_recurrence()").clone();
+        
+        let add_result = self.add_transaction_from_raw_parts(&template.get_value_string(), template.get_currency_string(), template.get_next_date(), template.description.clone(), template.tags.clone(), template.get_splits().to_vec(), template.get_account_uid());
+        if add_result.is_fail() { return add_result.fail("Failed to confirm a recurring Transaction.", "Bank::confirm_recurrence()") }
+        self.skip_recurrence(uid)
+    }
+
+    /// Moves a `RecurringTemplate` on to its next `Date` without adding a `Transaction`.
+    #[must_use]
+    pub fn skip_recurrence(&mut self, uid: Uid) -> Schrod<()> {
+        match self.recurring_templates.iter_mut().find(|template| template.get_uid() == uid) {
+            Some(template) => {
+                template.advance();
+                Pass(())
+            }
+            None => Schrod::new_fail("RecurringTemplate could not be found!", "Bank::skip_recurrence()"),
+        }
+    }
+    
+    /// Renames a `Tag` and its children everywhere it is used, including the `TagRegistry` and every `Filter`.
+    /// Fails if the new `Tag` is already in use, since that would be a merge.
+    #[must_use]
+    pub fn rename_tag(&mut self, old_tag: &Tag, new_tag: &Tag) -> Schrod<()> {
+        let tags = self.get_tags();
+        if old_tag.contains(new_tag) { return Schrod::new_fail("A tag cannot be renamed to itself or one of its children!", "Bank::rename_tag()") }
+        if !tags.contains(old_tag) { return Schrod::new_fail(&format!("There is no tag called \"{}\" to rename!", old_tag.get_label()), "Bank::rename_tag()") }
+        if tags.contains(new_tag) { return Schrod::new_fail(&format!("The tag \"{}\" already exists, so it can only be merged into!", new_tag.get_label()), "Bank::rename_tag()") }
+        
+        let replace_result = self.replace_tag(old_tag, new_tag);
+        if replace_result.is_fail() { return replace_result.fail("Failed to rename tag.", "Bank::rename_tag()") }
+        Pass(())
+    }
+    
+    /// Merges a `Tag` and its children into another existing `Tag` everywhere it is used.
+    /// The merged `Tag` keeps the color of the `Tag` it is merged into, unless that one has no color of its own.
+    #[must_use]
+    pub fn merge_tags(&mut self, source_tag: &Tag, target_tag: &Tag) -> Schrod<()> {
+        let tags = self.get_tags();
+        if source_tag.contains(target_tag) { return Schrod::new_fail("A tag cannot be merged into itself or one of its children!", "Bank::merge_tags()") }
+        if !tags.contains(source_tag) { return Schrod::new_fail(&format!("There is no tag called \"{}\" to merge!", source_tag.get_label()), "Bank::merge_tags()") }
+        if !tags.contains(target_tag) { return Schrod::new_fail(&format!("There is no tag called \"{}\" to merge into!", target_tag.get_label()), "Bank::merge_tags()") }
+        
+        let replace_result = self.replace_tag(source_tag, target_tag);
+        if replace_result.is_fail() { return replace_result.fail("Failed to merge tags.", "Bank::merge_tags()") }
+        Pass(())
+    }
+    
+    /// Replaces a `Tag` and its children with another `Tag` on every `Transaction`, `RecurringTemplate`, `Budget`, `TagRule`, and `Filter`,
+    /// and combines their `TagRegistration`s.
+    #[must_use]
+    fn replace_tag(&mut self, old_tag: &Tag, new_tag: &Tag) -> Schrod<()> {
+        for transaction in &mut self.ledger {
+            transaction.replace_tag(old_tag, new_tag);
+        }
+        for template in &mut self.recurring_templates {
+            template.replace_tag(old_tag, new_tag);
+        }
+        for budget in &mut self.budgets {
+            budget.tags = Tag::replaced_in(&budget.tags, old_tag, new_tag);
+        }
+        for tag_rule in &mut self.tag_rules {
+            tag_rule.tags = Tag::replaced_in(&tag_rule.tags, old_tag, new_tag);
+        }
+        self.tag_registry.replace_tag(old_tag, new_tag);
+        
+        let filter_results = vec![
+            self.primary_filter.replace_tag(old_tag, new_tag, &self.ledger),
+            self.deep_dive_1_filter.replace_tag(old_tag, new_tag, &self.ledger),
+            self.deep_dive_2_filter.replace_tag(old_tag, new_tag, &self.ledger),
+        ];
+        if Schrod::contains_fail(&filter_results) {
+            return Schrod::collect_and_fail(&filter_results, "Bank::replace_tag()")
+                .convert("Bank::replace_tag()")
+        }
+        
+        Pass(())
+    }
+    
+    /// Adds a `Budget`.
+    pub fn add_budget(&mut self, budget: Budget) {
+        self.budgets.push(budget);
+    }
+
+    /// Removes the `Budget` with the given `Uid`.
+    #[must_use]
+    pub fn remove_budget(&mut self, uid: Uid) -> Schrod<()> {
+        let count = self.budgets.len();
+        self.budgets.retain(|budget| budget.get_uid() != uid);
+        if self.budgets.len() == count { return Schrod::new_fail("Budget could not be found!", "Bank::remove_budget()") }
+        Pass(())
+    }
+    
+    /// Adds a `TagRule`.
+    /// It only applies to `Transaction`s added from now on, until the `TagRule`s are re-run over the `ledger`.
+    pub fn add_tag_rule(&mut self, tag_rule: TagRule) {
+        self.tag_rules.push(tag_rule);
+    }
+
+    /// Removes the `TagRule` with the given `Uid`.
+    /// `Tag`s it already added are kept.
+    #[must_use]
+    pub fn remove_tag_rule(&mut self, uid: Uid) -> Schrod<()> {
+        let count = self.tag_rules.len();
+        self.tag_rules.retain(|tag_rule| tag_rule.get_uid() != uid);
+        if self.tag_rules.len() == count { return Schrod::new_fail("TagRule could not be found!", "Bank::remove_tag_rule()") }
+        Pass(())
+    }
+
+    /// Applies the `TagRule`s to every `Transaction` in the `ledger`.
+    /// Returns how many `Transaction`s were changed.
+    #[must_use]
+    pub fn rerun_tag_rules(&mut self) -> Schrod<usize> {
+        let mut changed_count = 0;
+        for transaction in &mut self.ledger {
+            if TagRule::apply_all(&self.tag_rules, transaction) { changed_count += 1; }
+        }
+        let filter_result = self.refilter();
+        if filter_result.is_fail() {
+            return filter_result
+                .convert("Bank::rerun_tag_rules()")
+                .fail("Failed to re-run the tag rules.", "Bank::rerun_tag_rules()")
+        }
+        Pass(changed_count)
+    }
+
+    /// Makes one `BulkActions` change to every `Transaction` with one of the given `Uid`s.
+    /// Deleting or shifting the date of one side of a transfer does the same to the other side,
+    /// and the `Currency` of a transfer cannot be changed, since each side uses the `Currency` of its `Account`.
+    /// Returns how many `Transaction`s were changed or deleted.
+    #[must_use]
+    pub fn bulk_edit(&mut self, uids: &[Uid], action: &BulkActions) -> Schrod<usize> {
+        let selected_transactions = self.ledger.iter().filter(|transaction| uids.contains(&transaction.get_uid()));
+        if matches!(action, BulkActions::SetCurrency(_)) && selected_transactions.clone().any(Transaction::is_transfer) {
+            return Schrod::new_fail("The currency of a transfer cannot be changed, since it follows the currency of its account. Deselect the transfers and try again.", "Bank::bulk_edit()")
+                .fail("Failed to bulk edit transactions.", "Bank::bulk_edit()")
+        }
+
+        // the other sides of deleted or shifted transfers are changed as well
+        let mut affected_uids = uids.to_vec();
+        if matches!(action, BulkActions::Delete | BulkActions::ShiftDate(_)) {
+            let transfer_uids = selected_transactions.filter_map(Transaction::get_transfer_uid).collect::<Vec<_>>();
+            for transfer_uid in transfer_uids {
+                if !affected_uids.contains(&transfer_uid) { affected_uids.push(transfer_uid); }
+            }
+        }
+
+        let mut changed_count = 0;
+        if *action == BulkActions::Delete {
+            let count = self.ledger.len();
+            self.ledger.retain(|transaction| !affected_uids.contains(&transaction.get_uid()));
+            changed_count = count - self.ledger.len();
+        }
+        else {
+            for transaction in self.ledger.iter_mut().filter(|transaction| affected_uids.contains(&transaction.get_uid())) {
+                if action.apply(transaction) { changed_count += 1; }
+            }
+        }
+
+        // the changes can take away tags that the filters use
+        let filter_result = self.verify_filtered_tags();
+        if filter_result.is_fail() {
+            return filter_result
+                .convert("Bank::bulk_edit()")
+                .fail("Failed to bulk edit transactions.", "Bank::bulk_edit()")
+        }
+        Pass(changed_count)
+    }
+
+    /// Adds an `Account`.
+    pub fn add_account(&mut self, account: Account) {
+        self.accounts.push(account);
+    }
+
+    /// Removes the `Account` with the given `Uid`.
+    /// `Account`s that still have `Transaction`s, and the last `Account`, cannot be removed.
+    #[must_use]
+    pub fn remove_account(&mut self, uid: Uid) -> Schrod<()> {
+        if !self.can_remove_account(uid) { return Schrod::new_fail("Only accounts without any transactions can be removed, and there must always be one left.", "Bank::remove_account()") }
+        self.accounts.retain(|account| account.get_uid() != uid);
+        self.place_in_accounts();
+        Pass(())
+    }
+
+    /// Moves an amount from one `Account` to another as a pair of linked `Transaction`s.
+    /// The amount is in the `Currency` of the `Account` it leaves, and is converted if the other `Account` uses a different one.
+    #[must_use]
+    pub fn add_transfer(&mut self, from_account_uid: Uid, to_account_uid: Uid, amount_string: &str, date: Date, description: String) -> Schrod<()> {
+        if from_account_uid == to_account_uid { return Schrod::new_fail("A transfer needs two different accounts.", "Bank::add_transfer()") }
+        let accounts_result = Schrod::from_option(
+            self.get_account(from_account_uid).zip(self.get_account(to_account_uid)),
+            "The accounts of the transfer could not be found!",
+            "Bank::add_transfer()",
+        );
+        if accounts_result.is_fail() {
+            return accounts_result
+                .convert("Bank::add_transfer()")
+                .fail("Failed to add transfer.", "Bank::add_transfer()")
+        }
+        let (from_account, to_account) = accounts_result.wont_fail("This is past an is_fail() guard clause.", "Bank::add_transfer()");
+        let (from_currency_string, to_currency_string) = (from_account.get_currency_string().to_string(), to_account.get_currency_string().to_string());
+
+        // the amount arriving in the other account's currency
+        let amount_result = Schrod::from_result(Decimal::from_str_exact(amount_string.trim()), "The transfer amount is not a number.", "Bank::add_transfer()");
+        let currencies_result = Schrod::from_option(iso::find(&from_currency_string).zip(iso::find(&to_currency_string)), "The accounts of the transfer have invalid currencies.", "Bank::add_transfer()");
+        if amount_result.is_fail() || currencies_result.is_fail() {
+            return Schrod::collect_and_fail(&[amount_result.convert("Bank::add_transfer()"), currencies_result.convert("Bank::add_transfer()")], "Bank::add_transfer()")
+                .fail("Failed to add transfer.", "Bank::add_transfer()")
+        }
+        let amount = amount_result.wont_fail("This is past an is_fail() guard clause.", "Bank::add_transfer()").abs();
+        let (from_currency, to_currency) = currencies_result.wont_fail("This is past an is_fail() guard clause.", "Bank::add_transfer()");
+        if amount.is_zero() { return Schrod::new_fail("A transfer needs an amount.", "Bank::add_transfer()") }
+        let converted_result = self.currency_exchange.convert(&amount, from_currency, to_currency, date);
+        if converted_result.is_fail() {
+            return converted_result
+                .convert("Bank::add_transfer()")
+                .fail("Failed to add transfer.", "Bank::add_transfer()")
+        }
+        let converted_amount = converted_result.wont_fail("This is past an is_fail() guard clause.", "Bank::add_transfer()");
+
+        // the pair of transactions
+        let tags = vec![Transaction::transfer_tag()];
+        let outgoing_result = Transaction::new_from_parts(self.get_next_id(), Value::from_decimal(-amount, from_currency), date, description.clone(), tags.clone());
+        let incoming_result = Transaction::new_from_parts(self.get_next_id(), Value::from_decimal(converted_amount, to_currency), date, description, tags);
+        if outgoing_result.is_fail() || incoming_result.is_fail() {
+            return Schrod::collect_and_fail(&[outgoing_result.convert("Bank::add_transfer()"), incoming_result.convert("Bank::add_transfer()")], "Bank::add_transfer()")
+                .fail("Failed to add transfer.", "Bank::add_transfer()")
+        }
+        let mut outgoing = outgoing_result.wont_fail("This is past an is_fail() guard clause.", "Bank::add_transfer()");
+        let mut incoming = incoming_result.wont_fail("This is past an is_fail() guard clause.", "Bank::add_transfer()");
+        outgoing.set_account_uid(from_account_uid);
+        incoming.set_account_uid(to_account_uid);
+        outgoing.set_transfer_uid(Some(incoming.get_uid()));
+        incoming.set_transfer_uid(Some(outgoing.get_uid()));
+        self.ledger.push(outgoing);
+        self.ledger.push(incoming);
+        self.refilter()
+    }
+
     /// Returns an updated `TagRegistry` to match the current `Tag`s in the `ledger`.
     #[must_use]
     pub fn get_updated_tag_registry(tag_registry: TagRegistry, tags: Vec<Tag>) -> TagRegistry {
@@ -223,6 +575,26 @@ impl Bank {
         &mut self.ledger
     }
 
+    /// Returns the `Account` with the given `Uid`, if there is one.
+    #[must_use]
+    pub fn get_account(&self, uid: Uid) -> Option<&Account> {
+        self.accounts.iter().find(|account| account.get_uid() == uid)
+    }
+
+    /// Returns the `Uid` of the `Account` that new `Transaction`s go into unless another one is chosen.
+    #[must_use]
+    pub fn get_default_account_uid(&self) -> Uid {
+        self.accounts.first().map(Account::get_uid).unwrap_or_default()
+    }
+
+    /// Checks if an `Account` can be removed, which requires it to have no `Transaction`s and not be the last `Account`.
+    #[must_use]
+    pub fn can_remove_account(&self, uid: Uid) -> bool {
+        self.accounts.len() > 1
+            && self.get_account(uid).is_some()
+            && !self.ledger.iter().any(|transaction| transaction.get_account_uid() == uid)
+    }
+
     /// Returns an immutable reference to the `ledger`.
     #[must_use]
     pub fn get_ledger(&self) -> &Vec<Transaction> {
@@ -253,22 +625,87 @@ impl Bank {
         Schrod::new_fail("Transaction could not be found!", "Bank::get_mut()")
     }
 
+    /// Returns an immutable reference to the `Transaction` with the given `Uid`.
+    #[must_use]
+    pub fn get_by_uid(&self, uid: Uid) -> Schrod<&Transaction> {
+        match self.ledger.iter().find(|t| t.get_uid() == uid) {
+            Some(transaction) => Pass(transaction),
+            None => Schrod::new_fail(&format!("Could not find transaction of uid: {uid}."), "Bank::get_by_uid()"),
+        }
+    }
+
+    /// Returns whether a `Transaction` with the given `Uid` is in the `ledger`.
+    #[must_use]
+    pub fn contains_uid(&self, uid: Uid) -> bool {
+        self.ledger.iter().any(|t| t.get_uid() == uid)
+    }
+
     /// Gets the `Id`s from a list of `Transaction`s.
     #[must_use]
     pub fn get_ids_from(transactions: &Vec<&Transaction>) -> Vec<Id> {
         transactions.iter().filter_map(|t| t.get_id()).collect()
     }
 
-    /// Returns a list of existing `Tag`s
+    /// Returns an immutable reference to the `RecurringTemplate` with the given `Uid`.
+    #[must_use]
+    pub fn get_recurring_template(&self, uid: Uid) -> Schrod<&RecurringTemplate> {
+        match self.recurring_templates.iter().find(|template| template.get_uid() == uid) {
+            Some(template) => Pass(template),
+            None => Schrod::new_fail(&format!("Could not find recurring template of uid: {uid}."), "Bank::get_recurring_template()"),
+        }
+    }
+
+    /// Returns every `DueRecurrence` up to and including the given `Date`, oldest first.
+    #[must_use]
+    pub fn get_due_recurrences(&self, until: Date) -> Vec<DueRecurrence> {
+        let mut due_recurrences = self.recurring_templates
+            .iter()
+            .flat_map(|template| template.get_due_dates(until).into_iter().enumerate().map(|(i, date)| DueRecurrence { template_uid: template.get_uid(), date, is_next: i == 0 }))
+            .collect::<Vec<_>>();
+        due_recurrences.sort_by_key(|due_recurrence| due_recurrence.date.as_value());
+        due_recurrences
+    }
+
+    /// Returns the currency codes used by `Budget`s, which need exchange rates even if no `Transaction` uses them.
+    #[must_use]
+    pub fn get_budget_currencies(&self) -> Vec<String> {
+        self.budgets.iter().map(|budget| budget.get_currency_string().to_string()).collect()
+    }
+
+    /// Returns the changes that re-running the `TagRule`s would make to the `ledger`, without making them.
+    #[must_use]
+    pub fn get_tag_rule_changes(&self) -> Vec<TagRuleChange> {
+        self.ledger.iter()
+            .filter_map(|transaction| {
+                let added_tags = TagRule::get_added_tags(&self.tag_rules, transaction);
+                if added_tags.is_empty() { return None }
+                Some(TagRuleChange { id: transaction.get_id()?, description: transaction.description.clone(), added_tags })
+            })
+            .collect()
+    }
+
+    /// Returns a list of existing `Tag`s, along with the parents of any child `Tag`s.
     #[must_use]
     pub fn get_tags(&self) -> Vec<Tag> {
         let mut tags = Vec::new();
         for transaction in &self.ledger {
-            tags.extend(transaction.tags.clone());
+            tags.extend(transaction.get_all_tags());
         }
-        Tag::sorted(&tags)
+        Tag::with_ancestors(&tags)
     }
-    
+
+    /// Returns how many `Transaction`s have the given `Tag` or one of its children.
+    #[must_use]
+    pub fn count_transactions_with_tag(&self, tag: &Tag) -> usize {
+        self.ledger.iter().filter(|transaction| transaction.has_tag(tag)).count()
+    }
+
+    /// Returns whether a `Transaction` with the given statement identifier is already in the `ledger`.
+    #[must_use]
+    pub fn contains_statement_id(&self, statement_id: &str) -> bool {
+        self.ledger.iter().any(|t| t.get_statement_id() == Some(statement_id))
+    }
+
     /// Gets the `Date` of the latest `Transaction` in the ledger.
     /// If the `ledger` is empty, this returns the default `Date`.
     #[must_use]
@@ -473,8 +910,10 @@ impl Bank {
     }
     
     /// Refilters the `Transaction`s in the three `Bank`'s `Filter`s.
+    /// Any `Transaction` that does not belong to a known `Account` yet, such as a new or imported one, is placed in the default `Account` first.
     #[must_use]
     fn refilter(&mut self) -> Schrod<()> {
+        self.place_in_accounts();
         self.sort_ledger();
         
         let primary_filter_result = self.primary_filter.filter(&self.ledger);
@@ -492,26 +931,38 @@ impl Bank {
 
 
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ExchangeRateStatus {
     Valid,
     Warning,
+    /// The `ExchangeRate` has no rates of its own, but can be derived from other `ExchangeRate`s.
+    Derived,
     Invalid,
 }
 
 
 
-/// Defines an exchange rate for converting different `Currency`s.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A conversion multiplier that applied from a `Date` onwards, until the next one in the history of its `ExchangeRate`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DatedRate {
+    /// The `Date` the rate started applying.
+    pub date: Date,
+    /// The conversion multiplier.
+    pub rate: Decimal,
+}
+
+
+
+/// Defines an exchange rate for converting different `Currency`s, along with the history of its past rates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExchangeRate {
     /// The starting `Currency`.
     from_currency_string: String,
     /// The target `Currency`.
     to_currency_string: String,
-    /// The conversion multiplier.
-    rate: Decimal,
-    /// The `Date` the `ExchangeRate` was last updated.
-    date: Date,
+    /// The conversion multipliers over time, sorted from oldest to newest.
+    /// Every rate in the history is greater than zero.
+    history: Vec<DatedRate>,
     /// The status of the `ExchangeRate`.
     status: ExchangeRateStatus,
     /// Tracks the state of a new rate as it is being added.
@@ -530,16 +981,38 @@ impl ExchangeRate {
         &self.to_currency_string
     }
 
-    /// Gets the conversion rate as a `Decimal`.
+    /// Gets the latest conversion rate as a `Decimal`, or zero if no rate has been set yet.
     #[must_use]
     pub fn get_rate(&self) -> Decimal {
-        self.rate
+        self.history.last().map_or(Decimal::ZERO, |dated_rate| dated_rate.rate)
+    }
+
+    /// Gets the conversion rate that applied on a `Date`, which is the nearest earlier one in the history.
+    /// `Date`s before the start of the history use the oldest rate, since it is the closest one known.
+    #[must_use]
+    pub fn get_rate_on(&self, date: Date) -> Option<Decimal> {
+        self.history.iter().rev()
+            .find(|dated_rate| dated_rate.date.as_value() <= date.as_value())
+            .or(self.history.first())
+            .map(|dated_rate| dated_rate.rate)
+    }
+
+    /// Checks if a rate had been set on or before the given `Date`.
+    #[must_use]
+    pub fn has_rate_on(&self, date: Date) -> bool {
+        self.history.first().is_some_and(|dated_rate| dated_rate.date.as_value() <= date.as_value())
     }
 
-    /// Gets the `Date` the `ExchangeRate` was last updated.
+    /// Gets the history of conversion rates, sorted from oldest to newest.
     #[must_use]
-    pub fn get_date(&self) -> Date {
-        self.date
+    pub fn get_history(&self) -> &[DatedRate] {
+        &self.history
+    }
+
+    /// Gets the `Date` the `ExchangeRate` was last updated, or `None` if no rate has been set yet.
+    #[must_use]
+    pub fn get_date(&self) -> Option<Date> {
+        self.history.last().map(|dated_rate| dated_rate.date)
     }
 
     /// Gets the status of the `ExchangeRate`.
@@ -548,24 +1021,23 @@ impl ExchangeRate {
         self.status
     }
 
-    /// Gets the age of the `ExchangeRate` in days.
+    /// Gets the age of the latest rate of the `ExchangeRate` in days.
     #[must_use]
     pub fn get_age(&self) -> usize {
         let today_result = Date::today();
-        match today_result {
-            Schrod::Pass(today) => today.get_days_between(self.date),
-            Schrod::Fail(_) => 999,
+        match (today_result, self.get_date()) {
+            (Schrod::Pass(today), Some(date)) => today.get_days_between(date),
+            _ => 999,
         }
     }
     
-    /// Creates a new `ExchangeRate`.
+    /// Creates a new `ExchangeRate` without any rates.
     #[must_use]
-    fn new(from: &str, to: &str, rate: Decimal, date: Date) -> ExchangeRate {
+    fn new(from: &str, to: &str) -> ExchangeRate {
         let mut exchange_rate = ExchangeRate {
             from_currency_string: from.to_uppercase(),
             to_currency_string: to.to_uppercase(),
-            rate,
-            date,
+            history: Vec::new(),
             status: ExchangeRateStatus::Invalid,
             new_rate_string: String::new(),
         };
@@ -573,10 +1045,39 @@ impl ExchangeRate {
         exchange_rate
     }
 
+    /// Sets the rate that applied from a `Date` onwards, replacing any rate already set on that `Date`.
+    /// Please note that the rate must be greater than zero.
+    fn set_rate_on(&mut self, date: Date, rate: Decimal) {
+        self.history.retain(|dated_rate| dated_rate.date != date);
+        let index = self.history.partition_point(|dated_rate| dated_rate.date.as_value() < date.as_value());
+        self.history.insert(index, DatedRate { date, rate });
+        self.validate();
+    }
+
+    /// Sets many rates at once, replacing any rates already set on the same `Date`s.
+    /// Please note that the rates must be greater than zero.
+    fn merge_rates(&mut self, dated_rates: &[DatedRate]) {
+        let new_dates = dated_rates.iter().map(|dated_rate| dated_rate.date.as_value()).collect::<HashSet<u32>>();
+        self.history.retain(|dated_rate| !new_dates.contains(&dated_rate.date.as_value()));
+        self.history.extend_from_slice(dated_rates);
+        self.history.sort_by_key(|dated_rate| dated_rate.date.as_value());
+        self.history.dedup_by_key(|dated_rate| dated_rate.date.as_value());
+        self.validate();
+    }
+
+    /// Removes the rate set on a `Date`.
+    /// Returns whether there was one to remove.
+    fn remove_rate_on(&mut self, date: Date) -> bool {
+        let length = self.history.len();
+        self.history.retain(|dated_rate| dated_rate.date != date);
+        self.validate();
+        self.history.len() != length
+    }
+
     /// Returns whether the `ExchangeRate` is valid.
     #[must_use]
     pub fn is_valid(&self) -> bool {
-        self.rate > Decimal::from(0)
+        !self.history.is_empty()
     }
 
     /// Returns whether the `ExchangeRate` is fresh (within the last 30 days).
@@ -588,7 +1089,13 @@ impl ExchangeRate {
     /// Returns whether the `new_rate_string` can parsed into a valid `Decimal` rate.
     #[must_use]
     pub fn is_new_rate_string_valid(&self) -> bool {
-        let f64_result = self.new_rate_string.parse::<f64>();
+        ExchangeRate::is_rate_string_valid(&self.new_rate_string)
+    }
+
+    /// Returns whether a `String` can be parsed into a valid `Decimal` rate.
+    #[must_use]
+    pub fn is_rate_string_valid(rate_string: &str) -> bool {
+        let f64_result = rate_string.parse::<f64>();
         match f64_result {
             Ok(f64) => {
                 let decimal_result = Decimal::from_f64(f64);
@@ -618,14 +1125,28 @@ impl ExchangeRate {
 
 
 
+/// An hourly wage that applied from a `Date` onwards, until the next one in the time price history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatedTimePrice {
+    /// The `Date` the time price started applying.
+    pub date: Date,
+    /// The amount earned per hour, which is always greater than zero.
+    pub price: Decimal,
+    /// The code of the `Currency` the wage is earned in, or `None` to follow the main `Currency`.
+    pub currency_string: Option<String>,
+}
+
+
+
 /// Holds all the `ExchangeRate`s used by the `Bank` and how old they are.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CurrencyExchange {
     /// The `Currency` to use when none are specified.
     main_currency_string: String,
-    /// The time price (dollars earned per hour).
+    /// The time prices (money earned per hour) over time, sorted from oldest to newest.
     /// This is based on the idea that money can be represented as time since money is earned from time spent.
-    time_price: Decimal,
+    /// Without any, one unit of the main `Currency` is treated as an hour.
+    time_prices: Vec<DatedTimePrice>,
     /// Determines how to display `CashFlow`s.
     flow_type: FlowTypes,
     /// The list of `ExchangeRate`s used by the `CurrencyExchange`.
@@ -633,7 +1154,7 @@ pub struct CurrencyExchange {
 }
 impl Default for CurrencyExchange {
     fn default() -> CurrencyExchange {
-        CurrencyExchange { main_currency_string: "USD".to_string(), flow_type: FlowTypes::Collected, time_price: Decimal::from(1), rates: Vec::new() }
+        CurrencyExchange { main_currency_string: "USD".to_string(), flow_type: FlowTypes::Collected, time_prices: Vec::new(), rates: Vec::new() }
     }
 }
 impl CurrencyExchange {
@@ -657,13 +1178,33 @@ impl CurrencyExchange {
         }
     }
 
-    /// Gets the time price.
+    /// Gets the latest time price.
     #[must_use]
     pub fn get_time_price(&self) -> Decimal {
-        self.time_price
+        self.time_prices.last().map_or(Decimal::ONE, |time_price| time_price.price)
+    }
+
+    /// Gets the `Currency` of the latest time price.
+    #[must_use]
+    pub fn get_time_price_currency(&self) -> &'static Currency {
+        self.get_currency_of(self.time_prices.last())
+    }
+
+    /// Gets the history of time prices, sorted from oldest to newest.
+    #[must_use]
+    pub fn get_time_prices(&self) -> &[DatedTimePrice] {
+        &self.time_prices
+    }
+
+    /// Returns the time price that applied on the given `Date`.
+    /// This is the newest one set on or before the `Date`, or the oldest one if the `Date` comes before all of them.
+    #[must_use]
+    pub fn get_time_price_on(&self, date: Date) -> Option<&DatedTimePrice> {
+        let index = self.time_prices.partition_point(|time_price| time_price.date.as_value() <= date.as_value());
+        self.time_prices.get(index.saturating_sub(1))
     }
     
-    /// Sets the time price from a string.
+    /// Sets today's time price from a string, in the `Currency` of the latest time price.
     #[must_use]
     pub fn set_time_price(&mut self, time_price_string: &str) -> Schrod<()> {
         let decimal_result = Schrod::from_result(time_price_string.parse::<Decimal>(), "Failed to convert time price string to Decimal!", "CurrencyExchange::set_time_price()");
@@ -673,15 +1214,60 @@ impl CurrencyExchange {
                 .fail("Failed to set time price.", "CurrencyExchange::set_time_price()")
         }
         let time_price = decimal_result.wont_fail("This is past an is_guard clause.", "CurrencyExchange::set_time_price()");
-        
-        if time_price <= Decimal::ZERO {
-            return Schrod::new_fail("Time price must be greater than zero.", "CurrencyExchange::set_time_price()")
+
+        let today_result = Date::today();
+        if today_result.is_fail() {
+            return today_result
+                .convert("CurrencyExchange::set_time_price()")
                 .fail("Failed to set time price.", "CurrencyExchange::set_time_price()")
         }
-        
-        self.time_price = time_price;
+        let today = today_result.wont_fail("This is past an is_fail() guard clause.", "CurrencyExchange::set_time_price()");
+
+        let currency_string = self.time_prices.last().and_then(|time_price| time_price.currency_string.clone());
+        let set_result = self.set_time_price_on(time_price, currency_string.as_deref(), today);
+        if set_result.is_fail() { return set_result.fail("Failed to set time price.", "CurrencyExchange::set_time_price()") }
+        Pass(())
+    }
+
+    /// Sets the time price that applied from a `Date` onwards, replacing any time price already set on that `Date`.
+    /// A `Currency` of `None` follows the main `Currency`.
+    #[must_use]
+    pub fn set_time_price_on(&mut self, price: Decimal, currency_string: Option<&str>, date: Date) -> Schrod<()> {
+        if price <= Decimal::ZERO {
+            return Schrod::new_fail("Time price must be greater than zero.", "CurrencyExchange::set_time_price_on()")
+                .fail("Failed to set time price.", "CurrencyExchange::set_time_price_on()")
+        }
+        if let Some(currency_string) = currency_string && !Transaction::is_currency_string_valid(currency_string) {
+            return Schrod::new_fail(&format!("Cannot find currency {currency_string}!"), "CurrencyExchange::set_time_price_on()")
+                .fail("Failed to set time price.", "CurrencyExchange::set_time_price_on()")
+        }
+
+        self.time_prices.retain(|time_price| time_price.date != date);
+        let index = self.time_prices.partition_point(|time_price| time_price.date.as_value() < date.as_value());
+        self.time_prices.insert(index, DatedTimePrice { date, price, currency_string: currency_string.map(str::to_uppercase) });
         Pass(())
     }
+
+    /// Removes the time price that was set on a `Date`.
+    #[must_use]
+    pub fn remove_time_price_on(&mut self, date: Date) -> Schrod<()> {
+        let length = self.time_prices.len();
+        self.time_prices.retain(|time_price| time_price.date != date);
+        if self.time_prices.len() == length {
+            return Schrod::new_fail(&format!("There is no time price set on {}.", date.display_iso()), "CurrencyExchange::remove_time_price_on()")
+                .fail("Failed to remove time price.", "CurrencyExchange::remove_time_price_on()")
+        }
+        Pass(())
+    }
+
+    /// Returns the `Currency` of a time price, falling back to the main `Currency`.
+    #[must_use]
+    pub fn get_currency_of(&self, time_price_option: Option<&DatedTimePrice>) -> &'static Currency {
+        time_price_option
+            .and_then(|time_price| time_price.currency_string.as_deref())
+            .and_then(iso::find)
+            .unwrap_or_else(|| self.get_main_currency())
+    }
     
     /// Returns whether the given time price string is valid.
     #[must_use]
@@ -693,23 +1279,19 @@ impl CurrencyExchange {
         }
     }
 
-    /// Converts the given `Value` to a time price.
+    /// Converts the given `Value` to a time price, using the time price and exchange rate that applied on the given `Date`.
     #[must_use]
-    pub fn as_time_price(&self, value: &Value) -> Schrod<Decimal> {
-        let unified_result = self.convert(value.amount(), value.currency(), self.get_main_currency());
-        if unified_result.is_fail() {
-            return unified_result
+    pub fn as_time_price(&self, value: &Value, date: Date) -> Schrod<Decimal> {
+        let time_price_option = self.get_time_price_on(date);
+        let wage_currency = self.get_currency_of(time_price_option);
+        let wage_value_result = self.convert(value.amount(), value.currency(), wage_currency, date);
+        if wage_value_result.is_fail() {
+            return wage_value_result
                 .convert("CurrencyExchange::as_time_price()")
-                .fail("Failed to convert value to main currency.", "CurrencyExchange::as_time_price()");
+                .fail(&format!("Failed to convert value to {wage_currency}."), "CurrencyExchange::as_time_price()");
         }
-        Pass(unified_result.wont_fail("This is past an is_fail() guard clause.", "CurrencyExchange::as_time_price()") / self.time_price)
-    }
-
-    /// Converts the given `Decimal` value to a time price.
-    /// Please note that this assumes that the `Decimal` value is in the main currency.
-    #[must_use]
-    pub fn as_time_price_from_decimal(&self, decimal: Decimal) -> Decimal {
-        decimal / self.time_price
+        let price = time_price_option.map_or(Decimal::ONE, |time_price| time_price.price);
+        Pass(wage_value_result.wont_fail("This is past an is_fail() guard clause.", "CurrencyExchange::as_time_price()") / price)
     }
 
     /// Converts the given `Decimal` value to a time price formatted `String`.
@@ -748,7 +1330,7 @@ impl CurrencyExchange {
         self.rates.iter_mut().find(|rate| rate.from_currency_string.to_uppercase() == from.to_uppercase() && rate.to_currency_string.to_uppercase() == to.to_uppercase())
     }
     
-    /// Sets an `ExchangeRate`.
+    /// Sets today's rate of an `ExchangeRate`.
     #[must_use]
     pub fn set(&mut self, from: &str, to: &str, rate: Decimal) -> Schrod<()> {
         let today_result = Date::today();
@@ -759,30 +1341,70 @@ impl CurrencyExchange {
         }
         let today = today_result.wont_fail("This is past an is_guard clause.", "CurrencyExchange::set()");
         
-        let exchange_rate_option = self.get_mut(from, to);
-        if let Some(exchange_rate) = exchange_rate_option {
-            exchange_rate.rate = rate;
-            exchange_rate.new_rate_string = String::new();
-            exchange_rate.date = today;
-            exchange_rate.validate();
+        let set_result = self.set_on(from, to, rate, today);
+        if set_result.is_fail() { return set_result.fail("Failed to set exchange rate.", "CurrencyExchange::set()") }
+        if let Some(exchange_rate) = self.get_mut(from, to) { exchange_rate.new_rate_string = String::new(); }
+        Pass(())
+    }
+    
+    /// Sets the rate of an `ExchangeRate` that applied from a `Date` onwards, creating the `ExchangeRate` if it does not exist yet.
+    #[must_use]
+    pub fn set_on(&mut self, from: &str, to: &str, rate: Decimal, date: Date) -> Schrod<()> {
+        if rate <= Decimal::ZERO {
+            return Schrod::new_fail("Exchange rates must be greater than zero.", "CurrencyExchange::set_on()")
+                .fail(&format!("Failed to set exchange rate for {} -> {}.", from.to_uppercase(), to.to_uppercase()), "CurrencyExchange::set_on()")
         }
         
-        else {
-            let exchange_rate = ExchangeRate::new(from, to, rate, today);
-            self.rates.push(exchange_rate);
+        if self.get(from, to).is_none() { self.rates.push(ExchangeRate::new(from, to)); }
+        let exchange_rate_result = Schrod::from_option(self.get_mut(from, to), "The exchange rate was just added!", "CurrencyExchange::set_on()");
+        exchange_rate_result.wont_fail("The exchange rate was just added if it was missing.", "CurrencyExchange::set_on()").set_rate_on(date, rate);
+        self.validate_rates();
+        Pass(())
+    }
+    
+    /// Sets many dated rates of an `ExchangeRate` at once, creating the `ExchangeRate` if it does not exist yet.
+    /// This is much faster than calling `set_on()` for each rate when loading long histories.
+    #[must_use]
+    pub fn merge_on(&mut self, from: &str, to: &str, dated_rates: &[DatedRate]) -> Schrod<()> {
+        if dated_rates.iter().any(|dated_rate| dated_rate.rate <= Decimal::ZERO) {
+            return Schrod::new_fail("Exchange rates must be greater than zero.", "CurrencyExchange::merge_on()")
+                .fail(&format!("Failed to set exchange rates for {} -> {}.", from.to_uppercase(), to.to_uppercase()), "CurrencyExchange::merge_on()")
         }
-        
+
+        if self.get(from, to).is_none() { self.rates.push(ExchangeRate::new(from, to)); }
+        let exchange_rate_result = Schrod::from_option(self.get_mut(from, to), "The exchange rate was just added!", "CurrencyExchange::merge_on()");
+        exchange_rate_result.wont_fail("The exchange rate was just added if it was missing.", "CurrencyExchange::merge_on()").merge_rates(dated_rates);
+        self.validate_rates();
+        Pass(())
+    }
+    
+    /// Removes the rate of an `ExchangeRate` that was set on a `Date`.
+    #[must_use]
+    pub fn remove_on(&mut self, from: &str, to: &str, date: Date) -> Schrod<()> {
+        let exchange_rate_result = Schrod::from_option(self.get_mut(from, to), &format!("There is no exchange rate for {} -> {}.", from.to_uppercase(), to.to_uppercase()), "CurrencyExchange::remove_on()");
+        if exchange_rate_result.is_fail() {
+            return exchange_rate_result
+                .convert("CurrencyExchange::remove_on()")
+                .fail("Failed to remove exchange rate.", "CurrencyExchange::remove_on()")
+        }
+        let exchange_rate = exchange_rate_result.wont_fail("This is past an is_fail() guard clause.", "CurrencyExchange::remove_on()");
+        if !exchange_rate.remove_rate_on(date) {
+            return Schrod::new_fail(&format!("There is no rate set on {}.", date.display_iso()), "CurrencyExchange::remove_on()")
+                .fail("Failed to remove exchange rate.", "CurrencyExchange::remove_on()")
+        }
+        self.validate_rates();
         Pass(())
     }
 
-    /// Converts one `Currency` to another.
+    /// Converts one `Currency` to another at the rate that applied on the given `Date`.
+    /// If there is no rate between them, one is derived from the rates that lead from one to the other.
     #[must_use]
-    pub fn convert(&self, value: &Decimal, from: &Currency, to: &Currency) -> Schrod<Decimal> {
+    pub fn convert(&self, value: &Decimal, from: &Currency, to: &Currency, date: Date) -> Schrod<Decimal> {
         let from_str = from.to_string();
         let to_str = to.to_string();
         if from_str == to_str { return Pass(*value) }
         
-        let rate_result = Schrod::from_option(self.get(&from_str, &to_str), &format!("Cannot find saved exchange rate on disc for {from_str} -> {to_str}"), "CurrencyExchange::convert()");
+        let rate_result = Schrod::from_option(self.find_rate_on(&from_str, &to_str, date), &format!("Cannot find saved exchange rate on disc for {from_str} -> {to_str}"), "CurrencyExchange::convert()");
         if rate_result.is_fail() {
             return rate_result
                 .convert("CurrencyExchange::convert()")
@@ -790,22 +1412,126 @@ impl CurrencyExchange {
         }
 
         let rate = rate_result.wont_fail("This is past an is_fail() guard clause.", "CurrencyExchange::convert()");
-        Pass(value * rate.rate)
+        Pass(value * rate)
+    }
+
+    /// Returns the rate from one `Currency` to another that applied on the given `Date`.
+    /// Without an `ExchangeRate` of their own, the rate is derived along the shortest path of known rates, such as CAD -> USD -> EUR.
+    /// Paths made only of rates set on or before the `Date` are preferred, and the oldest rates are only used when there is no such path.
+    #[must_use]
+    pub fn find_rate_on(&self, from: &str, to: &str, date: Date) -> Option<Decimal> {
+        let path = self.find_path_through(from, to, |rate| rate.is_valid() && rate.has_rate_on(date))
+            .or_else(|| self.find_path(from, to))?;
+        path.windows(2).try_fold(Decimal::ONE, |rate, step| Some(rate * self.get_known_rate_on(&step[0], &step[1], date)?))
+    }
+
+    /// Returns today's rate from one `Currency` to another, derived if needed.
+    #[must_use]
+    pub fn find_rate(&self, from: &str, to: &str) -> Option<Decimal> {
+        let Pass(today) = Date::today() else { return None };
+        self.find_rate_on(from, to, today)
+    }
+
+    /// Finds the shortest path of known rates from one `Currency` to another, as a list of capitalized currency codes that starts with `from` and ends with `to`.
+    /// Rates can be followed in either direction, since the inverse of a known rate is known too.
+    #[must_use]
+    pub fn find_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        self.find_path_through(from, to, ExchangeRate::is_valid)
+    }
+
+    /// Finds the shortest path from one `Currency` to another that only follows the `ExchangeRate`s accepted by `is_usable`.
+    #[must_use]
+    fn find_path_through(&self, from: &str, to: &str, is_usable: impl Fn(&ExchangeRate) -> bool) -> Option<Vec<String>> {
+        let from = from.to_uppercase();
+        let to = to.to_uppercase();
+        if from == to { return Some(vec![from]) }
+
+        // searching breadth first, so that the fewest rates are chained
+        let mut previous_steps: HashMap<String, String> = HashMap::new();
+        let mut queue = VecDeque::from([from.clone()]);
+        while let Some(currency) = queue.pop_front() {
+            for rate in self.rates.iter().filter(|rate| is_usable(rate)) {
+                let next = if rate.from_currency_string == currency { &rate.to_currency_string }
+                else if rate.to_currency_string == currency { &rate.from_currency_string }
+                else { continue };
+                if *next == from || previous_steps.contains_key(next) { continue }
+                previous_steps.insert(next.clone(), currency.clone());
+
+                if *next == to {
+                    let mut path = vec![to.clone()];
+                    while let Some(previous) = previous_steps.get(path.last()?) { path.push(previous.clone()); }
+                    path.reverse();
+                    return Some(path)
+                }
+                queue.push_back(next.clone());
+            }
+        }
+        None
     }
 
-    /// Updates all `ExchangeRate`s for the `Currency`s used by the `Bank` (sourced from a duplicate `ledger`).
+    /// Returns how many more rates must be set before every `Currency` can be converted to every other one.
+    /// This is the number of rates in the smallest set that would connect them, not the number of `ExchangeRate`s without rates.
     #[must_use]
-    pub fn refresh(&mut self, transactions: Vec<Transaction>) -> Schrod<()> {
+    pub fn count_missing_rates(&self) -> usize {
+        let mut unconnected: Vec<&str> = Vec::new();
+        for rate in &self.rates {
+            for currency in [rate.from_currency_string.as_str(), rate.to_currency_string.as_str()] {
+                if !unconnected.contains(&currency) { unconnected.push(currency); }
+            }
+        }
+
+        // counting the groups of currencies that are connected to each other
+        let mut group_count: usize = 0;
+        while let Some(currency) = unconnected.pop() {
+            group_count += 1;
+            unconnected.retain(|other| self.find_path(currency, other).is_none());
+        }
+        group_count.saturating_sub(1)
+    }
+
+    /// Returns the rate between two `Currency`s that applied on the given `Date`, using their `ExchangeRate` or the inverse of the opposite one.
+    #[must_use]
+    fn get_known_rate_on(&self, from: &str, to: &str, date: Date) -> Option<Decimal> {
+        let direct_rate = self.get(from, to);
+        let inverse_rate = self.get(to, from);
+
+        // a direction that was already set on the date comes before the oldest rate of either direction
+        if let Some(rate) = direct_rate.filter(|rate| rate.has_rate_on(date)) { return rate.get_rate_on(date) }
+        if let Some(rate) = inverse_rate.filter(|rate| rate.has_rate_on(date)) { return rate.get_rate_on(date).map(|rate| Decimal::ONE / rate) }
+        direct_rate.and_then(|rate| rate.get_rate_on(date))
+            .or_else(|| inverse_rate.and_then(|rate| rate.get_rate_on(date)).map(|rate| Decimal::ONE / rate))
+    }
+
+    /// Validates every `ExchangeRate`, marking those without rates of their own as derived when a path of known rates connects their `Currency`s.
+    fn validate_rates(&mut self) {
+        for rate in &mut self.rates { rate.validate(); }
+        let derivable: Vec<bool> = self.rates.iter().map(|rate| !rate.is_valid() && self.find_path(&rate.from_currency_string, &rate.to_currency_string).is_some()).collect();
+        for (rate, is_derivable) in self.rates.iter_mut().zip(derivable) {
+            if is_derivable { rate.status = ExchangeRateStatus::Derived; }
+        }
+    }
+
+    /// Updates all `ExchangeRate`s for the `Currency`s used by the `Bank` (sourced from a duplicate `ledger`),
+    /// along with any other currencies that are needed, such as those of `Budget`s.
+    /// `ExchangeRate`s for currencies that are no longer used are only removed if they have no rates set,
+    /// so that a history is not lost when the last `Transaction` in a `Currency` is removed.
+    #[must_use]
+    pub fn refresh(&mut self, transactions: Vec<Transaction>, other_currencies: Vec<String>) -> Schrod<()> {
         // collecting the currencies used
         let mut currencies_used = Vec::new();
         for transaction in transactions {
             let currency = transaction.value.currency().to_string().to_uppercase();
             if !currencies_used.contains(&currency) { currencies_used.push(currency); }
         }
+        let time_price_currencies = self.time_prices.iter().filter_map(|time_price| time_price.currency_string.clone());
+        for currency in other_currencies.into_iter().chain(time_price_currencies) {
+            let currency = currency.to_uppercase();
+            if !currencies_used.contains(&currency) { currencies_used.push(currency); }
+        }
         if !currencies_used.contains(&self.get_main_currency().to_string().to_uppercase()) { currencies_used.push(self.get_main_currency().to_string().to_uppercase()); }
 
         // removing unnecessary exchange rates
-        self.rates.retain(|r| !r.has_unused_currency(&currencies_used));
+        self.rates.retain(|r| !r.history.is_empty() || !r.has_unused_currency(&currencies_used));
 
         // adding necessary exchange rates
         for from in &currencies_used {
@@ -814,21 +1540,12 @@ impl CurrencyExchange {
                 let from_str = from.clone();
                 let to_str = to.clone();
 
-                if self.get(&from_str, &to_str).is_none() {
-                    let set_result = self.set(&from_str, &to_str, Decimal::from(0));
-                    if set_result.is_fail() {
-                        return set_result
-                            .convert("CurrencyExchange::refresh()")
-                            .fail("Failed to refresh exchange rate for {from_str} -> {to_str}", "CurrencyExchange::refresh()")
-                    }
-                }
+                if self.get(&from_str, &to_str).is_none() { self.rates.push(ExchangeRate::new(&from_str, &to_str)); }
             }
         }
 
         // validates the exchange rates
-        for rate in &mut self.rates {
-            rate.validate();
-        }
+        self.validate_rates();
 
         // Returns a Pass
         Pass(())
@@ -839,7 +1556,8 @@ impl CurrencyExchange {
 
 /// Holds a list of `Tag`s with their bound colors.
 /// This registry holds no duplicate `Tag`s.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// A `Tag` without a color of its own inherits the color of its closest parent that has one.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TagRegistry {
     /// The list of `TagRegistration`s.
     registry: Vec<TagRegistration>,
@@ -883,6 +1601,20 @@ impl TagRegistry {
         else { Schrod::new_fail("Failed to get Tag Registration to edit!", "TagRegistry::change_tag()") }
     }
 
+    /// Moves the `TagRegistration`s of a `Tag` and its children over to another `Tag`.
+    /// If the other `Tag` is already registered, it keeps its own color unless it does not have one.
+    pub fn replace_tag(&mut self, old_tag: &Tag, new_tag: &Tag) {
+        let moved_registrations = self.registry.iter().filter(|registration| old_tag.contains(&registration.tag)).cloned().collect::<Vec<_>>();
+        self.registry.retain(|registration| !old_tag.contains(&registration.tag));
+        
+        for registration in moved_registrations {
+            if let Some(relabeled_tag) = registration.tag.relabeled(old_tag, new_tag)
+                && self.get_own(&relabeled_tag) == MaterialColors::Unavailable {
+                self.set(&relabeled_tag, registration.color);
+            }
+        }
+    }
+
     /// Removes a `Tag` from the `registry`.
     pub fn remove(&mut self, reference_tag: &Tag) {
         self.registry.retain(|reg| &reg.tag != reference_tag);
@@ -909,6 +1641,14 @@ impl TagRegistry {
         }
     }
 
+    /// Takes the colors from another `TagRegistry` for any `Tag`s that do not have a color yet.
+    /// Existing colors are left untouched.
+    pub fn merge(&mut self, other: &TagRegistry) {
+        for registration in &other.registry {
+            if self.get_own(&registration.tag) == MaterialColors::Unavailable { self.set(&registration.tag, registration.color); }
+        }
+    }
+
 
 
     // data retrieval and parsing
@@ -930,10 +1670,20 @@ impl TagRegistry {
         None
     }
 
-    /// Returns the color of a `Tag`.
-    /// If the `Tag` does not exist, a default color is returned.
+    /// Returns the color of a `Tag`, inheriting it from the closest parent if the `Tag` does not override it.
+    /// If neither the `Tag` nor any of its parents have a color, a default color is returned.
     #[must_use]
     pub fn get(&self, reference_tag: &Tag) -> MaterialColors {
+        iter::once(reference_tag.clone())
+            .chain(reference_tag.get_ancestors())
+            .map(|tag| self.get_own(&tag))
+            .find(|color| *color != MaterialColors::Unavailable)
+            .unwrap_or(MaterialColors::Unavailable)
+    }
+
+    /// Returns the color set on the `Tag` itself, without inheriting from its parents.
+    #[must_use]
+    pub fn get_own(&self, reference_tag: &Tag) -> MaterialColors {
         if let Some(registration) = self.get_registration(reference_tag) {
             return registration.color()
         }
@@ -990,4 +1740,179 @@ impl TagRegistration {
     pub fn color(&self) -> MaterialColors {
         self.color
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Bank, CurrencyExchange, ExchangeRate, ExchangeRateStatus, Filters, TagRegistry};
+    use crate::vault::account::{Account, AccountTypes};
+    use crate::vault::parse::CashFlow;
+    use crate::vault::recurring::{RecurrenceRules, RecurringTemplate};
+    use crate::vault::tag_rule::{RuleConditions, TagRule};
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::{Date, Months, Split, Tag};
+    use materialui::materials::MaterialColors;
+    use rust_decimal::Decimal;
+    use rusty_money::iso;
+
+    fn bank_with_tags(tag_lists: Vec<Vec<Tag>>) -> Bank {
+        bank_with(tag_lists.into_iter().map(|tags| transaction("-10", "USD", date(2026, Months::March, 2), "Test", tags)).collect())
+    }
+
+    #[test]
+    fn renaming_a_parent_renames_its_children_and_filters() {
+        let mut bank = bank_with_tags(vec![vec![tag("food/groceries")], vec![tag("food/restaurants"), tag("work")], vec![tag("seafood")]]);
+        assert!(bank.add_filter_tag(&tag("food/groceries"), Filters::Primary).is_pass());
+        let conditions = vec![RuleConditions::DescriptionContains("market".to_string())];
+        bank.add_tag_rule(TagRule::new(conditions, vec![tag("food/groceries"), tag("seafood")]).wont_fail("The rule is valid.", "tests"));
+        assert_eq!(bank.count_transactions_with_tag(&tag("food")), 2);
+
+        assert!(bank.rename_tag(&tag("food"), &tag("eating")).is_pass());
+        assert_eq!(bank.get_tags(), vec![tag("eating"), tag("eating/groceries"), tag("eating/restaurants"), tag("seafood"), tag("work")]);
+        assert!(bank.is_tag_filtered(&tag("eating/groceries"), Filters::Primary));
+        assert_eq!(bank.get_filtered_ids(Filters::Primary).len(), 1);
+        assert_eq!(bank.tag_rules[0].tags, vec![tag("eating/groceries"), tag("seafood")]);
+        assert!(bank.rename_tag(&tag("work"), &tag("seafood")).is_fail());
+    }
+
+    #[test]
+    fn merging_combines_tags_and_keeps_the_target_color() {
+        let mut bank = bank_with_tags(vec![vec![tag("gas")], vec![tag("fuel"), tag("gas")], vec![tag("fuel")]]);
+        bank.tag_registry.set(&tag("gas"), MaterialColors::Amber);
+        bank.tag_registry.set(&tag("fuel"), MaterialColors::Fern);
+
+        assert!(bank.merge_tags(&tag("gas"), &tag("fuel")).is_pass());
+        assert_eq!(bank.get_tags(), vec![tag("fuel")]);
+        assert!(bank.get_ledger().iter().all(|transaction| transaction.tags == vec![tag("fuel")]));
+        assert_eq!(bank.tag_registry.get(&tag("fuel")), MaterialColors::Fern);
+        assert!(bank.tag_registry.get_registration(&tag("gas")).is_none());
+    }
+
+    #[test]
+    fn confirmed_recurrences_keep_their_account_and_splits() {
+        let mut bank = bank_with(Vec::new());
+        let card = Account::new_from_raw("Card", AccountTypes::CreditCard, "0", "USD").wont_fail("The test account is valid.", "tests");
+        let card_uid = card.get_uid();
+        bank.add_account(card);
+        let work = Split::new_from_raw("-30", vec![tag("work")], "").wont_fail("The split is valid.", "tests");
+        let template = RecurringTemplate::new("-80", "USD", "Phone".to_string(), vec![tag("utilities")], vec![work.clone()], card_uid, RecurrenceRules::Monthly(1), date(2026, Months::March, 1))
+            .wont_fail("The template is valid.", "tests");
+        let template_uid = template.get_uid();
+        bank.add_recurring_template(template);
+
+        assert!(bank.confirm_recurrence(template_uid).is_pass());
+        let confirmed = &bank.get_ledger()[0];
+        assert_eq!(confirmed.get_account_uid(), card_uid);
+        assert_eq!(confirmed.get_splits(), &[work]);
+    }
+
+    #[test]
+    fn conversions_use_the_rate_from_the_date_of_each_transaction() {
+        let mut currency_exchange = CurrencyExchange::default();
+        assert!(currency_exchange.set_on("EUR", "USD", "1.10".parse().unwrap_or_default(), date(2019, Months::January, 1)).is_pass());
+        assert!(currency_exchange.set_on("EUR", "USD", "1.20".parse().unwrap_or_default(), date(2026, Months::January, 1)).is_pass());
+        assert!(currency_exchange.set_on("EUR", "USD", Decimal::ZERO, date(2026, Months::February, 1)).is_fail());
+
+        // the nearest earlier rate applies, and dates before the history use the oldest rate
+        let convert = |currency_exchange: &CurrencyExchange, day: Date| currency_exchange.convert(&Decimal::from(100), iso::EUR, iso::USD, day).wont_fail("The rate exists.", "tests").to_string();
+        assert_eq!(convert(&currency_exchange, date(2018, Months::June, 1)), "110.00");
+        assert_eq!(convert(&currency_exchange, date(2025, Months::December, 31)), "110.00");
+        assert_eq!(convert(&currency_exchange, date(2026, Months::January, 1)), "120.00");
+
+        // each purchase in a cash flow is unified at its own rate
+        let transactions = vec![
+            transaction("-100", "EUR", date(2019, Months::June, 1), "Old", vec![tag("travel")]),
+            transaction("-100", "EUR", date(2026, Months::March, 1), "New", vec![tag("travel")]),
+        ];
+        let mut bank = Bank::default();
+        assert!(bank.init(transactions, currency_exchange, TagRegistry::default(), Vec::new(), Vec::new(), Vec::new(), Vec::new()).is_pass());
+        let ids = Bank::get_ids_from(&bank.get_ledger().iter().collect());
+        let cash_flow = CashFlow::new(&bank, &ids).wont_fail("Both rates exist.", "tests");
+        assert_eq!(cash_flow.unified().amount().to_string(), "-230.00");
+
+        assert!(bank.currency_exchange.remove_on("EUR", "USD", date(2026, Months::January, 1)).is_pass());
+        assert!(bank.currency_exchange.remove_on("EUR", "USD", date(2026, Months::January, 1)).is_fail());
+        assert_eq!(convert(&bank.currency_exchange, date(2026, Months::March, 1)), "110.00");
+
+        // once no transactions use euros, the rate history is kept but the empty reverse rate is dropped
+        assert!(bank.currency_exchange.refresh(Vec::new(), Vec::new()).is_pass());
+        assert!(bank.currency_exchange.get("EUR", "USD").is_some_and(|rate| rate.get_history().len() == 1));
+        assert!(bank.currency_exchange.get("USD", "EUR").is_none());
+    }
+
+    #[test]
+    fn time_flows_use_the_time_price_from_the_date_of_each_transaction() {
+        let june_1 = |year: u32| date(year, Months::June, 1);
+        let mut currency_exchange = CurrencyExchange::default();
+        assert!(currency_exchange.set_time_price_on(Decimal::from(20), None, june_1(2020)).is_pass());
+        assert!(currency_exchange.set_time_price_on(Decimal::from(40), Some("eur"), june_1(2025)).is_pass());
+        assert!(currency_exchange.set_time_price_on(Decimal::ZERO, None, june_1(2026)).is_fail());
+        assert!(currency_exchange.set_on("USD", "EUR", "0.5".parse().unwrap_or_default(), june_1(2020)).is_pass());
+        assert_eq!(currency_exchange.get_time_price_currency(), iso::EUR);
+
+        // 100 USD at 20 USD an hour, then 100 USD (50 EUR) at 40 EUR an hour
+        let transactions = vec![
+            transaction("-100", "USD", june_1(2021), "Old", vec![tag("travel")]),
+            transaction("-100", "USD", june_1(2026), "New", vec![tag("travel")]),
+        ];
+        let mut bank = Bank::default();
+        assert!(bank.init(transactions, currency_exchange, TagRegistry::default(), Vec::new(), Vec::new(), Vec::new(), Vec::new()).is_pass());
+        let ids = Bank::get_ids_from(&bank.get_ledger().iter().collect());
+        let cash_flow = CashFlow::new(&bank, &ids).wont_fail("Every rate exists.", "tests");
+        assert_eq!(cash_flow.time(), Decimal::new(-625, 2));
+
+        assert!(bank.currency_exchange.remove_time_price_on(june_1(2025)).is_pass());
+        assert!(bank.currency_exchange.remove_time_price_on(june_1(2025)).is_fail());
+        let cash_flow = CashFlow::new(&bank, &ids).wont_fail("Every rate exists.", "tests");
+        assert_eq!(cash_flow.time(), Decimal::from(-10));
+    }
+
+    #[test]
+    fn missing_rates_are_derived_through_other_currencies() {
+        let march_1 = date(2026, Months::March, 1);
+        let coffee = |currency: &str| transaction("-10", currency, march_1, "Coffee", vec![tag("coffee")]);
+        let mut currency_exchange = CurrencyExchange::default();
+        assert!(currency_exchange.refresh(vec![coffee("CAD"), coffee("USD"), coffee("EUR"), coffee("JPY")], Vec::new()).is_pass());
+        assert_eq!(currency_exchange.get_rates().len(), 12);
+        assert_eq!(currency_exchange.count_missing_rates(), 3);
+
+        assert!(currency_exchange.set_on("CAD", "USD", "0.75".parse().unwrap_or_default(), march_1).is_pass());
+        assert!(currency_exchange.set_on("EUR", "USD", "1.25".parse().unwrap_or_default(), march_1).is_pass());
+        assert_eq!(currency_exchange.count_missing_rates(), 1);
+
+        // CAD -> USD -> EUR, following EUR -> USD backwards
+        assert_eq!(currency_exchange.find_path("cad", "eur"), Some(vec!["CAD".to_string(), "USD".to_string(), "EUR".to_string()]));
+        let converted = currency_exchange.convert(&Decimal::from(100), iso::CAD, iso::EUR, march_1).wont_fail("The rate can be derived.", "tests");
+        assert_eq!(converted, Decimal::from(60));
+        assert!(currency_exchange.convert(&Decimal::from(100), iso::CAD, iso::JPY, march_1).is_fail());
+
+        let status = |currency_exchange: &CurrencyExchange, from: &str, to: &str| currency_exchange.get(from, to).map(ExchangeRate::get_status);
+        assert_eq!(status(&currency_exchange, "USD", "CAD"), Some(ExchangeRateStatus::Derived));
+        assert_eq!(status(&currency_exchange, "EUR", "CAD"), Some(ExchangeRateStatus::Derived));
+        assert_eq!(status(&currency_exchange, "JPY", "EUR"), Some(ExchangeRateStatus::Invalid));
+
+        // a rate set directly takes priority over a derived one
+        assert!(currency_exchange.set_on("CAD", "EUR", "0.5".parse().unwrap_or_default(), march_1).is_pass());
+        assert_eq!(currency_exchange.find_rate_on("CAD", "EUR", march_1), Some("0.5".parse().unwrap_or_default()));
+        assert!(currency_exchange.remove_on("CAD", "EUR", march_1).is_pass());
+        assert_eq!(status(&currency_exchange, "CAD", "EUR"), Some(ExchangeRateStatus::Derived));
+    }
+
+    #[test]
+    fn derived_rates_prefer_paths_that_were_set_by_the_date() {
+        let march_1 = |year: u32| date(year, Months::March, 1);
+        let mut currency_exchange = CurrencyExchange::default();
+        assert!(currency_exchange.set_on("CAD", "USD", "0.75".parse().unwrap_or_default(), march_1(2019)).is_pass());
+        assert!(currency_exchange.set_on("EUR", "USD", "1.25".parse().unwrap_or_default(), march_1(2019)).is_pass());
+        assert!(currency_exchange.set_on("CAD", "EUR", "0.5".parse().unwrap_or_default(), march_1(2026)).is_pass());
+
+        // the direct rate did not exist yet in 2020, so the rate is derived through USD instead
+        assert_eq!(currency_exchange.find_rate_on("CAD", "EUR", march_1(2020)), Some("0.6".parse().unwrap_or_default()));
+        assert_eq!(currency_exchange.find_rate_on("CAD", "EUR", march_1(2026)), Some("0.5".parse().unwrap_or_default()));
+        assert_eq!(currency_exchange.find_rate_on("EUR", "CAD", march_1(2020)).map(|rate| rate.round_dp(4)), Some("1.6667".parse().unwrap_or_default()));
+
+        // before any rates were set, the shortest path falls back to its oldest rates
+        assert_eq!(currency_exchange.find_rate_on("CAD", "EUR", march_1(2018)), Some("0.5".parse().unwrap_or_default()));
+    }
+}
@@ -0,0 +1,238 @@
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rusty_money::iso;
+use rusty_money::iso::Currency;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::vault::bank::Bank;
+use crate::vault::parse::CashFlow;
+use crate::vault::transaction::{Date, Id, Tag, Transaction, Uid, Value};
+use crate::vault::trend_parse::Intervals;
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// A spending limit for a set of `Tag`s over each period of an interval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    uid: Uid,
+    /// The `Tag`s covered by the budget. A `Transaction` counts towards it if it has any of them.
+    pub tags: Vec<Tag>,
+    pub interval: Intervals,
+    limit_decimal: Decimal,
+    currency_string: String,
+}
+impl Budget {
+    // initializing
+    /// Creates a new `Budget`.
+    #[must_use]
+    pub fn new(tags: Vec<Tag>, interval: Intervals, limit_string: &str, currency_string: &str) -> Schrod<Budget> {
+        if !Budget::are_raw_parts_valid(&tags, limit_string, currency_string) {
+            return Schrod::new_fail("Invalid parts!", "Budget::new()")
+                .fail("Failed to create Budget.", "Budget::new()")
+        }
+        let limit_result = Schrod::from_result(limit_string.trim().parse::<Decimal>(), "Invalid limit!", "Budget::new()");
+        if limit_result.is_fail() {
+            return limit_result
+                .convert("Budget::new()")
+                .fail("Failed to create Budget.", "Budget::new()")
+        }
+
+        Pass(Budget {
+            uid: Uuid::new_v4(),
+            tags: Tag::sorted(&tags),
+            interval,
+            limit_decimal: limit_result.wont_fail("This is past an is_fail() guard clause.", "Budget::new()"),
+            currency_string: currency_string.trim().to_uppercase(),
+        })
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the `Uid`.
+    #[must_use]
+    pub fn get_uid(&self) -> Uid {
+        self.uid
+    }
+
+    /// Returns the currency code of the limit.
+    #[must_use]
+    pub fn get_currency_string(&self) -> &str {
+        &self.currency_string
+    }
+
+    /// Returns the `Currency` of the limit.
+    #[must_use]
+    pub fn get_currency(&self) -> Schrod<&'static Currency> {
+        Schrod::from_option(iso::find(&self.currency_string), &format!("Unknown currency: {}.", self.currency_string), "Budget::get_currency()")
+    }
+
+    /// Returns the labels of the `Tag`s, joined into a single `String`.
+    #[must_use]
+    pub fn display_tags(&self) -> String {
+        self.tags.iter().map(Tag::get_label).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Checks if a `Transaction` counts towards the budget in the period containing the given `Date`.
+    #[must_use]
+    pub fn covers(&self, transaction: &Transaction, date: Date) -> bool {
+        self.interval.are_in_same_period(transaction.date, date) && self.tags.iter().any(|tag| transaction.has_tag(tag))
+    }
+
+    /// Returns the `Id`s of every `Transaction` that counts towards the budget in the period containing the given `Date`.
+    #[must_use]
+    pub fn get_period_ids(&self, bank: &Bank, date: Date) -> Vec<Id> {
+        bank.get_ledger()
+            .iter()
+            .filter(|transaction| self.covers(transaction, date))
+            .filter_map(Transaction::get_id)
+            .collect()
+    }
+
+    /// Returns how much has been spent against the limit in the period containing the given `Date`.
+    /// Spending is the negative unified `CashFlow` of the covered `Transaction`s, so refunds are taken off of it.
+    /// Only the parts of split `Transaction`s that belong to the budget's `Tag`s are counted.
+    #[must_use]
+    pub fn get_progress(&self, bank: &Bank, date: Date) -> Schrod<BudgetProgress> {
+        let currency_result = self.get_currency();
+        if currency_result.is_fail() {
+            return currency_result
+                .convert("Budget::get_progress()")
+                .fail("Failed to get budget progress.", "Budget::get_progress()")
+        }
+        let currency = currency_result.wont_fail("This is past an is_fail() guard clause.", "Budget::get_progress()");
+
+        let cash_flow_result = CashFlow::new_for_tags(bank, &self.get_period_ids(bank, date), &self.tags);
+        if cash_flow_result.is_fail() {
+            return cash_flow_result
+                .convert("Budget::get_progress()")
+                .fail("Failed to get budget progress.", "Budget::get_progress()")
+        }
+        let unified_value = cash_flow_result.wont_fail("This is past an is_fail() guard clause.", "Budget::get_progress()").unified();
+
+        let spent_result = bank.currency_exchange.convert(&-*unified_value.amount(), unified_value.currency(), currency, date);
+        if spent_result.is_fail() {
+            return spent_result
+                .convert("Budget::get_progress()")
+                .fail("Failed to get budget progress.", "Budget::get_progress()")
+        }
+
+        Pass(BudgetProgress {
+            spent: Value::from_decimal(spent_result.wont_fail("This is past an is_fail() guard clause.", "Budget::get_progress()"), currency),
+            limit: Value::from_decimal(self.limit_decimal, currency),
+        })
+    }
+
+
+
+    // validating
+    /// Determines if the given parts can make a `Budget`.
+    #[must_use]
+    pub fn are_raw_parts_valid(tags: &[Tag], limit_string: &str, currency_string: &str) -> bool {
+        !tags.is_empty() && Budget::is_limit_string_valid(limit_string) && Transaction::is_currency_string_valid(currency_string.trim())
+    }
+
+    /// Determines if the given limit is a positive number.
+    #[must_use]
+    pub fn is_limit_string_valid(limit_string: &str) -> bool {
+        limit_string.trim().parse::<Decimal>().is_ok_and(|limit| limit > Decimal::ZERO)
+    }
+}
+
+
+
+/// The spending against a `Budget` for a single period, in the `Currency` of its limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetProgress {
+    pub spent: Value,
+    pub limit: Value,
+}
+impl BudgetProgress {
+    // data retrieval and parsing
+    /// Checks if more has been spent than the limit allows.
+    #[must_use]
+    pub fn is_overspent(&self) -> bool {
+        self.spent.amount() > self.limit.amount()
+    }
+
+    /// Returns the share of the limit that has been spent, between 0 and 1.
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        (self.spent.amount() / self.limit.amount()).to_f32().unwrap_or_default().clamp(0.0, 1.0)
+    }
+
+    /// Returns how much is left before the limit is reached, or how far over it the spending is.
+    #[must_use]
+    pub fn remaining(&self) -> Value {
+        Value::from_decimal(self.limit.amount() - self.spent.amount(), self.limit.currency())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::Budget;
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::{Date, Months, Split, Tag, Transaction};
+    use crate::vault::trend_parse::Intervals;
+
+    fn spending(value: &str, date: Date, tags: Vec<Tag>) -> Transaction {
+        transaction(value, "USD", date, "Test", tags)
+    }
+
+    #[test]
+    fn progress_counts_covered_transactions_in_the_current_period() {
+        let bank = bank_with(vec![
+            spending("-40", date(2026, Months::March, 2), vec![tag("food")]),
+            spending("-25.50", date(2026, Months::March, 20), vec![tag("dining"), tag("work")]),
+            spending("10", date(2026, Months::March, 21), vec![tag("food")]), // a refund
+            spending("-100", date(2026, Months::February, 27), vec![tag("food")]), // last period
+            spending("-500", date(2026, Months::March, 5), vec![tag("rent")]), // not covered
+        ]);
+
+        let budget = Budget::new(vec![tag("food"), tag("dining")], Intervals::Monthly, "50", "usd").wont_fail("The budget is valid.", "tests");
+        let progress = budget.get_progress(&bank, date(2026, Months::March, 31)).wont_fail("The currencies match.", "tests");
+        assert_eq!(progress.spent.amount().to_string(), "55.50");
+        assert!(progress.is_overspent());
+        assert_eq!(progress.remaining().amount().to_string(), "-5.50");
+        assert!((progress.fraction() - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn split_transactions_only_count_their_own_parts() {
+        let mut receipt = spending("-100", date(2026, Months::March, 2), vec![tag("food")]);
+        let household = Split::new_from_raw("-30", vec![tag("household")], "").wont_fail("The split is valid.", "tests");
+        assert!(receipt.set_splits(vec![household]).is_pass());
+        let bank = bank_with(vec![receipt]);
+
+        let food = Budget::new(vec![tag("food")], Intervals::Monthly, "500", "USD").wont_fail("The budget is valid.", "tests");
+        let household = Budget::new(vec![tag("household")], Intervals::Monthly, "500", "USD").wont_fail("The budget is valid.", "tests");
+        let both = Budget::new(vec![tag("food"), tag("household")], Intervals::Monthly, "500", "USD").wont_fail("The budget is valid.", "tests");
+        let today = date(2026, Months::March, 31);
+        assert_eq!(food.get_progress(&bank, today).wont_fail("The currencies match.", "tests").spent.amount().to_string(), "70");
+        assert_eq!(household.get_progress(&bank, today).wont_fail("The currencies match.", "tests").spent.amount().to_string(), "30");
+        assert_eq!(both.get_progress(&bank, today).wont_fail("The currencies match.", "tests").spent.amount().to_string(), "100");
+    }
+
+    #[test]
+    fn parent_tags_cover_their_children_only() {
+        let bank = bank_with(vec![
+            spending("-40", date(2026, Months::March, 2), vec![tag("food/groceries")]),
+            spending("-25", date(2026, Months::March, 3), vec![tag("Food / Restaurants")]),
+            spending("-60", date(2026, Months::March, 4), vec![tag("seafood")]), // not a child of food
+        ]);
+        assert!(bank.get_tags().contains(&tag("food")));
+
+        let budget = Budget::new(vec![tag("food")], Intervals::Monthly, "100", "USD").wont_fail("The budget is valid.", "tests");
+        let progress = budget.get_progress(&bank, date(2026, Months::March, 31)).wont_fail("The currencies match.", "tests");
+        assert_eq!(progress.spent.amount().to_string(), "65");
+    }
+
+    #[test]
+    fn invalid_budgets_are_rejected() {
+        assert!(Budget::new(Vec::new(), Intervals::Weekly, "50", "USD").is_fail());
+        assert!(Budget::new(vec![tag("food")], Intervals::Weekly, "0", "USD").is_fail());
+        assert!(Budget::new(vec![tag("food")], Intervals::Weekly, "50", "ZZZ").is_fail());
+    }
+}
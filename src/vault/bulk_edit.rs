@@ -0,0 +1,235 @@
+use rusty_money::iso;
+use rusty_money::iso::Currency;
+use crate::vault::transaction::{Tag, Transaction, Value};
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// The furthest a bulk edit can shift `Date`s, in days.
+pub const MAX_DATE_SHIFT_DAYS: i64 = 3660;
+
+/// The kinds of `BulkActions`, used to choose one before its input is entered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkActionTypes {
+    AddTag,
+    RemoveTag,
+    SetCurrency,
+    ShiftDate,
+    Ignore,
+    Delete,
+}
+impl BulkActionTypes {
+    /// Returns every `BulkActionTypes` variant.
+    #[must_use]
+    pub fn all() -> Vec<BulkActionTypes> {
+        vec![BulkActionTypes::AddTag, BulkActionTypes::RemoveTag, BulkActionTypes::SetCurrency, BulkActionTypes::ShiftDate, BulkActionTypes::Ignore, BulkActionTypes::Delete]
+    }
+
+    /// Returns the name of the kind of action.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            BulkActionTypes::AddTag => { "Add Tag" }
+            BulkActionTypes::RemoveTag => { "Remove Tag" }
+            BulkActionTypes::SetCurrency => { "Set Currency" }
+            BulkActionTypes::ShiftDate => { "Shift Date" }
+            BulkActionTypes::Ignore => { "Ignore" }
+            BulkActionTypes::Delete => { "Delete" }
+        }
+    }
+
+    /// Returns the placeholder for the input of the kind of action, or `None` if it takes no input.
+    #[must_use]
+    pub fn input_placeholder(&self) -> Option<&str> {
+        match self {
+            BulkActionTypes::AddTag | BulkActionTypes::RemoveTag => { Some("Tag") }
+            BulkActionTypes::SetCurrency => { Some("Currency") }
+            BulkActionTypes::ShiftDate => { Some("Days, e.g. -7") }
+            BulkActionTypes::Ignore | BulkActionTypes::Delete => { None }
+        }
+    }
+}
+
+
+
+/// A change made to every selected `Transaction` at once.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkActions {
+    AddTag(Tag),
+    /// Removes a `Tag`, except from `Transaction`s where it is the only one.
+    RemoveTag(Tag),
+    /// Changes the `Currency` while keeping the amount.
+    SetCurrency(&'static Currency),
+    /// Moves the `Date` forwards or backwards by a number of days.
+    ShiftDate(i64),
+    /// Adds the ignore `Tag`, which leaves the `Transaction` out of all data parsing.
+    Ignore,
+    Delete,
+}
+impl BulkActions {
+    // initializing
+    /// Creates a `BulkActions` of the given kind from the input entered by the user.
+    #[must_use]
+    pub fn new_from_raw(action_type: BulkActionTypes, input_string: &str) -> Schrod<BulkActions> {
+        let input_string = input_string.trim();
+        match action_type {
+            BulkActionTypes::AddTag | BulkActionTypes::RemoveTag => {
+                let tag_result = Tag::new(input_string);
+                if tag_result.is_fail() {
+                    return tag_result
+                        .convert("BulkActions::new_from_raw()")
+                        .fail("Failed to create bulk action.", "BulkActions::new_from_raw()")
+                }
+                let tag = tag_result.wont_fail("This is past an is_fail() guard clause.", "BulkActions::new_from_raw()");
+                if action_type == BulkActionTypes::AddTag { Pass(BulkActions::AddTag(tag)) } else { Pass(BulkActions::RemoveTag(tag)) }
+            }
+            BulkActionTypes::SetCurrency => {
+                match iso::find(&input_string.to_uppercase()) {
+                    Some(currency) => Pass(BulkActions::SetCurrency(currency)),
+                    None => Schrod::new_fail(&format!("\"{input_string}\" is not a valid currency."), "BulkActions::new_from_raw()"),
+                }
+            }
+            BulkActionTypes::ShiftDate => {
+                match input_string.parse::<i64>() {
+                    Ok(days) if days != 0 && days.abs() <= MAX_DATE_SHIFT_DAYS => Pass(BulkActions::ShiftDate(days)),
+                    _ => Schrod::new_fail(&format!("Dates can only be shifted by a whole number of days, up to {MAX_DATE_SHIFT_DAYS} either way."), "BulkActions::new_from_raw()"),
+                }
+            }
+            BulkActionTypes::Ignore => { Pass(BulkActions::Ignore) }
+            BulkActionTypes::Delete => { Pass(BulkActions::Delete) }
+        }
+    }
+
+
+
+    // management
+    /// Makes the change to a `Transaction`, unless it would not change anything.
+    /// Deleting is left to the `Bank`, since a `Transaction` cannot remove itself.
+    /// Returns whether the `Transaction` was changed.
+    pub fn apply(&self, transaction: &mut Transaction) -> bool {
+        if !self.changes(transaction) { return false }
+        match self {
+            BulkActions::AddTag(tag) => { transaction.add_tag(tag.clone()); }
+            BulkActions::RemoveTag(tag) => { transaction.remove_tag(tag); }
+            BulkActions::SetCurrency(currency) => { transaction.value = Value::from_decimal(*transaction.value.amount(), currency); }
+            BulkActions::ShiftDate(days) => {
+                for _ in 0..days.unsigned_abs() {
+                    if *days > 0 { transaction.date.advance_by_day(); } else { transaction.date.recede_by_day(); }
+                }
+            }
+            BulkActions::Ignore => { transaction.add_tag(Transaction::ignore_tag()); }
+            BulkActions::Delete => { return false }
+        }
+        true
+    }
+
+
+
+    // data retrieval and parsing
+    /// Checks if the change would do anything to a `Transaction`.
+    #[must_use]
+    pub fn changes(&self, transaction: &Transaction) -> bool {
+        match self {
+            BulkActions::AddTag(tag) => { !transaction.tags.contains(tag) }
+            BulkActions::RemoveTag(tag) => { transaction.tags.contains(tag) && transaction.tags.len() > 1 }
+            BulkActions::SetCurrency(currency) => { transaction.value.currency() != *currency }
+            BulkActions::Ignore => { !transaction.is_ignored() }
+            BulkActions::ShiftDate(_) | BulkActions::Delete => { true }
+        }
+    }
+
+    /// Returns a short description of the change.
+    #[must_use]
+    pub fn display(&self) -> String {
+        match self {
+            BulkActions::AddTag(tag) => { format!("add the tag \"{}\" to", tag.get_label()) }
+            BulkActions::RemoveTag(tag) => { format!("remove the tag \"{}\" from", tag.get_label()) }
+            BulkActions::SetCurrency(currency) => { format!("set the currency to {} on", currency.iso_alpha_code) }
+            BulkActions::ShiftDate(days) if *days > 0 => { format!("move the date {days} days later on") }
+            BulkActions::ShiftDate(days) => { format!("move the date {} days earlier on", days.unsigned_abs()) }
+            BulkActions::Ignore => { "ignore".to_string() }
+            BulkActions::Delete => { "delete".to_string() }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{BulkActionTypes, BulkActions};
+    use crate::vault::account::{Account, AccountTypes};
+    use crate::vault::bank::Bank;
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::{Months, Transaction};
+
+    fn bank() -> Bank {
+        let march_1 = date(2026, Months::March, 1);
+        let tagged = |description: &str, labels: &[&str]| transaction("-20", "USD", march_1, description, labels.iter().map(|label| tag(label)).collect());
+        bank_with(vec![
+            tagged("Shell", &["car", "gas"]),
+            tagged("Corner Market", &["groceries"]),
+            tagged("Garage", &["car"]),
+        ])
+    }
+
+    #[test]
+    fn raw_input_is_validated() {
+        assert_eq!(BulkActions::new_from_raw(BulkActionTypes::ShiftDate, " -7 ").wont_fail("The shift is valid.", "tests"), BulkActions::ShiftDate(-7));
+        assert!(BulkActions::new_from_raw(BulkActionTypes::ShiftDate, "0").is_fail());
+        assert!(BulkActions::new_from_raw(BulkActionTypes::ShiftDate, "99999").is_fail());
+        assert!(BulkActions::new_from_raw(BulkActionTypes::SetCurrency, "eur").is_pass());
+        assert!(BulkActions::new_from_raw(BulkActionTypes::SetCurrency, "euro").is_fail());
+        assert!(BulkActions::new_from_raw(BulkActionTypes::AddTag, "").is_fail());
+        assert_eq!(BulkActions::new_from_raw(BulkActionTypes::Delete, "ignored input").wont_fail("Deleting takes no input.", "tests"), BulkActions::Delete);
+    }
+
+    #[test]
+    fn only_selected_transactions_change() {
+        let mut bank = bank();
+        let uid_of = |bank: &Bank, description: &str| bank.get_ledger().iter().find(|t| t.description == description).map(Transaction::get_uid).unwrap_or_default();
+        let shell = uid_of(&bank, "Shell");
+        let garage = uid_of(&bank, "Garage");
+
+        // the only tag of a transaction is never removed
+        let remove_car = BulkActions::RemoveTag(tag("car"));
+        assert_eq!(bank.bulk_edit(&[shell, garage], &remove_car).wont_fail("The edit is valid.", "tests"), 1);
+        assert!(!bank.get_ledger().iter().any(|t| t.description == "Shell" && t.has_tag(&tag("car"))));
+        assert!(bank.get_ledger().iter().any(|t| t.description == "Garage" && t.has_tag(&tag("car"))));
+
+        let shift = BulkActions::ShiftDate(-1);
+        assert_eq!(bank.bulk_edit(&[garage], &shift).wont_fail("The edit is valid.", "tests"), 1);
+        let february_28 = date(2026, Months::February, 28);
+        assert!(bank.get_ledger().iter().any(|t| t.description == "Garage" && t.date == february_28));
+
+        assert_eq!(bank.bulk_edit(&[shell, garage], &BulkActions::Delete).wont_fail("The edit is valid.", "tests"), 2);
+        assert_eq!(bank.get_ledger().len(), 1);
+    }
+    #[test]
+    fn transfers_move_together_and_keep_their_currency() {
+        let mut bank = bank();
+        let checking = Account::new_from_raw("Checking", AccountTypes::Checking, "0", "USD").wont_fail("The test account is valid.", "tests");
+        let savings = Account::new_from_raw("Savings", AccountTypes::Savings, "0", "USD").wont_fail("The test account is valid.", "tests");
+        let (checking_uid, savings_uid) = (checking.get_uid(), savings.get_uid());
+        bank.add_account(checking);
+        bank.add_account(savings);
+        let march_5 = date(2026, Months::March, 5);
+        assert!(bank.add_transfer(checking_uid, savings_uid, "100", march_5, "Saving up".to_string()).is_pass());
+        let transfer_sides = bank.get_ledger().iter().filter(|t| t.is_transfer()).map(Transaction::get_uid).collect::<Vec<_>>();
+        let garage = bank.get_ledger().iter().find(|t| t.description == "Garage").map(Transaction::get_uid).unwrap_or_default();
+
+        // shifting one side of a transfer shifts the other side too
+        assert_eq!(bank.bulk_edit(&[transfer_sides[0]], &BulkActions::ShiftDate(2)).wont_fail("The edit is valid.", "tests"), 2);
+        let march_7 = date(2026, Months::March, 7);
+        assert!(bank.get_ledger().iter().filter(|t| t.is_transfer()).all(|t| t.date == march_7));
+
+        // selecting both sides still shifts each of them once
+        assert_eq!(bank.bulk_edit(&transfer_sides, &BulkActions::ShiftDate(-2)).wont_fail("The edit is valid.", "tests"), 2);
+        assert!(bank.get_ledger().iter().filter(|t| t.is_transfer()).all(|t| t.date == march_5));
+
+        // the currency of a transfer cannot be changed, so nothing in the selection changes
+        let set_euro = BulkActions::new_from_raw(BulkActionTypes::SetCurrency, "EUR").wont_fail("The currency is valid.", "tests");
+        assert!(bank.bulk_edit(&[garage, transfer_sides[1]], &set_euro).is_fail());
+        assert!(bank.get_ledger().iter().all(|t| t.value.currency().iso_alpha_code == "USD"));
+        assert_eq!(bank.bulk_edit(&[garage], &set_euro).wont_fail("The edit is valid.", "tests"), 1);
+    }
+}
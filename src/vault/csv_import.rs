@@ -0,0 +1,471 @@
+use std::path::PathBuf;
+use chrono::{Datelike, NaiveDate};
+use crate::vault::transaction::{Date, Months, Tag, Transaction};
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// An enumeration of the editable fields in a `CsvColumnMapping`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvFields {
+    ValueColumn,
+    CurrencyColumn,
+    DateColumn,
+    DescriptionColumn,
+    DateFormat,
+    FallbackCurrency,
+    ImportTag,
+    Delimiter,
+}
+impl CsvFields {
+    /// Returns the name for a given `CsvField`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            CsvFields::ValueColumn => { "Value Column" }
+            CsvFields::CurrencyColumn => { "Currency Column" }
+            CsvFields::DateColumn => { "Date Column" }
+            CsvFields::DescriptionColumn => { "Description Column" }
+            CsvFields::DateFormat => { "Date Format" }
+            CsvFields::FallbackCurrency => { "Fallback Currency" }
+            CsvFields::ImportTag => { "Import Tag" }
+            CsvFields::Delimiter => { "Delimiter" }
+        }
+    }
+}
+
+
+
+/// Describes how the columns of a CSV bank statement map onto the parts of a `Transaction`.
+/// Columns can be referenced by their header name or by their position starting at 1.
+#[derive(Debug, Clone)]
+pub struct CsvColumnMapping {
+    /// The column holding the value.
+    pub value_column: String,
+    /// The column holding the currency.
+    /// If this is left empty, the `fallback_currency` is used for every row.
+    pub currency_column: String,
+    /// The column holding the date.
+    pub date_column: String,
+    /// The column holding the description.
+    pub description_column: String,
+    /// The `chrono` format used to read dates (for example `%Y-%m-%d` or `%m/%d/%Y`).
+    pub date_format: String,
+    /// The currency used when there is no currency column or a row's currency cell is empty.
+    pub fallback_currency: String,
+    /// The `Tag` given to every imported `Transaction`.
+    pub import_tag: String,
+    /// The character separating the cells of each row.
+    pub delimiter: String,
+    /// Whether the first row of the file holds the column names.
+    pub has_header: bool,
+}
+impl CsvColumnMapping {
+    // initializing
+    /// Creates a new `CsvColumnMapping` with guesses based on the given header row.
+    #[must_use]
+    pub fn guessed_from(headers: &[String], fallback_currency: &str) -> CsvColumnMapping {
+        let find_column = |names: &[&str], fallback: &str| -> String {
+            headers
+                .iter()
+                .find(|header| names.iter().any(|name| header.trim().to_lowercase().contains(name)))
+                .map_or_else(|| fallback.to_string(), |header| header.trim().to_string())
+        };
+
+        CsvColumnMapping {
+            value_column: find_column(&["amount", "value"], "2"),
+            currency_column: find_column(&["currency"], ""),
+            date_column: find_column(&["date"], "1"),
+            description_column: find_column(&["description", "memo", "payee", "details", "name"], "3"),
+            date_format: "%Y-%m-%d".to_string(),
+            fallback_currency: fallback_currency.to_string(),
+            import_tag: "imported".to_string(),
+            delimiter: ",".to_string(),
+            has_header: true,
+        }
+    }
+
+
+
+    // management
+    /// Sets the given field to a new input.
+    pub fn set(&mut self, field: CsvFields, input: String) {
+        match field {
+            CsvFields::ValueColumn => { self.value_column = input; }
+            CsvFields::CurrencyColumn => { self.currency_column = input; }
+            CsvFields::DateColumn => { self.date_column = input; }
+            CsvFields::DescriptionColumn => { self.description_column = input; }
+            CsvFields::DateFormat => { self.date_format = input; }
+            CsvFields::FallbackCurrency => { self.fallback_currency = input; }
+            CsvFields::ImportTag => { self.import_tag = input; }
+            CsvFields::Delimiter => { self.delimiter = input; }
+        }
+    }
+
+    /// Gets the current input for the given field.
+    #[must_use]
+    pub fn get(&self, field: CsvFields) -> &str {
+        match field {
+            CsvFields::ValueColumn => { &self.value_column }
+            CsvFields::CurrencyColumn => { &self.currency_column }
+            CsvFields::DateColumn => { &self.date_column }
+            CsvFields::DescriptionColumn => { &self.description_column }
+            CsvFields::DateFormat => { &self.date_format }
+            CsvFields::FallbackCurrency => { &self.fallback_currency }
+            CsvFields::ImportTag => { &self.import_tag }
+            CsvFields::Delimiter => { &self.delimiter }
+        }
+    }
+
+    /// Returns the delimiter as a `char`.
+    /// Tabs can be entered as `\t`.
+    #[must_use]
+    pub fn get_delimiter(&self) -> Option<char> {
+        if self.delimiter == "\\t" { return Some('\t'); }
+        let mut chars = self.delimiter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(delimiter), None) if delimiter != '"' => Some(delimiter),
+            _ => None,
+        }
+    }
+}
+
+
+
+/// Holds the contents of a CSV bank statement while its `CsvColumnMapping` is being set up.
+#[derive(Debug, Clone)]
+pub struct CsvImport {
+    /// The raw contents of the file.
+    contents: String,
+    /// The cells of every row in the file, including the header row.
+    records: Vec<Vec<String>>,
+    /// How the columns map onto `Transaction`s.
+    mapping: CsvColumnMapping,
+}
+impl CsvImport {
+    // initializing
+    /// Loads a CSV file from the given `Path`.
+    #[must_use]
+    pub fn load_from(path: &PathBuf, fallback_currency: &str) -> Schrod<CsvImport> {
+        // reading the file
+        let contents_result = Schrod::from_result(std::fs::read_to_string(path), "Failed to read CSV file.", "CsvImport::load_from()");
+        if contents_result.is_fail() {
+            return contents_result
+                .convert("CsvImport::load_from()")
+                .fail("Failed to load CSV file.", "CsvImport::load_from()")
+        }
+        let contents = contents_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::load_from()");
+
+        let import_result = CsvImport::from_contents(contents, fallback_currency);
+        if import_result.is_fail() {
+            return import_result.fail("Failed to load CSV file.", "CsvImport::load_from()")
+        }
+        import_result
+    }
+
+    /// Splits the contents of a CSV file into records and guesses its `CsvColumnMapping`.
+    #[must_use]
+    pub fn from_contents(contents: String, fallback_currency: &str) -> Schrod<CsvImport> {
+        // splitting the file into records
+        let records = split_records(&contents, ',');
+        if records.is_empty() {
+            return Schrod::new_fail("The CSV file is empty.", "CsvImport::from_contents()")
+        }
+
+        // guessing the mapping
+        let mapping = CsvColumnMapping::guessed_from(&records[0], fallback_currency);
+
+        // returning the import
+        Pass(CsvImport { contents, records, mapping })
+    }
+
+
+
+    // management
+    /// Sets a field of the `CsvColumnMapping`.
+    /// Changing the delimiter splits the file again.
+    pub fn set_mapping_field(&mut self, field: CsvFields, input: String) {
+        self.mapping.set(field, input);
+        if field == CsvFields::Delimiter && let Some(delimiter) = self.mapping.get_delimiter() {
+            self.records = split_records(&self.contents, delimiter);
+        }
+    }
+
+    /// Toggles whether the first row is treated as a header.
+    pub fn toggle_header(&mut self) {
+        self.mapping.has_header = !self.mapping.has_header;
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns a reference to the `CsvColumnMapping`.
+    #[must_use]
+    pub fn get_mapping(&self) -> &CsvColumnMapping {
+        &self.mapping
+    }
+
+    /// Returns the names of the columns.
+    /// If there is no header row, the columns are numbered.
+    #[must_use]
+    pub fn get_headers(&self) -> Vec<String> {
+        let width = self.records.iter().map(Vec::len).max().unwrap_or(0);
+        (0..width).map(|index| {
+            match self.records.first().and_then(|header| header.get(index)) {
+                Some(header) if self.mapping.has_header => { format!("{}: {}", index + 1, header.trim()) }
+                _ => { format!("Column {}", index + 1) }
+            }
+        }).collect()
+    }
+
+    /// Returns the rows that hold `Transaction` data.
+    #[must_use]
+    pub fn get_rows(&self) -> &[Vec<String>] {
+        if self.mapping.has_header && !self.records.is_empty() { &self.records[1..] }
+        else { &self.records }
+    }
+
+    /// Returns the index of a column referenced by its header name or by its position starting at 1.
+    #[must_use]
+    fn get_column_index(&self, column: &str) -> Option<usize> {
+        let column = column.trim();
+        if column.is_empty() { return None; }
+
+        if self.mapping.has_header && let Some(header) = self.records.first() {
+            let header_index = header.iter().position(|name| name.trim().eq_ignore_ascii_case(column));
+            if header_index.is_some() { return header_index; }
+        }
+
+        match column.parse::<usize>() {
+            Ok(position) if position >= 1 => Some(position - 1),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the given field holds usable input.
+    #[must_use]
+    pub fn is_field_valid(&self, field: CsvFields) -> bool {
+        let input = self.mapping.get(field);
+        match field {
+            CsvFields::ValueColumn | CsvFields::DateColumn | CsvFields::DescriptionColumn => { self.get_column_index(input).is_some() }
+            CsvFields::CurrencyColumn => { input.trim().is_empty() || self.get_column_index(input).is_some() }
+            CsvFields::DateFormat => { !input.trim().is_empty() }
+            CsvFields::FallbackCurrency => { Transaction::is_currency_string_valid(input) }
+            CsvFields::ImportTag => { Tag::is_allowed(input) }
+            CsvFields::Delimiter => { self.mapping.get_delimiter().is_some() }
+        }
+    }
+
+    /// Parses every row into a `Transaction`.
+    /// Each row is parsed separately so a single bad row does not stop the rest of the import.
+    #[must_use]
+    pub fn parse(&self) -> Vec<Schrod<Transaction>> {
+        let row_offset = if self.mapping.has_header { 2 } else { 1 };
+        self.get_rows()
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.iter().any(|cell| !cell.trim().is_empty()))
+            .map(|(index, row)| {
+                let row_result = self.parse_row(row);
+                if row_result.is_fail() { row_result.fail(&format!("Failed to import row {}.", index + row_offset), "CsvImport::parse()") }
+                else { row_result }
+            })
+            .collect()
+    }
+
+    /// Returns all the `Transaction`s that could be parsed.
+    /// Please note that an `Id` must be filled in later with `set_id()`.
+    #[must_use]
+    pub fn get_transactions(&self) -> Vec<Transaction> {
+        self.parse()
+            .into_iter()
+            .filter_map(|transaction_result| match transaction_result {
+                Pass(transaction) => Some(transaction),
+                Schrod::Fail(_) => None,
+            })
+            .collect()
+    }
+
+    /// Parses a single row into a `Transaction`.
+    #[must_use]
+    fn parse_row(&self, row: &[String]) -> Schrod<Transaction> {
+        // value
+        let value_cell_result = self.get_cell(row, &self.mapping.value_column);
+        if value_cell_result.is_fail() { return value_cell_result.convert("CsvImport::parse_row()"); }
+        let value_string = cleaned_value_string(value_cell_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::parse_row()"));
+
+        // currency
+        let currency_string = match self.get_cell(row, &self.mapping.currency_column) {
+            Pass(currency) if !currency.trim().is_empty() => currency.trim().to_string(),
+            _ => self.mapping.fallback_currency.trim().to_string(),
+        };
+
+        // date
+        let date_cell_result = self.get_cell(row, &self.mapping.date_column);
+        if date_cell_result.is_fail() { return date_cell_result.convert("CsvImport::parse_row()"); }
+        let date_result = parse_date(date_cell_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::parse_row()"), &self.mapping.date_format);
+        if date_result.is_fail() { return date_result.convert("CsvImport::parse_row()"); }
+        let date = date_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::parse_row()");
+
+        // description
+        let description_cell_result = self.get_cell(row, &self.mapping.description_column);
+        if description_cell_result.is_fail() { return description_cell_result.convert("CsvImport::parse_row()"); }
+        let description = Transaction::sanitized_description(description_cell_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::parse_row()"));
+
+        // tags
+        let tag_result = Tag::new(&self.mapping.import_tag);
+        if tag_result.is_fail() { return tag_result.convert("CsvImport::parse_row()"); }
+        let tag = tag_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::parse_row()");
+
+        // returning the transaction
+        Transaction::load_from_raw(&value_string, &currency_string, date, description, vec![tag])
+    }
+
+    /// Gets the cell in the given row for a column referenced by its header name or by its position.
+    #[must_use]
+    fn get_cell<'a>(&self, row: &'a [String], column: &str) -> Schrod<&'a str> {
+        let index_result = Schrod::from_option(self.get_column_index(column), &format!("Unknown column \"{column}\"."), "CsvImport::get_cell()");
+        if index_result.is_fail() { return index_result.convert("CsvImport::get_cell()"); }
+        let index = index_result.wont_fail("This is past an is_fail() guard clause.", "CsvImport::get_cell()");
+
+        Schrod::from_option(row.get(index).map(String::as_str), &format!("The row has no cell in column \"{column}\"."), "CsvImport::get_cell()")
+    }
+}
+
+
+
+/// Splits CSV contents into rows of cells.
+/// Quoted cells may contain the delimiter, line breaks, and escaped (doubled) quotes.
+#[must_use]
+pub fn split_records(contents: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut is_quoted = false;
+    let mut chars = contents.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if is_quoted {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    cell.push('"');
+                    chars.next();
+                }
+                else { is_quoted = false; }
+            }
+            else { cell.push(c); }
+        }
+
+        else if c == '"' { is_quoted = true; }
+        else if c == delimiter { record.push(std::mem::take(&mut cell)); }
+        else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') { chars.next(); }
+            record.push(std::mem::take(&mut cell));
+            if record.iter().any(|cell| !cell.is_empty()) { records.push(std::mem::take(&mut record)); }
+            else { record.clear(); }
+        }
+        else { cell.push(c); }
+    }
+
+    // the final row might not end with a line break
+    record.push(cell);
+    if record.iter().any(|cell| !cell.is_empty()) { records.push(record); }
+
+    records
+}
+
+/// Strips currency symbols and grouping separators from a value cell.
+/// Values in parentheses are treated as negative.
+/// When both '.' and ',' appear, whichever comes last marks the decimal, so both 1,234.56 and 1.234,56 are read as 1234.56.
+/// When only one of them appears, it marks the decimal if it appears once, or once followed by one or two digits for a comma,
+/// and is a grouping separator otherwise.
+#[must_use]
+fn cleaned_value_string(cell: &str) -> String {
+    let trimmed = cell.trim();
+    let is_parenthesized = trimmed.starts_with('(') && trimmed.ends_with(')');
+    let mut kept: String = trimmed.chars().filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',' || *c == '-').collect();
+
+    // deciding which separator marks the decimal
+    let decimal_separator = match (kept.rfind('.'), kept.rfind(',')) {
+        (Some(dot), Some(comma)) => { if comma > dot { Some(',') } else { Some('.') } }
+        (Some(_), None) => { if kept.matches('.').count() == 1 { Some('.') } else { None } }
+        (None, Some(_)) => {
+            let is_decimal_comma = kept.matches(',').count() == 1 && kept.rsplit(',').next().is_some_and(|fraction| (1..=2).contains(&fraction.len()));
+            if is_decimal_comma { Some(',') } else { None }
+        }
+        (None, None) => { None }
+    };
+    kept = match decimal_separator {
+        Some(',') => { kept.replace('.', "").replace(',', ".") }
+        Some(_) => { kept.replace(',', "") }
+        None => { kept.replace(['.', ','], "") }
+    };
+
+    if is_parenthesized && !kept.starts_with('-') { format!("-{kept}") }
+    else { kept }
+}
+
+/// Parses a `Date` with the given `chrono` format.
+#[must_use]
+pub fn parse_date(cell: &str, date_format: &str) -> Schrod<Date> {
+    let naive_date_result = Schrod::from_result(NaiveDate::parse_from_str(cell.trim(), date_format), &format!("Failed to read \"{}\" as a date with the format \"{date_format}\".", cell.trim()), "csv_import::parse_date()");
+    if naive_date_result.is_fail() { return naive_date_result.convert("csv_import::parse_date()"); }
+    let naive_date = naive_date_result.wont_fail("This is past an is_fail() guard clause.", "csv_import::parse_date()");
+
+    let month_result = Months::from_value(naive_date.month());
+    if month_result.is_fail() { return month_result.convert("csv_import::parse_date()"); }
+    let month = month_result.wont_fail("This is past an is_fail() guard clause.", "csv_import::parse_date()");
+
+    #[allow(clippy::cast_sign_loss)]
+    Date::new(naive_date.year().max(0) as u32, month, naive_date.day())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{CsvFields, CsvImport, cleaned_value_string};
+    use crate::vault::test_helpers::date;
+    use crate::vault::transaction::Months;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn values_are_read_with_either_decimal_separator() {
+        assert_eq!(cleaned_value_string("1,234.56"), "1234.56");
+        assert_eq!(cleaned_value_string("1.234,56"), "1234.56");
+        assert_eq!(cleaned_value_string("1.234.567,89"), "1234567.89");
+        assert_eq!(cleaned_value_string("1,234,567"), "1234567");
+        assert_eq!(cleaned_value_string("12,5"), "12.5");
+        assert_eq!(cleaned_value_string("$ -7.25"), "-7.25");
+        assert_eq!(cleaned_value_string("(42.00)"), "-42.00");
+    }
+
+    #[test]
+    fn columns_are_mapped_by_name_or_position() {
+        let contents = "Booking Date,Payee,Amount,Currency\n14/03/2026,Corner Market,\"-1.234,56\",EUR\n15/03/2026,Refund,\"12,5\",\n".to_string();
+        let mut import = CsvImport::from_contents(contents, "USD").wont_fail("The test file is not empty.", "tests");
+
+        // the guessed mapping finds named columns, but the date format must be set
+        assert_eq!(import.get_mapping().value_column, "Amount");
+        assert!(import.get_transactions().is_empty());
+        import.set_mapping_field(CsvFields::DateFormat, "%d/%m/%Y".to_string());
+        import.set_mapping_field(CsvFields::DescriptionColumn, "2".to_string());
+        assert!(import.is_field_valid(CsvFields::CurrencyColumn));
+
+        let transactions = import.get_transactions();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(*transactions[0].value.amount(), Decimal::new(-123_456, 2));
+        assert_eq!(transactions[0].description, "Corner Market");
+        assert_eq!(transactions[0].date, date(2026, Months::March, 14));
+        // an empty currency cell falls back to the fallback currency
+        assert_eq!(transactions[1].value.currency().to_string(), "USD");
+        assert_eq!(*transactions[1].value.amount(), Decimal::new(125, 1));
+
+        // without a header, the first row is read as a transaction and fails
+        import.toggle_header();
+        import.set_mapping_field(CsvFields::ValueColumn, "3".to_string());
+        import.set_mapping_field(CsvFields::DateColumn, "1".to_string());
+        import.set_mapping_field(CsvFields::CurrencyColumn, "4".to_string());
+        assert_eq!(import.parse().iter().filter(|result| result.is_fail()).count(), 1);
+        assert_eq!(import.get_transactions().len(), 2);
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::Path;
+use serde::Serialize;
+use crate::vault::bank::{Bank, Filters};
+use crate::vault::save_engine::write_atomically;
+use crate::vault::transaction::{Id, Split, Transaction};
+use schrod::Schrod;
+use schrod::Schrod::{Pass, Fail};
+
+/// The separator placed between `Tag`s in a single CSV cell.
+const CSV_TAG_SEPARATOR: &str = "; ";
+
+/// The separator placed between `Split`s in a single CSV cell.
+const CSV_SPLIT_SEPARATOR: &str = " | ";
+
+/// The CSV header, in the same order as the fields of an `ExportRow`.
+const CSV_HEADER: [&str; 10] = ["uid", "date", "value", "currency", "description", "tags", "unified_value", "main_currency", "time_price", "splits"];
+
+
+
+/// An enumeration of the formats `Transaction`s can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormats {
+    Csv,
+    Json,
+}
+impl ExportFormats {
+    // data retrieval and parsing
+    /// Returns the format matching the extension of the given `Path`, if there is one.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<ExportFormats> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(ExportFormats::Csv),
+            "json" => Some(ExportFormats::Json),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the format.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ExportFormats::Csv => { "CSV" }
+            ExportFormats::Json => { "JSON" }
+        }
+    }
+
+    /// Returns the file extension of the format.
+    #[must_use]
+    pub fn extension(&self) -> &str {
+        match self {
+            ExportFormats::Csv => { "csv" }
+            ExportFormats::Json => { "json" }
+        }
+    }
+}
+
+
+
+/// A single exported `Transaction`, flattened so that it reads well outside of Ascent.
+/// Values are kept as `String`s so that no precision is lost.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportRow {
+    pub uid: String,
+    /// The date formatted as YYYY-MM-DD.
+    pub date: String,
+    pub value: String,
+    pub currency: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    /// The value converted to the main currency, or `None` if there is no exchange rate for it.
+    pub unified_value: Option<String>,
+    pub main_currency: String,
+    /// The value in hours, or `None` if there is no exchange rate for it.
+    pub time_price: Option<String>,
+    pub splits: Vec<ExportSplit>,
+}
+impl ExportRow {
+    // initializing
+    /// Creates an `ExportRow` from a `Transaction`.
+    #[must_use]
+    pub fn new(bank: &Bank, transaction: &Transaction) -> ExportRow {
+        let currency_exchange = &bank.currency_exchange;
+        let unified_value = match currency_exchange.convert(transaction.value.amount(), transaction.value.currency(), currency_exchange.get_main_currency(), transaction.date) {
+            Pass(unified_value) => Some(unified_value),
+            Fail(_) => None,
+        };
+
+        ExportRow {
+            uid: transaction.get_uid().to_string(),
+            date: transaction.date.display_iso(),
+            value: transaction.value.amount().to_string(),
+            currency: transaction.value.currency().to_string(),
+            description: transaction.description.clone(),
+            tags: transaction.tags.iter().map(|tag| tag.get_label()).collect(),
+            unified_value: unified_value.map(|unified_value| unified_value.round_dp(2).to_string()),
+            main_currency: currency_exchange.get_main_currency().to_string(),
+            time_price: match currency_exchange.as_time_price(&transaction.value, transaction.date) {
+                Pass(time_price) => Some(time_price.round_dp(2).to_string()),
+                Fail(_) => None,
+            },
+            splits: transaction.get_splits().iter().map(ExportSplit::new).collect(),
+        }
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the cells of the row, in the same order as the CSV header.
+    #[must_use]
+    fn as_cells(&self) -> [String; 10] {
+        [
+            self.uid.clone(),
+            self.date.clone(),
+            self.value.clone(),
+            self.currency.clone(),
+            self.description.clone(),
+            self.tags.join(CSV_TAG_SEPARATOR),
+            self.unified_value.clone().unwrap_or_default(),
+            self.main_currency.clone(),
+            self.time_price.clone().unwrap_or_default(),
+            self.splits.iter().map(ExportSplit::as_cell_part).collect::<Vec<_>>().join(CSV_SPLIT_SEPARATOR),
+        ]
+    }
+}
+
+
+
+/// A single exported `Split`, in the `Currency` of its `Transaction`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportSplit {
+    pub value: String,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+impl ExportSplit {
+    // initializing
+    /// Creates an `ExportSplit` from a `Split`.
+    #[must_use]
+    pub fn new(split: &Split) -> ExportSplit {
+        ExportSplit {
+            value: split.amount.to_string(),
+            tags: split.tags.iter().map(|tag| tag.get_label()).collect(),
+            note: split.note.clone(),
+        }
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the `Split` as it is written in the splits cell of a CSV row: its value, its tags, and its note in parentheses.
+    #[must_use]
+    fn as_cell_part(&self) -> String {
+        let note = self.note.as_ref().map(|note| format!(" ({note})")).unwrap_or_default();
+        format!("{} {}{note}", self.value, self.tags.join(CSV_TAG_SEPARATOR))
+    }
+}
+
+
+
+/// Returns the `ExportRow`s for the given `Transaction` `Id`s, newest first.
+#[must_use]
+pub fn export_rows(bank: &Bank, ids: &[Id]) -> Vec<ExportRow> {
+    let transactions = ids.iter()
+        .filter_map(|id| match bank.get(*id) {
+            Pass(transaction) => Some(transaction.clone()),
+            Fail(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    Bank::sorted_ledger(transactions)
+        .iter()
+        .map(|transaction| ExportRow::new(bank, transaction))
+        .collect()
+}
+
+/// Writes the `Transaction`s selected by a `Filter` to a file.
+/// Returns the number of `Transaction`s written.
+#[must_use]
+pub fn export_filtered(bank: &Bank, filter: Filters, path: &Path, format: ExportFormats) -> Schrod<usize> {
+    let rows = export_rows(bank, &bank.get_filtered_ids(filter));
+    let write_result = write_rows(&rows, path, format);
+    if write_result.is_fail() { return write_result.convert("export::export_filtered()") }
+    Pass(rows.len())
+}
+
+/// Writes `ExportRow`s to a file in the given format.
+#[must_use]
+pub fn write_rows(rows: &[ExportRow], path: &Path, format: ExportFormats) -> Schrod<()> {
+    let contents_result = match format {
+        ExportFormats::Csv => Pass(csv_of(rows)),
+        ExportFormats::Json => Schrod::from_result(serde_json::to_string_pretty(rows), "Failed to serialize the Transactions.", "export::write_rows()"),
+    };
+    if contents_result.is_fail() {
+        return contents_result
+            .convert("export::write_rows()")
+            .fail(&format!("Failed to export {}.", format.name()), "export::write_rows()")
+    }
+    let contents = contents_result.wont_fail("This is past an is_fail() guard clause.", "export::write_rows()");
+
+    let write_result = write_atomically(path, &contents);
+    if write_result.is_fail() {
+        return write_result.fail(&format!("Failed to export {}.", format.name()), "export::write_rows()")
+    }
+    Pass(())
+}
+
+/// Returns the CSV contents for `ExportRow`s, including the header.
+#[must_use]
+fn csv_of(rows: &[ExportRow]) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+    for row in rows {
+        lines.push(row.as_cells().iter().map(|cell| csv_cell(cell)).collect::<Vec<_>>().join(","));
+    }
+    lines.push(String::new()); // the file ends with a line break
+    lines.join("\r\n")
+}
+
+/// Quotes a CSV cell if it contains a delimiter, quote, or line break.
+#[must_use]
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) { format!("\"{}\"", cell.replace('"', "\"\"")) }
+    else { cell.to_string() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use super::{ExportFormats, ExportRow, ExportSplit, csv_of};
+
+    #[test]
+    fn csv_cells_are_quoted_when_needed() {
+        let row = ExportRow {
+            uid: "0d0e7a46-6e0c-4c7b-9f5e-0f5ad6a7d0a1".to_string(),
+            date: "2026-03-02".to_string(),
+            value: "-12.50".to_string(),
+            currency: "USD".to_string(),
+            description: "Lunch, \"the usual\"".to_string(),
+            tags: vec!["food".to_string(), "work".to_string()],
+            unified_value: None,
+            main_currency: "USD".to_string(),
+            time_price: None,
+            splits: Vec::new(),
+        };
+        let csv = csv_of(&[row]);
+        let lines = csv.split("\r\n").collect::<Vec<_>>();
+        assert_eq!(lines[0], "uid,date,value,currency,description,tags,unified_value,main_currency,time_price,splits");
+        assert_eq!(lines[1], "0d0e7a46-6e0c-4c7b-9f5e-0f5ad6a7d0a1,2026-03-02,-12.50,USD,\"Lunch, \"\"the usual\"\"\",food; work,,USD,,");
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn splits_are_nested_in_json_and_share_a_cell_in_csv() {
+        let row = ExportRow {
+            uid: "5c1f0a2e-8b7d-4e6c-a5f4-3e2d1c0b9a8f".to_string(),
+            date: "2026-03-07".to_string(),
+            value: "-50".to_string(),
+            currency: "EUR".to_string(),
+            description: "Market".to_string(),
+            tags: vec!["groceries".to_string()],
+            unified_value: Some("-54.50".to_string()),
+            main_currency: "USD".to_string(),
+            time_price: Some("-2.18".to_string()),
+            splits: vec![
+                ExportSplit { value: "-12.50".to_string(), tags: vec!["household".to_string()], note: Some("Cleaning supplies".to_string()) },
+                ExportSplit { value: "-5".to_string(), tags: vec!["pharmacy".to_string(), "health".to_string()], note: None },
+            ],
+        };
+        let csv = csv_of(std::slice::from_ref(&row));
+        assert_eq!(csv.split("\r\n").nth(1), Some("5c1f0a2e-8b7d-4e6c-a5f4-3e2d1c0b9a8f,2026-03-07,-50,EUR,Market,groceries,-54.50,USD,-2.18,-12.50 household (Cleaning supplies) | -5 pharmacy; health"));
+
+        let json = serde_json::to_value(&row).unwrap_or_default();
+        assert_eq!(json["splits"][0]["value"], "-12.50");
+        assert_eq!(json["splits"][0]["note"], "Cleaning supplies");
+        assert_eq!(json["splits"][1]["tags"][1], "health");
+    }
+
+    #[test]
+    fn formats_follow_the_extension() {
+        assert_eq!(ExportFormats::from_path(Path::new("out.CSV")), Some(ExportFormats::Csv));
+        assert_eq!(ExportFormats::from_path(Path::new("out.json")), Some(ExportFormats::Json));
+        assert_eq!(ExportFormats::from_path(Path::new("out")), None);
+    }
+}
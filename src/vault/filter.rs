@@ -108,6 +108,13 @@ impl Filter {
         self.filter(transactions)
     }
 
+    /// Replaces a `Tag` and its children with another `Tag`.
+    #[must_use]
+    pub fn replace_tag(&mut self, old_tag: &Tag, new_tag: &Tag, transactions: &[Transaction]) -> Schrod<()> {
+        self.tags = Tag::replaced_in(&self.tags, old_tag, new_tag);
+        self.filter(transactions)
+    }
+    
     /// Makes sure that the filtered `Tag`s all exist in the given list of `Tag`s.
     pub fn verify_filtered_tags(&mut self, existing_tags: &[Tag]) {
         self.tags.retain(|tag| existing_tags.contains(tag));
@@ -209,7 +216,7 @@ impl Filter {
                         does_search_term_match = true;
                         break;
                     }
-                    for tag in &transaction.tags {
+                    for tag in &transaction.get_all_tags() {
                         if tag.get_label().to_lowercase().contains(&term.to_lowercase()) {
                             does_search_term_match = true;
                             break;
@@ -296,7 +303,7 @@ impl Filter {
                     if transaction.description.to_lowercase().contains(&term.to_lowercase()) {
                         found = true;
                     }
-                    for tag in &transaction.tags {
+                    for tag in &transaction.get_all_tags() {
                         if tag.get_label().to_lowercase().contains(&term.to_lowercase()) {
                             found = true;
                         }
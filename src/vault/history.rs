@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use crate::vault::account::Account;
+use crate::vault::bank::{Bank, CurrencyExchange, TagRegistry};
+use crate::vault::budget::Budget;
+use crate::vault::recurring::RecurringTemplate;
+use crate::vault::tag_rule::TagRule;
+use crate::vault::transaction::{Transaction, Uid};
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// The most `BankCommand`s that can be undone.
+pub const MAX_HISTORY_LENGTH: usize = 100;
+
+/// A copy of the parts of a `Bank` that changes can be undone for.
+/// This is taken before a change so that it can be compared to the `Bank` afterwards.
+#[derive(Debug, Clone)]
+pub struct BankState {
+    ledger: Vec<Transaction>,
+    currency_exchange: CurrencyExchange,
+    tag_registry: TagRegistry,
+    recurring_templates: Vec<RecurringTemplate>,
+    budgets: Vec<Budget>,
+    tag_rules: Vec<TagRule>,
+    accounts: Vec<Account>,
+}
+impl BankState {
+    // initializing
+    /// Copies the current state of a `Bank`.
+    #[must_use]
+    pub fn of(bank: &Bank) -> BankState {
+        BankState {
+            ledger: bank.get_ledger_copy(),
+            currency_exchange: bank.currency_exchange.clone(),
+            tag_registry: bank.tag_registry.clone(),
+            recurring_templates: bank.recurring_templates.clone(),
+            budgets: bank.budgets.clone(),
+            tag_rules: bank.tag_rules.clone(),
+            accounts: bank.accounts.clone(),
+        }
+    }
+}
+
+
+
+/// A single change to a part of a `Bank`.
+/// `Transaction`s are tracked one by one through their `Uid`s, while the other parts are replaced as a whole.
+#[derive(Debug, Clone)]
+pub enum BankChanges {
+    AddedTransaction(Transaction),
+    RemovedTransaction(Transaction),
+    EditedTransaction { before: Transaction, after: Transaction },
+    ChangedCurrencyExchange { before: CurrencyExchange, after: CurrencyExchange },
+    ChangedTagRegistry { before: TagRegistry, after: TagRegistry },
+    ChangedRecurringTemplates { before: Vec<RecurringTemplate>, after: Vec<RecurringTemplate> },
+    ChangedBudgets { before: Vec<Budget>, after: Vec<Budget> },
+    ChangedTagRules { before: Vec<TagRule>, after: Vec<TagRule> },
+    ChangedAccounts { before: Vec<Account>, after: Vec<Account> },
+}
+impl BankChanges {
+    // data retrieval and parsing
+    /// Returns the change that reverses this one.
+    #[must_use]
+    fn inverted(&self) -> BankChanges {
+        match self.clone() {
+            BankChanges::AddedTransaction(transaction) => { BankChanges::RemovedTransaction(transaction) }
+            BankChanges::RemovedTransaction(transaction) => { BankChanges::AddedTransaction(transaction) }
+            BankChanges::EditedTransaction { before, after } => { BankChanges::EditedTransaction { before: after, after: before } }
+            BankChanges::ChangedCurrencyExchange { before, after } => { BankChanges::ChangedCurrencyExchange { before: after, after: before } }
+            BankChanges::ChangedTagRegistry { before, after } => { BankChanges::ChangedTagRegistry { before: after, after: before } }
+            BankChanges::ChangedRecurringTemplates { before, after } => { BankChanges::ChangedRecurringTemplates { before: after, after: before } }
+            BankChanges::ChangedBudgets { before, after } => { BankChanges::ChangedBudgets { before: after, after: before } }
+            BankChanges::ChangedTagRules { before, after } => { BankChanges::ChangedTagRules { before: after, after: before } }
+            BankChanges::ChangedAccounts { before, after } => { BankChanges::ChangedAccounts { before: after, after: before } }
+        }
+    }
+}
+
+
+
+/// A named group of `BankChanges` made by a single action, which can be undone and redone together.
+#[derive(Debug, Clone)]
+pub struct BankCommand {
+    name: String,
+    changes: Vec<BankChanges>,
+}
+impl BankCommand {
+    // initializing
+    /// Creates a `BankCommand` from the differences between an earlier `BankState` and a `Bank`.
+    /// Returns `None` if nothing changed.
+    #[must_use]
+    pub fn between(name: &str, before: &BankState, bank: &Bank) -> Option<BankCommand> {
+        let mut changes = Vec::new();
+
+        // comparing the transactions by uid
+        let before_transactions = before.ledger.iter().map(|transaction| (transaction.get_uid(), transaction)).collect::<HashMap<Uid, &Transaction>>();
+        let after_transactions = bank.get_ledger().iter().map(|transaction| (transaction.get_uid(), transaction)).collect::<HashMap<Uid, &Transaction>>();
+        for transaction in &before.ledger {
+            match after_transactions.get(&transaction.get_uid()) {
+                Some(after) if !BankCommand::are_identical(transaction, after) => { changes.push(BankChanges::EditedTransaction { before: transaction.clone(), after: (*after).clone() }); }
+                Some(_) => {}
+                None => { changes.push(BankChanges::RemovedTransaction(transaction.clone())); }
+            }
+        }
+        for transaction in bank.get_ledger() {
+            if !before_transactions.contains_key(&transaction.get_uid()) { changes.push(BankChanges::AddedTransaction(transaction.clone())); }
+        }
+
+        // comparing the other parts as a whole
+        if before.currency_exchange != bank.currency_exchange {
+            changes.push(BankChanges::ChangedCurrencyExchange { before: before.currency_exchange.clone(), after: bank.currency_exchange.clone() });
+        }
+        if before.tag_registry != bank.tag_registry {
+            changes.push(BankChanges::ChangedTagRegistry { before: before.tag_registry.clone(), after: bank.tag_registry.clone() });
+        }
+        if before.recurring_templates != bank.recurring_templates {
+            changes.push(BankChanges::ChangedRecurringTemplates { before: before.recurring_templates.clone(), after: bank.recurring_templates.clone() });
+        }
+        if before.budgets != bank.budgets {
+            changes.push(BankChanges::ChangedBudgets { before: before.budgets.clone(), after: bank.budgets.clone() });
+        }
+        if before.tag_rules != bank.tag_rules {
+            changes.push(BankChanges::ChangedTagRules { before: before.tag_rules.clone(), after: bank.tag_rules.clone() });
+        }
+        if before.accounts != bank.accounts {
+            changes.push(BankChanges::ChangedAccounts { before: before.accounts.clone(), after: bank.accounts.clone() });
+        }
+
+        if changes.is_empty() { return None }
+        Some(BankCommand { name: name.to_string(), changes })
+    }
+
+
+
+    // management
+    /// Reverses the `BankChanges` of the `BankCommand`.
+    #[must_use]
+    pub fn undo(&self, bank: &mut Bank) -> Schrod<()> {
+        BankCommand::apply(&self.changes.iter().map(BankChanges::inverted).collect::<Vec<_>>(), bank)
+    }
+
+    /// Makes the `BankChanges` of the `BankCommand` again after they were undone.
+    #[must_use]
+    pub fn redo(&self, bank: &mut Bank) -> Schrod<()> {
+        BankCommand::apply(&self.changes, bank)
+    }
+
+    /// Makes a list of `BankChanges` to a `Bank`.
+    #[must_use]
+    fn apply(changes: &[BankChanges], bank: &mut Bank) -> Schrod<()> {
+        let mut removed_uids = Vec::new();
+        let mut added_transactions = Vec::new();
+        let mut replaced_transactions = Vec::new();
+        for change in changes {
+            match change.clone() {
+                BankChanges::AddedTransaction(transaction) => { added_transactions.push(transaction); }
+                BankChanges::RemovedTransaction(transaction) => { removed_uids.push(transaction.get_uid()); }
+                BankChanges::EditedTransaction { after, .. } => { replaced_transactions.push(after); }
+                BankChanges::ChangedCurrencyExchange { after, .. } => { bank.currency_exchange = after; }
+                BankChanges::ChangedTagRegistry { after, .. } => { bank.tag_registry = after; }
+                BankChanges::ChangedRecurringTemplates { after, .. } => { bank.recurring_templates = after; }
+                BankChanges::ChangedBudgets { after, .. } => { bank.budgets = after; }
+                BankChanges::ChangedTagRules { after, .. } => { bank.tag_rules = after; }
+                BankChanges::ChangedAccounts { after, .. } => { bank.accounts = after; }
+            }
+        }
+
+        let ledger_result = bank.apply_ledger_changes(&removed_uids, added_transactions, replaced_transactions);
+        if ledger_result.is_fail() { return ledger_result.fail("Failed to apply the changes to the ledger.", "BankCommand::apply()") }
+        Pass(())
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the name of the action that made the `BankChanges`.
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks if two versions of a `Transaction` match in every saved detail.
+    #[must_use]
+    fn are_identical(a: &Transaction, b: &Transaction) -> bool {
+        a.has_same_contents(b) && a.tags == b.tags && a.get_statement_id() == b.get_statement_id()
+            && a.get_account_uid() == b.get_account_uid() && a.get_transfer_uid() == b.get_transfer_uid()
+    }
+}
+
+
+
+/// The `BankCommand`s that can be undone, and the undone ones that can be redone.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo_stack: Vec<BankCommand>,
+    redo_stack: Vec<BankCommand>,
+}
+impl History {
+    // management
+    /// Records a new `BankCommand`, which makes the undone `BankCommand`s impossible to redo.
+    pub fn record(&mut self, command: BankCommand) {
+        self.undo_stack.push(command);
+        if self.undo_stack.len() > MAX_HISTORY_LENGTH { self.undo_stack.remove(0); }
+        self.redo_stack.clear();
+    }
+
+    /// Undoes the latest `BankCommand`.
+    /// Returns the name of the undone `BankCommand`, or `None` if there was nothing to undo.
+    #[must_use]
+    pub fn undo(&mut self, bank: &mut Bank) -> Schrod<Option<String>> {
+        let Some(command) = self.undo_stack.pop() else { return Pass(None) };
+        let undo_result = command.undo(bank);
+        if undo_result.is_fail() {
+            return undo_result
+                .convert("History::undo()")
+                .fail(&format!("Failed to undo \"{}\".", command.get_name()), "History::undo()")
+        }
+        let name = command.get_name().to_string();
+        self.redo_stack.push(command);
+        Pass(Some(name))
+    }
+
+    /// Redoes the latest undone `BankCommand`.
+    /// Returns the name of the redone `BankCommand`, or `None` if there was nothing to redo.
+    #[must_use]
+    pub fn redo(&mut self, bank: &mut Bank) -> Schrod<Option<String>> {
+        let Some(command) = self.redo_stack.pop() else { return Pass(None) };
+        let redo_result = command.redo(bank);
+        if redo_result.is_fail() {
+            return redo_result
+                .convert("History::redo()")
+                .fail(&format!("Failed to redo \"{}\".", command.get_name()), "History::redo()")
+        }
+        let name = command.get_name().to_string();
+        self.undo_stack.push(command);
+        Pass(Some(name))
+    }
+
+    /// Forgets every `BankCommand`.
+    /// This is used when the `Bank` is replaced by another vault.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the name of the `BankCommand` that would be undone next.
+    #[must_use]
+    pub fn get_next_undo_name(&self) -> Option<&str> {
+        self.undo_stack.last().map(BankCommand::get_name)
+    }
+
+    /// Returns the name of the `BankCommand` that would be redone next.
+    #[must_use]
+    pub fn get_next_redo_name(&self) -> Option<&str> {
+        self.redo_stack.last().map(BankCommand::get_name)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{BankCommand, BankState, History};
+    use crate::vault::bank::Bank;
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::{Months, Transaction};
+    use materialui::materials::MaterialColors;
+    use schrod::Schrod::Pass;
+
+    fn bank() -> Bank {
+        let june_1 = date(2026, Months::June, 1);
+        bank_with(vec![
+            transaction("-30", "USD", june_1, "Books", vec![tag("hobbies")]),
+            transaction("-8", "USD", june_1, "Coffee", vec![tag("dining")]),
+        ])
+    }
+
+    /// Makes a change to a `Bank` and records it in a `History`.
+    fn record(history: &mut History, bank: &mut Bank, name: &str, change: impl FnOnce(&mut Bank)) {
+        let before = BankState::of(bank);
+        change(bank);
+        if let Some(command) = BankCommand::between(name, &before, bank) { history.record(command); }
+    }
+
+    fn descriptions(bank: &Bank) -> Vec<String> {
+        let mut descriptions = bank.get_ledger().iter().map(|t| t.description.clone()).collect::<Vec<_>>();
+        descriptions.sort();
+        descriptions
+    }
+
+    #[test]
+    fn removing_and_editing_can_be_undone_and_redone() {
+        let mut bank = bank();
+        let mut history = History::default();
+        let books_uid = bank.get_ledger().iter().find(|t| t.description == "Books").map(Transaction::get_uid).unwrap_or_default();
+
+        record(&mut history, &mut bank, "Remove Transaction", |bank| {
+            let id = bank.get_ledger().iter().find(|t| t.description == "Coffee").and_then(Transaction::get_id).unwrap_or_default();
+            assert!(bank.remove_transaction(id).is_pass());
+        });
+        record(&mut history, &mut bank, "Edit Transaction", |bank| {
+            let id = bank.get_ledger().iter().find(|t| t.description == "Books").and_then(Transaction::get_id).unwrap_or_default();
+            assert!(bank.edit_transaction_with_raw_parts(id, "-35", "USD", date(2026, Months::June, 2), "Novels".to_string(), vec![tag("hobbies")], Vec::new(), bank.get_default_account_uid()).is_pass());
+        });
+        // nothing changed, so nothing is recorded
+        record(&mut history, &mut bank, "Nothing", |_| {});
+        assert_eq!(descriptions(&bank), vec!["Novels"]);
+        assert_eq!(history.get_next_undo_name(), Some("Edit Transaction"));
+
+        assert!(matches!(history.undo(&mut bank), Pass(Some(name)) if name == "Edit Transaction"));
+        assert_eq!(descriptions(&bank), vec!["Books"]);
+        assert!(history.undo(&mut bank).is_pass());
+        assert_eq!(descriptions(&bank), vec!["Books", "Coffee"]);
+        assert!(matches!(history.undo(&mut bank), Pass(None)));
+        // the uid survives the round trip
+        assert!(bank.contains_uid(books_uid));
+
+        assert!(history.redo(&mut bank).is_pass());
+        assert_eq!(descriptions(&bank), vec!["Books"]);
+        assert_eq!(history.get_next_redo_name(), Some("Edit Transaction"));
+    }
+
+    #[test]
+    fn new_changes_clear_the_redo_stack() {
+        let mut bank = bank();
+        let mut history = History::default();
+
+        record(&mut history, &mut bank, "Change Tag Color", |bank| bank.tag_registry.set(&tag("dining"), MaterialColors::Rose));
+        assert!(history.undo(&mut bank).is_pass());
+        assert_ne!(bank.tag_registry.get(&tag("dining")), MaterialColors::Rose);
+        assert_eq!(history.get_next_redo_name(), Some("Change Tag Color"));
+
+        record(&mut history, &mut bank, "Set Time Price", |bank| assert!(bank.currency_exchange.set_time_price("25").is_pass()));
+        assert_eq!(history.get_next_redo_name(), None);
+        assert!(history.undo(&mut bank).is_pass());
+        assert_eq!(bank.currency_exchange.get_time_price().to_string(), "1");
+    }
+}
@@ -0,0 +1,307 @@
+use crate::vault::bank::{Bank, TagRegistry};
+use crate::vault::transaction::{Id, Transaction};
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// An enumeration of the ways imported save data can be brought into the `Bank`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportModes {
+    Merge,
+    Replace,
+}
+impl ImportModes {
+    /// Returns the name for a given `ImportMode`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ImportModes::Merge => { "Merge" }
+            ImportModes::Replace => { "Replace" }
+        }
+    }
+}
+
+/// An enumeration of the ways a conflict between a local and an imported `Transaction` can be resolved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictResolutions {
+    KeepLocal,
+    UseImported,
+    KeepBoth,
+}
+impl ConflictResolutions {
+    /// Returns the name for a given `ConflictResolution`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ConflictResolutions::KeepLocal => { "Keep Local" }
+            ConflictResolutions::UseImported => { "Use Imported" }
+            ConflictResolutions::KeepBoth => { "Keep Both" }
+        }
+    }
+
+    /// Returns every `ConflictResolution`.
+    #[must_use]
+    pub fn all() -> Vec<ConflictResolutions> {
+        vec![ConflictResolutions::KeepLocal, ConflictResolutions::UseImported, ConflictResolutions::KeepBoth]
+    }
+}
+
+
+
+/// A pair of `Transaction`s that look like two versions of the same `Transaction`.
+#[derive(Debug, Clone)]
+pub struct ImportConflict {
+    /// The `Id` of the `Transaction` already in the `Bank`.
+    local_id: Id,
+    /// A copy of the `Transaction` already in the `Bank`.
+    local: Transaction,
+    /// The `Transaction` from the imported save data.
+    imported: Transaction,
+    /// How the conflict will be resolved.
+    pub resolution: ConflictResolutions,
+}
+impl ImportConflict {
+    /// Returns the `Transaction` already in the `Bank`.
+    #[must_use]
+    pub fn get_local(&self) -> &Transaction {
+        &self.local
+    }
+
+    /// Returns the `Transaction` from the imported save data.
+    #[must_use]
+    pub fn get_imported(&self) -> &Transaction {
+        &self.imported
+    }
+}
+
+
+
+/// The difference between imported save data and the current `Bank`.
+#[derive(Debug, Clone)]
+pub struct ImportMerge {
+    /// Imported `Transaction`s that do not resemble anything in the `Bank`.
+    new_transactions: Vec<Transaction>,
+    /// The number of imported `Transaction`s that are already in the `Bank`.
+    identical_count: usize,
+    /// Imported `Transaction`s that resemble, but do not match, a `Transaction` in the `Bank`.
+    conflicts: Vec<ImportConflict>,
+    /// The imported `TagRegistry`.
+    tag_registry: TagRegistry,
+}
+impl ImportMerge {
+    // initializing
+    /// Compares imported `Transaction`s against the `Bank`.
+    /// Each local `Transaction` is matched at most once.
+    /// `Transaction`s sharing a `Uid` are matched first, then identical ones, then similar ones.
+    #[must_use]
+    pub fn new(bank: &Bank, imported_transactions: &[Transaction], imported_tag_registry: &TagRegistry) -> ImportMerge {
+        let ledger = bank.get_ledger();
+        let mut matched_ids: Vec<Id> = Vec::new();
+        let mut identical_count = 0;
+        let mut conflicts = Vec::new();
+        
+        // finding transactions that share a uid
+        let mut remaining: Vec<&Transaction> = Vec::new();
+        for imported in imported_transactions {
+            let local_match = ledger.iter().find(|local| local.get_uid() == imported.get_uid());
+            match local_match.and_then(|local| local.get_id().map(|id| (id, local))) {
+                Some((id, local)) if !matched_ids.contains(&id) => {
+                    matched_ids.push(id);
+                    if local.has_same_contents(imported) { identical_count += 1; }
+                    else { conflicts.push(ImportConflict { local_id: id, local: local.clone(), imported: imported.clone(), resolution: ConflictResolutions::KeepLocal }); }
+                }
+                _ => { remaining.push(imported); }
+            }
+        }
+        
+        // finding identical transactions
+        let mut unmatched: Vec<&Transaction> = Vec::new();
+        for imported in remaining {
+            let local_match = ledger.iter().find(|local| {
+                local.get_id().is_some_and(|id| !matched_ids.contains(&id)) && local.has_same_contents(imported)
+            });
+            match local_match.and_then(Transaction::get_id) {
+                Some(id) => {
+                    matched_ids.push(id);
+                    identical_count += 1;
+                }
+                None => { unmatched.push(imported); }
+            }
+        }
+
+        // finding conflicting and new transactions
+        let mut new_transactions = Vec::new();
+        for imported in unmatched {
+            let local_match = ledger.iter().find(|local| {
+                local.get_id().is_some_and(|id| !matched_ids.contains(&id)) && local.is_similar_to(imported)
+            });
+            match local_match.and_then(|local| local.get_id().map(|id| (id, local))) {
+                Some((local_id, local)) => {
+                    matched_ids.push(local_id);
+                    conflicts.push(ImportConflict { local_id, local: local.clone(), imported: imported.clone(), resolution: ConflictResolutions::KeepLocal });
+                }
+                _ => { new_transactions.push(imported.clone()); }
+            }
+        }
+
+        ImportMerge { new_transactions, identical_count, conflicts, tag_registry: imported_tag_registry.clone() }
+    }
+
+
+
+    // management
+    /// Sets how the conflict at the given index will be resolved.
+    pub fn set_resolution(&mut self, index: usize, resolution: ConflictResolutions) {
+        if let Some(conflict) = self.conflicts.get_mut(index) { conflict.resolution = resolution; }
+    }
+
+    /// Applies the merge to the `Bank`.
+    /// New `Transaction`s are added, conflicts are resolved as chosen, and any missing `Tag` colors are taken from the import.
+    #[must_use]
+    pub fn apply(&self, bank: &mut Bank) -> Schrod<()> {
+        let mut transactions_to_add = self.new_transactions.clone();
+
+        // resolving conflicts
+        for conflict in &self.conflicts {
+            match conflict.resolution {
+                ConflictResolutions::KeepLocal => {}
+                ConflictResolutions::KeepBoth => { transactions_to_add.push(conflict.imported.clone()); }
+                ConflictResolutions::UseImported => {
+                    let local_result = bank.get_mut(conflict.local_id);
+                    if local_result.is_fail() {
+                        return local_result
+                            .convert("ImportMerge::apply()")
+                            .fail("Failed to resolve an import conflict.", "ImportMerge::apply()")
+                    }
+                    let local = local_result.wont_fail("This is past an is_fail() guard clause.", "ImportMerge::apply()");
+                    local.value = conflict.imported.value;
+                    local.date = conflict.imported.date;
+                    local.description.clone_from(&conflict.imported.description);
+                    local.tags.clone_from(&conflict.imported.tags);
+                    let splits_result = local.set_splits(conflict.imported.get_splits().to_vec());
+                    if splits_result.is_fail() {
+                        return splits_result.fail("Failed to resolve an import conflict.", "ImportMerge::apply()")
+                    }
+                }
+            }
+        }
+
+        // adding the new transactions
+        let merge_result = bank.merge_transactions(transactions_to_add);
+        if merge_result.is_fail() {
+            return merge_result.fail("Failed to merge imported Transactions.", "ImportMerge::apply()")
+        }
+
+        // filling in tag colors
+        bank.tag_registry.merge(&self.tag_registry);
+
+        // removing filtered tags that no longer exist
+        let filter_result = bank.verify_filtered_tags();
+        if filter_result.is_fail() {
+            return filter_result.fail("Failed to merge imported Transactions.", "ImportMerge::apply()")
+        }
+
+        Pass(())
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the imported `Transaction`s that are not in the `Bank`.
+    #[must_use]
+    pub fn get_new_transactions(&self) -> &Vec<Transaction> {
+        &self.new_transactions
+    }
+
+    /// Returns the number of imported `Transaction`s that are already in the `Bank`.
+    #[must_use]
+    pub fn get_identical_count(&self) -> usize {
+        self.identical_count
+    }
+
+    /// Returns the conflicts between imported and local `Transaction`s.
+    #[must_use]
+    pub fn get_conflicts(&self) -> &Vec<ImportConflict> {
+        &self.conflicts
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{ConflictResolutions, ImportMerge};
+    use crate::vault::bank::{Filters, TagRegistry};
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::{Months, Split, Tag, Transaction};
+    use schrod::Schrod;
+
+    fn purchase(value: &str, description: &str, tags: Vec<Tag>) -> Transaction {
+        transaction(value, "USD", date(2026, Months::March, 14), description, tags)
+    }
+
+    #[test]
+    fn using_the_imported_version_replaces_the_splits() {
+        let mut local = purchase("-50", "Corner Market", vec![tag("groceries")]);
+        let household = Split::new_from_raw("-20", vec![tag("household")], "").wont_fail("The split is valid.", "tests");
+        assert!(local.set_splits(vec![household]).is_pass());
+        let mut bank = bank_with(vec![local]);
+        assert!(bank.add_filter_tag(&tag("household"), Filters::Primary).is_pass());
+
+        let mut imported = purchase("-60", "Corner Market", vec![tag("groceries")]);
+        let pharmacy = Split::new_from_raw("-15", vec![tag("pharmacy")], "").wont_fail("The split is valid.", "tests");
+        assert!(imported.set_splits(vec![pharmacy.clone()]).is_pass());
+        let mut merge = ImportMerge::new(&bank, &[imported], &TagRegistry::default());
+        merge.set_resolution(0, ConflictResolutions::UseImported);
+        assert!(merge.apply(&mut bank).is_pass());
+
+        let merged = &bank.get_ledger()[0];
+        assert_eq!(merged.get_splits(), &[pharmacy]);
+        // the split tag that disappeared is no longer filtered
+        assert!(!bank.get_filter(Filters::Primary).is_tag_filtered(&tag("household")));
+    }
+
+    #[test]
+    fn imports_are_sorted_into_identical_conflicting_and_new() {
+        let bank = bank_with(vec![
+            purchase("-50", "Corner Market", vec![tag("groceries")]),
+            purchase("-4", "Coffee", vec![tag("coffee")]),
+            purchase("-1000", "Rent", vec![tag("rent")]),
+        ]);
+        let local_coffee = bank.get_ledger().iter().find(|t| t.description == "Coffee").cloned();
+        let mut edited_coffee = Schrod::from_option(local_coffee, "The coffee is in the bank.", "tests").wont_fail("The coffee is in the bank.", "tests");
+        edited_coffee.description = "Coffee beans".to_string();
+
+        let imported = vec![
+            purchase("-50", "Corner Market", vec![tag("groceries")]), // identical, but with another uid
+            edited_coffee, // the same uid with other contents
+            purchase("-1000", "Rent payment", vec![tag("rent")]), // the same value on the same date
+            purchase("-12", "Bakery", vec![tag("bread")]),
+        ];
+        let merge = ImportMerge::new(&bank, &imported, &TagRegistry::default());
+        assert_eq!(merge.get_identical_count(), 1);
+        assert_eq!(merge.get_conflicts().len(), 2);
+        assert!(merge.get_conflicts().iter().any(|conflict| conflict.get_local().description == "Coffee" && conflict.get_imported().description == "Coffee beans"));
+        assert!(merge.get_conflicts().iter().all(|conflict| conflict.resolution == ConflictResolutions::KeepLocal));
+        assert_eq!(merge.get_new_transactions().len(), 1);
+        assert_eq!(merge.get_new_transactions()[0].description, "Bakery");
+    }
+
+    #[test]
+    fn each_resolution_is_applied() {
+        let merged_values = |resolution: ConflictResolutions| {
+            let mut bank = bank_with(vec![purchase("-50", "Corner Market", vec![tag("groceries")])]);
+            let imported = vec![purchase("-55", "Corner Market", vec![tag("groceries"), tag("household")])];
+            let mut merge = ImportMerge::new(&bank, &imported, &TagRegistry::default());
+            assert_eq!(merge.get_conflicts().len(), 1);
+            merge.set_resolution(0, resolution);
+            assert!(merge.apply(&mut bank).is_pass());
+            bank.get_ledger().iter().map(|t| format!("{} {}", t.value.amount(), t.tags.len())).collect::<Vec<_>>()
+        };
+
+        assert_eq!(merged_values(ConflictResolutions::KeepLocal), vec!["-50 1"]);
+        assert_eq!(merged_values(ConflictResolutions::UseImported), vec!["-55 2"]);
+        let mut kept_both = merged_values(ConflictResolutions::KeepBoth);
+        kept_both.sort();
+        assert_eq!(kept_both, vec!["-50 1", "-55 2"]);
+    }
+}
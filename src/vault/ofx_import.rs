@@ -0,0 +1,352 @@
+use std::path::PathBuf;
+use crate::vault::bank::Bank;
+use crate::vault::transaction::{Date, Tag, Transaction};
+use schrod::Schrod;
+use schrod::Schrod::{Pass, Fail};
+
+/// A single `<STMTTRN>` entry read from an OFX statement.
+#[derive(Debug, Clone, Default)]
+struct OfxEntry {
+    /// The raw `<TRNAMT>`.
+    value_string: String,
+    /// The `<CURDEF>` of the statement or the `<CURSYM>` of the entry.
+    currency_string: String,
+    /// The raw `<DTPOSTED>`.
+    date_string: String,
+    /// The `<NAME>` of the entry.
+    name: String,
+    /// The `<MEMO>` of the entry.
+    memo: String,
+    /// The `<FITID>` of the entry.
+    fitid: String,
+    /// The `<ACCTID>` of the statement.
+    account_id: String,
+}
+impl OfxEntry {
+    /// Returns the statement identifier used to detect repeated imports.
+    /// A `<FITID>` is only unique within an account, so it is combined with the `<ACCTID>`.
+    #[must_use]
+    fn statement_id(&self) -> Option<String> {
+        if self.fitid.is_empty() { None }
+        else if self.account_id.is_empty() { Some(self.fitid.clone()) }
+        else { Some(format!("{}/{}", self.account_id, self.fitid)) }
+    }
+
+    /// Returns the description built from the `<NAME>` and `<MEMO>`.
+    #[must_use]
+    fn description(&self) -> String {
+        let name = Transaction::sanitized_description(&self.name);
+        let memo = Transaction::sanitized_description(&self.memo);
+        if name.is_empty() { memo }
+        else if memo.is_empty() || name.eq_ignore_ascii_case(&memo) { name }
+        else { format!("{name} - {memo}") }
+    }
+
+    /// Creates a `Transaction` from the entry.
+    /// Please note that if this function is used, an `Id` must be filled in later with `set_id()`.
+    #[must_use]
+    fn into_transaction(self, tag: Tag) -> Schrod<Transaction> {
+        // date
+        let date_value_result = Schrod::from_result(self.date_string.chars().take(8).collect::<String>().parse::<u32>(), &format!("Failed to read \"{}\" as a date.", self.date_string), "OfxEntry::into_transaction()");
+        if date_value_result.is_fail() { return date_value_result.convert("OfxEntry::into_transaction()"); }
+        let date_result = Date::from_value(date_value_result.wont_fail("This is past an is_fail() guard clause.", "OfxEntry::into_transaction()"));
+        if date_result.is_fail() { return date_result.convert("OfxEntry::into_transaction()"); }
+        let date = date_result.wont_fail("This is past an is_fail() guard clause.", "OfxEntry::into_transaction()");
+
+        // value
+        let value_string = self.value_string.trim().trim_start_matches('+').replace(',', ".");
+
+        // transaction
+        let statement_id = self.statement_id();
+        let transaction_result = Transaction::load_from_raw(&value_string, &self.currency_string, date, self.description(), vec![tag]);
+        if transaction_result.is_fail() { return transaction_result.fail(&format!("Failed to import OFX entry \"{}\".", self.fitid), "OfxEntry::into_transaction()"); }
+        let mut transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "OfxEntry::into_transaction()");
+        transaction.set_statement_id(statement_id);
+
+        Pass(transaction)
+    }
+}
+
+
+
+/// Holds the entries of an OFX or QFX statement while the import is being confirmed.
+/// Both OFX 1.x (SGML) and OFX 2.x (XML) are supported.
+#[derive(Debug, Clone)]
+pub struct OfxImport {
+    /// The entries in the statement.
+    entries: Vec<OfxEntry>,
+    /// The `Tag` given to every imported `Transaction`.
+    import_tag: String,
+    /// The `Transaction`s parsed from the entries that could be read.
+    transactions: Vec<Transaction>,
+    /// The reason each entry that could not be read failed.
+    failures: Vec<String>,
+}
+impl OfxImport {
+    // initializing
+    /// Loads an OFX or QFX statement from the given `Path`.
+    #[must_use]
+    pub fn load_from(path: &PathBuf) -> Schrod<OfxImport> {
+        // reading the file
+        let contents_result = Schrod::from_result(std::fs::read(path), "Failed to read OFX file.", "OfxImport::load_from()");
+        if contents_result.is_fail() {
+            return contents_result
+                .convert("OfxImport::load_from()")
+                .fail("Failed to load OFX file.", "OfxImport::load_from()")
+        }
+        // older statements are often not encoded in UTF-8
+        let contents = String::from_utf8_lossy(&contents_result.wont_fail("This is past an is_fail() guard clause.", "OfxImport::load_from()")).to_string();
+
+        // parsing the statement
+        let import_result = OfxImport::parse(&contents);
+        if import_result.is_fail() {
+            return import_result.fail("Failed to load OFX file.", "OfxImport::load_from()")
+        }
+        import_result
+    }
+
+    /// Parses the contents of an OFX or QFX statement.
+    #[must_use]
+    pub fn parse(contents: &str) -> Schrod<OfxImport> {
+        let body_start_result = Schrod::from_option(contents.find("<OFX>").or_else(|| contents.find("<ofx>")), "The file does not contain an <OFX> element.", "OfxImport::parse()");
+        if body_start_result.is_fail() { return body_start_result.convert("OfxImport::parse()"); }
+        let body = &contents[body_start_result.wont_fail("This is past an is_fail() guard clause.", "OfxImport::parse()")..];
+
+        let mut entries = Vec::new();
+        let mut current_entry: Option<OfxEntry> = None;
+        let mut statement_currency = String::new();
+        let mut account_id = String::new();
+
+        for (tag, text) in tokenize(body) {
+            match tag.as_str() {
+                "CURDEF" => { statement_currency = text; }
+                "ACCTID" => { account_id = text; }
+                "STMTTRN" => {
+                    current_entry = Some(OfxEntry {
+                        currency_string: statement_currency.clone(),
+                        account_id: account_id.clone(),
+                        ..OfxEntry::default()
+                    });
+                }
+                "/STMTTRN" => {
+                    if let Some(entry) = current_entry.take() { entries.push(entry); }
+                }
+                _ => {
+                    if let Some(entry) = &mut current_entry {
+                        match tag.as_str() {
+                            "TRNAMT" => { entry.value_string = text; }
+                            "DTPOSTED" => { entry.date_string = text; }
+                            "NAME" | "PAYEE" if entry.name.is_empty() => { entry.name = text; }
+                            "MEMO" => { entry.memo = text; }
+                            "FITID" => { entry.fitid = text; }
+                            "CURSYM" if !text.is_empty() => { entry.currency_string = text; }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        if entries.is_empty() {
+            return Schrod::new_fail("The statement does not contain any <STMTTRN> entries.", "OfxImport::parse()")
+        }
+
+        let mut import = OfxImport { entries, import_tag: "imported".to_string(), transactions: Vec::new(), failures: Vec::new() };
+        import.parse_entries();
+        Pass(import)
+    }
+
+
+
+    // management
+    /// Sets the `Tag` given to every imported `Transaction` and parses the entries again.
+    pub fn set_import_tag(&mut self, import_tag: String) {
+        self.import_tag = import_tag;
+        self.parse_entries();
+    }
+
+    /// Parses every entry into a `Transaction`, keeping the reason for each entry that could not be read.
+    fn parse_entries(&mut self) {
+        self.transactions.clear();
+        self.failures.clear();
+
+        let tag_result = Tag::new(&self.import_tag);
+        for entry in self.entries.iter().cloned() {
+            let transaction_result = match &tag_result {
+                Pass(tag) => entry.into_transaction(tag.clone()),
+                Fail(_) => tag_result.convert("OfxImport::parse_entries()").fail("The import tag is not valid.", "OfxImport::parse_entries()"),
+            };
+            match transaction_result {
+                Pass(transaction) => { self.transactions.push(transaction); }
+                Fail(_) => { self.failures.push(transaction_result.results().last().cloned().unwrap_or_default()); }
+            }
+        }
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the `Tag` given to every imported `Transaction`.
+    #[must_use]
+    pub fn get_import_tag(&self) -> &str {
+        &self.import_tag
+    }
+
+    /// Returns the `Transaction`s that are not already in the `Bank`.
+    /// Entries repeated within the statement are only returned once.
+    /// Please note that an `Id` must be filled in later with `set_id()`.
+    #[must_use]
+    pub fn get_new_transactions(&self, bank: &Bank) -> Vec<Transaction> {
+        let mut new_transactions: Vec<Transaction> = Vec::new();
+        for transaction in &self.transactions {
+            let is_duplicate = match transaction.get_statement_id() {
+                Some(statement_id) => bank.contains_statement_id(statement_id) || new_transactions.iter().any(|t| t.get_statement_id() == Some(statement_id)),
+                None => false,
+            };
+            if !is_duplicate { new_transactions.push(transaction.clone()); }
+        }
+        new_transactions
+    }
+
+    /// Returns the number of entries that could not be read.
+    #[must_use]
+    pub fn get_failure_count(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Returns the first failure message, if there is one.
+    #[must_use]
+    pub fn get_first_failure(&self) -> Option<String> {
+        self.failures.first().cloned()
+    }
+
+    /// Returns the total number of entries in the statement.
+    #[must_use]
+    pub fn get_entry_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+
+
+/// Splits the body of an OFX document into element names and the text that directly follows them.
+/// This works for both SGML, where leaf elements are not closed, and XML.
+#[must_use]
+fn tokenize(body: &str) -> Vec<(String, String)> {
+    let mut tokens = Vec::new();
+    let mut rest = body;
+
+    while let Some(open) = rest.find('<') {
+        let after_open = &rest[open + 1..];
+        let Some(close) = after_open.find('>') else { break };
+        let tag = after_open[..close].trim();
+        let after_tag = &after_open[close + 1..];
+        let text_end = after_tag.find('<').unwrap_or(after_tag.len());
+
+        // skipping processing instructions and comments
+        if !tag.starts_with('?') && !tag.starts_with('!') {
+            let name = tag.split_whitespace().next().unwrap_or_default().to_uppercase();
+            tokens.push((name, decoded_text(after_tag[..text_end].trim())));
+        }
+
+        rest = &after_tag[text_end..];
+    }
+
+    tokens
+}
+
+/// Decodes the character entities used in OFX text.
+#[must_use]
+fn decoded_text(text: &str) -> String {
+    text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::OfxImport;
+    use crate::vault::bank::Bank;
+    use crate::vault::test_helpers::{date, tag};
+    use crate::vault::transaction::Months;
+    use rust_decimal::Decimal;
+
+    /// An OFX 1.x statement, where leaf elements are not closed.
+    const SGML_STATEMENT: &str = "OFXHEADER:100\nDATA:OFXSGML\nVERSION:102\n\n<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS>\n<CURDEF>CAD\n<BANKACCTFROM><ACCTID>12345</BANKACCTFROM>\n<BANKTRANLIST>\n\
+        <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20260314120000[-5:EST]<TRNAMT>-42.50<FITID>A1<NAME>CORNER MARKET<MEMO>Groceries &amp; more</STMTTRN>\n\
+        <STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20260315<TRNAMT>+1000,00<FITID>A2<MEMO>Payroll<CURRENCY><CURSYM>USD</CURRENCY></STMTTRN>\n\
+        <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20260316<TRNAMT>-5<FITID>A1<NAME>Corner Market</STMTTRN>\n\
+        <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>someday<TRNAMT>-5<FITID>A3<NAME>Broken</STMTTRN>\n\
+        </BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+
+    /// An OFX 2.x statement, which is well formed XML.
+    const XML_STATEMENT: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<?OFX OFXHEADER=\"200\" VERSION=\"220\"?>\n<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS>\n<CURDEF>EUR</CURDEF>\n\
+        <BANKTRANLIST><STMTTRN>\n  <TRNTYPE>DEBIT</TRNTYPE>\n  <DTPOSTED>20260301</DTPOSTED>\n  <TRNAMT>-12.00</TRNAMT>\n  <FITID>X1</FITID>\n  <NAME>Bakery</NAME>\n  <MEMO>bakery</MEMO>\n</STMTTRN></BANKTRANLIST>\n\
+        </STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+
+    #[test]
+    fn sgml_statements_are_read() {
+        let import = OfxImport::parse(SGML_STATEMENT).wont_fail("The statement is valid.", "tests");
+        assert_eq!(import.get_entry_count(), 4);
+        assert_eq!(import.get_failure_count(), 1);
+        assert!(import.get_first_failure().is_some_and(|failure| failure.contains("someday")));
+
+        // the repeated FITID is only imported once
+        let transactions = import.get_new_transactions(&Bank::default());
+        assert_eq!(transactions.len(), 2);
+        let market = &transactions[0];
+        assert_eq!(*market.value.amount(), Decimal::new(-4250, 2));
+        assert_eq!(market.value.currency().iso_alpha_code, "CAD");
+        assert_eq!(market.date, date(2026, Months::March, 14));
+        assert_eq!(market.get_statement_id(), Some("12345/A1"));
+        assert_eq!(market.tags, vec![tag("imported")]);
+
+        // a <CURSYM> overrides the <CURDEF> of the statement
+        assert_eq!(transactions[1].value.currency().iso_alpha_code, "USD");
+        assert_eq!(*transactions[1].value.amount(), Decimal::from(1000));
+    }
+
+    #[test]
+    fn xml_statements_are_read() {
+        let import = OfxImport::parse(XML_STATEMENT).wont_fail("The statement is valid.", "tests");
+        let transactions = import.get_new_transactions(&Bank::default());
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].value.currency().iso_alpha_code, "EUR");
+        assert_eq!(transactions[0].get_statement_id(), Some("X1"));
+        assert!(OfxImport::parse("<html></html>").is_fail());
+    }
+
+    #[test]
+    fn descriptions_combine_the_name_and_memo() {
+        let import = OfxImport::parse(SGML_STATEMENT).wont_fail("The statement is valid.", "tests");
+        let transactions = import.get_new_transactions(&Bank::default());
+        // the name comes first, then a different memo
+        assert_eq!(transactions[0].description, "CORNER MARKET - Groceries & more");
+        // without a name, only the memo is used
+        assert_eq!(transactions[1].description, "Payroll");
+        // a memo that repeats the name is left out
+        let xml_import = OfxImport::parse(XML_STATEMENT).wont_fail("The statement is valid.", "tests");
+        assert_eq!(xml_import.get_new_transactions(&Bank::default())[0].description, "Bakery");
+    }
+
+    #[test]
+    fn entries_already_in_the_bank_are_skipped() {
+        let mut import = OfxImport::parse(SGML_STATEMENT).wont_fail("The statement is valid.", "tests");
+        let mut bank = Bank::default();
+        assert!(bank.merge_transactions(import.get_new_transactions(&bank)).is_pass());
+        assert!(bank.contains_statement_id("12345/A1"));
+        assert!(import.get_new_transactions(&bank).is_empty());
+
+        // changing the import tag parses the entries again
+        import.set_import_tag("bank/statement".to_string());
+        assert_eq!(import.get_failure_count(), 1);
+        import.set_import_tag(String::new());
+        assert_eq!(import.get_failure_count(), 4);
+    }
+}
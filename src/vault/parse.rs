@@ -1,7 +1,7 @@
 use crate::vault::bank::{Bank, CurrencyExchange};
 use schrod::Schrod;
 use schrod::Schrod::Pass;
-use crate::vault::transaction::{Id, Value};
+use crate::vault::transaction::{Id, Tag, Value};
 use rust_decimal::Decimal;
 use rusty_money::iso::Currency;
 use serde::{Deserialize, Serialize};
@@ -34,8 +34,20 @@ impl CashFlow {
     /// Creates a new `CashFlow` from a list of `Transaction` `Id`s.
     #[must_use]
     pub fn new(bank: &Bank, transaction_ids: &[Id]) -> Schrod<CashFlow> {
+        CashFlow::new_with_tag_filter(bank, transaction_ids, None)
+    }
+
+    /// Creates a new `CashFlow` from a list of `Transaction` `Id`s, only counting the parts of each value that belong to any of the given `Tag`s.
+    #[must_use]
+    pub fn new_for_tags(bank: &Bank, transaction_ids: &[Id], tags: &[Tag]) -> Schrod<CashFlow> {
+        CashFlow::new_with_tag_filter(bank, transaction_ids, Some(tags))
+    }
+
+    /// Creates a new `CashFlow`, counting either the whole value of each `Transaction` or only the parts that belong to the given `Tag`s.
+    #[must_use]
+    fn new_with_tag_filter(bank: &Bank, transaction_ids: &[Id], tags_option: Option<&[Tag]>) -> Schrod<CashFlow> {
         // value flows
-        let value_flows_result = CashFlow::get_value_flows(bank, transaction_ids.to_owned());
+        let value_flows_result = CashFlow::get_value_flows(bank, transaction_ids.to_owned(), tags_option);
         if value_flows_result.is_fail() {
             return value_flows_result
                 .convert("CashFlow::new()")
@@ -44,7 +56,7 @@ impl CashFlow {
         let value_flows = value_flows_result.wont_fail("This is past an is_fail() guard clause.", "CashFlow::new()");
 
         // unified value
-        let unified_value_flow_result = CashFlow::get_unified_value_flow(bank, &value_flows);
+        let unified_value_flow_result = CashFlow::get_unified_value_flow(bank, transaction_ids, tags_option);
         if unified_value_flow_result.is_fail() {
             return unified_value_flow_result
                 .convert("CashFlow::new()")
@@ -53,7 +65,7 @@ impl CashFlow {
         let unified_value_flow = unified_value_flow_result.wont_fail("This is past an is_fail() guard clause.", "CashFlow::new()");
 
         // time flow
-        let time_flow_result = CashFlow::get_time_flow(&unified_value_flow, &bank.currency_exchange);
+        let time_flow_result = CashFlow::get_time_flow(bank, transaction_ids, tags_option);
         if time_flow_result.is_fail() {
             return time_flow_result
                 .convert("CashFlow::new()")
@@ -104,8 +116,9 @@ impl CashFlow {
 
     /// Turns a list of `Transaction`s into a collection of `Value`s, grouped by `Currency`,
     /// that each represent the overall cash flow for the given `Currency`.
+    /// If `Tag`s are given, only the parts of each value that belong to them are counted.
     #[must_use]
-    fn get_value_flows(bank: &Bank, transaction_ids: Vec<Id>) -> Schrod<Vec<Value>> {
+    fn get_value_flows(bank: &Bank, transaction_ids: Vec<Id>, tags_option: Option<&[Tag]>) -> Schrod<Vec<Value>> {
         // the list of all the transactions (by id) grouped by their currencies
         let mut coupled_value_groups: Vec<(Currency, Vec<Id>)> = Vec::new();
 
@@ -120,8 +133,8 @@ impl CashFlow {
             }
             let transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "CashFlow::get_value_flows()");
 
-            // skips if this transaction is ignored
-            if transaction.is_ignored() { continue; }
+            // skips if this transaction is ignored or a transfer
+            if transaction.is_excluded_from_parsing() { continue; }
             
             // checks if the currency has been used already
             let mut is_currency_used = false;
@@ -152,7 +165,10 @@ impl CashFlow {
                         .fail("Failed to get value flows.", "CashFlow::get_value_flows()");
                 }
                 let transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "CashFlow::get_value_flows()");
-                let value_amount = transaction.value.amount();
+                let value_amount = match tags_option {
+                    Some(tags) => transaction.get_amount_for_any(tags),
+                    None => *transaction.value.amount(),
+                };
                 flow = flow.add(value_amount);
             }
             
@@ -185,13 +201,31 @@ impl CashFlow {
         Pass(value_flows)
     }
 
-    /// Returns all value flows combined into the same `Currency` based on the `main_currency` in the `CurrencyExchange`.
+    /// Returns the value of every `Transaction` combined into the main `Currency` of the `CurrencyExchange`.
+    /// Each `Transaction` is converted at the rate that applied on its `Date`, so older spending keeps its value from back then.
+    /// If `Tag`s are given, only the parts of each value that belong to them are counted.
     #[must_use]
-    fn get_unified_value_flow(bank: &Bank, value_flows: &[Value]) -> Schrod<Value> {
-        let new_value_results: Vec<_> = value_flows
-            .iter()
-            .map(|flow| bank.currency_exchange.convert(flow.amount(), flow.currency(), bank.currency_exchange.get_main_currency()))
-            .collect();
+    fn get_unified_value_flow(bank: &Bank, transaction_ids: &[Id], tags_option: Option<&[Tag]>) -> Schrod<Value> {
+        let main_currency = bank.currency_exchange.get_main_currency();
+        let mut new_value_results = Vec::new();
+        for id in transaction_ids {
+            let transaction_result = bank.get(*id);
+            if transaction_result.is_fail() {
+                return transaction_result
+                    .convert("CashFlow::unified()")
+                    .fail("Failed to unify values!", "CashFlow::unified()")
+            }
+            let transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "CashFlow::unified()");
+
+            // skips if this transaction is ignored or a transfer
+            if transaction.is_excluded_from_parsing() { continue; }
+
+            let value_amount = match tags_option {
+                Some(tags) => transaction.get_amount_for_any(tags),
+                None => *transaction.value.amount(),
+            };
+            new_value_results.push(bank.currency_exchange.convert(&value_amount, transaction.value.currency(), main_currency, transaction.date));
+        }
 
         if Schrod::contains_fail(&new_value_results) {
             return Schrod::collect_and_fail(&new_value_results, "CashFlow::unified()")
@@ -206,12 +240,43 @@ impl CashFlow {
         let mut unified_value = Decimal::from(0);
         for value in new_values { unified_value += value; }
 
-        Pass(Value::from_decimal(unified_value, bank.currency_exchange.get_main_currency()))
+        Pass(Value::from_decimal(unified_value, main_currency))
     }
 
-    /// Gets the overall time flow value from a list of `Value`s.
+    /// Gets the overall time flow value of every `Transaction`.
+    /// Each `Transaction` is priced at the time price that applied on its `Date`, so a raise does not change what older spending cost in hours.
+    /// If `Tag`s are given, only the parts of each value that belong to them are counted.
     #[must_use]
-    fn get_time_flow(unified_value_flow: &Value, currency_exchange: &CurrencyExchange) -> Schrod<Decimal> {
-        currency_exchange.as_time_price(unified_value_flow)
+    fn get_time_flow(bank: &Bank, transaction_ids: &[Id], tags_option: Option<&[Tag]>) -> Schrod<Decimal> {
+        let mut time_price_results = Vec::new();
+        for id in transaction_ids {
+            let transaction_result = bank.get(*id);
+            if transaction_result.is_fail() {
+                return transaction_result
+                    .convert("CashFlow::get_time_flow()")
+                    .fail("Failed to get time flow!", "CashFlow::get_time_flow()")
+            }
+            let transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "CashFlow::get_time_flow()");
+
+            // skips if this transaction is ignored or a transfer
+            if transaction.is_excluded_from_parsing() { continue; }
+
+            let value = match tags_option {
+                Some(tags) => Value::from_decimal(transaction.get_amount_for_any(tags), transaction.value.currency()),
+                None => transaction.value,
+            };
+            time_price_results.push(bank.currency_exchange.as_time_price(&value, transaction.date));
+        }
+
+        if Schrod::contains_fail(&time_price_results) {
+            return Schrod::collect_and_fail(&time_price_results, "CashFlow::get_time_flow()")
+                .convert("CashFlow::get_time_flow()")
+                .fail("Failed to get time flow!", "CashFlow::get_time_flow()")
+        }
+
+        Pass(time_price_results
+            .into_iter()
+            .map(|r| r.wont_fail("This is past a contains_fail() guard clause.", "CashFlow::get_time_flow()"))
+            .sum())
     }
 }
\ No newline at end of file
@@ -0,0 +1,398 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use rust_decimal::Decimal;
+use rusty_money::iso;
+use crate::vault::bank::{CurrencyExchange, DatedRate};
+use crate::vault::csv_import::{parse_date, split_records};
+use crate::vault::transaction::Date;
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// The number of decimal places kept on rates derived by crossing through the euro.
+const DERIVED_RATE_DECIMAL_PLACES: u32 = 10;
+
+/// The file formats that exchange rates can be imported from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateFileFormats {
+    /// The European Central Bank's `eurofxref` CSV, with a column of euro rates for each `Currency`.
+    EcbCsv,
+    /// The European Central Bank's `eurofxref` XML, with a `<Cube>` of euro rates for each day.
+    EcbXml,
+    /// A CSV with a `date,from,to,rate` row for each rate.
+    Simple,
+}
+impl RateFileFormats {
+    /// Returns the name of the file format.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            RateFileFormats::EcbCsv => { "ECB reference rates (CSV)" }
+            RateFileFormats::EcbXml => { "ECB reference rates (XML)" }
+            RateFileFormats::Simple => { "Exchange rates (CSV)" }
+        }
+    }
+}
+
+
+
+/// A single rate read from a file.
+#[derive(Debug, Clone)]
+struct QuotedRate {
+    date: Date,
+    /// The capitalized code of the `Currency` being converted from.
+    from_string: String,
+    /// The capitalized code of the `Currency` being converted to.
+    to_string: String,
+    rate: Decimal,
+}
+
+
+
+/// Holds the rates read from an exchange rate file while the import is being confirmed.
+/// Nothing is downloaded; the file is expected to have been saved beforehand.
+#[derive(Debug, Clone)]
+pub struct RateImport {
+    format: RateFileFormats,
+    /// The rates in the file.
+    quotes: Vec<QuotedRate>,
+    /// The number of rates in the file that could not be read.
+    skipped_count: usize,
+    /// The dated rates for each `ExchangeRate` of the `CurrencyExchange` the file was loaded for, oldest first.
+    pair_rates: Vec<(String, String, Vec<DatedRate>)>,
+}
+impl RateImport {
+    // initializing
+    /// Loads an exchange rate file from the given `Path`, matching its rates to the `ExchangeRate`s of the `CurrencyExchange`.
+    #[must_use]
+    pub fn load_from(path: &PathBuf, currency_exchange: &CurrencyExchange) -> Schrod<RateImport> {
+        let contents_result = Schrod::from_result(std::fs::read(path), "Failed to read exchange rate file.", "RateImport::load_from()");
+        if contents_result.is_fail() {
+            return contents_result
+                .convert("RateImport::load_from()")
+                .fail("Failed to load exchange rate file.", "RateImport::load_from()")
+        }
+        let contents = String::from_utf8_lossy(&contents_result.wont_fail("This is past an is_fail() guard clause.", "RateImport::load_from()")).to_string();
+
+        let import_result = RateImport::parse(&contents, currency_exchange);
+        if import_result.is_fail() {
+            return import_result.fail("Failed to load exchange rate file.", "RateImport::load_from()")
+        }
+        import_result
+    }
+
+    /// Parses the contents of an exchange rate file, detecting its format, and matches its rates to the `ExchangeRate`s of the `CurrencyExchange`.
+    #[must_use]
+    pub fn parse(contents: &str, currency_exchange: &CurrencyExchange) -> Schrod<RateImport> {
+        let mut import = if contents.contains("<Cube") { RateImport::parse_ecb_xml(contents) }
+        else {
+            let records = split_records(contents, ',');
+            let header: Vec<String> = records.first().map(|row| row.iter().map(|cell| cell.trim().to_lowercase()).collect()).unwrap_or_default();
+            if header.first().is_some_and(|cell| cell == "date") {
+                if header.iter().skip(1).take(3).map(String::as_str).eq(["from", "to", "rate"]) { RateImport::parse_simple(&records[1..]) }
+                else { RateImport::parse_ecb_csv(&records) }
+            }
+            // the simple format may be written without a header
+            else if header.len() == 4 && parse_any_date(&header[0]).is_pass() { RateImport::parse_simple(&records) }
+            else {
+                return Schrod::new_fail("The file is not an ECB reference rate file or a date,from,to,rate CSV.", "RateImport::parse()")
+            }
+        };
+
+        if import.quotes.is_empty() {
+            return Schrod::new_fail("The file does not contain any exchange rates.", "RateImport::parse()")
+        }
+        import.quotes.sort_by_key(|quote| quote.date.as_value());
+        import.pair_rates = import.derive_rates_for(currency_exchange);
+        Pass(import)
+    }
+
+    /// Parses the ECB XML format, where each `<Cube time='...'>` holds a `<Cube currency='...' rate='...'/>` for each `Currency`.
+    #[must_use]
+    fn parse_ecb_xml(contents: &str) -> RateImport {
+        let mut import = RateImport { format: RateFileFormats::EcbXml, quotes: Vec::new(), skipped_count: 0, pair_rates: Vec::new() };
+        let mut current_date: Option<Date> = None;
+
+        for element in contents.split("<Cube").skip(1) {
+            let tag = element.split('>').next().unwrap_or_default();
+            if let Some(time) = attribute(tag, "time") {
+                current_date = match parse_any_date(time) {
+                    Pass(date) => Some(date),
+                    _ => None,
+                };
+                if current_date.is_none() { import.skipped_count += 1; }
+            }
+            if let (Some(currency), Some(rate_string)) = (attribute(tag, "currency"), attribute(tag, "rate")) {
+                match (current_date, parse_rate(rate_string)) {
+                    (Some(date), Some(rate)) if iso::find(&currency.to_uppercase()).is_some() => {
+                        import.quotes.push(QuotedRate { date, from_string: "EUR".to_string(), to_string: currency.to_uppercase(), rate });
+                    }
+                    _ => { import.skipped_count += 1; }
+                }
+            }
+        }
+
+        import
+    }
+
+    /// Parses the ECB CSV format, where the header names a `Currency` for each column after the date.
+    #[must_use]
+    fn parse_ecb_csv(records: &[Vec<String>]) -> RateImport {
+        let mut import = RateImport { format: RateFileFormats::EcbCsv, quotes: Vec::new(), skipped_count: 0, pair_rates: Vec::new() };
+        let Some((header, rows)) = records.split_first() else { return import };
+        // the ECB lists some currencies that no longer exist, which are left out
+        let currencies: Vec<Option<String>> = header.iter().skip(1)
+            .map(|cell| cell.trim().to_uppercase())
+            .map(|code| iso::find(&code).map(|_| code))
+            .collect();
+
+        for row in rows {
+            let Pass(date) = parse_any_date(row.first().map(String::as_str).unwrap_or_default()) else {
+                import.skipped_count += 1;
+                continue
+            };
+            for (cell, currency) in row.iter().skip(1).zip(&currencies) {
+                let Some(currency) = currency else { continue };
+                // the ECB writes N/A for currencies it did not quote on a day
+                let cell = cell.trim();
+                if cell.is_empty() || cell.eq_ignore_ascii_case("N/A") { continue }
+                match parse_rate(cell) {
+                    Some(rate) => { import.quotes.push(QuotedRate { date, from_string: "EUR".to_string(), to_string: currency.clone(), rate }); }
+                    None => { import.skipped_count += 1; }
+                }
+            }
+        }
+
+        import
+    }
+
+    /// Parses the simple format, with a `date,from,to,rate` row for each rate.
+    #[must_use]
+    fn parse_simple(rows: &[Vec<String>]) -> RateImport {
+        let mut import = RateImport { format: RateFileFormats::Simple, quotes: Vec::new(), skipped_count: 0, pair_rates: Vec::new() };
+
+        for row in rows {
+            let cell = |index: usize| row.get(index).map(|cell| cell.trim()).unwrap_or_default();
+            let from_string = cell(1).to_uppercase();
+            let to_string = cell(2).to_uppercase();
+            match (parse_any_date(cell(0)), parse_rate(cell(3))) {
+                (Pass(date), Some(rate)) if from_string != to_string && iso::find(&from_string).is_some() && iso::find(&to_string).is_some() => {
+                    import.quotes.push(QuotedRate { date, from_string, to_string, rate });
+                }
+                _ => { import.skipped_count += 1; }
+            }
+        }
+
+        import
+    }
+
+
+
+    // management
+    /// Sets the imported rates on every `ExchangeRate` of the `CurrencyExchange`.
+    /// Returns the number of rates that were set.
+    #[must_use]
+    pub fn apply_to(&self, currency_exchange: &mut CurrencyExchange) -> Schrod<usize> {
+        let mut results = Vec::new();
+        for (from_string, to_string, dated_rates) in &self.pair_rates {
+            if dated_rates.is_empty() { continue }
+            results.push(currency_exchange.merge_on(from_string, to_string, dated_rates));
+        }
+
+        if Schrod::contains_fail(&results) {
+            return Schrod::collect_and_fail(&results, "RateImport::apply_to()")
+                .convert("RateImport::apply_to()")
+                .fail("Failed to import exchange rates.", "RateImport::apply_to()")
+        }
+        Pass(self.get_rate_count())
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the format of the file.
+    #[must_use]
+    pub fn get_format(&self) -> RateFileFormats {
+        self.format
+    }
+
+    /// Returns the number of rates read from the file.
+    #[must_use]
+    pub fn get_quote_count(&self) -> usize {
+        self.quotes.len()
+    }
+
+    /// Returns the number of rates in the file that could not be read.
+    #[must_use]
+    pub fn get_skipped_count(&self) -> usize {
+        self.skipped_count
+    }
+
+    /// Returns the `Date`s of the oldest and newest rates in the file.
+    #[must_use]
+    pub fn get_date_range(&self) -> Option<(Date, Date)> {
+        Some((self.quotes.first()?.date, self.quotes.last()?.date))
+    }
+
+    /// Returns the currency pairs the file was matched to, with the dated rates found for each.
+    #[must_use]
+    pub fn get_pair_rates(&self) -> &[(String, String, Vec<DatedRate>)] {
+        &self.pair_rates
+    }
+
+    /// Returns the number of rates that will be set.
+    #[must_use]
+    pub fn get_rate_count(&self) -> usize {
+        self.pair_rates.iter().map(|(_, _, dated_rates)| dated_rates.len()).sum()
+    }
+
+    /// Returns the dated rates for each `ExchangeRate` of the `CurrencyExchange`, oldest first.
+    /// Pairs that are not in the file directly are derived by crossing through the euro, such as USD -> EUR -> JPY.
+    #[must_use]
+    fn derive_rates_for(&self, currency_exchange: &CurrencyExchange) -> Vec<(String, String, Vec<DatedRate>)> {
+        // grouping the rates by day
+        let mut days: BTreeMap<u32, (Date, Vec<&QuotedRate>)> = BTreeMap::new();
+        for quote in &self.quotes {
+            days.entry(quote.date.as_value()).or_insert_with(|| (quote.date, Vec::new())).1.push(quote);
+        }
+
+        let mut pairs: Vec<(String, String, Vec<DatedRate>)> = currency_exchange.get_rates().iter()
+            .map(|rate| (rate.get_from().to_uppercase(), rate.get_to().to_uppercase(), Vec::new()))
+            .collect();
+
+        for (date, quotes) in days.values() {
+            // the amount of each currency that one euro buys
+            let mut euro_rates: HashMap<&str, Decimal> = HashMap::from([("EUR", Decimal::ONE)]);
+            for quote in quotes {
+                if quote.from_string == "EUR" { euro_rates.insert(&quote.to_string, quote.rate); }
+                else if quote.to_string == "EUR" { euro_rates.insert(&quote.from_string, Decimal::ONE / quote.rate); }
+            }
+
+            for (from_string, to_string, dated_rates) in &mut pairs {
+                let direct_rate = quotes.iter().rev().find(|quote| quote.from_string == *from_string && quote.to_string == *to_string).map(|quote| quote.rate);
+                let crossed_rate = || match (euro_rates.get(from_string.as_str()), euro_rates.get(to_string.as_str())) {
+                    (Some(from_rate), Some(to_rate)) => Some((to_rate / from_rate).round_dp(DERIVED_RATE_DECIMAL_PLACES).normalize()),
+                    _ => None,
+                };
+                if let Some(rate) = direct_rate.or_else(crossed_rate).filter(|rate| *rate > Decimal::ZERO) {
+                    dated_rates.push(DatedRate { date: *date, rate });
+                }
+            }
+        }
+
+        pairs
+    }
+}
+
+
+
+/// Reads a `Date` written as either 2024-01-05 or 05 January 2024, the two forms the ECB uses.
+#[must_use]
+fn parse_any_date(cell: &str) -> Schrod<Date> {
+    let iso_result = parse_date(cell, "%Y-%m-%d");
+    if iso_result.is_pass() { return iso_result }
+    parse_date(cell, "%d %B %Y")
+}
+
+/// Reads a rate, which must be greater than zero.
+#[must_use]
+fn parse_rate(cell: &str) -> Option<Decimal> {
+    cell.trim().parse::<Decimal>().ok().filter(|rate| *rate > Decimal::ZERO)
+}
+
+/// Returns the value of an attribute within the inside of an XML tag, which may be quoted with either ' or ".
+#[must_use]
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(index) = rest.find(name) {
+        let is_whole_name = rest[..index].chars().last().is_none_or(char::is_whitespace);
+        let after_name = rest[index + name.len()..].trim_start();
+        if is_whole_name && let Some(after_equals) = after_name.strip_prefix('=') {
+            let after_equals = after_equals.trim_start();
+            let quote = after_equals.chars().next()?;
+            if quote != '\'' && quote != '"' { return None }
+            let value = &after_equals[1..];
+            return value.find(quote).map(|end| &value[..end])
+        }
+        rest = &rest[index + name.len()..];
+    }
+    None
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{RateFileFormats, RateImport};
+    use crate::vault::bank::CurrencyExchange;
+    use crate::vault::test_helpers::{date, tag, transaction};
+    use crate::vault::transaction::{Date, Months, Value};
+    use rust_decimal::Decimal;
+    use rusty_money::iso;
+    use schrod::Schrod;
+
+    const ECB_CSV: &str = "Date,USD,JPY,CYP,GBP,\n2024-01-03,1.0919,155.22,N/A,0.86283,\n2024-01-02,1.0956,155.52,N/A,0.86810,\n";
+    const ECB_XML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gesmes:Envelope><Cube><Cube time='2024-01-03'><Cube currency='USD' rate='1.0919'/><Cube currency='JPY' rate='155.22'/></Cube>\n<Cube time='2024-01-02'><Cube currency='USD' rate='1.0956'/><Cube currency='JPY' rate='155.52'/></Cube></Cube></gesmes:Envelope>";
+    const SIMPLE_CSV: &str = "date,from,to,rate\n2024-01-02,USD,JPY,142.1\n2024-01-02,USD,EUR,0.9127\nnot a date,USD,EUR,0.9\n";
+
+    fn january(day: u32) -> Date {
+        date(2024, Months::January, day)
+    }
+
+    /// A `CurrencyExchange` that needs rates between USD, EUR, and JPY.
+    fn currency_exchange() -> CurrencyExchange {
+        let coffee = |currency: &str| transaction("-10", currency, january(1), "Coffee", vec![tag("coffee")]);
+        let mut currency_exchange = CurrencyExchange::default();
+        assert!(currency_exchange.refresh(vec![coffee("USD"), coffee("EUR"), coffee("JPY")], Vec::new()).is_pass());
+        currency_exchange
+    }
+
+    fn decimal(string: &str) -> Decimal {
+        string.parse().unwrap_or_default()
+    }
+
+    fn rate(currency_exchange: &CurrencyExchange, from: &str, to: &str, day: u32) -> Decimal {
+        let rate_option = currency_exchange.get(from, to).and_then(|rate| rate.get_rate_on(january(day)));
+        Schrod::from_option(rate_option, "The test rate was imported.", "tests::rate()").wont_fail("The test rate was imported.", "tests::rate()")
+    }
+
+    #[test]
+    fn ecb_files_fill_every_pair_by_crossing_through_the_euro() {
+        for (contents, format) in [(ECB_CSV, RateFileFormats::EcbCsv), (ECB_XML, RateFileFormats::EcbXml)] {
+            let mut currency_exchange = currency_exchange();
+            let import = RateImport::parse(contents, &currency_exchange).wont_fail("The test file is valid.", "tests");
+            assert_eq!(import.get_format(), format);
+            assert_eq!(import.get_date_range(), Some((january(2), january(3))));
+
+            assert_eq!(import.apply_to(&mut currency_exchange).wont_fail("The import is valid.", "tests"), 12);
+            assert_eq!(rate(&currency_exchange, "EUR", "USD", 2), decimal("1.0956"));
+            assert_eq!(rate(&currency_exchange, "JPY", "EUR", 3), (Decimal::ONE / decimal("155.22")).round_dp(10).normalize());
+            assert_eq!(rate(&currency_exchange, "USD", "JPY", 3), (decimal("155.22") / decimal("1.0919")).round_dp(10).normalize());
+        }
+    }
+
+    #[test]
+    fn simple_files_prefer_direct_rates() {
+        let mut currency_exchange = currency_exchange();
+        let import = RateImport::parse(SIMPLE_CSV, &currency_exchange).wont_fail("The test file is valid.", "tests");
+        assert_eq!(import.get_format(), RateFileFormats::Simple);
+        assert_eq!(import.get_skipped_count(), 1);
+
+        assert!(import.apply_to(&mut currency_exchange).is_pass());
+        assert_eq!(rate(&currency_exchange, "USD", "JPY", 2), decimal("142.1"));
+        // USD -> EUR is crossed the other way, but JPY is never quoted against the euro
+        assert_eq!(rate(&currency_exchange, "EUR", "USD", 2), (Decimal::ONE / decimal("0.9127")).round_dp(10).normalize());
+        assert!(currency_exchange.get("JPY", "EUR").is_some_and(|rate| rate.get_history().is_empty()));
+
+        // imported rates are used when converting transactions from those days
+        let value = Value::from_decimal(Decimal::from(10), iso::USD);
+        let converted = currency_exchange.convert(value.amount(), iso::USD, iso::JPY, january(2)).wont_fail("The rate was imported.", "tests");
+        assert_eq!(converted, Decimal::from(1421));
+    }
+
+    #[test]
+    fn unrecognized_files_are_rejected() {
+        let currency_exchange = currency_exchange();
+        assert!(RateImport::parse("Date,Amount,Description\n", &currency_exchange).is_fail());
+        assert!(RateImport::parse("hello world", &currency_exchange).is_fail());
+    }
+}
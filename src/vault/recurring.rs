@@ -0,0 +1,266 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::vault::transaction::{Date, Months, Split, Tag, Transaction, Uid};
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// An enumeration of how often a `RecurringTemplate` repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RecurrenceRules {
+    Weekly,
+    BiWeekly,
+    /// Repeats on the given day of every month, or on the last day of shorter months.
+    Monthly(u32),
+    /// Repeats on the given `Month` and day of every year, or on February 28 outside of leap years.
+    Yearly(Months, u32),
+}
+impl RecurrenceRules {
+    // initializing
+    /// Returns every kind of rule, anchored to the given `Date`.
+    #[must_use]
+    pub fn all_anchored_to(date: Date) -> [RecurrenceRules; 4] {
+        [
+            RecurrenceRules::Weekly,
+            RecurrenceRules::BiWeekly,
+            RecurrenceRules::Monthly(date.get_day()),
+            RecurrenceRules::Yearly(date.get_month(), date.get_day()),
+        ]
+    }
+
+    /// Returns the same kind of rule, but repeating on the day (and `Month`) of the given `Date`.
+    #[must_use]
+    pub fn anchored_to(&self, date: Date) -> RecurrenceRules {
+        match self {
+            RecurrenceRules::Weekly => { RecurrenceRules::Weekly }
+            RecurrenceRules::BiWeekly => { RecurrenceRules::BiWeekly }
+            RecurrenceRules::Monthly(_) => { RecurrenceRules::Monthly(date.get_day()) }
+            RecurrenceRules::Yearly(_, _) => { RecurrenceRules::Yearly(date.get_month(), date.get_day()) }
+        }
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the name of the kind of rule.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            RecurrenceRules::Weekly => { "Weekly" }
+            RecurrenceRules::BiWeekly => { "Every 2 Weeks" }
+            RecurrenceRules::Monthly(_) => { "Monthly" }
+            RecurrenceRules::Yearly(_, _) => { "Yearly" }
+        }
+    }
+
+    /// Returns a description of the rule, including when it repeats.
+    #[must_use]
+    pub fn display(&self) -> String {
+        match self {
+            RecurrenceRules::Weekly | RecurrenceRules::BiWeekly => { self.name().to_string() }
+            RecurrenceRules::Monthly(day) => { format!("Monthly on day {day}") }
+            RecurrenceRules::Yearly(month, day) => { format!("Yearly on {} {day}", month.display()) }
+        }
+    }
+
+    /// Returns the first `Date` after the given one on which the rule repeats.
+    #[must_use]
+    pub fn next_date_after(&self, date: Date) -> Date {
+        let mut next_date = date;
+        match self {
+            RecurrenceRules::Weekly => { for _ in 0..7 { next_date.advance_by_day(); } }
+            RecurrenceRules::BiWeekly => { for _ in 0..14 { next_date.advance_by_day(); } }
+            RecurrenceRules::Monthly(day) => {
+                next_date.advance_by_month();
+                let month = next_date.get_month();
+                let year = next_date.get_year();
+                next_date = Date::new(year, month, (*day).min(month.days_in_month(year))).wont_fail("The day is clamped to the days in the month.", "RecurrenceRules::next_date_after()");
+            }
+            RecurrenceRules::Yearly(month, day) => {
+                let in_year = |year: u32| Date::new(year, *month, (*day).min(month.days_in_month(year))).wont_fail("The day is clamped to the days in the month.", "RecurrenceRules::next_date_after()");
+                next_date = in_year(date.get_year());
+                if next_date.as_value() <= date.as_value() { next_date = in_year(Date::get_advanced_year(date.get_year())); }
+            }
+        }
+        next_date
+    }
+}
+
+
+
+/// A `Transaction` that repeats, along with the next `Date` it is due.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecurringTemplate {
+    uid: Uid,
+    value_decimal: Decimal,
+    currency_string: String,
+    pub description: String,
+    pub tags: Vec<Tag>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    splits: Vec<Split>,
+    /// The `Account` the `Transaction`s are added to.
+    #[serde(default)]
+    account_uid: Uid,
+    pub rule: RecurrenceRules,
+    /// The earliest `Date` that has not been confirmed or skipped yet.
+    next_date: Date,
+}
+impl RecurringTemplate {
+    // initializing
+    /// Creates a new `RecurringTemplate` that is first due on the given `Date`.
+    #[must_use]
+    #[allow(clippy::too_many_arguments)] // one for each part of the repeated transaction, plus when it repeats
+    pub fn new(value_string: &str, currency_string: &str, description: String, tags: Vec<Tag>, splits: Vec<Split>, account_uid: Uid, rule: RecurrenceRules, first_date: Date) -> Schrod<RecurringTemplate> {
+        if !Transaction::are_raw_parts_valid(value_string, currency_string, &description, &tags) {
+            return Schrod::new_fail("Invalid parts!", "RecurringTemplate::new()")
+                .fail("Failed to create RecurringTemplate.", "RecurringTemplate::new()")
+        }
+        let value_result = Schrod::from_result(value_string.trim().parse::<Decimal>(), "Invalid value!", "RecurringTemplate::new()");
+        if value_result.is_fail() {
+            return value_result
+                .convert("RecurringTemplate::new()")
+                .fail("Failed to create RecurringTemplate.", "RecurringTemplate::new()")
+        }
+
+        Pass(RecurringTemplate {
+            uid: Uuid::new_v4(),
+            value_decimal: value_result.wont_fail("This is past an is_fail() guard clause.", "RecurringTemplate::new()"),
+            currency_string: currency_string.trim().to_uppercase(),
+            description,
+            tags,
+            splits,
+            account_uid,
+            rule,
+            next_date: first_date,
+        })
+    }
+
+
+
+    // management
+    /// Moves on to the next `Date` the template is due, after the current one is confirmed or skipped.
+    pub fn advance(&mut self) {
+        self.next_date = self.rule.next_date_after(self.next_date);
+    }
+
+    /// Replaces a `Tag` and its children with another `Tag` on the template and its `Split`s.
+    pub fn replace_tag(&mut self, old_tag: &Tag, new_tag: &Tag) {
+        self.tags = Tag::replaced_in(&self.tags, old_tag, new_tag);
+        for split in &mut self.splits {
+            split.tags = Tag::replaced_in(&split.tags, old_tag, new_tag);
+        }
+    }
+
+    /// Sets the `Account` the `Transaction`s are added to.
+    pub fn set_account_uid(&mut self, account_uid: Uid) {
+        self.account_uid = account_uid;
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the `Uid`.
+    #[must_use]
+    pub fn get_uid(&self) -> Uid {
+        self.uid
+    }
+
+    /// Returns the value as a `String` that can be used to create a `Transaction`.
+    #[must_use]
+    pub fn get_value_string(&self) -> String {
+        self.value_decimal.to_string()
+    }
+
+    /// Returns the currency code.
+    #[must_use]
+    pub fn get_currency_string(&self) -> &str {
+        &self.currency_string
+    }
+
+    /// Returns the `Split`s each `Transaction` is divided into.
+    #[must_use]
+    pub fn get_splits(&self) -> &[Split] {
+        &self.splits
+    }
+
+    /// Returns the `Uid` of the `Account` the `Transaction`s are added to.
+    #[must_use]
+    pub fn get_account_uid(&self) -> Uid {
+        self.account_uid
+    }
+
+    /// Returns the earliest `Date` that has not been confirmed or skipped yet.
+    #[must_use]
+    pub fn get_next_date(&self) -> Date {
+        self.next_date
+    }
+
+    /// Returns every `Date` the template has been due on since it was last confirmed or skipped, up to and including the given `Date`.
+    #[must_use]
+    pub fn get_due_dates(&self, until: Date) -> Vec<Date> {
+        let mut due_dates = Vec::new();
+        let mut date = self.next_date;
+        while date.as_value() <= until.as_value() {
+            due_dates.push(date);
+            let next_date = self.rule.next_date_after(date);
+            if next_date == date { break } // the last supported year has been reached
+            date = next_date;
+        }
+        due_dates
+    }
+}
+
+
+
+/// A single due occurrence of a `RecurringTemplate`, waiting to be confirmed or skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueRecurrence {
+    pub template_uid: Uid,
+    pub date: Date,
+    /// Whether this is the earliest due occurrence of its template, which has to be handled before the later ones.
+    pub is_next: bool,
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{RecurrenceRules, RecurringTemplate};
+    use crate::vault::test_helpers::{date, tag};
+    use crate::vault::transaction::{Months, Uid};
+
+    #[test]
+    fn monthly_rules_return_to_their_day_after_short_months() {
+        let rule = RecurrenceRules::Monthly(31);
+        let february = rule.next_date_after(date(2026, Months::January, 31));
+        assert_eq!(february, date(2026, Months::February, 28));
+        assert_eq!(rule.next_date_after(february), date(2026, Months::March, 31));
+        assert_eq!(RecurrenceRules::Monthly(31).next_date_after(date(2027, Months::December, 31)), date(2028, Months::January, 31));
+    }
+
+    #[test]
+    fn yearly_rules_handle_leap_days() {
+        let rule = RecurrenceRules::Yearly(Months::February, 29);
+        let common_year = rule.next_date_after(date(2028, Months::February, 29));
+        assert_eq!(common_year, date(2029, Months::February, 28));
+        assert_eq!(rule.next_date_after(date(2031, Months::February, 28)), date(2032, Months::February, 29));
+    }
+
+    #[test]
+    fn weekly_rules_cross_months_and_years() {
+        assert_eq!(RecurrenceRules::Weekly.next_date_after(date(2026, Months::December, 28)), date(2027, Months::January, 4));
+        assert_eq!(RecurrenceRules::BiWeekly.next_date_after(date(2026, Months::February, 20)), date(2026, Months::March, 6));
+    }
+
+    #[test]
+    fn due_dates_run_up_to_the_given_date() {
+        let tags = vec![tag("rent")];
+        let mut template = RecurringTemplate::new("-1200", "usd", "Rent".to_string(), tags, Vec::new(), Uid::default(), RecurrenceRules::Monthly(1), date(2026, Months::January, 1))
+            .wont_fail("The template is valid.", "tests");
+        assert_eq!(template.get_due_dates(date(2026, Months::March, 15)), vec![date(2026, Months::January, 1), date(2026, Months::February, 1), date(2026, Months::March, 1)]);
+
+        template.advance();
+        assert_eq!(template.get_next_date(), date(2026, Months::February, 1));
+        assert!(template.get_due_dates(date(2026, Months::January, 31)).is_empty());
+    }
+}
@@ -135,15 +135,17 @@ impl RingParse {
         }
         let mut transactions = transaction_results.into_iter().map(|r| r.wont_fail("This is past a contains_fail() block.", "RingParse::assemble()")).collect::<Vec<&Transaction>>();
         
-        // filters out transactions that do not match the flow direction or are ignored
-        transactions.retain(|t| { flow_direction.matches(t) && !t.is_ignored() });
+        // filters out transactions that do not match the flow direction, are ignored, or are transfers
+        transactions.retain(|t| { flow_direction.matches(t) && !t.is_excluded_from_parsing() });
         
         
         
-        // assembles a list of segments from the tags
-        let segment_results: Vec<_> = Tag::get_tags_from(&transactions).into_iter().map(|tag| {
+        // assembles a list of segments from the tags, rolling child tags up into the top of their hierarchies if needed
+        let tags = Tag::get_tags_from(&transactions);
+        let segment_tags = if app.are_ring_tags_rolled_up { Tag::sorted(&tags.iter().map(Tag::get_root).collect::<Vec<_>>()) } else { tags };
+        let segment_results: Vec<_> = segment_tags.into_iter().map(|tag| {
             // gets the percentage for the tag
-            let percentage_result: Schrod<f64> = Tag::get_tag_percentage(&tag, &transactions);
+            let percentage_result: Schrod<f64> = Tag::get_tag_percentage(&tag, &transactions, app.are_ring_tags_rolled_up);
             if percentage_result.is_fail() { percentage_result.convert("RingParse::assemble()") }
             
             // creates a segment for the tag
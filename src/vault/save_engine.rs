@@ -1,30 +1,74 @@
-use std::path::PathBuf;
-use crate::{vault::{bank::{CurrencyExchange, TagRegistry}, transaction::{Date, Tag, Transaction, Value}}};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::{vault::{account::Account, bank::{Bank, CurrencyExchange, TagRegistry}, budget::Budget, recurring::RecurringTemplate, tag_rule::TagRule, transaction::{Date, Split, Tag, Transaction, Uid, Value}}};
 use schrod::Schrod::{Pass, Fail};
+use chrono::{Datelike, NaiveDateTime};
 use materialui::materials::MaterialThemes;
 use rust_decimal::Decimal;
 use rusty_money::iso;
 use schrod::Schrod;
 use serde::{Deserialize, Serialize};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::{AeadCore, Key, KeyInit, XChaCha20Poly1305, XNonce};
+use chacha20poly1305::aead::{Aead, OsRng, rand_core::RngCore};
+use zeroize::Zeroizing;
 
 //====================================================================================================//
 // STANDARD
 //====================================================================================================//
+/// The version of the save format written by this version of Ascent.
+/// Whenever the shape of `SaveDataBundle` changes, this must be increased and a matching step added to `migrate()`.
+pub const CURRENT_FORMAT_VERSION: u32 = 10;
+
 pub struct SaveData {
     pub theme: MaterialThemes,
     pub transactions: Vec<Transaction>,
     pub currency_exchange: CurrencyExchange,
     pub tag_registry: TagRegistry,
+    pub recurring_templates: Vec<RecurringTemplate>,
+    pub budgets: Vec<Budget>,
+    pub tag_rules: Vec<TagRule>,
+    pub accounts: Vec<Account>,
+    /// Whether the data was upgraded while loading and should be saved again to make the upgrade permanent.
+    pub was_migrated: bool,
+    /// Explains what happened if the save file was damaged and the data was recovered from the previous save.
+    pub recovery_notice: Option<String>,
 }
 impl SaveData {
     /// Used if there is no save data to load.
     #[must_use]
-    fn empty() -> SaveData {
+    pub fn empty() -> SaveData {
         SaveData {
             theme: MaterialThemes::Midnight,
             transactions: Vec::new(),
             tag_registry: TagRegistry::default(),
             currency_exchange: CurrencyExchange::default(),
+            recurring_templates: Vec::new(),
+            budgets: Vec::new(),
+            tag_rules: Vec::new(),
+            accounts: Vec::new(),
+            was_migrated: false,
+            recovery_notice: None,
+        }
+    }
+    
+    /// Collects the persistent data of a `Bank` so that it can be saved.
+    #[must_use]
+    pub fn from_bank(theme: MaterialThemes, bank: &Bank) -> SaveData {
+        SaveData {
+            theme,
+            transactions: bank.get_ledger_copy(),
+            currency_exchange: bank.currency_exchange.clone(),
+            tag_registry: bank.tag_registry.clone(),
+            recurring_templates: bank.recurring_templates.clone(),
+            budgets: bank.budgets.clone(),
+            tag_rules: bank.tag_rules.clone(),
+            accounts: bank.accounts.clone(),
+            was_migrated: false,
+            recovery_notice: None,
         }
     }
 }
@@ -32,21 +76,33 @@ impl SaveData {
 /// Holds the various pieces of data used in `SaveData` in a serializable format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SaveDataBundle {
+    format_version: u32,
     theme: MaterialThemes,
     transaction_bundles: Vec<TransactionDataBundle>,
-    #[serde(default)]
     currency_exchange: CurrencyExchange,
     tag_registry: TagRegistry,
+    recurring_templates: Vec<RecurringTemplate>,
+    budgets: Vec<Budget>,
+    tag_rules: Vec<TagRule>,
+    accounts: Vec<Account>,
 }
 
 /// A serializable bundle of transaction data.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TransactionDataBundle {
+    uid: Uid,
     value_decimal: Decimal,
     currency_string: String,
     date: Date,
     description: String,
     tags: Vec<Tag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    statement_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    splits: Vec<Split>,
+    account_uid: Uid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transfer_uid: Option<Uid>,
 }
 impl TransactionDataBundle {
     /// Creates a new `TransactionDataBundle` from a `Transaction`.
@@ -57,13 +113,20 @@ impl TransactionDataBundle {
         let date = transaction.date;
         let description = transaction.description.clone();
         let tags = transaction.tags.clone();
+        let statement_id = transaction.get_statement_id().map(str::to_string);
+        let splits = transaction.get_splits().to_vec();
         
         TransactionDataBundle {
+            uid: transaction.get_uid(),
             value_decimal,
             currency_string,
             date,
             description,
             tags,
+            statement_id,
+            splits,
+            account_uid: transaction.get_account_uid(),
+            transfer_uid: transaction.get_transfer_uid(),
         }
     }
     
@@ -80,104 +143,273 @@ impl TransactionDataBundle {
         let currency = currency_result.wont_fail("This is past an is_fail() guard clause.", "TransactionDataBundle::into_transaction()");
         
         let value = Value::from_decimal(self.value_decimal, currency);
-        Transaction::load_from_parts(value, self.date, self.description, self.tags)
+        let transaction_result = Transaction::load_from_parts(value, self.date, self.description, self.tags);
+        if transaction_result.is_fail() {
+            return transaction_result
+                .fail("Failed to convert TransactionDataBundle into Transaction.", "TransactionDataBundle::into_transaction()")
+        }
+        let mut transaction = transaction_result.wont_fail("This is past an is_fail() guard clause.", "TransactionDataBundle::into_transaction()");
+        let splits_result = transaction.set_splits(self.splits);
+        if splits_result.is_fail() {
+            return splits_result
+                .convert("TransactionDataBundle::into_transaction()")
+                .fail("Failed to convert TransactionDataBundle into Transaction.", "TransactionDataBundle::into_transaction()")
+        }
+        transaction.set_statement_id(self.statement_id);
+        transaction.set_uid(self.uid);
+        transaction.set_account_uid(self.account_uid);
+        transaction.set_transfer_uid(self.transfer_uid);
+        Pass(transaction)
     }
 }
 
-/// Returns the `Path` to the save location and creates it if it doesn't exist.
+/// The name of the vault used when no other vault has been chosen.
+pub const DEFAULT_VAULT_NAME: &str = "Main";
+
+/// Returns the directory that holds all vaults and backups and creates it if it doesn't exist.
+/// In order of priority, this is the `--data-dir` argument, the `ASCENT_DATA_DIR` environment variable, or the platform default.
 #[must_use]
-fn save_path() -> Schrod<PathBuf> {
-    // executable path
-    let exe_path_result = Schrod::from_result(std::env::current_exe(), "Failed to fetch the executable directory.", "save_engine::save_path()");
-    if exe_path_result.is_fail() {
-        return exe_path_result
-            .convert("save_engine::save_path()")
-            .fail("Failed to save.", "save_engine::save_path()")
+pub fn data_dir() -> Schrod<PathBuf> {
+    // choosing the directory
+    let data_dir = match data_dir_argument().or_else(|| std::env::var_os("ASCENT_DATA_DIR").filter(|d| !d.is_empty()).map(PathBuf::from)) {
+        Some(data_dir) => data_dir,
+        None => {
+            let default_data_dir_result = default_data_dir();
+            if default_data_dir_result.is_fail() {
+                return default_data_dir_result.fail("Failed to find the data directory.", "save_engine::data_dir()")
+            }
+            default_data_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::data_dir()")
+        }
+    };
+    
+    // creating the directory
+    let creation_result = Schrod::from_result(std::fs::create_dir_all(&data_dir), &format!("Failed to create the data directory at {}.", data_dir.display()), "save_engine::data_dir()");
+    if creation_result.is_fail() {
+        return creation_result
+            .convert("save_engine::data_dir()")
+            .fail("Failed to find the data directory.", "save_engine::data_dir()")
     }
-    let exe_path = exe_path_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::save_path()");
     
-    // upstream path
-    let upstream_path_result = Schrod::from_option(exe_path.parent(), "Failed to get parent directory of the executable.", "save_engine::save_path()");
-    if upstream_path_result.is_fail() {
-        return upstream_path_result
-            .convert("save_engine::save_path()")
-            .fail("Failed to save.", "save_engine::save_path()")
+    Pass(data_dir)
+}
+
+/// Returns the directory given with `--data-dir <path>` or `--data-dir=<path>`, if there is one.
+#[must_use]
+fn data_dir_argument() -> Option<PathBuf> {
+    let mut arguments = std::env::args_os().skip(1);
+    while let Some(argument) = arguments.next() {
+        let argument_string = argument.to_string_lossy();
+        if argument_string == "--data-dir" { return arguments.next().map(PathBuf::from) }
+        if let Some(data_dir) = argument_string.strip_prefix("--data-dir=") { return Some(PathBuf::from(data_dir)) }
     }
-    let upstream_path = upstream_path_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::save_path()");
+    None
+}
+
+/// Returns the platform's usual location for application data.
+/// On Linux and other Unix systems this follows the XDG base directory specification.
+#[must_use]
+fn default_data_dir() -> Schrod<PathBuf> {
+    let home = std::env::var_os("HOME").filter(|h| !h.is_empty()).map(PathBuf::from);
     
-    // save location path
-    let save_location_path = upstream_path.join("save_data");
-    let location_creation_result = Schrod::from_result(std::fs::create_dir_all(save_location_path.clone()), "Failed to create save data location.", "save_engine::save_path()");
-    if location_creation_result.is_fail() {
-        return location_creation_result
-            .convert("save_engine::save_path()")
-            .fail("Failed to save.", "save_engine::save_path()")
+    let data_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(|app_data| PathBuf::from(app_data).join("Ascent"))
     }
+    else if cfg!(target_os = "macos") {
+        home.map(|home| home.join("Library").join("Application Support").join("Ascent"))
+    }
+    else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|xdg_data_home| xdg_data_home.is_absolute())
+            .or_else(|| home.map(|home| home.join(".local").join("share")))
+            .map(|data_home| data_home.join("ascent"))
+    };
     
-    // save path
-    let save_path = save_location_path.join("data.json");
+    Schrod::from_option(data_dir, "Could not find a home directory. Please set ASCENT_DATA_DIR or pass --data-dir.", "save_engine::default_data_dir()")
+}
+
+/// Returns the directory holding the vault files and creates it if it doesn't exist.
+#[must_use]
+fn vaults_dir() -> Schrod<PathBuf> {
+    let data_dir_result = data_dir();
+    if data_dir_result.is_fail() { return data_dir_result }
+    let vaults_dir = data_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::vaults_dir()").join("vaults");
     
-    // returning the save path
-    Pass(save_path)
+    let creation_result = Schrod::from_result(std::fs::create_dir_all(&vaults_dir), "Failed to create the vault location.", "save_engine::vaults_dir()");
+    if creation_result.is_fail() { return creation_result.convert("save_engine::vaults_dir()") }
+    
+    Pass(vaults_dir)
 }
 
-/// Returns the `Path` to the backup location and creates it if it doesn't exist.
+/// Returns the `Path` to the save file of a vault.
 #[must_use]
-pub fn backup_path() -> Schrod<PathBuf> {
-    // executable path
-    let exe_path_result = Schrod::from_result(std::env::current_exe(), "Failed to fetch the executable directory.", "save_engine::backup_path()");
-    if exe_path_result.is_fail() {
-        return exe_path_result
-            .convert("save_engine::backup_path()")
-            .fail("Failed to create backup.", "save_engine::backup_path()")
+fn save_path(vault_name: &str) -> Schrod<PathBuf> {
+    if !is_vault_name_valid(vault_name) {
+        return Schrod::new_fail(&format!("\"{vault_name}\" is not a valid vault name."), "save_engine::save_path()")
     }
-    let exe_path = exe_path_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::backup_path()");
     
-    // upstream path
-    let upstream_path_result = Schrod::from_option(exe_path.parent(), "Failed to get parent directory of the executable.", "save_engine::backup_path()");
-    if upstream_path_result.is_fail() {
-        return upstream_path_result
-            .convert("save_engine::backup_path()")
-            .fail("Failed to create backup.", "save_engine::backup_path()")
+    let vaults_dir_result = vaults_dir();
+    if vaults_dir_result.is_fail() {
+        return vaults_dir_result.fail("Failed to find the save file.", "save_engine::save_path()")
     }
-    let upstream_path = upstream_path_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::backup_path()");
+    let vaults_dir = vaults_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::save_path()");
+    
+    Pass(vaults_dir.join(format!("{vault_name}.json")))
+}
+
+/// Returns the directory holding the backups of a vault and creates it if it doesn't exist.
+#[must_use]
+fn backups_dir(vault_name: &str) -> Schrod<PathBuf> {
+    // data path
+    let data_dir_result = data_dir();
+    if data_dir_result.is_fail() { return data_dir_result }
+    let data_dir = data_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::backups_dir()");
     
-    // save location path
-    let backup_location_path = upstream_path.join("backups");
-    let location_creation_result = Schrod::from_result(std::fs::create_dir_all(backup_location_path.clone()), "Failed to create backup location.", "save_engine::backup_path()");
-    if location_creation_result.is_fail() {
-        return location_creation_result
-            .convert("save_engine::backup_path()")
-            .fail("Failed to create backup.", "save_engine::backup_path()");
+    // backup location path
+    let backup_location_path = data_dir.join("backups").join(vault_name);
+    let location_creation_result = Schrod::from_result(std::fs::create_dir_all(backup_location_path.clone()), "Failed to create backup location.", "save_engine::backups_dir()");
+    if location_creation_result.is_fail() { return location_creation_result.convert("save_engine::backups_dir()") }
+    
+    Pass(backup_location_path)
+}
+
+/// Returns the `Path` for a new backup of a vault and creates its location if it doesn't exist.
+#[must_use]
+pub fn backup_path(vault_name: &str) -> Schrod<PathBuf> {
+    // backup location path
+    let backups_dir_result = backups_dir(vault_name);
+    if backups_dir_result.is_fail() {
+        return backups_dir_result.fail("Failed to create backup.", "save_engine::backup_path()")
     }
+    let backups_dir = backups_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::backup_path()");
 
-    // save path
-    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    // backup path
+    let timestamp = chrono::Local::now().format(BACKUP_TIMESTAMP_FORMAT).to_string();
     let filename = format!("backup_{timestamp}.json");
-    let export_path = backup_location_path.join(filename);
+    let export_path = backups_dir.join(filename);
     
-    // returning the save path
+    // returning the backup path
     Pass(export_path)
 }
 
-/// Checks if the save file exists.
+/// Returns the `Path` of the save file that older versions of Ascent kept next to the executable.
 #[must_use]
-pub fn does_save_file_exist() -> bool {
-    match save_path() {
+fn portable_save_path() -> Option<PathBuf> {
+    let exe_path = std::env::current_exe().ok()?;
+    Some(exe_path.parent()?.join("save_data").join("data.json"))
+}
+
+/// Checks if the save file of a vault exists.
+#[must_use]
+pub fn does_save_file_exist(vault_name: &str) -> bool {
+    match save_path(vault_name) {
         Pass(path) => path.exists(),
         Fail(_) => false,
     }
 }
 
-/// Serializes the given `SaveData` into a JSON `String`.
+
+
+// vaults
+/// Checks if a vault name can be used as a file name on every platform.
+#[must_use]
+pub fn is_vault_name_valid(vault_name: &str) -> bool {
+    !vault_name.trim().is_empty()
+        && vault_name.trim() == vault_name
+        && vault_name.chars().count() <= 64
+        && vault_name.chars().all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+}
+
+/// Returns the names of all vaults, sorted alphabetically.
+/// The active vault is always included, even if it has not been saved yet.
+#[must_use]
+pub fn list_vaults() -> Schrod<Vec<String>> {
+    let vaults_dir_result = vaults_dir();
+    if vaults_dir_result.is_fail() {
+        return vaults_dir_result.convert("save_engine::list_vaults()").fail("Failed to list vaults.", "save_engine::list_vaults()")
+    }
+    let vaults_dir = vaults_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::list_vaults()");
+    
+    let entries_result = Schrod::from_result(std::fs::read_dir(&vaults_dir), "Failed to read the vault location.", "save_engine::list_vaults()");
+    if entries_result.is_fail() {
+        return entries_result.convert("save_engine::list_vaults()").fail("Failed to list vaults.", "save_engine::list_vaults()")
+    }
+    let entries = entries_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::list_vaults()");
+    
+    let mut vault_names = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+        .filter(|name| is_vault_name_valid(name))
+        .collect::<Vec<_>>();
+    let active_vault = get_active_vault();
+    if !vault_names.contains(&active_vault) { vault_names.push(active_vault); }
+    vault_names.sort_by_key(|name| name.to_lowercase());
+    
+    Pass(vault_names)
+}
+
+/// Creates a new, empty vault.
+#[must_use]
+pub fn create_vault(vault_name: &str) -> Schrod<()> {
+    let save_path_result = save_path(vault_name);
+    if save_path_result.is_fail() {
+        return save_path_result.convert("save_engine::create_vault()").fail("Failed to create vault.", "save_engine::create_vault()")
+    }
+    let save_path = save_path_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::create_vault()");
+    if save_path.exists() {
+        return Schrod::new_fail(&format!("A vault named \"{vault_name}\" already exists."), "save_engine::create_vault()")
+    }
+    
+    let json_result = get_serialized_save_data(SaveData::empty(), None);
+    if json_result.is_fail() {
+        return json_result.convert("save_engine::create_vault()").fail("Failed to create vault.", "save_engine::create_vault()")
+    }
+    let json = json_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::create_vault()");
+    
+    let write_result = write_atomically(&save_path, &json);
+    if write_result.is_fail() { return write_result.fail("Failed to create vault.", "save_engine::create_vault()") }
+    Pass(())
+}
+
+/// Returns the name of the vault that was open last.
+#[must_use]
+pub fn get_active_vault() -> String {
+    let data_dir = match data_dir() {
+        Pass(data_dir) => data_dir,
+        Fail(_) => return DEFAULT_VAULT_NAME.to_string(),
+    };
+    std::fs::read_to_string(data_dir.join("active_vault"))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| is_vault_name_valid(name))
+        .unwrap_or_else(|| DEFAULT_VAULT_NAME.to_string())
+}
+
+/// Remembers which vault is open so that it is opened again on the next launch.
 #[must_use]
-fn get_serialized_save_data(save_data: SaveData) -> Schrod<String> {
+pub fn set_active_vault(vault_name: &str) -> Schrod<()> {
+    let data_dir_result = data_dir();
+    if data_dir_result.is_fail() {
+        return data_dir_result.convert("save_engine::set_active_vault()").fail("Failed to remember the open vault.", "save_engine::set_active_vault()")
+    }
+    let data_dir = data_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::set_active_vault()");
+    
+    let write_result = Schrod::from_result(std::fs::write(data_dir.join("active_vault"), vault_name), "Failed to write the open vault.", "save_engine::set_active_vault()");
+    if write_result.is_fail() { return write_result.fail("Failed to remember the open vault.", "save_engine::set_active_vault()") }
+    Pass(())
+}
+
+/// Serializes the given `SaveData` into a JSON `String`, encrypting it if a `VaultKey` is given.
+#[must_use]
+fn get_serialized_save_data(save_data: SaveData, vault_key: Option<&VaultKey>) -> Schrod<String> {
     // converting transactions into bundles
     let transaction_bundles = save_data.transactions
         .iter()
         .map(TransactionDataBundle::from_transaction)
         .collect();
-    let bundles = SaveDataBundle { theme: save_data.theme, transaction_bundles, currency_exchange: save_data.currency_exchange, tag_registry: save_data.tag_registry };
+    let bundles = SaveDataBundle { format_version: CURRENT_FORMAT_VERSION, theme: save_data.theme, transaction_bundles, currency_exchange: save_data.currency_exchange, tag_registry: save_data.tag_registry, recurring_templates: save_data.recurring_templates, budgets: save_data.budgets, tag_rules: save_data.tag_rules, accounts: save_data.accounts };
 
     // serializing
     let json_result = Schrod::from_result(serde_json::to_string_pretty(&bundles), "Failed to serialize transaction data.", "save_engine::get_serialized_save_data()");
@@ -188,15 +420,24 @@ fn get_serialized_save_data(save_data: SaveData) -> Schrod<String> {
     }
     let json = json_result.wont_fail("Past is_fail() guard clause.", "save_engine::get_serialized_save_data()");
     
+    // encrypting
+    if let Some(vault_key) = vault_key {
+        let encrypted_result = encrypted(&json, vault_key);
+        if encrypted_result.is_fail() {
+            return encrypted_result.fail("Failed to save.", "save_engine::get_serialized_save_data()")
+        }
+        return encrypted_result
+    }
+    
     // returning the json
     Pass(json)
 }
 
-/// Saves the given save data to a JSON file at the given path.
+/// Saves the given save data to the save file of a vault, encrypting it if a `VaultKey` is given.
 #[must_use]
-pub fn save(save_data: SaveData) -> Schrod<()> {
+pub fn save(save_data: SaveData, vault_name: &str, vault_key: Option<&VaultKey>) -> Schrod<()> {
     // getting the json
-    let json_result = get_serialized_save_data(save_data);
+    let json_result = get_serialized_save_data(save_data, vault_key);
     if json_result.is_fail() {
         return json_result
             .convert("save_engine::save()")
@@ -204,30 +445,98 @@ pub fn save(save_data: SaveData) -> Schrod<()> {
     }
     let json = json_result.wont_fail("Past is_fail() guard clause.", "save_engine::save()");
     
-    // writing the file
-    let save_path_result = save_path();
+    // finding the save path
+    let save_path_result = save_path(vault_name);
     if save_path_result.is_fail() {
         return save_path_result
             .convert("save_engine::save()")
             .fail("Failed to save.", "save_engine::save()")
     }
     let save_path = save_path_result.wont_fail("Past is_fail() guard clause.", "save_engine::save()");
-    let write_result = Schrod::from_result(std::fs::write(save_path, json), "Failed to write save file.", "save_engine::save()");
+    
+    // writing the save file
+    let save_result = save_to(&save_path, &json);
+    if save_result.is_fail() {
+        return save_result.fail("Failed to save.", "save_engine::save()")
+    }
+
+    // returning success
+    Pass(())
+}
+
+/// Replaces the save file at the given `Path` with the given JSON, keeping the previous save next to it.
+#[must_use]
+fn save_to(save_path: &Path, json: &str) -> Schrod<()> {
+    // keeping the previous save
+    if save_path.exists() {
+        let copy_result = Schrod::from_result(std::fs::copy(save_path, previous_save_path(save_path)), "Failed to keep a copy of the previous save file.", "save_engine::save_to()");
+        if copy_result.is_fail() {
+            return copy_result.convert("save_engine::save_to()")
+        }
+    }
+    
+    // writing the file
+    let write_result = write_atomically(save_path, json);
     if write_result.is_fail() {
-        return write_result
-            .convert("save_engine::save()")
-            .fail("Failed to save.", "save_engine::save()")
+        return write_result.fail("Failed to write the save file.", "save_engine::save_to()")
     }
 
     // returning success
     Pass(())
 }
 
-/// Saves the given save data to a JSON file at the given path.
+/// Counts the temporary files created by `write_atomically()`, so that overlapping writes never share one.
+static TEMPORARY_FILE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Writes a file so that it is either fully written or not changed at all.
+/// The contents are written to a temporary file of their own and flushed to the disk before replacing the original.
+/// The temporary file is removed if anything fails.
+#[must_use]
+pub fn write_atomically(path: &Path, contents: &str) -> Schrod<()> {
+    let temporary_number = TEMPORARY_FILE_COUNT.fetch_add(1, Ordering::Relaxed);
+    let temporary_path = path.with_extension(format!("json.{}-{temporary_number}.tmp", std::process::id()));
+    
+    // writing the temporary file
+    let file_result = Schrod::from_result(std::fs::File::create(&temporary_path), "Failed to create temporary file.", "save_engine::write_atomically()");
+    if file_result.is_fail() { return file_result.convert("save_engine::write_atomically()") }
+    let mut file = file_result.wont_fail("Past is_fail() guard clause.", "save_engine::write_atomically()");
+    let write_result = match file.write_all(contents.as_bytes()) {
+        Ok(()) => Schrod::from_result(file.sync_all(), "Failed to flush temporary file to the disk.", "save_engine::write_atomically()"),
+        Err(error) => Schrod::from_result(Err(error), "Failed to write temporary file.", "save_engine::write_atomically()"),
+    };
+    drop(file);
+    if write_result.is_fail() {
+        std::fs::remove_file(&temporary_path).ok();
+        return write_result
+    }
+    
+    // replacing the original
+    let rename_result = Schrod::from_result(std::fs::rename(&temporary_path, path), "Failed to replace the file with the temporary file.", "save_engine::write_atomically()");
+    if rename_result.is_fail() {
+        std::fs::remove_file(&temporary_path).ok();
+        return rename_result
+    }
+    
+    // making the rename itself durable
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() && let Ok(directory) = std::fs::File::open(parent) {
+        directory.sync_all().ok();
+    }
+    
+    Pass(())
+}
+
+/// Returns the `Path` of the copy of the previous save, which sits next to the save file.
 #[must_use]
-pub fn backup(save_data: SaveData) -> Schrod<()> {
+fn previous_save_path(save_path: &Path) -> PathBuf {
+    save_path.with_extension("json.bak")
+}
+
+/// Saves the given save data to a new backup file of a vault, encrypting it if a `VaultKey` is given.
+#[must_use]
+pub fn backup(save_data: SaveData, vault_name: &str, vault_key: Option<&VaultKey>) -> Schrod<()> {
     // getting the json
-    let json_result = get_serialized_save_data(save_data);
+    let json_result = get_serialized_save_data(save_data, vault_key);
     if json_result.is_fail() {
         return json_result
             .convert("save_engine::backup()")
@@ -236,27 +545,55 @@ pub fn backup(save_data: SaveData) -> Schrod<()> {
     let json = json_result.wont_fail("Past is_fail() guard clause.", "save_engine::backup()");
     
     // writing the file
-    let backup_path_result = backup_path();
+    let backup_path_result = backup_path(vault_name);
     if backup_path_result.is_fail() {
         return backup_path_result
             .convert("save_engine::backup()")
             .fail("Failed to create backup.", "save_engine::backup()")
     }
     let backup_path = backup_path_result.wont_fail("Past is_fail() guard clause.", "save_engine::backup()");
-    let write_result = Schrod::from_result(std::fs::write(backup_path, json), "Failed to write backup file.", "save_engine::backup()");
+    let write_result = write_atomically(&backup_path, &json);
     if write_result.is_fail() {
-        return write_result
-            .convert("save_engine::backup()")
-            .fail("Failed to create backup.", "save_engine::backup()")
+        return write_result.fail("Failed to create backup.", "save_engine::backup()")
+    }
+    
+    // removing backups that are no longer needed
+    let prune_result = prune_backups(vault_name);
+    if prune_result.is_fail() {
+        return prune_result.convert("save_engine::backup()").fail("The backup was created, but older backups could not be cleaned up.", "save_engine::backup()")
     }
 
     // returning success
     Pass(())
 }
 
+/// Saves the given save data to a JSON file at the given path, where it can later be imported.
+/// The file is never encrypted.
+#[must_use]
+pub fn export_to(save_data: SaveData, path: &Path) -> Schrod<()> {
+    // getting the json
+    let json_result = get_serialized_save_data(save_data, None);
+    if json_result.is_fail() {
+        return json_result
+            .convert("save_engine::export_to()")
+            .fail("Failed to export.", "save_engine::export_to()")
+    }
+    let json = json_result.wont_fail("Past is_fail() guard clause.", "save_engine::export_to()");
+    
+    // writing the file
+    let write_result = write_atomically(path, &json);
+    if write_result.is_fail() {
+        return write_result.fail("Failed to export.", "save_engine::export_to()")
+    }
+    
+    // returning success
+    Pass(())
+}
+
 /// Loads save data from a JSON file at the given path.
+/// Encrypted files can only be loaded with the matching `VaultKey`.
 #[must_use]
-pub fn load_from(path: &PathBuf) -> Schrod<SaveData> {
+pub fn load_from(path: &PathBuf, vault_key: Option<&VaultKey>) -> Schrod<SaveData> {
     // reading the file
     let data_result = Schrod::from_result(std::fs::read_to_string(path), "Failed to read save file.", "save_engine::load_from()");
     if data_result.is_fail() {
@@ -265,15 +602,53 @@ pub fn load_from(path: &PathBuf) -> Schrod<SaveData> {
             .fail("Failed to load save data.", "save_engine::load_from()")
     }
     let data = data_result.wont_fail("Past is_fail() guard clause.", "save_engine::load_from()");
+    
+    // decrypting the data
+    let json_result = decrypted(&data, vault_key);
+    if json_result.is_fail() {
+        return json_result
+            .convert("save_engine::load_from()")
+            .fail("Failed to load save data.", "save_engine::load_from()")
+    }
+    let json = json_result.wont_fail("Past is_fail() guard clause.", "save_engine::load_from()");
+
+    // parsing the data
+    let save_data_result = parse_save_data(&json);
+    if save_data_result.is_fail() {
+        return save_data_result.fail("Failed to load save data.", "save_engine::load_from()")
+    }
+    save_data_result
+}
 
+/// Parses save data from a JSON `String`, upgrading it first if it was written in an older format.
+#[must_use]
+pub fn parse_save_data(data: &str) -> Schrod<SaveData> {
+    // reading the raw json
+    let raw_result = Schrod::from_result(serde_json::from_str::<serde_json::Value>(data), "Failed to read save data as JSON.", "save_engine::parse_save_data()");
+    if raw_result.is_fail() {
+        return raw_result
+            .convert("save_engine::parse_save_data()")
+            .fail("Failed to parse save data.", "save_engine::parse_save_data()")
+    }
+    let mut raw = raw_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::parse_save_data()");
+    
+    // upgrading older formats
+    let migration_result = migrate(&mut raw);
+    if migration_result.is_fail() {
+        return migration_result
+            .convert("save_engine::parse_save_data()")
+            .fail("Failed to parse save data.", "save_engine::parse_save_data()")
+    }
+    let was_migrated = migration_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::parse_save_data()");
+    
     // deserializing the data into bundles
-    let bundle_result: Schrod<SaveDataBundle> = Schrod::from_result(serde_json::from_str(&data), "Failed to deserialize save data.", "save_engine::load_from()");
+    let bundle_result: Schrod<SaveDataBundle> = Schrod::from_result(serde_json::from_value(raw), "Failed to deserialize save data.", "save_engine::parse_save_data()");
     if bundle_result.is_fail() {
         return bundle_result
-            .convert("save_engine::load_from()")
-            .fail("Failed to load save data.", "save_engine::load_from()")
+            .convert("save_engine::parse_save_data()")
+            .fail("Failed to parse save data.", "save_engine::parse_save_data()")
     }
-    let bundle = bundle_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::load_from()");
+    let bundle = bundle_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::parse_save_data()");
     
     // converting transaction bundles into transactions
     let mut transactions = Vec::new();
@@ -281,41 +656,785 @@ pub fn load_from(path: &PathBuf) -> Schrod<SaveData> {
         let transaction_result = transaction_bundle.into_transaction();
         if transaction_result.is_fail() {
             return transaction_result
-                .convert("save_engine::load_from()")
-                .fail("Failed to load save data.", "save_engine::load_from()")
+                .convert("save_engine::parse_save_data()")
+                .fail("Failed to parse save data.", "save_engine::parse_save_data()")
         }
-        transactions.push(transaction_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::load_from()"));
+        transactions.push(transaction_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::parse_save_data()"));
     }
     
     // returning the `SaveData`
-    Pass(SaveData { theme: bundle.theme, transactions, currency_exchange: bundle.currency_exchange, tag_registry: bundle.tag_registry })
+    Pass(SaveData { theme: bundle.theme, transactions, currency_exchange: bundle.currency_exchange, tag_registry: bundle.tag_registry, recurring_templates: bundle.recurring_templates, budgets: bundle.budgets, tag_rules: bundle.tag_rules, accounts: bundle.accounts, was_migrated, recovery_notice: None })
 }
 
-/// Loads save data from a JSON file from the default `Path`.
+/// Loads save data from the save file of a vault.
+/// If the save file is damaged, the previous save is loaded instead and restored as the save file.
 #[must_use]
-pub fn load() -> Schrod<SaveData> {
-    // returning empty save data if there is no save file
-    if !does_save_file_exist() { return Pass(SaveData::empty()); }
-    
-    // reading the file
-    let save_path_result = save_path();
+pub fn load(vault_name: &str, vault_key: Option<&VaultKey>) -> Schrod<SaveData> {
+    // finding the save path
+    let save_path_result = save_path(vault_name);
     if save_path_result.is_fail() {
         return save_path_result
             .convert("save_engine::load()")
-            .fail("Failed to save.", "save_engine::load()")
+            .fail("Failed to load.", "save_engine::load()")
     }
     let save_path = save_path_result.wont_fail("Past is_fail() guard clause.", "save_engine::load()");
     
+    // bringing over the save file kept next to the executable by older versions
+    if vault_name == DEFAULT_VAULT_NAME && !save_path.exists() && let Some(portable_save_path) = portable_save_path() && portable_save_path.exists() {
+        let copy_result = Schrod::from_result(std::fs::copy(&portable_save_path, &save_path), "Failed to copy the save file from next to the executable.", "save_engine::load()");
+        if copy_result.is_fail() {
+            return copy_result
+                .convert("save_engine::load()")
+                .fail("Failed to load.", "save_engine::load()")
+        }
+    }
+    
     // returning the `SaveData`
-    load_from(&save_path)
+    load_or_recover(&save_path, vault_key)
+}
+
+/// Loads save data from the given save file, falling back to the previous save if it is damaged.
+#[must_use]
+fn load_or_recover(save_path: &Path, vault_key: Option<&VaultKey>) -> Schrod<SaveData> {
+    let previous_save_path = previous_save_path(save_path);
+    
+    // returning empty save data if there is no save file
+    if !save_path.exists() && !previous_save_path.exists() { return Pass(SaveData::empty()); }
+    
+    // loading the save file
+    let save_data_result = if save_path.exists() { load_from(&save_path.to_path_buf(), vault_key) } else { Schrod::new_fail("The save file is missing.", "save_engine::load_or_recover()") };
+    if save_data_result.is_pass() || !previous_save_path.exists() || is_from_newer_version(save_path, vault_key) { return save_data_result }
+    
+    // falling back to the previous save
+    let previous_save_data_result = load_from(&previous_save_path, vault_key);
+    if previous_save_data_result.is_fail() {
+        return save_data_result.fail("The previous save could not be loaded either.", "save_engine::load_or_recover()")
+    }
+    let mut save_data = previous_save_data_result.wont_fail("Past is_fail() guard clause.", "save_engine::load_or_recover()");
+    
+    // restoring the previous save while keeping the damaged file for inspection
+    let damaged_path = save_path.with_extension("json.damaged");
+    if save_path.exists() { std::fs::rename(save_path, &damaged_path).ok(); }
+    let restore_result = Schrod::from_result(std::fs::copy(&previous_save_path, save_path), "Failed to restore the previous save.", "save_engine::load_or_recover()");
+    
+    let failure_reason = save_data_result.results().first().cloned().unwrap_or_default();
+    let mut notice = format!("The save file could not be loaded ({failure_reason}), so the previous save was loaded instead. Changes made since then may be missing.");
+    if damaged_path.exists() { notice.push_str(&format!(" The damaged file was kept at {}.", damaged_path.display())); }
+    if restore_result.is_fail() { notice.push_str(" The previous save could not be restored as the save file."); }
+    save_data.recovery_notice = Some(notice);
+    
+    Pass(save_data)
+}
+
+
+
+
+
+
+
+//====================================================================================================//
+// BACKUPS
+//====================================================================================================//
+/// The format of the timestamp in backup file names.
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// The number of saves between automatic backups.
+pub const SAVES_PER_AUTOMATIC_BACKUP: u32 = 25;
+
+/// How many backups are kept.
+/// The newest backup of each of the most recent days, weeks, and months is kept, and everything else is removed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetentionPolicy {
+    pub daily: usize,
+    pub weekly: usize,
+    pub monthly: usize,
+}
+impl Default for RetentionPolicy {
+    /// Keeps 7 daily, 4 weekly, and 12 monthly backups.
+    fn default() -> RetentionPolicy {
+        RetentionPolicy { daily: 7, weekly: 4, monthly: 12 }
+    }
+}
+impl RetentionPolicy {
+    /// Returns which of the given backup times should be kept.
+    /// The result lines up with the given list.
+    #[must_use]
+    pub fn kept(&self, times: &[NaiveDateTime]) -> Vec<bool> {
+        let mut order = (0..times.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| std::cmp::Reverse(times[index]));
+        
+        let mut kept = vec![false; times.len()];
+        let days = times.iter().map(|time| (time.year(), time.ordinal())).collect::<Vec<_>>();
+        let weeks = times.iter().map(|time| (time.iso_week().year(), time.iso_week().week())).collect::<Vec<_>>();
+        let months = times.iter().map(|time| (time.year(), time.month())).collect::<Vec<_>>();
+        
+        // keeping the newest backup of each period, newest periods first
+        for (count, periods) in [(self.daily, days), (self.weekly, weeks), (self.monthly, months)] {
+            let mut seen_periods = Vec::new();
+            for &index in &order {
+                if seen_periods.len() >= count { break }
+                let period = periods[index];
+                if !seen_periods.contains(&period) {
+                    seen_periods.push(period);
+                    kept[index] = true;
+                }
+            }
+        }
+        
+        kept
+    }
+}
+
+/// Describes a backup file.
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    /// Where the backup is.
+    pub path: PathBuf,
+    /// When the backup was made.
+    pub time: NaiveDateTime,
+    /// The number of `Transaction`s in the backup, if it could be read.
+    pub transaction_count: Option<usize>,
+}
+
+/// Lists the backups of a vault, newest first.
+/// Encrypted backups can only be counted with the matching `VaultKey`.
+#[must_use]
+pub fn list_backups(vault_name: &str, vault_key: Option<&VaultKey>) -> Schrod<Vec<BackupInfo>> {
+    let backups_dir_result = backups_dir(vault_name);
+    if backups_dir_result.is_fail() {
+        return backups_dir_result.convert("save_engine::list_backups()").fail("Failed to list backups.", "save_engine::list_backups()")
+    }
+    let backups_dir = backups_dir_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::list_backups()");
+    
+    let entries_result = Schrod::from_result(std::fs::read_dir(&backups_dir), "Failed to read the backup location.", "save_engine::list_backups()");
+    if entries_result.is_fail() {
+        return entries_result.convert("save_engine::list_backups()").fail("Failed to list backups.", "save_engine::list_backups()")
+    }
+    let entries = entries_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::list_backups()");
+    
+    let mut backups = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let time = backup_time_of(&path)?;
+            Some(BackupInfo { transaction_count: transaction_count_of(&path, vault_key), path, time })
+        })
+        .collect::<Vec<_>>();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.time));
+    
+    Pass(backups)
+}
+
+/// Removes the backups of a vault that the default `RetentionPolicy` does not keep.
+/// Returns the number of backups removed.
+#[must_use]
+pub fn prune_backups(vault_name: &str) -> Schrod<usize> {
+    let backups_result = list_backups(vault_name, None);
+    if backups_result.is_fail() {
+        return backups_result.convert("save_engine::prune_backups()").fail("Failed to clean up backups.", "save_engine::prune_backups()")
+    }
+    let backups = backups_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::prune_backups()");
+    
+    let times = backups.iter().map(|backup| backup.time).collect::<Vec<_>>();
+    let kept = RetentionPolicy::default().kept(&times);
+    
+    let mut removed_count = 0;
+    for (backup, is_kept) in backups.iter().zip(kept) {
+        if is_kept { continue }
+        let remove_result = Schrod::from_result(std::fs::remove_file(&backup.path), &format!("Failed to remove {}.", backup.path.display()), "save_engine::prune_backups()");
+        if remove_result.is_fail() { return remove_result.convert("save_engine::prune_backups()").fail("Failed to clean up backups.", "save_engine::prune_backups()") }
+        removed_count += 1;
+    }
+    
+    Pass(removed_count)
 }
 
+/// Reads the time a backup was made from its file name.
+#[must_use]
+fn backup_time_of(path: &Path) -> Option<NaiveDateTime> {
+    if path.extension()? != "json" { return None }
+    let timestamp = path.file_stem()?.to_str()?.strip_prefix("backup_")?;
+    NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()
+}
+
+/// Counts the `Transaction`s in a backup without fully loading it.
+#[must_use]
+fn transaction_count_of(path: &Path, vault_key: Option<&VaultKey>) -> Option<usize> {
+    let data = std::fs::read_to_string(path).ok()?;
+    let json = match decrypted(&data, vault_key) { Pass(json) => json, Fail(_) => return None };
+    let raw = serde_json::from_str::<serde_json::Value>(&json).ok()?;
+    raw.get("transaction_bundles")?.as_array().map(Vec::len)
+}
 
 
 
 
 
 
+
+//====================================================================================================//
+// ENCRYPTION
+//====================================================================================================//
+/// The cipher used to encrypt save files.
+const ENCRYPTION_CIPHER: &str = "XChaCha20-Poly1305";
+
+/// The function used to turn a passphrase into a key.
+const ENCRYPTION_KDF: &str = "Argon2id";
+
+/// The number of random bytes mixed into each passphrase before it is turned into a key.
+const SALT_LENGTH: usize = 16;
+
+/// The settings used to turn a passphrase into a key.
+/// These are stored with every encrypted file so that they can be strengthened later without breaking older files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct KdfSettings {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    /// The base64 encoded salt.
+    salt: String,
+}
+
+/// Describes how an encrypted file was encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptionHeader {
+    cipher: String,
+    kdf: String,
+    #[serde(flatten)]
+    kdf_settings: KdfSettings,
+    /// The base64 encoded nonce.
+    nonce: String,
+}
+
+/// A save file whose JSON has been encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptedBundle {
+    encryption: EncryptionHeader,
+    /// The base64 encoded, encrypted JSON.
+    ciphertext: String,
+}
+
+/// The key derived from a passphrase, used to encrypt and decrypt the files of a vault.
+/// The passphrase itself is not kept, and the key is wiped from memory when it is dropped.
+#[derive(Clone)]
+pub struct VaultKey {
+    kdf_settings: KdfSettings,
+    key: Zeroizing<[u8; 32]>,
+}
+impl VaultKey {
+    // initializing
+    /// Creates a `VaultKey` for a new passphrase.
+    #[must_use]
+    pub fn new(passphrase: &str) -> Schrod<VaultKey> {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        let params = Params::default();
+        let kdf_settings = KdfSettings { memory_kib: params.m_cost(), iterations: params.t_cost(), parallelism: params.p_cost(), salt: BASE64.encode(salt) };
+        
+        let key_result = derive_key(passphrase, &kdf_settings);
+        if key_result.is_fail() {
+            return key_result.convert("VaultKey::new()").fail("Failed to create a key from the passphrase.", "VaultKey::new()")
+        }
+        let key = key_result.wont_fail("This is past an is_fail() guard clause.", "VaultKey::new()");
+        
+        Pass(VaultKey { kdf_settings, key })
+    }
+    
+    /// Creates the `VaultKey` that opens the save file of a vault, failing if the passphrase is wrong.
+    #[must_use]
+    pub fn unlock(vault_name: &str, passphrase: &str) -> Schrod<VaultKey> {
+        // reading the save file
+        let save_path_result = save_path(vault_name);
+        if save_path_result.is_fail() {
+            return save_path_result.convert("VaultKey::unlock()").fail("Failed to unlock the vault.", "VaultKey::unlock()")
+        }
+        let mut save_path = save_path_result.wont_fail("This is past an is_fail() guard clause.", "VaultKey::unlock()");
+        if !save_path.exists() { save_path = previous_save_path(&save_path); }
+        let data_result = Schrod::from_result(std::fs::read_to_string(&save_path), "Failed to read the save file.", "VaultKey::unlock()");
+        if data_result.is_fail() {
+            return data_result.convert("VaultKey::unlock()").fail("Failed to unlock the vault.", "VaultKey::unlock()")
+        }
+        let data = data_result.wont_fail("This is past an is_fail() guard clause.", "VaultKey::unlock()");
+        let bundle_result = Schrod::from_option(encrypted_bundle_of(&data), "The vault is not encrypted.", "VaultKey::unlock()");
+        if bundle_result.is_fail() {
+            return bundle_result.convert("VaultKey::unlock()").fail("Failed to unlock the vault.", "VaultKey::unlock()")
+        }
+        let bundle = bundle_result.wont_fail("This is past an is_fail() guard clause.", "VaultKey::unlock()");
+        
+        // deriving the key with the settings of the save file
+        let key_result = derive_key(passphrase, &bundle.encryption.kdf_settings);
+        if key_result.is_fail() {
+            return key_result.convert("VaultKey::unlock()").fail("Failed to unlock the vault.", "VaultKey::unlock()")
+        }
+        let key = key_result.wont_fail("This is past an is_fail() guard clause.", "VaultKey::unlock()");
+        let vault_key = VaultKey { kdf_settings: bundle.encryption.kdf_settings, key };
+        
+        // checking the passphrase
+        let decrypt_result = decrypted(&data, Some(&vault_key));
+        if decrypt_result.is_fail() {
+            return decrypt_result.convert("VaultKey::unlock()").fail("Failed to unlock the vault.", "VaultKey::unlock()")
+        }
+        
+        Pass(vault_key)
+    }
+    
+    
+    
+    // data retrieval and parsing
+    /// Checks if the given passphrase is the one this `VaultKey` was made from, by deriving its key again.
+    #[must_use]
+    pub fn has_passphrase(&self, passphrase: &str) -> bool {
+        match derive_key(passphrase, &self.kdf_settings) {
+            Pass(key) => key == self.key,
+            Fail(_) => false,
+        }
+    }
+}
+
+/// Turns a passphrase into a key.
+#[must_use]
+fn derive_key(passphrase: &str, kdf_settings: &KdfSettings) -> Schrod<Zeroizing<[u8; 32]>> {
+    let salt_result = Schrod::from_result(BASE64.decode(&kdf_settings.salt), "The salt is not valid base64.", "save_engine::derive_key()");
+    if salt_result.is_fail() { return salt_result.convert("save_engine::derive_key()") }
+    let salt = salt_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::derive_key()");
+    
+    let params_result = Schrod::from_result(Params::new(kdf_settings.memory_kib, kdf_settings.iterations, kdf_settings.parallelism, Some(32)), "The key settings are not valid.", "save_engine::derive_key()");
+    if params_result.is_fail() { return params_result.convert("save_engine::derive_key()") }
+    let params = params_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::derive_key()");
+    
+    let mut key = Zeroizing::new([0u8; 32]);
+    let hash_result = Schrod::from_result(Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(passphrase.as_bytes(), &salt, key.as_mut_slice()), "Failed to derive a key from the passphrase.", "save_engine::derive_key()");
+    if hash_result.is_fail() { return hash_result.convert("save_engine::derive_key()") }
+    
+    Pass(key)
+}
+
+/// Encrypts JSON with a `VaultKey`, returning the JSON of the `EncryptedBundle`.
+#[must_use]
+fn encrypted(json: &str, vault_key: &VaultKey) -> Schrod<String> {
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(vault_key.key.as_slice()));
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext_result = Schrod::from_result(cipher.encrypt(&nonce, json.as_bytes()), "Failed to encrypt the save data.", "save_engine::encrypted()");
+    if ciphertext_result.is_fail() { return ciphertext_result.convert("save_engine::encrypted()") }
+    let ciphertext = ciphertext_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::encrypted()");
+    
+    let bundle = EncryptedBundle {
+        encryption: EncryptionHeader {
+            cipher: ENCRYPTION_CIPHER.to_string(),
+            kdf: ENCRYPTION_KDF.to_string(),
+            kdf_settings: vault_key.kdf_settings.clone(),
+            nonce: BASE64.encode(nonce),
+        },
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    Schrod::from_result(serde_json::to_string_pretty(&bundle), "Failed to serialize the encrypted save data.", "save_engine::encrypted()")
+}
+
+/// Returns the JSON held by a file, decrypting it first if it is encrypted.
+#[must_use]
+fn decrypted(data: &str, vault_key: Option<&VaultKey>) -> Schrod<String> {
+    let Some(bundle) = encrypted_bundle_of(data) else { return Pass(data.to_string()) };
+    let Some(vault_key) = vault_key else {
+        return Schrod::new_fail("This file is encrypted, and its passphrase is needed to open it.", "save_engine::decrypted()")
+    };
+    if bundle.encryption.cipher != ENCRYPTION_CIPHER || bundle.encryption.kdf != ENCRYPTION_KDF {
+        return Schrod::new_fail(&format!("This file is encrypted with {} and {}, which this version of Ascent does not support.", bundle.encryption.cipher, bundle.encryption.kdf), "save_engine::decrypted()")
+    }
+    
+    // only the key is kept, so files derived with other settings cannot be opened
+    if bundle.encryption.kdf_settings != vault_key.kdf_settings {
+        return Schrod::new_fail("This file was encrypted with another passphrase, so it cannot be opened with the current one.", "save_engine::decrypted()")
+    }
+
+    // reading the encrypted parts
+    let nonce_result = Schrod::from_option(BASE64.decode(&bundle.encryption.nonce).ok().filter(|nonce| nonce.len() == 24), "The nonce is not valid.", "save_engine::decrypted()");
+    if nonce_result.is_fail() { return nonce_result.convert("save_engine::decrypted()") }
+    let nonce = nonce_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::decrypted()");
+    let ciphertext_result = Schrod::from_result(BASE64.decode(&bundle.ciphertext), "The encrypted data is not valid base64.", "save_engine::decrypted()");
+    if ciphertext_result.is_fail() { return ciphertext_result.convert("save_engine::decrypted()") }
+    let ciphertext = ciphertext_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::decrypted()");
+    
+    // decrypting
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(vault_key.key.as_slice()));
+    let plaintext_result = Schrod::from_option(cipher.decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice()).ok(), "The passphrase is incorrect, or the file has been damaged.", "save_engine::decrypted()");
+    if plaintext_result.is_fail() { return plaintext_result.convert("save_engine::decrypted()") }
+    let plaintext = plaintext_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::decrypted()");
+    Schrod::from_result(String::from_utf8(plaintext), "The decrypted data is not valid text.", "save_engine::decrypted()")
+}
+
+/// Returns the `EncryptedBundle` held by a file, if it is encrypted.
+#[must_use]
+fn encrypted_bundle_of(data: &str) -> Option<EncryptedBundle> {
+    serde_json::from_str::<EncryptedBundle>(data).ok()
+}
+
+/// Checks if the file at the given `Path` is encrypted.
+#[must_use]
+pub fn is_encrypted(path: &Path) -> bool {
+    std::fs::read_to_string(path).ok().is_some_and(|data| encrypted_bundle_of(&data).is_some())
+}
+
+/// Checks if the save file of a vault is encrypted, and so needs a passphrase to be loaded.
+#[must_use]
+pub fn is_vault_encrypted(vault_name: &str) -> bool {
+    match save_path(vault_name) {
+        Pass(path) if path.exists() => is_encrypted(&path),
+        Pass(path) => is_encrypted(&previous_save_path(&path)),
+        Fail(_) => false,
+    }
+}
+
+/// Re-encrypts the save file, the previous save, and every backup of a vault.
+/// Passing no new `VaultKey` removes the encryption.
+/// Backups are converted first and the save file last, so the vault stays openable with the old passphrase if anything fails.
+#[must_use]
+pub fn change_encryption(vault_name: &str, old_vault_key: Option<&VaultKey>, new_vault_key: Option<&VaultKey>) -> Schrod<()> {
+    // finding the files
+    let backups_result = list_backups(vault_name, None);
+    if backups_result.is_fail() {
+        return backups_result.convert("save_engine::change_encryption()").fail("Failed to change the passphrase.", "save_engine::change_encryption()")
+    }
+    let mut paths = backups_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::change_encryption()")
+        .into_iter()
+        .map(|backup| backup.path)
+        .collect::<Vec<_>>();
+    let save_path_result = save_path(vault_name);
+    if save_path_result.is_fail() {
+        return save_path_result.convert("save_engine::change_encryption()").fail("Failed to change the passphrase.", "save_engine::change_encryption()")
+    }
+    let save_path = save_path_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::change_encryption()");
+    paths.push(previous_save_path(&save_path));
+    paths.push(save_path);
+    
+    // converting the files
+    for path in paths.iter().filter(|path| path.exists()) {
+        let data_result = Schrod::from_result(std::fs::read_to_string(path), &format!("Failed to read {}.", path.display()), "save_engine::change_encryption()");
+        if data_result.is_fail() {
+            return data_result.convert("save_engine::change_encryption()").fail("Failed to change the passphrase.", "save_engine::change_encryption()")
+        }
+        let data = data_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::change_encryption()");
+        
+        let json_result = decrypted(&data, old_vault_key);
+        if json_result.is_fail() {
+            return json_result.convert("save_engine::change_encryption()").fail(&format!("Failed to decrypt {}.", path.display()), "save_engine::change_encryption()").fail("Failed to change the passphrase.", "save_engine::change_encryption()")
+        }
+        let json = json_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::change_encryption()");
+        let contents_result = match new_vault_key {
+            Some(new_vault_key) => encrypted(&json, new_vault_key),
+            None => Pass(json),
+        };
+        if contents_result.is_fail() {
+            return contents_result.convert("save_engine::change_encryption()").fail("Failed to change the passphrase.", "save_engine::change_encryption()")
+        }
+        let contents = contents_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::change_encryption()");
+        
+        let write_result = write_atomically(path, &contents);
+        if write_result.is_fail() { return write_result.fail("Failed to change the passphrase.", "save_engine::change_encryption()") }
+    }
+    
+    Pass(())
+}
+
+
+
+
+
+
+
+//====================================================================================================//
+// MIGRATION
+//====================================================================================================//
+/// Returns the format version of raw save data.
+/// Save files written before the format was versioned are treated as version 0.
+#[must_use]
+fn format_version_of(raw: &serde_json::Value) -> Schrod<u32> {
+    match raw.get("format_version") {
+        None => Pass(0),
+        Some(version) => {
+            let version_result = Schrod::from_option(version.as_u64().and_then(|v| u32::try_from(v).ok()), "The format version is not a valid number.", "save_engine::format_version_of()");
+            if version_result.is_fail() { return version_result.fail("Failed to read the format version.", "save_engine::format_version_of()") }
+            version_result
+        }
+    }
+}
+
+/// Returns whether the file at the given `Path` was written by a newer version of Ascent.
+/// Such a file is not damaged, so it must never be replaced by the previous save.
+#[must_use]
+fn is_from_newer_version(path: &Path, vault_key: Option<&VaultKey>) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|data| match decrypted(&data, vault_key) { Pass(json) => Some(json), Fail(_) => None })
+        .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        .is_some_and(|raw| matches!(format_version_of(&raw), Pass(version) if version > CURRENT_FORMAT_VERSION))
+}
+
+/// Upgrades raw save data one format version at a time until it reaches `CURRENT_FORMAT_VERSION`.
+/// Returns whether any upgrade was made.
+/// Save data from a newer version of Ascent is rejected, since loading it would silently drop what this version does not understand.
+#[must_use]
+fn migrate(raw: &mut serde_json::Value) -> Schrod<bool> {
+    let version_result = format_version_of(raw);
+    if version_result.is_fail() {
+        return version_result
+            .convert("save_engine::migrate()")
+            .fail("Failed to upgrade save data.", "save_engine::migrate()")
+    }
+    let mut version = version_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate()");
+    
+    // rejecting newer formats
+    if version > CURRENT_FORMAT_VERSION {
+        return Schrod::new_fail(&format!("This save data was written by a newer version of Ascent (format version {version}, but only up to {CURRENT_FORMAT_VERSION} is supported). Please update Ascent to load it."), "save_engine::migrate()")
+    }
+    
+    // upgrading step by step
+    let was_migrated = version < CURRENT_FORMAT_VERSION;
+    while version < CURRENT_FORMAT_VERSION {
+        let step_result = match version {
+            0 => migrate_v0_to_v1(raw),
+            1 => migrate_v1_to_v2(raw),
+            2 => migrate_v2_to_v3(raw),
+            3 => migrate_v3_to_v4(raw),
+            4 => migrate_v4_to_v5(raw),
+            5 => migrate_v5_to_v6(raw),
+            6 => migrate_v6_to_v7(raw),
+            7 => migrate_v7_to_v8(raw),
+            8 => migrate_v8_to_v9(raw),
+            9 => migrate_v9_to_v10(raw),
+            _ => Schrod::new_fail(&format!("There is no upgrade from format version {version}."), "save_engine::migrate()"),
+        };
+        if step_result.is_fail() {
+            return step_result
+                .convert("save_engine::migrate()")
+                .fail(&format!("Failed to upgrade save data from format version {version}."), "save_engine::migrate()")
+        }
+        version += 1;
+    }
+    
+    // stamping the new version
+    let root_result = Schrod::from_option(raw.as_object_mut(), "The save data is not a JSON object.", "save_engine::migrate()");
+    if root_result.is_fail() { return root_result.convert("save_engine::migrate()").fail("Failed to upgrade save data.", "save_engine::migrate()") }
+    let root = root_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate()");
+    root.insert("format_version".to_string(), serde_json::Value::from(version));
+    
+    Pass(was_migrated)
+}
+
+/// Upgrades format version 0 to 1.
+/// Version 1 added the `CurrencyExchange`, so save data without one gets the default of that version.
+#[must_use]
+fn migrate_v0_to_v1(raw: &mut serde_json::Value) -> Schrod<()> {
+    let root_result = Schrod::from_option(raw.as_object_mut(), "The save data is not a JSON object.", "save_engine::migrate_v0_to_v1()");
+    if root_result.is_fail() { return root_result.convert("save_engine::migrate_v0_to_v1()") }
+    let root = root_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v0_to_v1()");
+    
+    // later migrations upgrade the CurrencyExchange from this shape
+    if !root.contains_key("currency_exchange") {
+        let currency_exchange = serde_json::json!({ "main_currency_string": "USD", "time_price": "1", "flow_type": "Collected", "rates": [] });
+        root.insert("currency_exchange".to_string(), currency_exchange);
+    }
+    
+    Pass(())
+}
+
+/// Upgrades format version 1 to 2.
+/// Version 2 gave every `Transaction` a persistent `Uid`, so any `Transaction` without one gets a new one.
+#[must_use]
+fn migrate_v1_to_v2(raw: &mut serde_json::Value) -> Schrod<()> {
+    let bundles_result = Schrod::from_option(raw.get_mut("transaction_bundles").and_then(serde_json::Value::as_array_mut), "The save data has no transaction list.", "save_engine::migrate_v1_to_v2()");
+    if bundles_result.is_fail() { return bundles_result.convert("save_engine::migrate_v1_to_v2()") }
+    let bundles = bundles_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v1_to_v2()");
+    
+    for bundle in bundles {
+        let bundle_result = Schrod::from_option(bundle.as_object_mut(), "A transaction is not a JSON object.", "save_engine::migrate_v1_to_v2()");
+        if bundle_result.is_fail() { return bundle_result.convert("save_engine::migrate_v1_to_v2()") }
+        let bundle = bundle_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v1_to_v2()");
+        
+        if !bundle.contains_key("uid") {
+            bundle.insert("uid".to_string(), serde_json::Value::from(Uid::new_v4().to_string()));
+        }
+    }
+    
+    Pass(())
+}
+
+/// Upgrades format version 2 to 3.
+/// Version 3 added `RecurringTemplate`s, so save data without any gets an empty list.
+#[must_use]
+fn migrate_v2_to_v3(raw: &mut serde_json::Value) -> Schrod<()> {
+    let root_result = Schrod::from_option(raw.as_object_mut(), "The save data is not a JSON object.", "save_engine::migrate_v2_to_v3()");
+    if root_result.is_fail() { return root_result.convert("save_engine::migrate_v2_to_v3()") }
+    let root = root_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v2_to_v3()");
+    
+    if !root.contains_key("recurring_templates") {
+        root.insert("recurring_templates".to_string(), serde_json::Value::Array(Vec::new()));
+    }
+    
+    Pass(())
+}
+
+/// Upgrades format version 3 to 4.
+/// Version 4 added `Budget`s, so save data without any gets an empty list.
+#[must_use]
+fn migrate_v3_to_v4(raw: &mut serde_json::Value) -> Schrod<()> {
+    let root_result = Schrod::from_option(raw.as_object_mut(), "The save data is not a JSON object.", "save_engine::migrate_v3_to_v4()");
+    if root_result.is_fail() { return root_result.convert("save_engine::migrate_v3_to_v4()") }
+    let root = root_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v3_to_v4()");
+    
+    if !root.contains_key("budgets") {
+        root.insert("budgets".to_string(), serde_json::Value::Array(Vec::new()));
+    }
+    
+    Pass(())
+}
+
+/// Upgrades format version 4 to 5.
+/// Version 5 added `Split`s to `Transaction`s. They are optional, so nothing needs to change,
+/// but the bump makes older versions of Ascent reject the save data instead of dropping the `Split`s.
+#[must_use]
+fn migrate_v4_to_v5(_raw: &mut serde_json::Value) -> Schrod<()> {
+    Pass(())
+}
+
+/// Upgrades format version 5 to 6.
+/// Version 6 added `TagRule`s, so save data without any gets an empty list.
+#[must_use]
+fn migrate_v5_to_v6(raw: &mut serde_json::Value) -> Schrod<()> {
+    let root_result = Schrod::from_option(raw.as_object_mut(), "The save data is not a JSON object.", "save_engine::migrate_v5_to_v6()");
+    if root_result.is_fail() { return root_result.convert("save_engine::migrate_v5_to_v6()") }
+    let root = root_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v5_to_v6()");
+    
+    if !root.contains_key("tag_rules") {
+        root.insert("tag_rules".to_string(), serde_json::Value::Array(Vec::new()));
+    }
+    
+    Pass(())
+}
+
+/// Upgrades format version 6 to 7.
+/// Version 7 added `Account`s, so every `Transaction` is placed in a new default `Account` in the main currency.
+#[must_use]
+fn migrate_v6_to_v7(raw: &mut serde_json::Value) -> Schrod<()> {
+    // the main currency, which the default account keeps its balance in.
+    // only the currency is read, since the rest of the currency exchange is upgraded by later steps
+    let main_currency_result = Schrod::from_option(
+        raw.get("currency_exchange")
+            .and_then(|currency_exchange| currency_exchange.get("main_currency_string"))
+            .and_then(serde_json::Value::as_str)
+            .and_then(iso::find),
+        "The save data has no readable main currency.",
+        "save_engine::migrate_v6_to_v7()",
+    );
+    if main_currency_result.is_fail() { return main_currency_result.convert("save_engine::migrate_v6_to_v7()") }
+    let main_currency = main_currency_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v6_to_v7()");
+    let account = Account::default_for(main_currency);
+    
+    let bundles_result = Schrod::from_option(raw.get_mut("transaction_bundles").and_then(serde_json::Value::as_array_mut), "The save data has no transaction list.", "save_engine::migrate_v6_to_v7()");
+    if bundles_result.is_fail() { return bundles_result.convert("save_engine::migrate_v6_to_v7()") }
+    let bundles = bundles_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v6_to_v7()");
+    for bundle in bundles {
+        let bundle_result = Schrod::from_option(bundle.as_object_mut(), "A transaction is not a JSON object.", "save_engine::migrate_v6_to_v7()");
+        if bundle_result.is_fail() { return bundle_result.convert("save_engine::migrate_v6_to_v7()") }
+        let bundle = bundle_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v6_to_v7()");
+        bundle.insert("account_uid".to_string(), serde_json::Value::from(account.get_uid().to_string()));
+    }
+    
+    let account_result = Schrod::from_result(serde_json::to_value(vec![account]), "Failed to serialize the default Account.", "save_engine::migrate_v6_to_v7()");
+    if account_result.is_fail() { return account_result.convert("save_engine::migrate_v6_to_v7()") }
+    let root_result = Schrod::from_option(raw.as_object_mut(), "The save data is not a JSON object.", "save_engine::migrate_v6_to_v7()");
+    if root_result.is_fail() { return root_result.convert("save_engine::migrate_v6_to_v7()") }
+    let root = root_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v6_to_v7()");
+    root.insert("accounts".to_string(), account_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v6_to_v7()"));
+    
+    Pass(())
+}
+
+/// Upgrades format version 7 to 8.
+/// Version 8 gave every `ExchangeRate` a dated history, so each saved rate becomes the only rate in its history, starting on the `Date` it was set.
+/// Rates that were never set become empty histories.
+#[must_use]
+fn migrate_v7_to_v8(raw: &mut serde_json::Value) -> Schrod<()> {
+    let rates_result = Schrod::from_option(
+        raw.get_mut("currency_exchange").and_then(|currency_exchange| currency_exchange.get_mut("rates")).and_then(serde_json::Value::as_array_mut),
+        "The save data has no exchange rate list.",
+        "save_engine::migrate_v7_to_v8()",
+    );
+    if rates_result.is_fail() { return rates_result.convert("save_engine::migrate_v7_to_v8()") }
+    let rates = rates_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v7_to_v8()");
+    
+    for rate in rates {
+        let rate_result = Schrod::from_option(rate.as_object_mut(), "An exchange rate is not a JSON object.", "save_engine::migrate_v7_to_v8()");
+        if rate_result.is_fail() { return rate_result.convert("save_engine::migrate_v7_to_v8()") }
+        let rate = rate_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v7_to_v8()");
+        
+        let multiplier = rate.remove("rate").and_then(|multiplier| serde_json::from_value::<Decimal>(multiplier).ok()).unwrap_or_default();
+        let date = rate.remove("date");
+        let history = match date {
+            Some(date) if multiplier > Decimal::ZERO => vec![serde_json::json!({ "date": date, "rate": multiplier })],
+            _ => Vec::new(),
+        };
+        rate.insert("history".to_string(), serde_json::Value::Array(history));
+    }
+    
+    Pass(())
+}
+
+/// Upgrades format version 8 to 9.
+/// Version 9 replaced the single time price with a dated history of time prices.
+/// The old time price is dated to the oldest `Transaction`, so that it keeps applying to every one of them.
+/// Without any `Transaction`s it is given the default `Date`, since the oldest time price applies to every earlier date anyway.
+#[must_use]
+fn migrate_v8_to_v9(raw: &mut serde_json::Value) -> Schrod<()> {
+    let oldest_date = raw.get("transaction_bundles")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|bundle| bundle.get("date").and_then(|date| serde_json::from_value::<Date>(date.clone()).ok()))
+        .min_by_key(Date::as_value);
+
+    let currency_exchange_result = Schrod::from_option(
+        raw.get_mut("currency_exchange").and_then(serde_json::Value::as_object_mut),
+        "The save data has no currency exchange.",
+        "save_engine::migrate_v8_to_v9()",
+    );
+    if currency_exchange_result.is_fail() { return currency_exchange_result.convert("save_engine::migrate_v8_to_v9()") }
+    let currency_exchange = currency_exchange_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v8_to_v9()");
+
+    let price = currency_exchange.remove("time_price").and_then(|price| serde_json::from_value::<Decimal>(price).ok()).unwrap_or(Decimal::ONE);
+    let time_prices = if price > Decimal::ZERO {
+        vec![serde_json::json!({ "date": oldest_date.unwrap_or_default(), "price": price, "currency_string": null })]
+    }
+    else { Vec::new() };
+    currency_exchange.insert("time_prices".to_string(), serde_json::Value::Array(time_prices));
+
+    Pass(())
+}
+
+/// Upgrades format version 9 to 10.
+/// Version 10 gave every `RecurringTemplate` an `Account` and `Split`s, so each template is placed in the first `Account`, where its `Transaction`s used to go.
+/// `Split`s are optional, so templates are left without any. Without any `Account`s, the templates are placed once the default `Account` is made.
+#[must_use]
+fn migrate_v9_to_v10(raw: &mut serde_json::Value) -> Schrod<()> {
+    let Some(account_uid) = raw.get("accounts")
+        .and_then(|accounts| accounts.get(0))
+        .and_then(|account| account.get("uid"))
+        .cloned() else { return Pass(()) };
+
+    let templates_result = Schrod::from_option(raw.get_mut("recurring_templates").and_then(serde_json::Value::as_array_mut), "The save data has no recurring transaction list.", "save_engine::migrate_v9_to_v10()");
+    if templates_result.is_fail() { return templates_result.convert("save_engine::migrate_v9_to_v10()") }
+    let templates = templates_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v9_to_v10()");
+    for template in templates {
+        let template_result = Schrod::from_option(template.as_object_mut(), "A recurring transaction is not a JSON object.", "save_engine::migrate_v9_to_v10()");
+        if template_result.is_fail() { return template_result.convert("save_engine::migrate_v9_to_v10()") }
+        let template = template_result.wont_fail("This is past an is_fail() guard clause.", "save_engine::migrate_v9_to_v10()");
+        template.insert("account_uid".to_string(), account_uid.clone());
+    }
+
+    Pass(())
+}
+
+
+
 //====================================================================================================//
 // LEGACY
 //====================================================================================================//
@@ -404,15 +1523,26 @@ pub mod legacy {
                 .fail("Failed to load legacy transactions.", "LegacyTransactionDataBundle::load_legacy_from()")
         }
         let data = data_result.wont_fail("Past is_fail() guard clause.", "LegacyTransactionDataBundle::load_legacy_from()");
+        
+        // parsing the data
+        let transactions_result = parse_legacy(&data);
+        if transactions_result.is_fail() {
+            return transactions_result.fail("Failed to load legacy transactions.", "LegacyTransactionDataBundle::load_legacy_from()")
+        }
+        transactions_result
+    }
     
+    /// Parses legacy `Transaction`s from a JSON `String`.
+    #[must_use]
+    pub fn parse_legacy(data: &str) -> Schrod<Vec<Transaction>> {
         // deserializing the data into bundles
-        let bundles_result: Schrod<Vec<LegacyTransactionDataBundle>> = Schrod::from_result(serde_json::from_str(&data), "Failed to deserialize legacy transaction data.", "LegacyTransactionDataBundle::load_legacy_from()");
+        let bundles_result: Schrod<Vec<LegacyTransactionDataBundle>> = Schrod::from_result(serde_json::from_str(data), "Failed to deserialize legacy transaction data.", "LegacyTransactionDataBundle::parse_legacy()");
         if bundles_result.is_fail() {
             return bundles_result
-                .convert("LegacyTransactionDataBundle::load_legacy_from()")
-                .fail("Failed to load legacy transactions.", "LegacyTransactionDataBundle::load_legacy_from()")
+                .convert("LegacyTransactionDataBundle::parse_legacy()")
+                .fail("Failed to load legacy transactions.", "LegacyTransactionDataBundle::parse_legacy()")
         }
-        let bundles = bundles_result.wont_fail("This is past an is_fail() guard clause.", "LegacyTransactionDataBundle::load_legacy_from()");
+        let bundles = bundles_result.wont_fail("This is past an is_fail() guard clause.", "LegacyTransactionDataBundle::parse_legacy()");
         
         // converting bundles into transactions
         let mut transactions = Vec::new();
@@ -420,13 +1550,377 @@ pub mod legacy {
             let transaction_result = bundle.into_transaction();
             if transaction_result.is_fail() {
                 return transaction_result
-                    .convert("LegacyTransactionDataBundle::load_legacy_from()")
-                    .fail("Failed to load legacy transactions.", "LegacyTransactionDataBundle::load_legacy_from()")
+                    .convert("LegacyTransactionDataBundle::parse_legacy()")
+                    .fail("Failed to load legacy transactions.", "LegacyTransactionDataBundle::parse_legacy()")
             }
-            transactions.push(transaction_result.wont_fail("This is past an is_fail() guard clause.", "LegacyTransactionDataBundle::load_legacy_from()"));
+            transactions.push(transaction_result.wont_fail("This is past an is_fail() guard clause.", "LegacyTransactionDataBundle::parse_legacy()"));
         }
         
         // returning the transactions
         Pass(transactions)
     }
-}
\ No newline at end of file
+}
+
+
+
+
+
+
+//====================================================================================================//
+// TESTS
+//====================================================================================================//
+#[cfg(test)]
+mod tests {
+    use super::{CURRENT_FORMAT_VERSION, RetentionPolicy, SaveData, VaultKey, decrypted, get_serialized_save_data, is_encrypted, is_vault_name_valid, load_or_recover, parse_save_data, previous_save_path, save_to, legacy::parse_legacy, write_atomically};
+    use crate::vault::account::AccountTypes;
+    use crate::vault::recurring::RecurrenceRules;
+    use crate::vault::trend_parse::Intervals;
+    use crate::vault::test_helpers::tag;
+    use crate::vault::transaction::{Transaction, Uid};
+    use rust_decimal::Decimal;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+    use schrod::Schrod;
+    
+    const V0_ORIGINAL: &str = include_str!("../../tests/fixtures/save_data/v0_original.json");
+    const V1_CURRENCY_EXCHANGE: &str = include_str!("../../tests/fixtures/save_data/v1_currency_exchange.json");
+    const V2_UIDS: &str = include_str!("../../tests/fixtures/save_data/v2_uids.json");
+    const V3_RECURRING_TEMPLATES: &str = include_str!("../../tests/fixtures/save_data/v3_recurring_templates.json");
+    const V4_BUDGETS: &str = include_str!("../../tests/fixtures/save_data/v4_budgets.json");
+    const V5_SPLITS: &str = include_str!("../../tests/fixtures/save_data/v5_splits.json");
+    const V6_TAG_RULES: &str = include_str!("../../tests/fixtures/save_data/v6_tag_rules.json");
+    const V7_ACCOUNTS: &str = include_str!("../../tests/fixtures/save_data/v7_accounts.json");
+    const V8_RATE_HISTORY: &str = include_str!("../../tests/fixtures/save_data/v8_rate_history.json");
+    const V8_NO_TRANSACTIONS: &str = include_str!("../../tests/fixtures/save_data/v8_no_transactions.json");
+    const V9_TIME_PRICE_HISTORY: &str = include_str!("../../tests/fixtures/save_data/v9_time_price_history.json");
+    const V10_TEMPLATE_ACCOUNTS: &str = include_str!("../../tests/fixtures/save_data/v10_template_accounts.json");
+    const UNVERSIONED_UIDS: &str = include_str!("../../tests/fixtures/save_data/unversioned_uids.json");
+    const NEWER_VERSION: &str = include_str!("../../tests/fixtures/save_data/newer_version.json");
+    const LEGACY: &str = include_str!("../../tests/fixtures/save_data/legacy.txrctr");
+    
+    /// Returns a fresh save file `Path` inside an empty temporary directory.
+    fn temporary_save_path(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("ascent_test_{name}_{}", std::process::id()));
+        std::fs::remove_dir_all(&directory).ok();
+        std::fs::create_dir_all(&directory).ok();
+        directory.join("data.json")
+    }
+    
+    /// Checks whether any temporary files were left next to the save file.
+    fn has_temporary_files(save_path: &std::path::Path) -> bool {
+        let entries = save_path.parent().and_then(|directory| std::fs::read_dir(directory).ok());
+        entries.into_iter().flatten().flatten().any(|entry| entry.path().extension().is_some_and(|extension| extension == "tmp"))
+    }
+    
+    /// Parses save data that is expected to load.
+    fn parsed(data: &str) -> SaveData {
+        parse_save_data(data).wont_fail("The fixture should load.", "tests::parsed()")
+    }
+    
+    #[test]
+    fn v0_gets_default_currency_exchange_and_uids() {
+        let save_data = parsed(V0_ORIGINAL);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.transactions.len(), 2);
+        assert_eq!(save_data.currency_exchange.get_main_currency().to_string(), "USD");
+        // the default time price of version 1 is carried through the later migrations
+        assert_eq!(save_data.currency_exchange.get_time_prices().len(), 1);
+        assert_eq!(save_data.currency_exchange.get_time_price(), Decimal::ONE);
+        assert_ne!(save_data.transactions[0].get_uid(), save_data.transactions[1].get_uid());
+        assert_eq!(save_data.transactions[1].tags, vec![tag("income"), tag("work")]);
+    }
+    
+    #[test]
+    fn v1_keeps_currency_exchange_and_statement_ids() {
+        let save_data = parsed(V1_CURRENCY_EXCHANGE);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.currency_exchange.get_main_currency().to_string(), "EUR");
+        assert_eq!(save_data.transactions[0].get_statement_id(), None);
+        assert_eq!(save_data.transactions[1].get_statement_id(), Some("12345/20250601-1"));
+        
+        // the single saved rate starts the history on the day it was set
+        let rate = Schrod::from_option(save_data.currency_exchange.get("USD", "EUR"), "The rate was saved.", "tests").wont_fail("The rate was saved.", "tests");
+        assert_eq!(rate.get_history().len(), 1);
+        assert_eq!(rate.get_date().map(|date| date.display_iso()), Some("2025-06-01".to_string()));
+        assert_eq!(rate.get_rate().to_string(), "0.92");
+    }
+    
+    #[test]
+    fn v2_keeps_uids_and_gets_no_recurring_templates() {
+        let save_data = parsed(V2_UIDS);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.transactions[0].get_uid(), Uid::from_str("0f5ad335-812f-419f-90cf-221b9aae2a54").unwrap_or_default());
+        assert!(save_data.recurring_templates.is_empty());
+    }
+    
+    #[test]
+    fn v3_keeps_recurring_templates_and_gets_no_budgets() {
+        let save_data = parsed(V3_RECURRING_TEMPLATES);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.recurring_templates.len(), 1);
+        assert_eq!(save_data.recurring_templates[0].rule, RecurrenceRules::Monthly(31));
+        assert_eq!(save_data.recurring_templates[0].get_next_date().display_iso(), "2026-02-28");
+        assert!(save_data.budgets.is_empty());
+    }
+    
+    #[test]
+    fn v4_keeps_budgets_and_gets_no_splits() {
+        let save_data = parsed(V4_BUDGETS);
+        assert!(save_data.was_migrated);
+        assert!(save_data.transactions[0].get_splits().is_empty());
+        assert_eq!(save_data.budgets.len(), 1);
+        assert_eq!(save_data.budgets[0].interval, Intervals::Monthly);
+        assert_eq!(save_data.budgets[0].display_tags(), "dining, groceries");
+        assert_eq!(save_data.budgets[0].get_currency_string(), "EUR");
+    }
+    
+    #[test]
+    fn v5_keeps_splits_and_gets_no_tag_rules() {
+        let save_data = parsed(V5_SPLITS);
+        assert!(save_data.was_migrated);
+        assert!(save_data.tag_rules.is_empty());
+        let splits = save_data.transactions[0].get_splits();
+        assert_eq!(splits.len(), 1);
+        assert_eq!(splits[0].amount.to_string(), "-12.50");
+        assert_eq!(splits[0].note.as_deref(), Some("Cleaning supplies"));
+        assert_eq!(save_data.transactions[0].get_remaining_amount().to_string(), "-71.70");
+    }
+    
+    #[test]
+    fn v6_keeps_tag_rules_and_gets_a_default_account() {
+        let save_data = parsed(V6_TAG_RULES);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.tag_rules.len(), 2);
+        assert_eq!(save_data.tag_rules[0].display_conditions(), "description contains \"SHELL\"");
+        assert_eq!(save_data.tag_rules[0].display_tags(), "car, gas");
+        assert_eq!(save_data.tag_rules[1].display_conditions(), "value < -1000 and currency is EUR");
+        assert!(save_data.tag_rules[1].matches(&save_data.transactions[0]));
+        
+        // every transaction is placed in one account in the main currency
+        assert_eq!(save_data.accounts.len(), 1);
+        assert_eq!(save_data.accounts[0].get_currency_string(), "EUR");
+        assert!(save_data.transactions.iter().all(|t| t.get_account_uid() == save_data.accounts[0].get_uid()));
+    }
+    
+    #[test]
+    fn v7_keeps_accounts_and_gets_rate_histories() {
+        let save_data = parsed(V7_ACCOUNTS);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.accounts.len(), 2);
+        assert_eq!(save_data.accounts[1].name, "Rainy Day");
+        assert_eq!(save_data.accounts[1].account_type, AccountTypes::Savings);
+        assert_eq!(save_data.transactions[0].get_account_uid(), save_data.accounts[0].get_uid());
+        assert_eq!(save_data.transactions[0].get_transfer_uid(), Some(save_data.transactions[1].get_uid()));
+        assert!(save_data.transactions.iter().all(|t| t.is_transfer()));
+        assert!(save_data.currency_exchange.get_rates().is_empty());
+    }
+    
+    #[test]
+    fn v8_keeps_rate_histories_and_gets_time_price_histories() {
+        let save_data = parsed(V8_RATE_HISTORY);
+        assert!(save_data.was_migrated);
+        let rate = Schrod::from_option(save_data.currency_exchange.get("USD", "EUR"), "The rate was saved.", "tests").wont_fail("The rate was saved.", "tests");
+        assert_eq!(rate.get_history().len(), 2);
+        assert_eq!(rate.get_rate_on(save_data.transactions[1].date).unwrap_or_default().to_string(), "0.87");
+        assert_eq!(rate.get_rate_on(save_data.transactions[0].date).unwrap_or_default().to_string(), "0.92");
+        assert!(save_data.currency_exchange.get("EUR", "USD").is_some_and(|rate| rate.get_history().is_empty()));
+
+        // the old time price applies from the oldest transaction onwards
+        let time_prices = save_data.currency_exchange.get_time_prices();
+        assert_eq!(time_prices.len(), 1);
+        assert_eq!(time_prices[0].date, save_data.transactions[1].date);
+        assert_eq!(time_prices[0].price.to_string(), "20");
+        assert_eq!(time_prices[0].currency_string, None);
+    }
+    
+    #[test]
+    fn v8_without_transactions_keeps_its_time_price() {
+        let save_data = parsed(V8_NO_TRANSACTIONS);
+        assert!(save_data.was_migrated);
+        assert!(save_data.transactions.is_empty());
+        let time_prices = save_data.currency_exchange.get_time_prices();
+        assert_eq!(time_prices.len(), 1);
+        assert_eq!(time_prices[0].price.to_string(), "15.50");
+        assert_eq!(save_data.currency_exchange.get_time_price().to_string(), "15.50");
+    }
+    
+    #[test]
+    fn v9_keeps_time_price_histories_and_gets_template_accounts() {
+        let save_data = parsed(V9_TIME_PRICE_HISTORY);
+        assert!(save_data.was_migrated);
+        let currency_exchange = &save_data.currency_exchange;
+        assert_eq!(currency_exchange.get_time_prices().len(), 2);
+        assert_eq!(currency_exchange.get_time_price_currency().to_string(), "USD");
+
+        // each purchase is priced at the wage of its own time, in the currency the wage was earned in
+        let time_price_of = |transaction: &Transaction| currency_exchange.as_time_price(&transaction.value, transaction.date).wont_fail("The rates exist.", "tests").round_dp(2).to_string();
+        assert_eq!(time_price_of(&save_data.transactions[1]), "-4.35");
+        assert_eq!(time_price_of(&save_data.transactions[0]), "-3.33");
+
+        // the recurring transaction goes into the first account, as it always did
+        let template = &save_data.recurring_templates[0];
+        assert_eq!(template.get_account_uid(), save_data.accounts[0].get_uid());
+        assert!(template.get_splits().is_empty());
+    }
+    
+    #[test]
+    fn v10_loads_without_migrating() {
+        let save_data = parsed(V10_TEMPLATE_ACCOUNTS);
+        assert!(!save_data.was_migrated);
+        let template = &save_data.recurring_templates[0];
+        assert_eq!(template.get_account_uid(), save_data.accounts[1].get_uid());
+        assert_eq!(template.get_splits().len(), 1);
+        assert_eq!(template.get_splits()[0].note.as_deref(), Some("Work phone"));
+    }
+    
+    #[test]
+    fn unversioned_uids_are_kept() {
+        let save_data = parsed(UNVERSIONED_UIDS);
+        assert!(save_data.was_migrated);
+        assert_eq!(save_data.transactions[0].get_uid(), Uid::from_str("6b1f3f0e-2c1e-4d9a-9a57-3f2f8d1c7e21").unwrap_or_default());
+    }
+    
+    #[test]
+    fn newer_versions_are_rejected() {
+        let result = parse_save_data(NEWER_VERSION);
+        assert!(result.is_fail());
+        assert!(result.results().iter().any(|message| message.contains("newer version")));
+    }
+    
+    #[test]
+    fn migrated_data_round_trips_at_current_version() {
+        let save_data = parsed(V0_ORIGINAL);
+        let uids = save_data.transactions.iter().map(|t| t.get_uid()).collect::<Vec<_>>();
+        let json = get_serialized_save_data(save_data, None).wont_fail("Serializing should not fail.", "tests::migrated_data_round_trips_at_current_version()");
+        assert!(json.contains(&format!("\"format_version\": {CURRENT_FORMAT_VERSION}")));
+        
+        let reloaded = parsed(&json);
+        assert!(!reloaded.was_migrated);
+        assert_eq!(reloaded.transactions.iter().map(|t| t.get_uid()).collect::<Vec<_>>(), uids);
+    }
+    
+    #[test]
+    fn legacy_transactions_load() {
+        let transactions = parse_legacy(LEGACY).wont_fail("The fixture should load.", "tests::legacy_transactions_load()");
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].description, "Weekly groceries");
+        assert_eq!(transactions[0].tags.len(), 2);
+        assert_eq!(transactions[1].value.currency().to_string(), "CAD");
+    }
+    
+    #[test]
+    fn saving_keeps_the_previous_save() {
+        let save_path = temporary_save_path("keeps_previous");
+        assert!(save_to(&save_path, V0_ORIGINAL).is_pass());
+        assert!(save_to(&save_path, V2_UIDS).is_pass());
+        assert_eq!(std::fs::read_to_string(&save_path).unwrap_or_default(), V2_UIDS);
+        assert_eq!(std::fs::read_to_string(previous_save_path(&save_path)).unwrap_or_default(), V0_ORIGINAL);
+        assert!(!has_temporary_files(&save_path));
+    }
+    
+    #[test]
+    fn overlapping_writes_do_not_share_a_temporary_file() {
+        let save_path = temporary_save_path("overlapping_writes");
+        let writers = [V0_ORIGINAL, V2_UIDS, V7_ACCOUNTS, V9_TIME_PRICE_HISTORY].map(|contents| {
+            let save_path = save_path.clone();
+            std::thread::spawn(move || (0..10).all(|_| write_atomically(&save_path, contents).is_pass()))
+        });
+        assert!(writers.into_iter().all(|writer| writer.join().unwrap_or_default()));
+        
+        // whichever write finished last, the file holds all of it
+        let contents = std::fs::read_to_string(&save_path).unwrap_or_default();
+        assert!([V0_ORIGINAL, V2_UIDS, V7_ACCOUNTS, V9_TIME_PRICE_HISTORY].contains(&contents.as_str()));
+        assert!(!has_temporary_files(&save_path));
+    }
+    
+    #[test]
+    fn damaged_save_falls_back_to_the_previous_save() {
+        let save_path = temporary_save_path("falls_back");
+        assert!(save_to(&save_path, V2_UIDS).is_pass());
+        assert!(save_to(&save_path, "{ \"theme\": \"Midn").is_pass());
+        
+        let save_data = load_or_recover(&save_path, None).wont_fail("The previous save should load.", "tests::damaged_save_falls_back_to_the_previous_save()");
+        assert!(save_data.recovery_notice.is_some());
+        assert_eq!(save_data.transactions.len(), 1);
+        assert_eq!(std::fs::read_to_string(&save_path).unwrap_or_default(), V2_UIDS);
+        assert!(save_path.with_extension("json.damaged").exists());
+    }
+    
+    #[test]
+    fn newer_save_is_not_replaced_by_the_previous_save() {
+        let save_path = temporary_save_path("newer_not_replaced");
+        assert!(save_to(&save_path, V2_UIDS).is_pass());
+        assert!(save_to(&save_path, NEWER_VERSION).is_pass());
+        
+        assert!(load_or_recover(&save_path, None).is_fail());
+        assert_eq!(std::fs::read_to_string(&save_path).unwrap_or_default(), NEWER_VERSION);
+    }
+    
+    #[test]
+    fn vault_names_must_be_usable_as_file_names() {
+        assert!(is_vault_name_valid("Household"));
+        assert!(is_vault_name_valid("Business 2026_q1-draft"));
+        assert!(!is_vault_name_valid(""));
+        assert!(!is_vault_name_valid(" Household"));
+        assert!(!is_vault_name_valid("../Household"));
+        assert!(!is_vault_name_valid("House.hold"));
+    }
+    
+    #[test]
+    fn retention_keeps_the_newest_backup_of_each_period() {
+        // one backup every 12 hours for 400 days
+        let start = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap_or_default().and_hms_opt(9, 0, 0).unwrap_or_default();
+        let times = (0..800).map(|i| start + chrono::Duration::hours(12 * i)).collect::<Vec<_>>();
+        let kept = RetentionPolicy::default().kept(&times);
+        
+        // the newest backup is always kept, and the total never exceeds the policy
+        assert!(kept[799]);
+        assert!(!kept[798]);
+        assert!(kept.iter().filter(|k| **k).count() <= 7 + 4 + 12);
+        
+        // the 7 newest days are each kept once
+        let kept_in_last_week = (786..800).filter(|&i| kept[i]).count();
+        assert_eq!(kept_in_last_week, 7);
+        
+        // the oldest backups are removed
+        assert!(!kept[0]);
+    }
+    
+    #[test]
+    fn retention_keeps_everything_when_there_are_few_backups() {
+        let start = chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap_or_default().and_hms_opt(9, 0, 0).unwrap_or_default();
+        let times = (0..3).map(|i| start + chrono::Duration::days(i)).collect::<Vec<_>>();
+        assert_eq!(RetentionPolicy::default().kept(&times), vec![true, true, true]);
+    }
+    
+    #[test]
+    fn encrypted_save_data_needs_the_right_passphrase() {
+        let vault_key = VaultKey::new("correct horse battery staple").wont_fail("Deriving a key should not fail.", "tests::encrypted_save_data_needs_the_right_passphrase()");
+        let wrong_vault_key = VaultKey::new("incorrect horse").wont_fail("Deriving a key should not fail.", "tests::encrypted_save_data_needs_the_right_passphrase()");
+        let encrypted_json = get_serialized_save_data(parsed(V2_UIDS), Some(&vault_key)).wont_fail("Encrypting should not fail.", "tests::encrypted_save_data_needs_the_right_passphrase()");
+        assert!(!encrypted_json.contains("transaction_bundles"));
+        
+        assert!(decrypted(&encrypted_json, None).is_fail());
+        assert!(decrypted(&encrypted_json, Some(&wrong_vault_key)).is_fail());
+        assert!(vault_key.has_passphrase("correct horse battery staple"));
+        assert!(!vault_key.has_passphrase("incorrect horse"));
+        let json = decrypted(&encrypted_json, Some(&vault_key)).wont_fail("The right passphrase should decrypt.", "tests::encrypted_save_data_needs_the_right_passphrase()");
+        assert_eq!(parsed(&json).transactions.len(), parsed(V2_UIDS).transactions.len());
+        
+        // unencrypted data is read as it is, with or without a key
+        assert_eq!(decrypted(V2_UIDS, Some(&vault_key)).wont_fail("Plain data should pass through.", "tests::encrypted_save_data_needs_the_right_passphrase()"), V2_UIDS);
+    }
+    
+    #[test]
+    fn encrypted_save_files_load_with_their_key() {
+        let save_path = temporary_save_path("encrypted");
+        let vault_key = VaultKey::new("passphrase").wont_fail("Deriving a key should not fail.", "tests::encrypted_save_files_load_with_their_key()");
+        let encrypted_json = get_serialized_save_data(parsed(V2_UIDS), Some(&vault_key)).wont_fail("Encrypting should not fail.", "tests::encrypted_save_files_load_with_their_key()");
+        assert!(save_to(&save_path, &encrypted_json).is_pass());
+        assert!(is_encrypted(&save_path));
+        
+        assert!(load_or_recover(&save_path, None).is_fail());
+        assert!(load_or_recover(&save_path, Some(&vault_key)).is_pass());
+        assert!(save_path.exists() && !save_path.with_extension("json.damaged").exists());
+    }
+}
@@ -0,0 +1,218 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::vault::transaction::{Id, Tag, Transaction, Uid};
+use schrod::Schrod;
+use schrod::Schrod::Pass;
+
+/// A condition that a `Transaction` must meet to be tagged by a `TagRule`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RuleConditions {
+    /// The description contains the text, ignoring case.
+    DescriptionContains(String),
+    /// The value is less than the amount, in the `Transaction`'s own `Currency`.
+    ValueBelow(Decimal),
+    /// The value is greater than the amount, in the `Transaction`'s own `Currency`.
+    ValueAbove(Decimal),
+    /// The `Currency` has the given code.
+    CurrencyIs(String),
+}
+impl RuleConditions {
+    // data retrieval and parsing
+    /// Checks if a `Transaction` meets the condition.
+    #[must_use]
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match self {
+            RuleConditions::DescriptionContains(text) => { transaction.description.to_lowercase().contains(&text.to_lowercase()) }
+            RuleConditions::ValueBelow(amount) => { transaction.value.amount() < amount }
+            RuleConditions::ValueAbove(amount) => { transaction.value.amount() > amount }
+            RuleConditions::CurrencyIs(code) => { transaction.value.currency().iso_alpha_code.eq_ignore_ascii_case(code) }
+        }
+    }
+
+    /// Returns a short description of the condition.
+    #[must_use]
+    pub fn display(&self) -> String {
+        match self {
+            RuleConditions::DescriptionContains(text) => { format!("description contains \"{text}\"") }
+            RuleConditions::ValueBelow(amount) => { format!("value < {amount}") }
+            RuleConditions::ValueAbove(amount) => { format!("value > {amount}") }
+            RuleConditions::CurrencyIs(code) => { format!("currency is {code}") }
+        }
+    }
+}
+
+
+
+/// Adds `Tag`s to every `Transaction` that meets all of its conditions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagRule {
+    uid: Uid,
+    pub conditions: Vec<RuleConditions>,
+    /// The `Tag`s added to matching `Transaction`s.
+    pub tags: Vec<Tag>,
+}
+impl TagRule {
+    // initializing
+    /// Creates a new `TagRule`.
+    #[must_use]
+    pub fn new(conditions: Vec<RuleConditions>, tags: Vec<Tag>) -> Schrod<TagRule> {
+        if conditions.is_empty() {
+            return Schrod::new_fail("A rule needs at least one condition!", "TagRule::new()")
+                .fail("Failed to create TagRule.", "TagRule::new()")
+        }
+        if !Transaction::are_tags_valid(&tags) {
+            return Schrod::new_fail("A rule needs at least one tag to add!", "TagRule::new()")
+                .fail("Failed to create TagRule.", "TagRule::new()")
+        }
+
+        Pass(TagRule { uid: Uuid::new_v4(), conditions, tags: Tag::sorted(&tags) })
+    }
+
+    /// Creates a new `TagRule` from the raw parts entered by the user.
+    /// Each empty part is left out of the conditions, and the tags are separated by commas.
+    #[must_use]
+    pub fn new_from_raw(description_string: &str, below_string: &str, above_string: &str, currency_string: &str, tags_string: &str) -> Schrod<TagRule> {
+        if !TagRule::are_raw_conditions_valid(below_string, above_string, currency_string) {
+            return Schrod::new_fail("Invalid conditions!", "TagRule::new_from_raw()")
+                .fail("Failed to create TagRule.", "TagRule::new_from_raw()")
+        }
+        let tags_result = Tag::list_from(tags_string);
+        if tags_result.is_fail() {
+            return tags_result
+                .convert("TagRule::new_from_raw()")
+                .fail("Failed to create TagRule.", "TagRule::new_from_raw()")
+        }
+        let tags = tags_result.wont_fail("This is past an is_fail() guard clause.", "TagRule::new_from_raw()");
+
+        let mut conditions = Vec::new();
+        if !description_string.trim().is_empty() { conditions.push(RuleConditions::DescriptionContains(description_string.trim().to_string())); }
+        if let Ok(amount) = Decimal::from_str(below_string.trim()) { conditions.push(RuleConditions::ValueBelow(amount)); }
+        if let Ok(amount) = Decimal::from_str(above_string.trim()) { conditions.push(RuleConditions::ValueAbove(amount)); }
+        if !currency_string.trim().is_empty() { conditions.push(RuleConditions::CurrencyIs(currency_string.trim().to_uppercase())); }
+
+        TagRule::new(conditions, tags)
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the `Uid`.
+    #[must_use]
+    pub fn get_uid(&self) -> Uid {
+        self.uid
+    }
+
+    /// Checks if a `Transaction` meets all of the conditions.
+    #[must_use]
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        self.conditions.iter().all(|condition| condition.matches(transaction))
+    }
+
+    /// Returns the conditions, joined into a single `String`.
+    #[must_use]
+    pub fn display_conditions(&self) -> String {
+        self.conditions.iter().map(RuleConditions::display).collect::<Vec<_>>().join(" and ")
+    }
+
+    /// Returns the labels of the `Tag`s, joined into a single `String`.
+    #[must_use]
+    pub fn display_tags(&self) -> String {
+        self.tags.iter().map(Tag::get_label).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Returns the `Tag`s that the given `TagRule`s would add to a `Transaction` that does not have them yet.
+    #[must_use]
+    pub fn get_added_tags(rules: &[TagRule], transaction: &Transaction) -> Vec<Tag> {
+        let added_tags = rules.iter()
+            .filter(|rule| rule.matches(transaction))
+            .flat_map(|rule| rule.tags.iter().cloned())
+            .filter(|tag| !transaction.tags.contains(tag))
+            .collect::<Vec<_>>();
+        Tag::sorted(&added_tags)
+    }
+
+    /// Adds the `Tag`s of every matching `TagRule` to a `Transaction`.
+    /// Returns whether any `Tag`s were added.
+    pub fn apply_all(rules: &[TagRule], transaction: &mut Transaction) -> bool {
+        let added_tags = TagRule::get_added_tags(rules, transaction);
+        if added_tags.is_empty() { return false }
+        transaction.tags = Tag::sorted(&[transaction.tags.clone(), added_tags].concat());
+        true
+    }
+
+
+
+    // validating
+    /// Checks if the optional value and currency conditions can be parsed.
+    /// Empty parts are allowed, since they are left out of the conditions.
+    #[must_use]
+    pub fn are_raw_conditions_valid(below_string: &str, above_string: &str, currency_string: &str) -> bool {
+        let is_amount_valid = |amount_string: &str| amount_string.trim().is_empty() || Decimal::from_str(amount_string.trim()).is_ok();
+        let is_currency_valid = currency_string.trim().is_empty() || Transaction::is_currency_string_valid(currency_string.trim());
+        is_amount_valid(below_string) && is_amount_valid(above_string) && is_currency_valid
+    }
+
+    /// Checks if a `TagRule` can be created from the given raw parts.
+    #[must_use]
+    pub fn are_raw_parts_valid(description_string: &str, below_string: &str, above_string: &str, currency_string: &str, tags_string: &str) -> bool {
+        let has_condition = [description_string, below_string, above_string, currency_string].iter().any(|part| !part.trim().is_empty());
+        let are_tags_valid = matches!(Tag::list_from(tags_string), Pass(tags) if Transaction::are_tags_valid(&tags));
+        has_condition && are_tags_valid && TagRule::are_raw_conditions_valid(below_string, above_string, currency_string)
+    }
+}
+
+
+
+/// A change that re-running the `TagRule`s would make to a `Transaction` in the ledger.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagRuleChange {
+    pub id: Id,
+    pub description: String,
+    pub added_tags: Vec<Tag>,
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{RuleConditions, TagRule};
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::{Months, Transaction};
+
+    fn imported(value: &str, currency: &str, description: &str) -> Transaction {
+        transaction(value, currency, date(2026, Months::March, 2), description, vec![tag("imported")])
+    }
+
+    #[test]
+    fn rules_need_every_condition_to_match() {
+        let rule = TagRule::new_from_raw("", "-1000", "", "eur", "travel").wont_fail("The rule is valid.", "tests");
+        assert_eq!(rule.conditions, vec![RuleConditions::ValueBelow("-1000".parse().unwrap_or_default()), RuleConditions::CurrencyIs("EUR".to_string())]);
+        assert!(rule.matches(&imported("-1200", "EUR", "Flights")));
+        assert!(!rule.matches(&imported("-1200", "USD", "Flights")));
+        assert!(!rule.matches(&imported("-800", "EUR", "Hotel")));
+
+        assert!(TagRule::new_from_raw("", "", "", "", "travel").is_fail());
+        assert!(TagRule::new_from_raw("shell", "", "", "", "").is_fail());
+    }
+
+    #[test]
+    fn rules_tag_new_transactions_and_preview_reruns() {
+        let mut bank = bank_with(vec![imported("-45", "USD", "SHELL 1234"), imported("-12", "USD", "Bakery")]);
+        bank.add_tag_rule(TagRule::new_from_raw("shell", "", "", "", "gas, car").wont_fail("The rule is valid.", "tests"));
+
+        // only the existing shell transaction changes, and nothing is applied until the rules are re-run
+        let changes = bank.get_tag_rule_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].added_tags, vec![tag("car"), tag("gas")]);
+        assert!(bank.get_tags().iter().all(|t| *t != tag("gas")));
+        assert!(matches!(bank.rerun_tag_rules(), schrod::Schrod::Pass(1)));
+        assert!(bank.get_tag_rule_changes().is_empty());
+
+        // new transactions are tagged as they are added
+        assert!(bank.add_transaction_from_raw_parts("-50", "USD", date(2026, Months::March, 9), "Shell Station".to_string(), vec![tag("imported")], Vec::new(), bank.get_default_account_uid()).is_pass());
+        let added = bank.get_ledger().iter().find(|t| t.description == "Shell Station").map(|t| t.tags.clone()).unwrap_or_default();
+        assert_eq!(added, vec![tag("car"), tag("gas"), tag("imported")]);
+    }
+}
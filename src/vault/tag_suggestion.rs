@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use crate::vault::bank::Bank;
+use crate::vault::transaction::Tag;
+
+/// The most `Tag`s suggested at once.
+pub const MAX_SUGGESTIONS: usize = 5;
+/// How much each past `Transaction` that shares a `Tag` with the one being written counts towards a suggestion.
+const CO_OCCURRENCE_WEIGHT: usize = 3;
+/// How much each word shared with the description of a past `Transaction` counts towards a suggestion.
+const DESCRIPTION_WEIGHT: usize = 5;
+/// The shortest word in a description that is compared to other descriptions.
+const MIN_WORD_LENGTH: usize = 3;
+
+/// How closely a `Tag` matches the text typed into the tag field.
+/// The order of the variants is the order in which matches are ranked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TextMatches {
+    /// The label, or one of its levels, starts with the text.
+    Prefix,
+    /// The label contains the text.
+    Contains,
+    /// The label contains every character of the text in order, which catches most typos.
+    Scattered,
+}
+impl TextMatches {
+    /// Returns how closely a `Tag` matches the typed text, if at all.
+    /// The text must already be lowercase and trimmed.
+    #[must_use]
+    fn of(tag: &Tag, text: &str) -> Option<TextMatches> {
+        let label = tag.get_label().to_lowercase();
+        if label.split(Tag::HIERARCHY_SEPARATOR).any(|level| level.starts_with(text)) { return Some(TextMatches::Prefix) }
+        if label.contains(text) { return Some(TextMatches::Contains) }
+        let mut label_characters = label.chars();
+        if text.chars().all(|character| label_characters.any(|c| c == character)) { return Some(TextMatches::Scattered) }
+        None
+    }
+}
+
+
+
+/// Ranked `Tag` suggestions for a `Transaction` that is being written, along with the one selected by keybind.
+#[derive(Debug, Clone, Default)]
+pub struct TagSuggestions {
+    suggestions: Vec<Tag>,
+    selected_option: Option<usize>,
+}
+impl TagSuggestions {
+    // initializing
+    /// Ranks the existing `Tag`s of a `Bank` as suggestions for a `Transaction`.
+    /// `Tag`s used often, used alongside the `Transaction`'s `Tag`s, or used on `Transaction`s with a similar description rank higher.
+    /// Without typed text, only `Tag`s that relate to the `Transaction`'s `Tag`s or description are suggested.
+    #[must_use]
+    pub fn new(bank: &Bank, typed_text: &str, current_tags: &[Tag], description: &str) -> TagSuggestions {
+        let typed_text = typed_text.trim().to_lowercase();
+        let description_words = TagSuggestions::words_of(description);
+
+        // scoring every tag on the past transactions
+        let mut frequencies: HashMap<Tag, usize> = HashMap::new();
+        let mut relations: HashMap<Tag, usize> = HashMap::new();
+        for transaction in bank.get_ledger() {
+            let tags = Tag::with_ancestors(&transaction.get_all_tags());
+            let co_occurrences = current_tags.iter().filter(|current_tag| transaction.has_tag(current_tag)).count();
+            let shared_words = TagSuggestions::words_of(&transaction.description).iter().filter(|word| description_words.contains(word)).count();
+            for tag in tags {
+                *frequencies.entry(tag.clone()).or_default() += 1;
+                let relation = co_occurrences * CO_OCCURRENCE_WEIGHT + shared_words * DESCRIPTION_WEIGHT;
+                if relation > 0 { *relations.entry(tag).or_default() += relation; }
+            }
+        }
+
+        // ranking the tags that are not on the transaction yet
+        let mut ranked = bank.get_tags().into_iter()
+            .filter(|tag| !current_tags.contains(tag))
+            .filter_map(|tag| {
+                let relation = relations.get(&tag).copied().unwrap_or_default();
+                let text_match = if typed_text.is_empty() {
+                    if relation == 0 { return None }
+                    TextMatches::Prefix
+                }
+                else { TextMatches::of(&tag, &typed_text)? };
+                let score = frequencies.get(&tag).copied().unwrap_or_default() + relation;
+                Some((text_match, score, tag))
+            })
+            .collect::<Vec<_>>();
+        ranked.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.get_label().cmp(&b.2.get_label())));
+
+        TagSuggestions {
+            suggestions: ranked.into_iter().take(MAX_SUGGESTIONS).map(|(_, _, tag)| tag).collect(),
+            selected_option: None,
+        }
+    }
+
+
+
+    // management
+    /// Selects the next suggestion, wrapping around to the first one.
+    pub fn select_next(&mut self) {
+        if self.suggestions.is_empty() { return }
+        self.selected_option = match self.selected_option {
+            Some(index) if index + 1 < self.suggestions.len() => Some(index + 1),
+            _ => Some(0),
+        };
+    }
+
+    /// Selects the previous suggestion, wrapping around to the last one.
+    pub fn select_previous(&mut self) {
+        if self.suggestions.is_empty() { return }
+        self.selected_option = match self.selected_option {
+            Some(index) if index > 0 => Some(index - 1),
+            _ => Some(self.suggestions.len() - 1),
+        };
+    }
+
+
+
+    // data retrieval and parsing
+    /// Returns the suggested `Tag`s, best first.
+    #[must_use]
+    pub fn get_all(&self) -> &[Tag] {
+        &self.suggestions
+    }
+
+    /// Returns the selected suggestion, if there is one.
+    #[must_use]
+    pub fn get_selected(&self) -> Option<&Tag> {
+        self.suggestions.get(self.selected_option?)
+    }
+
+    /// Returns the lowercase words of a description that are long enough to compare.
+    #[must_use]
+    fn words_of(description: &str) -> Vec<String> {
+        description
+            .split(|character: char| !character.is_alphanumeric())
+            .filter(|word| word.chars().count() >= MIN_WORD_LENGTH && !word.chars().all(|character| character.is_ascii_digit()))
+            .map(str::to_lowercase)
+            .collect()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::TagSuggestions;
+    use crate::vault::bank::Bank;
+    use crate::vault::test_helpers::{bank_with, date, tag, transaction};
+    use crate::vault::transaction::Months;
+
+    fn bank() -> Bank {
+        let may_4 = date(2026, Months::May, 4);
+        let tagged = |description: &str, labels: &[&str]| transaction("-20", "USD", may_4, description, labels.iter().map(|label| tag(label)).collect());
+        bank_with(vec![
+            tagged("Shell 4411", &["car", "gas"]),
+            tagged("Shell 4412", &["car", "gas"]),
+            tagged("Corner Market", &["groceries"]),
+            tagged("Corner Market", &["groceries"]),
+            tagged("Corner Market", &["groceries"]),
+            tagged("Garage", &["car", "repairs"]),
+        ])
+    }
+
+    #[test]
+    fn typed_text_matches_prefixes_before_typos() {
+        let bank = bank();
+        assert_eq!(TagSuggestions::new(&bank, "g", &[], "").get_all(), &[tag("groceries"), tag("gas")]);
+        // a typo still finds the tag it was meant to be
+        assert_eq!(TagSuggestions::new(&bank, "grocries", &[], "").get_all(), &[tag("groceries")]);
+        assert!(TagSuggestions::new(&bank, "xyz", &[], "").get_all().is_empty());
+    }
+
+    #[test]
+    fn related_tags_and_descriptions_rank_first() {
+        let bank = bank();
+        // gas is used less than groceries, but always alongside car
+        assert_eq!(TagSuggestions::new(&bank, "g", &[tag("car")], "").get_all(), &[tag("gas"), tag("groceries")]);
+        // a similar description is enough to suggest tags without any typed text
+        assert_eq!(TagSuggestions::new(&bank, "", &[], "SHELL 9000").get_all(), &[tag("car"), tag("gas")]);
+        assert!(TagSuggestions::new(&bank, "", &[], "Bakery").get_all().is_empty());
+
+        let mut suggestions = TagSuggestions::new(&bank, "", &[tag("car")], "");
+        assert_eq!(suggestions.get_all(), &[tag("gas"), tag("repairs")]);
+        suggestions.select_previous();
+        assert_eq!(suggestions.get_selected(), Some(&tag("repairs")));
+        suggestions.select_next();
+        assert_eq!(suggestions.get_selected(), Some(&tag("gas")));
+    }
+}
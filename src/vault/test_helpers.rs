@@ -0,0 +1,30 @@
+//! Shorthands shared by the tests of the `vault` modules.
+use crate::vault::bank::{Bank, CurrencyExchange, TagRegistry};
+use crate::vault::transaction::{Date, Months, Tag, Transaction};
+
+/// Creates a `Date` that is known to be valid.
+#[must_use]
+pub fn date(year: u32, month: Months, day: u32) -> Date {
+    Date::new(year, month, day).wont_fail("The test dates are valid.", "test_helpers::date()")
+}
+
+/// Creates a `Tag` that is known to be valid.
+#[must_use]
+pub fn tag(label: &str) -> Tag {
+    Tag::new(label).wont_fail("The test tags are valid.", "test_helpers::tag()")
+}
+
+/// Creates a `Transaction` without an `Id` from parts that are known to be valid.
+#[must_use]
+pub fn transaction(value: &str, currency: &str, date: Date, description: &str, tags: Vec<Tag>) -> Transaction {
+    Transaction::load_from_raw(value, currency, date, description.to_string(), tags).wont_fail("The test transactions are valid.", "test_helpers::transaction()")
+}
+
+/// Creates a `Bank` holding the given `Transaction`s and nothing else.
+#[must_use]
+pub fn bank_with(transactions: Vec<Transaction>) -> Bank {
+    let mut bank = Bank::default();
+    let init_result = bank.init(transactions, CurrencyExchange::default(), TagRegistry::default(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    assert!(init_result.is_pass());
+    bank
+}
@@ -7,6 +7,8 @@ use crate::vault::{bank::CurrencyExchange};
 use schrod::Schrod;
 use schrod::Schrod::{Pass, Fail};
 use std::hash::{Hash, Hasher};
+use std::iter;
+use uuid::Uuid;
 
 /// A custom type that helps to clarify how the `Money` object is used in a `Transaction` context.
 pub type Value = Money<'static, Currency>;
@@ -14,6 +16,9 @@ pub type Value = Money<'static, Currency>;
 /// Used in each `Transaction` instead of a `usize` for clarity.
 pub type Id = usize;
 
+/// Used in each `Transaction` as an identifier that is saved and stays the same across sessions.
+pub type Uid = Uuid;
+
 
 
 /// A list of styles for formatting `Tag` `label`s.
@@ -32,6 +37,9 @@ pub enum TagStyles {
 pub struct Transaction {
     /// The internal id.
     id: Option<Id>,
+    /// The persistent, globally unique id.
+    /// Unlike the internal id, this is saved and survives reindexing and imports.
+    uid: Uid,
     /// The positive or negative dollar value.
     pub value: Value,
     /// The date.
@@ -45,6 +53,17 @@ pub struct Transaction {
     /// ( gas, transportation, ... ),
     /// ...
     pub tags: Vec<Tag>,
+    /// The identifier the bank gave the `Transaction` if it was imported from a statement.
+    /// This is used to detect statements that are imported more than once.
+    statement_id: Option<String>,
+    /// Parts of the value that belong to their own `Tag`s instead of the `Transaction`'s.
+    /// Whatever is left over still belongs to the `Transaction`'s `Tag`s.
+    splits: Vec<Split>,
+    /// The `Uid` of the `Account` the `Transaction` belongs to.
+    /// New `Transaction`s start with the nil `Uid` and are placed in the default `Account` by the `Bank`.
+    account_uid: Uid,
+    /// The `Uid` of the matching `Transaction` in the other `Account` if this is one side of a transfer.
+    transfer_uid: Option<Uid>,
 }
 impl PartialEq for Transaction {
     fn eq(&self, other: &Self) -> bool {
@@ -59,7 +78,7 @@ impl Transaction {
     /// This is intended to be used when a new `Transaction` is created from within the `App`.
     #[must_use]
     pub fn new_from_parts(id: Id, value: Value, date: Date, description: String, tags: Vec<Tag>) -> Schrod<Transaction> {
-        if Transaction::are_parts_valid(&description, &tags) { Pass(Transaction { id: Some(id), value, date, description, tags }) }
+        if Transaction::are_parts_valid(&description, &tags) { Pass(Transaction { id: Some(id), uid: Uuid::new_v4(), value, date, description, tags, statement_id: None, splits: Vec::new(), account_uid: Uuid::nil(), transfer_uid: None }) }
         else {
             Schrod::new_fail("Invalid parts!", "Transaction::new_from_parts()")
                 .fail("Failed to create Transaction.", "Transaction::new_from_parts()")
@@ -79,7 +98,7 @@ impl Transaction {
         let currency_result = Schrod::from_option(iso::find(&currency_string.to_uppercase()), "Failed to convert currency_string to Currency.", "Transaction::new_from_raw()");
 
         if let (Pass(value), Pass(currency)) = (&decimal_value_result, &currency_result) {
-            Pass(Transaction { id: Some(id), value: Value::from_decimal(*value, currency), date, description, tags })
+            Pass(Transaction { id: Some(id), uid: Uuid::new_v4(), value: Value::from_decimal(*value, currency), date, description, tags, statement_id: None, splits: Vec::new(), account_uid: Uuid::nil(), transfer_uid: None })
         }
 
         else {
@@ -95,7 +114,7 @@ impl Transaction {
     /// Please note that if this function is used, an `Id` must be filled in later with `set_id()`.
     #[must_use]
     pub fn load_from_parts(value: Value, date: Date, description: String, tags: Vec<Tag>) -> Schrod<Transaction> {
-        if Transaction::are_parts_valid(&description, &tags) { Pass(Transaction { id: None, value, date, description, tags }) }
+        if Transaction::are_parts_valid(&description, &tags) { Pass(Transaction { id: None, uid: Uuid::new_v4(), value, date, description, tags, statement_id: None, splits: Vec::new(), account_uid: Uuid::nil(), transfer_uid: None }) }
         else {
             Schrod::new_fail("Invalid parts!", "Transaction::load_from_parts()")
                 .fail("Failed to load Transaction.", "Transaction::load_from_parts()")
@@ -116,7 +135,7 @@ impl Transaction {
         let currency_result = Schrod::from_option(iso::find(&currency_string.to_uppercase()), "Failed to convert currency_string to Currency.", "Transaction::load_from_raw()");
 
         if let (Pass(value), Pass(currency)) = (&decimal_value_result, &currency_result) {
-            Pass(Transaction { id: None, value: Value::from_decimal(*value, currency), date, description, tags })
+            Pass(Transaction { id: None, uid: Uuid::new_v4(), value: Value::from_decimal(*value, currency), date, description, tags, statement_id: None, splits: Vec::new(), account_uid: Uuid::nil(), transfer_uid: None })
         }
         
         else {
@@ -141,6 +160,49 @@ impl Transaction {
     pub fn override_id(&mut self, id: Id) {
         self.id = Some(id);
     }
+    
+    /// Sets the persistent `Uid`.
+    /// Used primarily for `Transaction`s that are loaded from `SaveData`.
+    pub fn set_uid(&mut self, uid: Uid) {
+        self.uid = uid;
+    }
+    
+    /// Sets the identifier the bank gave the `Transaction` on a statement.
+    pub fn set_statement_id(&mut self, statement_id: Option<String>) {
+        self.statement_id = statement_id;
+    }
+    
+    /// Moves the `Transaction` to another `Account`.
+    pub fn set_account_uid(&mut self, account_uid: Uid) {
+        self.account_uid = account_uid;
+    }
+    
+    /// Links the `Transaction` to the matching side of a transfer, or unlinks it.
+    pub fn set_transfer_uid(&mut self, transfer_uid: Option<Uid>) {
+        self.transfer_uid = transfer_uid;
+    }
+    
+    /// Replaces a `Tag` and its children with another `Tag` on the `Transaction` and its `Split`s.
+    /// Returns whether anything was changed.
+    pub fn replace_tag(&mut self, old_tag: &Tag, new_tag: &Tag) -> bool {
+        if !self.has_tag(old_tag) { return false }
+        self.tags = Tag::replaced_in(&self.tags, old_tag, new_tag);
+        for split in &mut self.splits {
+            split.tags = Tag::replaced_in(&split.tags, old_tag, new_tag);
+        }
+        true
+    }
+    
+    /// Replaces the `Split`s, as long as they fit within the value.
+    #[must_use]
+    pub fn set_splits(&mut self, splits: Vec<Split>) -> Schrod<()> {
+        if !Transaction::are_splits_valid(*self.value.amount(), &splits) {
+            return Schrod::new_fail("The splits must have the same sign as the value and cannot add up to more than it!", "Transaction::set_splits()")
+                .fail("Failed to split Transaction.", "Transaction::set_splits()")
+        }
+        self.splits = splits;
+        Pass(())
+    }
 
 
 
@@ -169,6 +231,21 @@ impl Transaction {
         Decimal::from_str(value_string).is_ok()
     }
 
+    /// Checks if the `Split`s have the same sign as the value and add up to no more than it.
+    #[must_use]
+    pub fn are_splits_valid(value_amount: Decimal, splits: &[Split]) -> bool {
+        let has_same_sign = |amount: Decimal| if value_amount.is_sign_negative() { amount.is_sign_negative() } else { amount.is_sign_positive() };
+        let split_sum: Decimal = splits.iter().map(|split| split.amount).sum();
+        splits.iter().all(|split| !split.amount.is_zero() && has_same_sign(split.amount))
+            && split_sum.abs() <= value_amount.abs()
+    }
+
+    /// Checks if the `Split`s fit within a value that has not been parsed yet.
+    #[must_use]
+    pub fn are_splits_valid_for_raw(value_string: &str, splits: &[Split]) -> bool {
+        Decimal::from_str(value_string).is_ok_and(|value_amount| Transaction::are_splits_valid(value_amount, splits))
+    }
+
     /// Returns whether a `String` can be parsed into a `Currency`.
     #[must_use]
     pub fn is_currency_string_valid(currency_string: &str) -> bool {
@@ -181,6 +258,19 @@ impl Transaction {
         Tag::is_allowed(description)
     }
 
+    /// Replaces any characters that are not allowed in a `description` with spaces.
+    /// This is used to clean up descriptions that come from external files.
+    #[must_use]
+    pub fn sanitized_description(input: &str) -> String {
+        input
+            .chars()
+            .map(|c| if Tag::is_allowed(&c.to_string()) { c } else { ' ' })
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// Determines if the given list of `Tag`s is valid.
     /// Every `Tag` in the list is already guaranteed to be valid.
     #[must_use]
@@ -241,6 +331,37 @@ impl Transaction {
         self.id
     }
     
+    /// Returns the persistent `Uid`.
+    #[must_use]
+    pub fn get_uid(&self) -> Uid {
+        self.uid
+    }
+    
+    /// Returns the identifier the bank gave the `Transaction` on a statement, if there is one.
+    #[must_use]
+    pub fn get_statement_id(&self) -> Option<&str> {
+        self.statement_id.as_deref()
+    }
+    
+    /// Returns the `Uid` of the `Account` the `Transaction` belongs to.
+    #[must_use]
+    pub fn get_account_uid(&self) -> Uid {
+        self.account_uid
+    }
+    
+    /// Returns the `Uid` of the matching side of the transfer, if the `Transaction` is part of one.
+    #[must_use]
+    pub fn get_transfer_uid(&self) -> Option<Uid> {
+        self.transfer_uid
+    }
+    
+    /// Checks if the `Transaction` moves money between two `Account`s.
+    /// Transfers are neither spending nor earning, so they are left out of all data parsing.
+    #[must_use]
+    pub fn is_transfer(&self) -> bool {
+        self.transfer_uid.is_some()
+    }
+    
     /// Returns a mutable reference to the `Transaction` with the given `Id`.
     #[must_use]
     pub fn get_from(transactions: &mut [Transaction], id: Id) -> Schrod<&mut Transaction> {
@@ -255,10 +376,68 @@ impl Transaction {
         }
     }
 
-    /// Returns if the `Transaction` contains the given `Tag`.
+    /// Returns the `Split`s.
+    #[must_use]
+    pub fn get_splits(&self) -> &[Split] {
+        &self.splits
+    }
+    
+    /// Returns the part of the value that is not split off, which belongs to the `Transaction`'s own `Tag`s.
+    #[must_use]
+    pub fn get_remaining_amount(&self) -> Decimal {
+        self.value.amount() - self.splits.iter().map(|split| split.amount).sum::<Decimal>()
+    }
+    
+    /// Returns the `Tag`s of the `Transaction` along with those of its `Split`s.
+    #[must_use]
+    pub fn get_all_tags(&self) -> Vec<Tag> {
+        Tag::sorted(&self.tags.iter().chain(self.splits.iter().flat_map(|split| &split.tags)).cloned().collect::<Vec<_>>())
+    }
+    
+    /// Returns if the `Transaction` or any of its `Split`s has the given `Tag` or one of its children.
     #[must_use]
     pub fn has_tag(&self, tag: &Tag) -> bool {
-        self.tags.contains(tag)
+        self.get_all_tags().iter().any(|own_tag| tag.contains(own_tag))
+    }
+    
+    /// Returns the part of the value that belongs to any of the given `Tag`s or their children.
+    /// Each `Split` only counts towards its own `Tag`s, and the rest only counts towards the `Transaction`'s.
+    #[must_use]
+    pub fn get_amount_for_any(&self, tags: &[Tag]) -> Decimal {
+        self.get_amount_where(|own_tag| tags.iter().any(|tag| tag.contains(own_tag)))
+    }
+    
+    /// Returns the part of the value that belongs to exactly the given `Tag`, without counting its children.
+    #[must_use]
+    pub fn get_amount_for_exactly(&self, tag: &Tag) -> Decimal {
+        self.get_amount_where(|own_tag| own_tag == tag)
+    }
+    
+    /// Returns the part of the value whose `Tag`s match the given check.
+    #[must_use]
+    fn get_amount_where(&self, matches: impl Fn(&Tag) -> bool) -> Decimal {
+        let has_any = |own_tags: &[Tag]| own_tags.iter().any(&matches);
+        let remaining_amount = if has_any(&self.tags) { self.get_remaining_amount() } else { Decimal::ZERO };
+        remaining_amount + self.splits.iter().filter(|split| has_any(&split.tags)).map(|split| split.amount).sum::<Decimal>()
+    }
+    
+    /// Returns whether another `Transaction` has the same value, date, description, and `Tag`s.
+    /// The `Id`s are not compared.
+    #[must_use]
+    pub fn has_same_contents(&self, other: &Transaction) -> bool {
+        self.value == other.value
+            && self.date == other.date
+            && self.description == other.description
+            && Tag::sorted(&self.tags) == Tag::sorted(&other.tags)
+            && self.splits == other.splits
+    }
+    
+    /// Returns whether another `Transaction` looks like a different version of this one.
+    /// This is the case when they share a date and either a value or a description.
+    #[must_use]
+    pub fn is_similar_to(&self, other: &Transaction) -> bool {
+        self.date == other.date
+            && (self.value == other.value || self.description.trim().eq_ignore_ascii_case(other.description.trim()))
     }
     
     /// Returns the sum value of all `Transaction`s in a given list.
@@ -266,7 +445,7 @@ impl Transaction {
     pub fn get_sum_value_from(transactions: &[&Transaction]) -> Decimal {
         transactions
             .iter()
-            .filter(|t| !t.is_ignored())
+            .filter(|t| !t.is_excluded_from_parsing())
             .map(|t| { t.value.amount() })
             .sum()
     }
@@ -274,17 +453,24 @@ impl Transaction {
     /// Returns the value of the `Value` in hours.
     #[must_use]
     pub fn get_time_price(&self, currency_exchange: &CurrencyExchange) -> Decimal {
-        let time_price_result = currency_exchange.as_time_price(&self.value);
+        let time_price_result = currency_exchange.as_time_price(&self.value, self.date);
         match time_price_result {
             Pass(time_price) => { time_price }
             Fail(_) => { Decimal::from(0) }
         }
     }
 
+    /// Checks if this `Transaction` is left out of all data parsing, either because it is ignored or because it is a transfer.
+    #[must_use]
+    pub fn is_excluded_from_parsing(&self) -> bool {
+        self.is_ignored() || self.is_transfer()
+    }
+
     /// Checks if this `Transaction` is marked to be ignored in all data parsing.
+    /// Only the `Transaction`'s own `Tag`s are checked, since a `Split` cannot be ignored on its own.
     #[must_use]
     pub fn is_ignored(&self) -> bool {
-        self.has_tag(&Transaction::ignore_tag())
+        self.tags.contains(&Transaction::ignore_tag())
     }
 
 
@@ -295,6 +481,59 @@ impl Transaction {
     pub fn ignore_tag() -> Tag {
         Tag::new("ignore").wont_fail("This is a hardcoded Tag.", "Transaction::ignore_tag()")
     }
+
+    /// Returns the `Tag` given to both sides of a transfer.
+    #[must_use]
+    pub fn transfer_tag() -> Tag {
+        Tag::new("transfer").wont_fail("This is a hardcoded Tag.", "Transaction::transfer_tag()")
+    }
+}
+
+
+
+/// A part of a `Transaction`'s value, such as the household items on a grocery receipt, that belongs to its own `Tag`s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Split {
+    /// The part of the value, in the `Transaction`'s `Currency`.
+    pub amount: Decimal,
+    pub tags: Vec<Tag>,
+    /// A short note about what the `Split` is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+impl Split {
+    // initializing
+    /// Creates a new `Split` from raw data parts. An empty note is left out.
+    #[must_use]
+    pub fn new_from_raw(amount_string: &str, tags: Vec<Tag>, note: &str) -> Schrod<Split> {
+        if !Split::are_raw_parts_valid(amount_string, &tags, note) {
+            return Schrod::new_fail("Invalid parts!", "Split::new_from_raw()")
+                .fail("Failed to create Split.", "Split::new_from_raw()")
+        }
+        let amount_result = Schrod::from_result(Decimal::from_str(amount_string.trim()), "Failed to convert amount_string to Decimal.", "Split::new_from_raw()");
+        if amount_result.is_fail() {
+            return amount_result
+                .convert("Split::new_from_raw()")
+                .fail("Failed to create Split.", "Split::new_from_raw()")
+        }
+
+        Pass(Split {
+            amount: amount_result.wont_fail("This is past an is_fail() guard clause.", "Split::new_from_raw()"),
+            tags: Tag::sorted(&tags),
+            note: if note.trim().is_empty() { None } else { Some(note.trim().to_string()) },
+        })
+    }
+
+
+
+    // validating
+    /// Checks if a `Split` can be created from the given raw parts.
+    #[must_use]
+    pub fn are_raw_parts_valid(amount_string: &str, tags: &[Tag], note: &str) -> bool {
+        let is_amount_valid = Decimal::from_str(amount_string.trim()).is_ok_and(|amount| !amount.is_zero());
+        let is_note_valid = note.trim().is_empty() || Transaction::is_description_valid(note);
+        is_amount_valid && Transaction::are_tags_valid(tags) && is_note_valid
+    }
 }
 
 
@@ -512,6 +751,12 @@ impl Date {
         format!("{} {}, {}", self.month.display(), self.day, self.year)
     }
 
+    /// Returns the `Date` formatted as YYYY-MM-DD.
+    #[must_use]
+    pub fn display_iso(&self) -> String {
+        format!("{}-{:02}-{:02}", self.year, self.month.as_value(), self.day)
+    }
+
     /// Returns the year.
     #[must_use]
     pub fn get_year(&self) -> u32 {
@@ -736,6 +981,7 @@ impl Months {
 
 
 /// A custom tag object tailored for parsing and sorting `Transaction`s with overlapping categories.
+/// `Tag`s form a hierarchy through their labels, so `food/groceries` is a child of `food`.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Tag {
     /// The label of the tag.
@@ -754,6 +1000,12 @@ impl Hash for Tag {
     }
 }
 impl Tag {
+    // constants
+    /// The character that separates a parent `Tag` from its children in a label.
+    pub const HIERARCHY_SEPARATOR: char = '/';
+    
+    
+    
     // initializing
     /// Creates a new `Tag`.
     #[must_use]
@@ -768,6 +1020,18 @@ impl Tag {
         }
     }
 
+    /// Creates a list of `Tag`s from comma-separated labels, skipping empty ones.
+    #[must_use]
+    pub fn list_from(labels: &str) -> Schrod<Vec<Tag>> {
+        let tag_results = labels.split(',').filter(|label| !label.trim().is_empty()).map(Tag::new).collect::<Vec<_>>();
+        if Schrod::contains_fail(&tag_results) {
+            return Schrod::collect_and_fail(&tag_results, "Tag::list_from()")
+                .convert("Tag::list_from()")
+                .fail(&format!("\"{labels}\" is not a valid list of tags."), "Tag::list_from()")
+        }
+        Pass(Tag::sorted(&tag_results.into_iter().map(|result| result.wont_fail("This is past a contains_fail() guard clause.", "Tag::list_from()")).collect::<Vec<_>>()))
+    }
+
 
 
     // validating
@@ -835,6 +1099,7 @@ impl Tag {
     }
 
     /// Returns a validated `Tag` `label` to ensure it only contains allowed characters.
+    /// Each level of the hierarchy is trimmed, and none of them can be empty.
     #[must_use]
     fn validated_label(new_label: &str) -> Schrod<String> {
         let new_label = new_label.trim().to_lowercase();
@@ -842,13 +1107,49 @@ impl Tag {
             return Schrod::new_fail("Tag contains invalid characters!", "Tag::validated_label()")
                 .fail("Failed to get validated Tag label!", "Tag::validated_label()")
         }
-        Pass(new_label)
+        let levels = new_label.split(Tag::HIERARCHY_SEPARATOR).map(str::trim).collect::<Vec<_>>();
+        if levels.iter().any(|level| level.is_empty()) {
+            return Schrod::new_fail("Tag has an empty level in its hierarchy!", "Tag::validated_label()")
+                .fail("Failed to get validated Tag label!", "Tag::validated_label()")
+        }
+        Pass(levels.join(&Tag::HIERARCHY_SEPARATOR.to_string()))
     }
 
-    /// Determines if the `Tag` contains another `Tag`.
+    /// Determines if the `Tag` is the same as another `Tag` or one of its parents.
     #[must_use]
     pub fn contains(&self, other_tag: &Tag) -> bool {
-        self.label.contains(&other_tag.label)
+        other_tag.label == self.label
+            || other_tag.label.strip_prefix(&self.label).is_some_and(|rest| rest.starts_with(Tag::HIERARCHY_SEPARATOR))
+    }
+
+    /// Returns the parent of the `Tag`, or `None` if it is at the top of its hierarchy.
+    #[must_use]
+    pub fn get_parent(&self) -> Option<Tag> {
+        self.label.rsplit_once(Tag::HIERARCHY_SEPARATOR).map(|(parent_label, _)| Tag { label: parent_label.to_string() })
+    }
+
+    /// Returns every parent of the `Tag`, from the closest to the top of its hierarchy.
+    #[must_use]
+    pub fn get_ancestors(&self) -> Vec<Tag> {
+        let mut ancestors = Vec::new();
+        let mut current_parent = self.get_parent();
+        while let Some(parent) = current_parent {
+            current_parent = parent.get_parent();
+            ancestors.push(parent);
+        }
+        ancestors
+    }
+
+    /// Returns the `Tag` at the top of the hierarchy, which is itself if it has no parent.
+    #[must_use]
+    pub fn get_root(&self) -> Tag {
+        self.get_ancestors().pop().unwrap_or_else(|| self.clone())
+    }
+
+    /// Returns how many parents the `Tag` has.
+    #[must_use]
+    pub fn get_depth(&self) -> usize {
+        self.label.matches(Tag::HIERARCHY_SEPARATOR).count()
     }
 
     /// Returns a new list of `Tag`s that doesn't have duplicates.
@@ -863,6 +1164,20 @@ impl Tag {
         unique_tags
     }
 
+    /// Returns the `Tag` with the given parent swapped for another, keeping the rest of its hierarchy,
+    /// or `None` if the `Tag` is not the given parent or one of its children.
+    #[must_use]
+    pub fn relabeled(&self, old_tag: &Tag, new_tag: &Tag) -> Option<Tag> {
+        if !old_tag.contains(self) { return None }
+        Some(Tag { label: format!("{}{}", new_tag.label, &self.label[old_tag.label.len()..]) })
+    }
+    
+    /// Returns a new sorted list of `Tag`s with a `Tag` and its children swapped for another `Tag`.
+    #[must_use]
+    pub fn replaced_in(list: &[Tag], old_tag: &Tag, new_tag: &Tag) -> Vec<Tag> {
+        Tag::sorted(&list.iter().map(|tag| tag.relabeled(old_tag, new_tag).unwrap_or_else(|| tag.clone())).collect::<Vec<_>>())
+    }
+
     /// Returns a new list of `Tag`s sorted alphabetically.
     #[must_use]
     pub fn sorted(list: &[Tag]) -> Vec<Tag> {
@@ -872,18 +1187,74 @@ impl Tag {
         sorted_tags
     }
     
+    /// Returns the given `Tag`s along with all of their parents.
+    #[must_use]
+    pub fn with_ancestors(list: &[Tag]) -> Vec<Tag> {
+        Tag::sorted(&list.iter().flat_map(|tag| iter::once(tag.clone()).chain(tag.get_ancestors())).collect::<Vec<Tag>>())
+    }
+    
     /// Returns the `Tag`s that are in a given list of `Transaction`s.
     #[must_use]
     pub fn get_tags_from(transactions: &Vec<&Transaction>) -> Vec<Tag> {
-        Tag::sorted(&transactions.iter().flat_map(|t| t.tags.clone()).collect::<Vec<Tag>>())
+        Tag::sorted(&transactions.iter().flat_map(|t| t.get_all_tags()).collect::<Vec<Tag>>())
     }
     
     /// Gets the percentage of the `Value`s of the `Transaction`s tagged with a given `Tag` from a list of `Transaction`s.
+    /// Only the parts of split `Transaction`s that belong to the `Tag` are counted.
+    /// If the children are rolled up, the parts tagged with any child of the `Tag` are counted as well.
     #[must_use]
-    pub fn get_tag_percentage(tag: &Tag, transactions: &Vec<&Transaction>) -> Schrod<f64> {
-        let tagged_transactions = transactions.clone().into_iter().filter(|t| t.has_tag(tag)).collect::<Vec<&Transaction>>();
+    pub fn get_tag_percentage(tag: &Tag, transactions: &Vec<&Transaction>, are_children_rolled_up: bool) -> Schrod<f64> {
         let sum_value = Transaction::get_sum_value_from(transactions);
-        let tagged_value = Transaction::get_sum_value_from(&tagged_transactions);
+        let tagged_value: Decimal = transactions
+            .iter()
+            .filter(|t| !t.is_excluded_from_parsing())
+            .map(|t| if are_children_rolled_up { t.get_amount_for_any(std::slice::from_ref(tag)) } else { t.get_amount_for_exactly(tag) })
+            .sum();
         Schrod::from_option((tagged_value / sum_value).to_f64(), "Failed to convert Tag percentage to f64.", "Tag::get_tag_percentage()")
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Tag;
+    use crate::vault::bank::TagRegistry;
+    use crate::vault::test_helpers::tag;
+    use materialui::materials::MaterialColors;
+
+    #[test]
+    fn labels_are_normalized() {
+        assert_eq!(tag("Food / Restaurants").get_label(), "food/restaurants");
+        assert_eq!(tag("  FOOD  "), tag("food"));
+        assert!(Tag::new("food//restaurants").is_fail());
+        assert!(Tag::new(" / ").is_fail());
+    }
+
+    #[test]
+    fn tags_only_contain_themselves_and_their_children() {
+        assert!(tag("food").contains(&tag("food")));
+        assert!(tag("food").contains(&tag("Food / Restaurants")));
+        assert!(!tag("food").contains(&tag("seafood")));
+        assert!(!tag("food").contains(&tag("foods/snacks")));
+        assert!(!tag("food/restaurants").contains(&tag("food")));
+    }
+
+    #[test]
+    fn ancestors_are_added_once() {
+        let tags = Tag::with_ancestors(&[tag("food/restaurants/fast"), tag("food/groceries"), tag("seafood")]);
+        assert_eq!(tags, vec![tag("food"), tag("food/groceries"), tag("food/restaurants"), tag("food/restaurants/fast"), tag("seafood")]);
+    }
+
+    #[test]
+    fn colors_are_inherited_from_the_closest_parent() {
+        let mut tag_registry = TagRegistry::default();
+        tag_registry.set(&tag("food"), MaterialColors::Amber);
+        tag_registry.set(&tag("food/restaurants"), MaterialColors::Fern);
+
+        assert_eq!(tag_registry.get(&tag("food/groceries")), MaterialColors::Amber);
+        assert_eq!(tag_registry.get(&tag("food/restaurants/fast")), MaterialColors::Fern);
+        assert_eq!(tag_registry.get_own(&tag("food/groceries")), MaterialColors::Unavailable);
+        // a tag that only starts with the same letters does not inherit
+        assert_eq!(tag_registry.get(&tag("seafood")), MaterialColors::Unavailable);
+    }
+}
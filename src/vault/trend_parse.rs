@@ -8,9 +8,10 @@ use rust_decimal::{Decimal, prelude::ToPrimitive};
 use iced::widget::image::Handle;
 use plotters_bitmap::BitMapBackend;
 use plotters_bitmap::bitmap_pixel::RGBPixel;
+use serde::{Deserialize, Serialize};
 
 /// Defines how groups of `Transaction`s can be split by time intervals.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Intervals {
     /// Groups by week.
     Weekly,
@@ -23,6 +24,38 @@ pub enum Intervals {
     /// Groups by year.
     Yearly,
 }
+impl Intervals {
+    // data retrieval and parsing
+    /// Returns every interval, shortest first.
+    #[must_use]
+    pub fn all() -> [Intervals; 5] {
+        [Intervals::Weekly, Intervals::BiWeekly, Intervals::Monthly, Intervals::Quarterly, Intervals::Yearly]
+    }
+
+    /// Returns the name of the interval.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Intervals::Weekly => { "Weekly" }
+            Intervals::BiWeekly => { "BiWeekly" }
+            Intervals::Monthly => { "Monthly" }
+            Intervals::Quarterly => { "Quarterly" }
+            Intervals::Yearly => { "Yearly" }
+        }
+    }
+
+    /// Checks if two `Date`s fall within the same period of the interval.
+    #[must_use]
+    pub fn are_in_same_period(&self, first: Date, second: Date) -> bool {
+        match self {
+            Intervals::Weekly => TimeGroup::is_in_same_week(first, second),
+            Intervals::BiWeekly => TimeGroup::is_in_same_biweek(first, second),
+            Intervals::Monthly => TimeGroup::is_in_same_month(first, second),
+            Intervals::Quarterly => TimeGroup::is_in_same_quarter(first, second),
+            Intervals::Yearly => TimeGroup::is_in_same_year(first, second),
+        }
+    }
+}
 
 
 
@@ -45,13 +78,7 @@ impl<'a> TimeGroup<'a> {
     fn contains_date(&self, date: Date) -> bool {
         if self.transactions.is_empty() { return false; }
         
-        match self.interval {
-            Intervals::Weekly => TimeGroup::is_in_same_week(self.transactions[0].date, date),
-            Intervals::BiWeekly => TimeGroup::is_in_same_biweek(self.transactions[0].date, date),
-            Intervals::Monthly => TimeGroup::is_in_same_month(self.transactions[0].date, date),
-            Intervals::Quarterly => TimeGroup::is_in_same_quarter(self.transactions[0].date, date),
-            Intervals::Yearly => TimeGroup::is_in_same_year(self.transactions[0].date, date),
-        }
+        self.interval.are_in_same_period(self.transactions[0].date, date)
     }
 
     /// Filters out all `Transaction`s that do not have the given `Tag`.
@@ -61,7 +88,7 @@ impl<'a> TimeGroup<'a> {
 
     /// Sorts a given list of `TimeGroup`s by `Date`.
     fn sort_time_groups(groups: &mut Vec<TimeGroup>) {
-        groups.sort_by(|a, b| b.date.as_value().cmp(&a.date.as_value()));
+        groups.sort_by_key(|t| std::cmp::Reverse(t.date.as_value()));
     }
     
     /// Places a `Transaction` into the correct group of `Transaction`s.
@@ -104,9 +131,9 @@ impl<'a> TimeGroup<'a> {
         groups.push(TimeGroup::new(vec![transaction], transaction.date, interval));
     }
 
-    /// Removes any `Transaction` marked as ignored.
+    /// Removes any `Transaction` marked as ignored, along with transfers.
     fn filter_ignored(&mut self) {
-        self.transactions.retain(|t| !t.is_ignored());
+        self.transactions.retain(|t| !t.is_excluded_from_parsing());
     }
     
     /// Gets the label for the `TimeGroup`.
@@ -558,7 +585,7 @@ impl TimeLine {
         for transaction in transactions { TimeGroup::place_into_time_group(transaction, &mut all_time_groups, interval); }
         TimeGroup::sort_time_groups(&mut all_time_groups);
 
-        // filters out all ignored transactions
+        // filters out all ignored transactions and transfers
         for group in &mut all_time_groups { group.filter_ignored(); }
 
         // finds the first group to add.
@@ -600,8 +627,16 @@ impl TimeLine {
             }
         }
 
-        // collects the cash flows for the collected time groups
-        let cash_flow_results: Vec<Schrod<CashFlow>> = collected_time_groups.iter().map(|group| CashFlow::new(bank, &Bank::get_ids_from(&group.transactions))).collect();
+        // collects the cash flows for the collected time groups, only counting the parts of split
+        // transactions that belong to the tag. each period is unified at the exchange rates that
+        // applied on the dates of its transactions, not at today's rates
+        let cash_flow_results: Vec<Schrod<CashFlow>> = collected_time_groups.iter().map(|group| {
+            let ids = Bank::get_ids_from(&group.transactions);
+            match &trending_tag {
+                Some(tag) => CashFlow::new_for_tags(bank, &ids, std::slice::from_ref(tag)),
+                None => CashFlow::new(bank, &ids),
+            }
+        }).collect();
         if Schrod::contains_fail(&cash_flow_results) {
             return Schrod::collect_and_fail(&cash_flow_results, "TimeLine::new()")
                 .convert("TimeLine::new()")